@@ -0,0 +1,16 @@
+//! Fixtures shared by the benchmarks.
+
+/// 50 chapters × 9 sections × 10 topics, 5,000 headings in total.
+pub fn synthetic_document() -> String {
+    let mut markdown = String::new();
+    for chapter in 0..50 {
+        markdown.push_str(&format!("# Chapter {chapter}\n\nIntro.\n\n"));
+        for section in 0..9 {
+            markdown.push_str(&format!("## Section {chapter}.{section}\n\nText.\n\n"));
+            for sub in 0..10 {
+                markdown.push_str(&format!("### Topic {chapter}.{section}.{sub}\n\nMore.\n\n"));
+            }
+        }
+    }
+    markdown
+}
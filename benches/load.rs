@@ -0,0 +1,47 @@
+//! Parsing a synthetic 10MB document, in one pass and in progress chunks.
+//!
+//! Run with `cargo bench --bench load`.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use treemd::parse_markdown;
+use treemd::parser::parse_markdown_with_progress;
+
+/// Chapters of headings, prose, lists and code until the text passes 10MB.
+fn synthetic_document() -> String {
+    let mut markdown = String::with_capacity(10 * 1024 * 1024 + 4096);
+    let mut chapter = 0;
+    while markdown.len() < 10 * 1024 * 1024 {
+        markdown.push_str(&format!("# Chapter {chapter}\n\nIntro.\n\n"));
+        for section in 0..10 {
+            markdown.push_str(&format!(
+                "## Section {chapter}.{section}\n\n{}\n\n- one\n- two\n\n```rust\n# fn main() {{}}\n```\n\n",
+                "Some prose with **bold** and `code`. ".repeat(20)
+            ));
+        }
+        chapter += 1;
+    }
+    markdown
+}
+
+fn load(c: &mut Criterion) {
+    let markdown = synthetic_document();
+    let mut group = c.benchmark_group("load 10MB");
+    group.sample_size(10);
+    group.bench_function("parse_markdown", |b| {
+        b.iter(|| black_box(parse_markdown(&markdown).headings.len()))
+    });
+    group.bench_function("parse_markdown_with_progress", |b| {
+        b.iter(|| {
+            black_box(
+                parse_markdown_with_progress(&markdown, |_| {})
+                    .headings
+                    .len(),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, load);
+criterion_main!(benches);
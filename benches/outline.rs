@@ -0,0 +1,58 @@
+//! Outline benchmarks on a synthetic 5,000-heading document.
+//!
+//! Run with `cargo bench --bench outline`.
+
+mod common;
+
+use common::synthetic_document;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::path::PathBuf;
+use treemd::tui::ColorMode;
+use treemd::{App, Config, parse_markdown};
+
+fn outline_toggle(c: &mut Criterion) {
+    let document = parse_markdown(&synthetic_document());
+    assert_eq!(document.headings.len(), 5_000);
+    let mut app = App::new(
+        document,
+        "bench.md".to_string(),
+        PathBuf::from("bench.md"),
+        Config::default(),
+        ColorMode::Rgb,
+        false,
+    );
+
+    // A chapter in the middle of the outline: collapse, then expand it again
+    let chapter = app
+        .outline_items
+        .iter()
+        .position(|item| item.text == "Chapter 25")
+        .unwrap();
+    c.bench_function("toggle_expand chapter (5k headings)", |b| {
+        b.iter(|| {
+            app.outline_state.select(Some(chapter));
+            app.toggle_expand();
+            app.toggle_expand();
+            black_box(app.outline_items.len())
+        })
+    });
+
+    // A leaf-level parent: collapse, then expand a single section
+    let section = app
+        .outline_items
+        .iter()
+        .position(|item| item.text == "Section 25.5")
+        .unwrap();
+    c.bench_function("toggle_expand section (5k headings)", |b| {
+        b.iter(|| {
+            app.outline_state.select(Some(section));
+            app.toggle_expand();
+            app.toggle_expand();
+            black_box(app.outline_items.len())
+        })
+    });
+}
+
+criterion_group!(benches, outline_toggle);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+//! Per-frame section lookups on a synthetic 5,000-heading document.
+//!
+//! Run with `cargo bench --bench section`.
+
+mod common;
+
+use common::synthetic_document;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::path::PathBuf;
+use treemd::tui::ColorMode;
+use treemd::{App, Config, parse_markdown};
+
+fn section_lookup(c: &mut Criterion) {
+    let mut app = App::new(
+        parse_markdown(&synthetic_document()),
+        "bench.md".to_string(),
+        PathBuf::from("bench.md"),
+        Config::default(),
+        ColorMode::Rgb,
+        false,
+    );
+
+    // A topic near the end, so finding its heading scans most of the document
+    let topic = app
+        .outline_items
+        .iter()
+        .position(|item| item.text == "Topic 45.5.5")
+        .unwrap();
+    app.outline_state.select(Some(topic));
+    app.update_content_metrics();
+
+    // What every frame does: update the metrics, then render the section
+    c.bench_function("frame section lookups (5k headings)", |b| {
+        b.iter(|| {
+            app.update_content_metrics();
+            black_box(app.selected_section_content().len())
+        })
+    });
+
+    // Baseline without the cache: both lookups extract the section again
+    let offset = app
+        .document
+        .headings
+        .iter()
+        .find(|heading| heading.text == "Topic 45.5.5")
+        .unwrap()
+        .offset;
+    let behavior = Config::default().behavior;
+    c.bench_function("frame section lookups, uncached (5k headings)", |b| {
+        b.iter(|| {
+            let metrics = behavior.section_at(&app.document, offset).unwrap();
+            let rendered = behavior.section_at(&app.document, offset).unwrap();
+            black_box(metrics.len() + rendered.len())
+        })
+    });
+}
+
+criterion_group!(benches, section_lookup);
+criterion_main!(benches);
@@ -33,6 +33,7 @@ pub struct Cli {
     ///   treemd .                 # Open file picker in current directory
     ///   treemd docs/             # Open file picker in docs directory
     ///   treemd *.md              # Open file picker with matched files
+    ///   treemd -l docs/*.md      # List headings of each file
     ///   treemd -                 # Read from stdin
     ///   cat doc.md | treemd -l   # Pipe markdown
     #[arg(add = markdown_file_completer())]
@@ -55,6 +56,21 @@ pub struct Cli {
     #[arg(long = "tree")]
     pub tree: bool,
 
+    /// Show at most N levels of the --tree output
+    ///
+    /// Headings deeper in the tree are hidden, and headings whose children
+    /// were hidden are marked with ▶. Useful for a high-level overview.
+    ///
+    /// Example: --tree --max-level 2
+    #[arg(
+        long = "max-level",
+        visible_alias = "collapse-below",
+        value_name = "N",
+        requires = "tree",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_level: Option<u64>,
+
     /// Filter headings by text pattern (case-insensitive)
     ///
     /// Only shows headings containing the specified text.
@@ -79,8 +95,10 @@ pub struct Cli {
     ///   plain - Human-readable text (default)
     ///   json  - JSON array for scripting/parsing
     ///   tree  - Box-drawing tree structure
-    #[arg(short = 'o', long = "output", default_value = "plain")]
-    pub output: OutputFormat,
+    ///
+    /// Without this flag, `cli.default_output` from the config file applies.
+    #[arg(short = 'o', long = "output")]
+    pub output: Option<OutputFormat>,
 
     /// Extract specific section by heading name
     ///
@@ -98,6 +116,106 @@ pub struct Cli {
     #[arg(long = "count")]
     pub count: bool,
 
+    /// Print a stats report for the document or one section
+    ///
+    /// Shows word count and reading time, headings by level, code blocks by
+    /// language, links by type, images, tables and task completion.
+    /// Pass a heading name with '=' to limit the report to that section
+    /// and its subsections.
+    ///
+    /// Examples:
+    ///   --stats README.md
+    ///   --stats=Installation README.md
+    #[arg(
+        long = "stats",
+        value_name = "SECTION",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = ""
+    )]
+    pub stats: Option<String>,
+
+    /// Split the document into one file per top-level section
+    ///
+    /// Writes each section to DIR/<slug>.md plus an index.md linking them.
+    /// DIR defaults to a directory named after the input file. Existing files
+    /// are never overwritten.
+    ///
+    /// Examples:
+    ///   --split spec.md
+    ///   --split=chapters --split-level 2 spec.md
+    #[arg(
+        long = "split",
+        value_name = "DIR",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = ""
+    )]
+    pub split: Option<String>,
+
+    /// Heading level to split on with --split (default: 1)
+    #[arg(
+        long = "split-level",
+        value_name = "LEVEL",
+        default_value_t = 1,
+        requires = "split",
+        value_parser = clap::value_parser!(u8).range(1..=6)
+    )]
+    pub split_level: u8,
+
+    /// Compare heading structure with an older version of the file
+    ///
+    /// Reports headings that were added, removed, renamed, moved to another
+    /// parent, or changed level, instead of a line diff. Headings are matched
+    /// by their path, then by text, then by similar text under the same
+    /// parent. Use -o json for machine-readable output.
+    ///
+    /// Example: --diff old.md new.md
+    #[arg(long = "diff", value_name = "OLD", add = markdown_file_completer())]
+    pub diff: Option<PathBuf>,
+
+    /// Move every heading level by N when printing or exporting
+    ///
+    /// Use a positive N to demote headings (## becomes ###) when pulling a
+    /// section into a larger document, or a negative one to promote them.
+    /// Levels are clamped to 1-6 with a warning. Applies to --section and
+    /// each --split file; on its own, prints the whole shifted document.
+    /// Lines inside code blocks are never changed.
+    ///
+    /// Examples:
+    ///   --section Usage --shift 1 README.md
+    ///   --shift=-1 notes.md
+    #[arg(
+        long = "shift",
+        value_name = "N",
+        allow_negative_numbers = true,
+        value_parser = clap::value_parser!(i8).range(-5..=5)
+    )]
+    pub shift: Option<i8>,
+
+    /// Export a report about the document instead of viewing it
+    ///
+    /// linkreport: every link with its text, raw target, resolved target,
+    /// type (anchor/relative/wiki/external), source line and status. Anchors
+    /// and local files are checked; external URLs are listed as "unchecked"
+    /// so the report never touches the network. Prints a markdown table, or
+    /// JSON with -o json.
+    ///
+    /// mermaid, mermaid:flowchart: the heading tree as a Mermaid mindmap or
+    /// `graph TD` flowchart, ready to paste into docs that render Mermaid.
+    ///
+    /// man: groff man page source. The first # heading is the .TH title (a
+    /// trailing "(N)" sets the section), ## headings become .SH and ###
+    /// headings .SS.
+    ///
+    /// Examples:
+    ///   --export linkreport README.md
+    ///   --export linkreport -o json README.md
+    ///   --export mermaid README.md
+    ///   --export man treemd.1.md > treemd.1
+    #[arg(long = "export", value_name = "KIND", value_enum)]
+    pub export: Option<ExportKind>,
+
     /// Set up shell completions interactively
     ///
     /// Interactive helper to configure tab completion for your shell (bash/zsh/fish).
@@ -109,18 +227,20 @@ pub struct Cli {
     /// Set theme for TUI mode
     ///
     /// Override the saved theme preference. Available themes:
-    /// OceanDark, Nord, Dracula, Solarized, Monokai, Gruvbox, TokyoNight, CatppuccinMocha
+    /// OceanDark, Nord, Dracula, Solarized, Monokai, Gruvbox, TokyoNight, CatppuccinMocha,
+    /// HighContrast, Deuteranopia
     ///
     /// Example: --theme Nord
     #[arg(long = "theme", value_name = "THEME")]
     pub theme: Option<String>,
 
-    /// Force color mode (auto, rgb, 256)
+    /// Force color mode (auto, rgb, 256, none)
     ///
     /// Override automatic terminal detection:
     ///   auto - Detect terminal capabilities (default)
     ///   rgb  - Force true color (16M colors)
     ///   256  - Force 256-color palette
+    ///   none - No colors, for logs, CI and monochrome terminals
     ///
     /// Example: --color-mode 256
     #[arg(long = "color-mode", value_name = "MODE")]
@@ -142,6 +262,28 @@ pub struct Cli {
     #[arg(long = "images", conflicts_with = "no_images")]
     pub images: bool,
 
+    /// Print the rendered document instead of opening the TUI
+    ///
+    /// Renders the document like the content pane, with colors from the
+    /// theme, and pipes it through $PAGER (default: less -R) when stdout is a
+    /// terminal. This is also what happens when stdout isn't a terminal.
+    /// Colors are dropped when NO_COLOR is set or output isn't a terminal,
+    /// unless --color-mode is given.
+    ///
+    /// Example: treemd --pager README.md
+    #[arg(long = "pager", visible_alias = "render", alias = "no-tui")]
+    pub pager: bool,
+
+    /// Write navigation events to stderr as JSON lines (TUI mode)
+    ///
+    /// Emits section_selected, link_followed, file_loaded and checkbox_toggled
+    /// events, each with the file path, current heading and a timestamp in
+    /// milliseconds. Redirect stderr so the events don't mix with the display.
+    ///
+    /// Example: treemd --emit-events README.md 2>events.jsonl
+    #[arg(long = "emit-events")]
+    pub emit_events: bool,
+
     /// Query expression for selecting/filtering document elements
     ///
     /// Uses a jq-like syntax for navigating and extracting markdown structure.
@@ -178,10 +320,24 @@ pub struct Cli {
     ///   jsonl  - Line-delimited JSON
     ///   md     - Raw markdown
     ///   tree   - Tree structure
+    ///   csv    - CSV with a header row for objects
+    ///   template:T - Render T per result, replacing {field} placeholders
+    ///
+    /// Without this flag, `cli.default_query_output` from the config file
+    /// applies.
     ///
     /// Example: -q '.h2' --query-output json
     #[arg(long = "query-output", value_name = "FORMAT")]
     pub query_output: Option<String>,
+
+    /// Warn about query filters that match nothing
+    ///
+    /// Reports filters like `.h2[Instalation]` that match none of the
+    /// document's h2 headings, with the closest heading as a suggestion,
+    /// and exits with status 1. Kinds missing from the document (no code
+    /// blocks at all, say) are an empty result, not a warning.
+    #[arg(long = "query-validate", requires = "query")]
+    pub query_validate: bool,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -193,6 +349,8 @@ pub enum ColorModeArg {
     /// Force 256-color mode
     #[value(name = "256")]
     Color256,
+    /// Plain text styling, no colors
+    None,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -210,6 +368,19 @@ pub enum Command {
     },
 }
 
+#[derive(Debug, Clone, ValueEnum)]
+pub enum ExportKind {
+    /// Every link in the document with its resolved target and status
+    Linkreport,
+    /// Heading tree as a Mermaid mindmap
+    Mermaid,
+    /// Heading tree as a Mermaid `graph TD` flowchart
+    #[value(name = "mermaid:flowchart")]
+    MermaidFlowchart,
+    /// Document as groff man page source
+    Man,
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 pub enum OutputFormat {
     /// Plain text output
@@ -1,4 +1,4 @@
 mod commands;
 pub mod setup;
 
-pub use commands::{Cli, ColorModeArg, OutputFormat};
+pub use commands::{Cli, ColorModeArg, ExportKind, OutputFormat};
@@ -3,8 +3,9 @@ use crate::tui::theme::ThemeName;
 use opensesame::EditorConfig;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
@@ -31,6 +32,19 @@ pub struct Config {
     /// Content filtering options
     #[serde(default)]
     pub content: ContentConfig,
+
+    /// How sections are extracted and shown
+    #[serde(default)]
+    pub behavior: BehaviorConfig,
+
+    /// Defaults for the command-line modes
+    #[serde(default)]
+    pub cli: CliConfig,
+
+    /// Recently opened files and where they were left (maintained by treemd
+    /// in the state file, see [`Config::state_path`])
+    #[serde(default, skip_serializing)]
+    pub history: HistoryConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +58,74 @@ pub struct UiConfig {
     /// Tree rendering style: "compact" (default, gapless) or "spaced"
     #[serde(default = "default_tree_style")]
     pub tree_style: String,
+
+    /// Whether document search matches text inside code blocks (default: true)
+    #[serde(default = "default_search_in_code")]
+    pub search_in_code: bool,
+
+    /// Whether outline search also keeps headings under a matching heading
+    /// (default: false)
+    #[serde(default)]
+    pub search_match_path: bool,
+
+    /// Move the outline selection to the heading at the top of the content
+    /// view while scrolling (default: false)
+    #[serde(default)]
+    pub sync_outline: bool,
+
+    /// Milliseconds to wait for the next key of a multi-key sequence like
+    /// `g g` before discarding it (default: 500)
+    #[serde(default = "default_chord_timeout_ms")]
+    pub chord_timeout_ms: u64,
+
+    /// Reload the document when its file changes on disk (default: true)
+    #[serde(default = "default_auto_reload")]
+    pub auto_reload: bool,
+
+    /// Where copies go: "system" (default) or "osc52" to have the terminal
+    /// set its clipboard, which also works over SSH
+    #[serde(default = "default_clipboard")]
+    pub clipboard: String,
+
+    /// Show task completion next to outline headings (default: false)
+    #[serde(default)]
+    pub outline_task_progress: bool,
+
+    /// Status bar layout with `{token}` placeholders; see the README for
+    /// the available tokens
+    #[serde(default = "default_status_format")]
+    pub status_format: String,
+
+    /// Columns between tab stops in code blocks and the raw source view
+    /// (default: 4)
+    #[serde(default = "default_tab_width")]
+    pub tab_width: usize,
+
+    /// Stop collecting content search matches after this many, so searching
+    /// a common word in a huge file stays responsive (default: 5000)
+    #[serde(default = "default_max_search_matches")]
+    pub max_search_matches: usize,
+
+    /// Wrap long table cells over several lines to fit the pane; when off,
+    /// each row stays on one line and long cells are cut off (default: true)
+    #[serde(default = "default_table_wrap")]
+    pub table_wrap: bool,
+
+    /// Most files kept on each of the back and forward stacks; the oldest
+    /// are dropped first (default: 50)
+    #[serde(default = "default_history_depth")]
+    pub history_depth: usize,
+
+    /// Blank lines between rendered blocks: 1 separates every block, 0 also
+    /// drops the blank line between consecutive lists and after a heading
+    /// followed by a paragraph (default: 1)
+    #[serde(default = "default_block_spacing")]
+    pub block_spacing: usize,
+
+    /// In interactive mode, let the nested objects and arrays of `json` and
+    /// `yaml` code blocks be folded with Enter (default: false)
+    #[serde(default)]
+    pub fold_data_blocks: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,6 +158,54 @@ fn default_images_enabled() -> bool {
     true
 }
 
+/// Section behavior shared by the TUI and the command line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BehaviorConfig {
+    /// Show a heading's subsections along with it in the content pane and
+    /// `--section` output; when false, a section stops at its first
+    /// subheading (default: true)
+    #[serde(default = "default_include_subsections")]
+    pub include_subsections: bool,
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        Self {
+            include_subsections: default_include_subsections(),
+        }
+    }
+}
+
+fn default_include_subsections() -> bool {
+    true
+}
+
+impl BehaviorConfig {
+    /// Content of the section whose heading starts at `offset`, with or
+    /// without its subsections as configured.
+    pub fn section_at(&self, doc: &crate::parser::Document, offset: usize) -> Option<String> {
+        if self.include_subsections {
+            doc.extract_section_at(offset)
+        } else {
+            doc.extract_section_shallow_at(offset)
+        }
+    }
+}
+
+/// Command-line defaults, overridden by the matching flags
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CliConfig {
+    /// Format for `--list`, `--tree` and other heading output when `-o` is
+    /// not given: "plain" (default), "json" or "tree"
+    #[serde(default)]
+    pub default_output: Option<String>,
+
+    /// Format for `-q` results when `--query-output` is not given, e.g.
+    /// "json" (default: "plain")
+    #[serde(default)]
+    pub default_query_output: Option<String>,
+}
+
 /// Content filtering configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContentConfig {
@@ -194,6 +324,50 @@ pub struct CustomThemeConfig {
     pub help_desc_fg: Option<ColorValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub footer_bg: Option<ColorValue>,
+    /// Glyph for checked task checkboxes (default: ☑)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkbox_checked: Option<String>,
+    /// Glyph for unchecked task checkboxes (default: ☐)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkbox_unchecked: Option<String>,
+}
+
+/// Maximum number of entries kept in the recent-files list
+pub const MAX_RECENT_FILES: usize = 20;
+
+/// Recently opened files and where they were left.
+///
+/// Kept in the state file; only read from config.toml to pick up history
+/// saved there by older versions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// Absolute paths of recently opened files, most recent first
+    #[serde(default)]
+    pub recent_files: Vec<PathBuf>,
+
+    /// Where each recent file was left, restored when it is opened again
+    #[serde(default)]
+    pub positions: BTreeMap<PathBuf, FilePosition>,
+}
+
+/// What treemd saves to the state file, apart from the user's config
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct State {
+    /// Missing in state files written before recent files moved there
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    recent_files: Option<Vec<PathBuf>>,
+    #[serde(default)]
+    positions: BTreeMap<PathBuf, FilePosition>,
+}
+
+/// Reading position saved for a file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilePosition {
+    /// Text of the selected outline entry
+    pub heading: Option<String>,
+    /// Content scroll offset within that section
+    #[serde(default)]
+    pub content_scroll: u16,
 }
 
 /// Color value that can be specified in multiple formats
@@ -243,14 +417,72 @@ impl Default for UiConfig {
             theme: default_theme(),
             outline_width: default_outline_width(),
             tree_style: default_tree_style(),
+            search_in_code: default_search_in_code(),
+            search_match_path: false,
+            sync_outline: false,
+            chord_timeout_ms: default_chord_timeout_ms(),
+            auto_reload: default_auto_reload(),
+            clipboard: default_clipboard(),
+            outline_task_progress: false,
+            status_format: default_status_format(),
+            tab_width: default_tab_width(),
+            max_search_matches: default_max_search_matches(),
+            table_wrap: default_table_wrap(),
+            history_depth: default_history_depth(),
+            block_spacing: default_block_spacing(),
+            fold_data_blocks: false,
         }
     }
 }
 
+/// Status bar layout used when `ui.status_format` is not set
+pub const DEFAULT_STATUS_FORMAT: &str =
+    " [{focus}] {position} {bookmark} {history} • {outline} • Theme:{theme} {reload} {raw}";
+
+fn default_status_format() -> String {
+    DEFAULT_STATUS_FORMAT.to_string()
+}
+
+fn default_tab_width() -> usize {
+    crate::tui::DEFAULT_TAB_WIDTH
+}
+
+fn default_max_search_matches() -> usize {
+    5000
+}
+
+fn default_history_depth() -> usize {
+    50
+}
+
+fn default_block_spacing() -> usize {
+    1
+}
+
+fn default_table_wrap() -> bool {
+    true
+}
+
 fn default_tree_style() -> String {
     "compact".to_string()
 }
 
+fn default_search_in_code() -> bool {
+    true
+}
+
+fn default_chord_timeout_ms() -> u64 {
+    500
+}
+
+fn default_auto_reload() -> bool {
+    true
+}
+
+fn default_clipboard() -> String {
+    "system".to_string()
+}
+
 impl Default for TerminalConfig {
     fn default() -> Self {
         Self {
@@ -288,9 +520,30 @@ impl Config {
         dirs::config_dir().map(|p| p.join("treemd").join("config.toml"))
     }
 
-    /// Load config from file, or return default if file doesn't exist
-    /// On macOS, checks ~/.config/treemd first, then falls back to ~/Library/Application Support
+    /// Path of the state file, `state.toml` next to the config file.
+    ///
+    /// Holds what treemd records on its own, such as where each file was
+    /// left, so saving it never rewrites the user's config.toml.
+    pub fn state_path() -> Option<PathBuf> {
+        Self::config_path().map(|path| path.with_file_name("state.toml"))
+    }
+
+    /// Load config from file, or return default if file doesn't exist.
+    /// The state file is read separately and fills in [`Config::history`].
     pub fn load() -> Self {
+        let mut config = Self::load_config_file();
+        if let Some(state) = Self::state_path().and_then(|path| State::read(&path)) {
+            if let Some(recent_files) = state.recent_files {
+                config.history.recent_files = recent_files;
+            }
+            config.history.positions = state.positions;
+        }
+        config
+    }
+
+    /// Load config.toml alone.
+    /// On macOS, checks ~/.config/treemd first, then falls back to ~/Library/Application Support
+    fn load_config_file() -> Self {
         #[cfg(target_os = "macos")]
         {
             // Prefer XDG-style path on macOS for CLI tools
@@ -328,6 +581,16 @@ impl Config {
         Ok(())
     }
 
+    /// Save the state file
+    fn save_state(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::state_path().ok_or("Could not determine config directory")?;
+        let state = State {
+            recent_files: Some(self.history.recent_files.clone()),
+            positions: self.history.positions.clone(),
+        };
+        state.write(&path)
+    }
+
     /// Parse theme name from string
     pub fn theme_name(&self) -> ThemeName {
         match self.ui.theme.as_str() {
@@ -339,6 +602,8 @@ impl Config {
             "Gruvbox" => ThemeName::Gruvbox,
             "TokyoNight" => ThemeName::TokyoNight,
             "CatppuccinMocha" => ThemeName::CatppuccinMocha,
+            "HighContrast" => ThemeName::HighContrast,
+            "Deuteranopia" => ThemeName::Deuteranopia,
             _ => ThemeName::OceanDark, // Default fallback
         }
     }
@@ -354,6 +619,8 @@ impl Config {
             ThemeName::Gruvbox => "Gruvbox",
             ThemeName::TokyoNight => "TokyoNight",
             ThemeName::CatppuccinMocha => "CatppuccinMocha",
+            ThemeName::HighContrast => "HighContrast",
+            ThemeName::Deuteranopia => "Deuteranopia",
         }
         .to_string();
 
@@ -366,12 +633,68 @@ impl Config {
         self.save()
     }
 
+    /// Update the auto-reload preference and save config
+    pub fn set_auto_reload(&mut self, enabled: bool) -> Result<(), Box<dyn std::error::Error>> {
+        self.ui.auto_reload = enabled;
+        self.save()
+    }
+
     /// Mark that we've warned the user about Terminal.app
     pub fn set_warned_terminal_app(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.terminal.warned_terminal_app = true;
         self.save()
     }
 
+    /// Record `path` as the most recently opened file and save the state file
+    pub fn push_recent(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        self.add_recent(path);
+        self.save_state()
+    }
+
+    /// Move `path` to the front of the recent-files list without saving
+    fn add_recent(&mut self, path: &Path) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let recent = &mut self.history.recent_files;
+        recent.retain(|p| p != &path);
+        recent.insert(0, path);
+        recent.truncate(MAX_RECENT_FILES);
+    }
+
+    /// Saved reading position for `path`, if it was opened before
+    pub fn file_position(&self, path: &Path) -> Option<&FilePosition> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.history.positions.get(&path)
+    }
+
+    /// Record where `path` was left and save the state file
+    pub fn save_file_position(
+        &mut self,
+        path: &Path,
+        position: FilePosition,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.set_file_position(path, position);
+        self.save_state()
+    }
+
+    /// Record where `path` was left without saving. Positions are only kept
+    /// for files in the recent-files list.
+    fn set_file_position(&mut self, path: &Path, position: FilePosition) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let recent = &self.history.recent_files;
+        self.history.positions.insert(path, position);
+        self.history.positions.retain(|p, _| recent.contains(p));
+    }
+
+    /// Recently opened files that still exist, most recent first
+    pub fn recent_files(&self) -> Vec<PathBuf> {
+        self.history
+            .recent_files
+            .iter()
+            .filter(|p| p.is_file())
+            .cloned()
+            .collect()
+    }
+
     /// Get keybindings with user customizations applied
     pub fn keybindings(&self) -> Keybindings {
         self.keybindings.to_keybindings()
@@ -382,3 +705,136 @@ impl Config {
         self.ui.tree_style == "compact"
     }
 }
+
+impl State {
+    /// Read a state file, or `None` if it is missing or unreadable
+    fn read(path: &Path) -> Option<Self> {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+    }
+
+    fn write(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_files_are_deduplicated_most_recent_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths: Vec<PathBuf> = ["a.md", "b.md", "c.md"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                fs::write(&path, "# Title\n").unwrap();
+                path.canonicalize().unwrap()
+            })
+            .collect();
+
+        let mut config = Config::default();
+        config.add_recent(&paths[0]);
+        config.add_recent(&paths[1]);
+        config.add_recent(&paths[2]);
+        config.add_recent(&paths[0]);
+        assert_eq!(
+            config.recent_files(),
+            vec![paths[0].clone(), paths[2].clone(), paths[1].clone()]
+        );
+
+        // Deleted files are skipped but kept in the stored list
+        fs::remove_file(&paths[2]).unwrap();
+        assert_eq!(
+            config.recent_files(),
+            vec![paths[0].clone(), paths[1].clone()]
+        );
+
+        // Saved to the state file, not config.toml
+        assert!(!toml::to_string_pretty(&config).unwrap().contains("a.md"));
+        let state_path = dir.path().join("state.toml");
+        State {
+            recent_files: Some(config.history.recent_files.clone()),
+            ..State::default()
+        }
+        .write(&state_path)
+        .unwrap();
+        let state = State::read(&state_path).unwrap();
+        assert_eq!(state.recent_files, Some(config.history.recent_files));
+    }
+
+    #[test]
+    fn test_recent_files_are_capped() {
+        let mut config = Config::default();
+        for i in 0..MAX_RECENT_FILES + 5 {
+            config.add_recent(Path::new(&format!("/nonexistent/{}.md", i)));
+        }
+        assert_eq!(config.history.recent_files.len(), MAX_RECENT_FILES);
+        assert_eq!(
+            config.history.recent_files[0],
+            PathBuf::from(format!("/nonexistent/{}.md", MAX_RECENT_FILES + 4))
+        );
+    }
+
+    #[test]
+    fn test_file_positions_follow_recent_files() {
+        let mut config = Config::default();
+        let a = PathBuf::from("/nonexistent/a.md");
+        let b = PathBuf::from("/nonexistent/b.md");
+        let position = FilePosition {
+            heading: Some("Usage".to_string()),
+            content_scroll: 12,
+        };
+
+        config.add_recent(&a);
+        config.set_file_position(&a, position.clone());
+        // Files outside the recent list are not tracked
+        config.set_file_position(&b, position.clone());
+        assert_eq!(config.file_position(&a), Some(&position));
+        assert_eq!(config.file_position(&b), None);
+
+        // Saved to the state file, not config.toml
+        let toml = toml::to_string_pretty(&config).unwrap();
+        assert!(!toml.contains("Usage"));
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join("state.toml");
+        State {
+            positions: config.history.positions.clone(),
+            ..State::default()
+        }
+        .write(&state_path)
+        .unwrap();
+        let state = State::read(&state_path).unwrap();
+        assert_eq!(state.positions.get(&a), Some(&position));
+
+        // Positions written to config.toml by older versions are still read
+        let legacy = "[history]\nrecent_files = [\"/nonexistent/a.md\"]\n\n\
+                      [history.positions.\"/nonexistent/a.md\"]\nheading = \"Usage\"\n\
+                      content_scroll = 12\n";
+        let loaded: Config = toml::from_str(legacy).unwrap();
+        assert_eq!(loaded.file_position(&a), Some(&position));
+
+        // Dropping out of the recent list forgets the position on next save
+        config.history.recent_files.clear();
+        config.add_recent(&b);
+        config.set_file_position(&b, FilePosition::default());
+        assert_eq!(config.file_position(&a), None);
+    }
+
+    #[test]
+    fn test_cli_defaults_are_optional() {
+        let config: Config = toml::from_str("[cli]\ndefault_output = \"json\"\n").unwrap();
+        assert_eq!(config.cli.default_output.as_deref(), Some("json"));
+        assert_eq!(config.cli.default_query_output, None);
+
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.cli.default_output.is_none());
+        assert!(!toml::to_string(&config).unwrap().contains("default_output"));
+    }
+}
@@ -2,7 +2,13 @@
 //!
 //! Provides robust stdin reading with UTF-8 validation and format detection.
 //! Includes security limits to prevent denial-of-service via large inputs.
+//!
+//! Org-mode and AsciiDoc input is converted to markdown on a best-effort
+//! basis: headings and code blocks are translated so the outline works, and
+//! everything else passes through unchanged. Piped `find` and `ls -R`
+//! listings become a heading per directory with its files listed below.
 
+use std::collections::BTreeMap;
 use std::io::{self, BufRead, IsTerminal};
 use std::path::Path;
 
@@ -148,20 +154,390 @@ pub fn determine_input_source(file_path: Option<&Path>) -> Result<InputSource, I
     }
 }
 
+/// Markup formats treemd can read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    Markdown,
+    /// Emacs org-mode (`* Heading`)
+    Org,
+    /// AsciiDoc (`= Title`, `== Section`)
+    AsciiDoc,
+}
+
+impl InputFormat {
+    /// Detect the format from the file extension, falling back to the
+    /// leading lines of `content` and then to markdown.
+    pub fn detect(path: Option<&Path>, content: &str) -> Self {
+        let extension = path
+            .and_then(|p| p.extension())
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+        match extension.as_deref() {
+            Some("org") => return InputFormat::Org,
+            Some("adoc" | "asciidoc" | "asc") => return InputFormat::AsciiDoc,
+            Some("md" | "markdown" | "mdown") => return InputFormat::Markdown,
+            _ => {}
+        }
+
+        let first_line = content
+            .lines()
+            .map(str::trim_end)
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or("");
+        if is_org_keyword(first_line) {
+            return InputFormat::Org;
+        }
+        if adoc_heading(first_line).is_some() {
+            return InputFormat::AsciiDoc;
+        }
+        // A lone "* item" could be a markdown list; nested stars could not
+        let has_org_heading = |depth: usize| {
+            content
+                .lines()
+                .any(|line| org_heading(line).is_some_and(|(level, _)| level == depth))
+        };
+        if !content.lines().any(|line| line.starts_with("# "))
+            && has_org_heading(1)
+            && has_org_heading(2)
+        {
+            return InputFormat::Org;
+        }
+        InputFormat::Markdown
+    }
+}
+
+/// Convert `content` written in `format` to markdown.
+///
+/// Headings become ATX headings and code blocks become fenced blocks; other
+/// syntax is left as-is.
+///
+/// ```
+/// use treemd::input::{InputFormat, to_markdown};
+///
+/// let org = "* Intro\n** Setup :tag:\n#+BEGIN_SRC sh\n* not a heading\n#+END_SRC\n";
+/// assert_eq!(
+///     to_markdown(org.to_string(), InputFormat::Org),
+///     "# Intro\n## Setup\n```sh\n* not a heading\n```\n"
+/// );
+/// ```
+pub fn to_markdown(content: String, format: InputFormat) -> String {
+    match format {
+        InputFormat::Markdown => content,
+        InputFormat::Org => convert_lines(&content, convert_org_line),
+        InputFormat::AsciiDoc => convert_lines(&content, convert_adoc_line),
+    }
+}
+
+/// Convert a file's content to markdown based on its path and content
+pub fn markdown_for_file(path: &Path, content: String) -> String {
+    let format = InputFormat::detect(Some(path), &content);
+    to_markdown(content, format)
+}
+
+/// State carried between lines while converting
+#[derive(Default)]
+struct ConvertState {
+    /// Closing delimiter of the code block being copied verbatim
+    in_code: Option<&'static str>,
+    /// AsciiDoc `[source,lang]` line waiting for the block it describes
+    pending_source: Option<String>,
+}
+
+fn convert_lines(content: &str, convert: fn(&str, &mut ConvertState) -> Option<String>) -> String {
+    let mut state = ConvertState::default();
+    let mut out = String::with_capacity(content.len());
+    for line in content.lines() {
+        if let Some(converted) = convert(line, &mut state) {
+            out.push_str(&converted);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn markdown_heading(level: usize, text: &str) -> String {
+    format!("{} {}", "#".repeat(level.min(6)), text)
+}
+
+/// `#+KEYWORD:` lines such as `#+TITLE: Notes`
+fn is_org_keyword(line: &str) -> bool {
+    line.strip_prefix("#+")
+        .and_then(|rest| rest.split_once(':'))
+        .is_some_and(|(key, _)| {
+            !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_')
+        })
+}
+
+/// Level and title of an org heading, with trailing `:tags:` removed
+fn org_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '*').count();
+    let title = line[level..].strip_prefix(' ')?.trim();
+    if level == 0 || title.is_empty() {
+        return None;
+    }
+    let title = match title.rsplit_once(char::is_whitespace) {
+        Some((rest, tags)) if tags.len() > 2 && tags.starts_with(':') && tags.ends_with(':') => {
+            rest.trim_end()
+        }
+        _ => title,
+    };
+    Some((level, title))
+}
+
+fn convert_org_line(line: &str, state: &mut ConvertState) -> Option<String> {
+    let lower = line.trim().to_lowercase();
+    if let Some(end) = state.in_code {
+        if lower.starts_with(end) {
+            state.in_code = None;
+            return Some("```".to_string());
+        }
+        return Some(line.to_string());
+    }
+    if let Some(rest) = lower.strip_prefix("#+begin_src") {
+        state.in_code = Some("#+end_src");
+        let language = rest.split_whitespace().next().unwrap_or("");
+        return Some(format!("```{}", language));
+    }
+    if lower.starts_with("#+begin_example") {
+        state.in_code = Some("#+end_example");
+        return Some("```".to_string());
+    }
+    Some(match org_heading(line) {
+        Some((level, title)) => markdown_heading(level, title),
+        None => line.to_string(),
+    })
+}
+
+/// Level and title of an AsciiDoc heading (`= Title` is level 1)
+fn adoc_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '=').count();
+    let title = line[level..].strip_prefix(' ')?.trim();
+    (level > 0 && !title.is_empty()).then_some((level, title))
+}
+
+fn convert_adoc_line(line: &str, state: &mut ConvertState) -> Option<String> {
+    let trimmed = line.trim_end();
+    if let Some(end) = state.in_code {
+        if trimmed == end {
+            state.in_code = None;
+            return Some("```".to_string());
+        }
+        return Some(line.to_string());
+    }
+    if trimmed.starts_with("[source") && trimmed.ends_with(']') {
+        state.pending_source = Some(trimmed.to_string());
+        return None;
+    }
+    let fence = match trimmed {
+        "----" => Some("----"),
+        "...." => Some("...."),
+        _ => None,
+    };
+    if let Some(fence) = fence {
+        state.in_code = Some(fence);
+        // "[source,rust]" -> "rust"
+        let language = state
+            .pending_source
+            .take()
+            .and_then(|attrs| {
+                let attrs = attrs.trim_start_matches('[').trim_end_matches(']');
+                attrs.split(',').nth(1).map(|lang| lang.trim().to_string())
+            })
+            .unwrap_or_default();
+        return Some(format!("```{}", language));
+    }
+    if let Some(attrs) = state.pending_source.take() {
+        // The attribute line did not introduce a block; keep it
+        return Some(match convert_adoc_line(line, state) {
+            Some(rest) => format!("{}\n{}", attrs, rest),
+            None => attrs,
+        });
+    }
+    Some(match adoc_heading(line) {
+        Some((level, title)) => markdown_heading(level, title),
+        None => line.to_string(),
+    })
+}
+
+/// Directories of a `find` or `ls -R` listing; entries without children
+/// are files
+#[derive(Default)]
+struct DirTree {
+    children: BTreeMap<String, DirTree>,
+}
+
+impl DirTree {
+    /// Add the entry at `path`, relative to this directory, creating its
+    /// parent directories
+    fn insert(&mut self, path: &str) {
+        let mut node = self;
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            node = node.children.entry(name.to_string()).or_default();
+        }
+    }
+
+    /// Write the heading for this directory, its files, then its
+    /// subdirectories one level down. Below h6 the heading shows the path
+    /// from the last h6 directory instead.
+    fn write_dir(&self, label: &str, level: usize, out: &mut String) {
+        out.push_str(&markdown_heading(level, &format!("{}/", label)));
+        out.push_str("\n\n");
+        let (dirs, files): (Vec<_>, Vec<_>) = self
+            .children
+            .iter()
+            .partition(|(_, node)| !node.children.is_empty());
+        for (name, _) in &files {
+            out.push_str(&format!("- {}\n", name));
+        }
+        if !files.is_empty() {
+            out.push('\n');
+        }
+        for (name, dir) in dirs {
+            let label = if level >= 6 {
+                format!("{}/{}", label, name)
+            } else {
+                name.clone()
+            };
+            dir.write_dir(&label, level + 1, out);
+        }
+    }
+}
+
+/// The part of `path` inside `root`, empty for `root` itself
+fn relative_to<'a>(path: &'a str, root: &str) -> Option<&'a str> {
+    let rest = path.strip_prefix(root)?;
+    (rest.is_empty() || rest.starts_with('/') || root.ends_with('/')).then_some(rest)
+}
+
+/// Root and tree of `find` output: one path per line, all inside a common
+/// directory, like `.` then `./src/main.rs`
+fn parse_find_listing(content: &str) -> Option<(&str, DirTree)> {
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let first = *lines.first()?;
+    if lines.len() < 2
+        || !lines.iter().any(|line| line.contains('/'))
+        || lines
+            .iter()
+            .any(|line| line.starts_with(char::is_whitespace))
+    {
+        return None;
+    }
+
+    // Directory components every path starts with
+    let mut common: Vec<&str> = first.split('/').collect();
+    for line in &lines[1..] {
+        let shared = common
+            .iter()
+            .zip(line.split('/'))
+            .take_while(|(a, b)| **a == *b)
+            .count();
+        common.truncate(shared);
+    }
+    if common.is_empty() {
+        return None;
+    }
+    let root = &first[..common.join("/").len()];
+
+    let mut tree = DirTree::default();
+    for line in lines {
+        tree.insert(relative_to(line, root)?);
+    }
+    Some((root, tree))
+}
+
+/// Root and tree of `ls -R` output: blocks of names under `dir:` headers,
+/// separated by blank lines, with every header inside the first one
+fn parse_ls_listing(content: &str) -> Option<(&str, DirTree)> {
+    let mut tree = DirTree::default();
+    let mut root: Option<&str> = None;
+    let mut current: Option<&str> = None;
+    let mut headers = 0;
+
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            current = None;
+            continue;
+        }
+        match current {
+            None => {
+                let header = line.strip_suffix(':')?;
+                let dir = relative_to(header, root.get_or_insert(header))?;
+                tree.insert(dir);
+                current = Some(dir);
+                headers += 1;
+            }
+            Some(dir) => {
+                // `ls -RF` marks directories with a trailing slash
+                let name = line.strip_suffix('/').unwrap_or(line);
+                if name.contains('/') {
+                    return None;
+                }
+                tree.insert(&format!("{}/{}", dir, name));
+            }
+        }
+    }
+    root.filter(|_| headers >= 2).map(|root| (root, tree))
+}
+
+/// Convert a piped `find` or `ls -R` listing to markdown, if it is one.
+///
+/// ```
+/// use treemd::input::listing_to_markdown;
+///
+/// let find = ".\n./Cargo.toml\n./src\n./src/main.rs\n";
+/// assert_eq!(
+///     listing_to_markdown(find).unwrap(),
+///     "# ./\n\n- Cargo.toml\n\n## src/\n\n- main.rs\n\n"
+/// );
+/// assert!(listing_to_markdown("Just some text\n").is_none());
+/// ```
+pub fn listing_to_markdown(content: &str) -> Option<String> {
+    let (root, tree) = parse_ls_listing(content).or_else(|| parse_find_listing(content))?;
+    let mut out = String::new();
+    tree.write_dir(root.trim_end_matches('/'), 1, &mut out);
+    Some(out)
+}
+
 /// Process input and return content ready for markdown parsing
 ///
 /// Supports:
-/// - Raw markdown (passed through)
-/// - Plain text (wrapped in markdown heading)
+/// - Files (passed through as-is, even when empty or without headings)
+/// - Raw markdown from stdin (passed through)
+/// - Org-mode and AsciiDoc (headings and code blocks converted to markdown)
+/// - `find` and `ls -R` listings from stdin (one heading per directory)
+/// - Plain text from stdin (wrapped in markdown heading)
 pub fn process_input(source: InputSource) -> Result<String, Box<dyn std::error::Error>> {
+    process_input_from(source, None)
+}
+
+/// Like [`process_input`], using `path`'s extension to detect the format
+pub fn process_input_from(
+    source: InputSource,
+    path: Option<&Path>,
+) -> Result<String, Box<dyn std::error::Error>> {
     let content = match source {
-        InputSource::File(c) | InputSource::Stdin(c) => c,
+        // Files are converted only when they are org-mode or AsciiDoc.
+        // Markdown passes through unchanged, even without headings: those
+        // documents are shown under the overview entry, not wrapped in one
+        InputSource::File(c) => {
+            let format = InputFormat::detect(path, &c);
+            return Ok(to_markdown(c, format));
+        }
+        InputSource::Stdin(c) => c,
     };
 
+    let format = InputFormat::detect(path, &content);
+    if format != InputFormat::Markdown {
+        return Ok(to_markdown(content, format));
+    }
+
     // Check if content looks like markdown (has headings)
-    if content.trim_start().starts_with('#') || content.contains("\n#") {
-        // Markdown content, pass through
+    if content.trim().is_empty() || content.trim_start().starts_with('#') || content.contains("\n#")
+    {
+        // Markdown (or blank) content, pass through
         Ok(content)
+    } else if let Some(markdown) = listing_to_markdown(&content) {
+        Ok(markdown)
     } else {
         // Plain text - wrap in a document heading for basic viewing
         let mut markdown = String::from("# Input\n\n");
@@ -192,4 +568,93 @@ mod tests {
         assert!(result.starts_with("# Input\n\n"));
         assert!(result.contains("Just some plain text"));
     }
+
+    #[test]
+    fn test_process_file_without_headings_passes_through() {
+        for content in ["", "   \n\t\n", "Body text only\n"] {
+            let source = InputSource::File(content.to_string());
+            assert_eq!(process_input(source).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn test_process_whitespace_stdin_passes_through() {
+        let source = InputSource::Stdin("  \n\n".to_string());
+        assert_eq!(process_input(source).unwrap(), "  \n\n");
+    }
+
+    #[test]
+    fn test_detect_format_by_extension_and_content() {
+        let detect =
+            |path: Option<&str>, content: &str| InputFormat::detect(path.map(Path::new), content);
+        assert_eq!(detect(Some("notes.org"), "text"), InputFormat::Org);
+        assert_eq!(detect(Some("guide.ADOC"), "text"), InputFormat::AsciiDoc);
+        assert_eq!(
+            detect(Some("README.md"), "= Title\n"),
+            InputFormat::Markdown
+        );
+        assert_eq!(detect(None, "#+TITLE: Notes\n* A\n"), InputFormat::Org);
+        assert_eq!(
+            detect(None, "\n= Guide\n\n== Install\n"),
+            InputFormat::AsciiDoc
+        );
+        assert_eq!(detect(None, "* A\n** B\n"), InputFormat::Org);
+        // Markdown bullet lists are not mistaken for org headings
+        assert_eq!(detect(None, "* one\n* two\n"), InputFormat::Markdown);
+        assert_eq!(detect(None, "# Title\n* A\n** B\n"), InputFormat::Markdown);
+    }
+
+    #[test]
+    fn test_asciidoc_converts_headings_and_listings() {
+        let adoc = "= Guide\n:toc:\n\n== Install\n\n[source,sh]\n----\n== not a heading\n----\n\n[NOTE]\nText\n";
+        let source = InputSource::File(adoc.to_string());
+        let markdown = process_input_from(source, Some(Path::new("guide.adoc"))).unwrap();
+        assert_eq!(
+            markdown,
+            "# Guide\n:toc:\n\n## Install\n\n```sh\n== not a heading\n```\n\n[NOTE]\nText\n"
+        );
+
+        let doc = crate::parse_markdown(&markdown);
+        let titles: Vec<_> = doc.headings.iter().map(|h| h.text.as_str()).collect();
+        assert_eq!(titles, ["Guide", "Install"]);
+    }
+
+    #[test]
+    fn test_org_from_stdin_is_converted() {
+        let org = "#+TITLE: Notes\n* Tasks :work:\n** TODO Write docs\n";
+        let markdown = process_input(InputSource::Stdin(org.to_string())).unwrap();
+        assert_eq!(markdown, "#+TITLE: Notes\n# Tasks\n## TODO Write docs\n");
+    }
+
+    #[test]
+    fn test_find_and_ls_listings_become_directory_outline() {
+        let find = ".\n./src\n./src/tui\n./src/tui/app.rs\n./src/main.rs\n./README.md\n";
+        let expected = "# ./\n\n- README.md\n\n## src/\n\n- main.rs\n\n### tui/\n\n- app.rs\n\n";
+        let markdown = process_input(InputSource::Stdin(find.to_string())).unwrap();
+        assert_eq!(markdown, expected);
+
+        let ls = ".:\nREADME.md\nsrc\n\n./src:\nmain.rs\ntui\n\n./src/tui:\napp.rs\n";
+        let markdown = process_input(InputSource::Stdin(ls.to_string())).unwrap();
+        assert_eq!(markdown, expected);
+
+        // Filtered find output is rooted at the directory the paths share
+        let find = "/home/me/src/lib.rs\n/home/me/src/cli/args.rs\n";
+        assert_eq!(
+            listing_to_markdown(find).unwrap(),
+            "# /home/me/src/\n\n- lib.rs\n\n## cli/\n\n- args.rs\n\n"
+        );
+    }
+
+    #[test]
+    fn test_text_that_is_not_a_listing_is_left_alone() {
+        for text in [
+            "Notes:\nbuy milk\n",
+            "see src/main.rs\nand src/lib.rs\n",
+            "a/x\nb/y\n",
+            "single/path\n",
+            "Todo:\nthings\n\nDone:\nstuff\n",
+        ] {
+            assert!(listing_to_markdown(text).is_none(), "{:?}", text);
+        }
+    }
 }
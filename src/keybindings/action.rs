@@ -67,6 +67,10 @@ pub enum Action {
     EnterDocSearch,
     /// Toggle between outline and document search modes
     ToggleSearchMode,
+    /// Toggle whether document search includes code blocks
+    ToggleSearchInCode,
+    /// Toggle whether outline search keeps headings under a match
+    ToggleSearchMatchPath,
     /// Exit current mode (generic escape)
     ExitMode,
 
@@ -77,6 +81,8 @@ pub enum Action {
     PreviousLink,
     /// Follow/activate the selected link
     FollowLink,
+    /// Open the selected link's target in the editor at the linked heading
+    EditLinkTarget,
     /// Start link search/filter
     LinkSearch,
 
@@ -85,6 +91,10 @@ pub enum Action {
     InteractiveNext,
     /// Move to previous interactive element
     InteractivePrevious,
+    /// Move to next element of the same type (e.g. next code block)
+    InteractiveNextOfType,
+    /// Move to previous element of the same type
+    InteractivePrevOfType,
     /// Activate/toggle the selected element
     InteractiveActivate,
     /// Move to next link within element
@@ -99,6 +109,8 @@ pub enum Action {
     // === View ===
     /// Toggle raw markdown source view
     ToggleRawSource,
+    /// Toggle focus mode (whole document with only the selected section expanded)
+    ToggleFocusMode,
     /// Toggle help popup
     ToggleHelp,
     /// Toggle theme picker
@@ -109,8 +121,16 @@ pub enum Action {
     // === Clipboard ===
     /// Copy current section content
     CopyContent,
+    /// Name a register (`"a`) for the next copy instead of the clipboard
+    SelectRegister,
+    /// Copy current section as rich HTML
+    CopyContentHtml,
     /// Copy anchor/heading text
     CopyAnchor,
+    /// Copy the heading as `path/to/file.md#anchor` from the project root
+    CopyFileAnchor,
+    /// Copy the table under the cursor as CSV (in table mode)
+    CopyTableCsv,
 
     // === File Operations ===
     /// Navigate back in file history
@@ -123,6 +143,12 @@ pub enum Action {
     UndoEdit,
     /// Open file picker to switch between markdown files
     OpenFilePicker,
+    /// Show the current file's directory in the system file manager
+    OpenContainingDir,
+    /// Reload the current file from disk
+    Reload,
+    /// Toggle reloading automatically when the file changes on disk
+    ToggleAutoReload,
 
     // === Dialog Actions ===
     /// Confirm action in dialog
@@ -197,6 +223,11 @@ pub enum Action {
     NextMatch,
     /// Previous search match
     PrevMatch,
+
+    // === Configuration ===
+    /// Remove the key's binding (config only; written as `"None"` or `"Unbind"`)
+    #[serde(alias = "None")]
+    Unbind,
 }
 
 impl Action {
@@ -234,17 +265,22 @@ impl Action {
             Action::EnterSearchMode => "Search/filter headings",
             Action::EnterDocSearch => "Search document content",
             Action::ToggleSearchMode => "Toggle outline/content search",
+            Action::ToggleSearchInCode => "Toggle searching inside code blocks",
+            Action::ToggleSearchMatchPath => "Toggle matching outline search on heading paths",
             Action::ExitMode => "Exit current mode",
 
             // Link navigation
             Action::NextLink => "Next link",
             Action::PreviousLink => "Previous link",
             Action::FollowLink => "Follow link",
+            Action::EditLinkTarget => "Edit link target",
             Action::LinkSearch => "Search links",
 
             // Interactive mode
             Action::InteractiveNext => "Next element",
             Action::InteractivePrevious => "Previous element",
+            Action::InteractiveNextOfType => "Next element of same type",
+            Action::InteractivePrevOfType => "Previous element of same type",
             Action::InteractiveActivate => "Activate element",
             Action::InteractiveNextLink => "Next link in element",
             Action::InteractivePreviousLink => "Previous link in element",
@@ -253,13 +289,18 @@ impl Action {
 
             // View
             Action::ToggleRawSource => "Toggle raw source view",
+            Action::ToggleFocusMode => "Toggle focus mode",
             Action::ToggleHelp => "Toggle help",
             Action::ToggleThemePicker => "Open theme picker",
             Action::ApplyTheme => "Apply selected theme",
 
             // Clipboard
             Action::CopyContent => "Copy content",
+            Action::SelectRegister => "Copy into a register",
+            Action::CopyContentHtml => "Copy content as HTML",
+            Action::CopyTableCsv => "Copy table as CSV",
             Action::CopyAnchor => "Copy heading/anchor",
+            Action::CopyFileAnchor => "Copy file#anchor reference",
 
             // File operations
             Action::GoBack => "Go back",
@@ -267,6 +308,9 @@ impl Action {
             Action::OpenInEditor => "Open in editor",
             Action::UndoEdit => "Undo last edit",
             Action::OpenFilePicker => "Open file picker",
+            Action::OpenContainingDir => "Open containing folder",
+            Action::Reload => "Reload file",
+            Action::ToggleAutoReload => "Toggle auto-reload",
 
             // Dialog
             Action::ConfirmAction => "Confirm",
@@ -323,6 +367,7 @@ impl Action {
             // Doc search
             Action::NextMatch => "Next search match",
             Action::PrevMatch => "Previous search match",
+            Action::Unbind => "Remove key binding",
         }
     }
 
@@ -355,14 +400,20 @@ impl Action {
             | Action::EnterSearchMode
             | Action::EnterDocSearch
             | Action::ToggleSearchMode
+            | Action::ToggleSearchInCode
+            | Action::ToggleSearchMatchPath
             | Action::ExitMode => "Modes",
 
-            Action::NextLink | Action::PreviousLink | Action::FollowLink | Action::LinkSearch => {
-                "Links"
-            }
+            Action::NextLink
+            | Action::PreviousLink
+            | Action::FollowLink
+            | Action::EditLinkTarget
+            | Action::LinkSearch => "Links",
 
             Action::InteractiveNext
             | Action::InteractivePrevious
+            | Action::InteractiveNextOfType
+            | Action::InteractivePrevOfType
             | Action::InteractiveActivate
             | Action::InteractiveNextLink
             | Action::InteractivePreviousLink
@@ -370,17 +421,26 @@ impl Action {
             | Action::InteractiveRight => "Interactive",
 
             Action::ToggleRawSource
+            | Action::ToggleFocusMode
             | Action::ToggleHelp
             | Action::ToggleThemePicker
             | Action::ApplyTheme => "View",
 
-            Action::CopyContent | Action::CopyAnchor => "Clipboard",
+            Action::CopyContent
+            | Action::SelectRegister
+            | Action::CopyContentHtml
+            | Action::CopyAnchor
+            | Action::CopyFileAnchor
+            | Action::CopyTableCsv => "Clipboard",
 
             Action::GoBack
             | Action::GoForward
             | Action::OpenInEditor
             | Action::UndoEdit
-            | Action::OpenFilePicker => "Files",
+            | Action::OpenFilePicker
+            | Action::OpenContainingDir
+            | Action::Reload
+            | Action::ToggleAutoReload => "Files",
 
             Action::ConfirmAction
             | Action::CancelAction
@@ -421,6 +481,8 @@ impl Action {
             | Action::CommandPaletteNext
             | Action::CommandPalettePrev
             | Action::CommandPaletteAutocomplete => "Command Palette",
+
+            Action::Unbind => "Configuration",
         }
     }
 }
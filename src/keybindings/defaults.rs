@@ -106,12 +106,16 @@ fn add_normal_mode(kb: &mut Keybindings) {
 
     // View
     bind(kb, Normal, "r", ToggleRawSource);
+    bind(kb, Normal, "z", ToggleFocusMode);
     bind(kb, Normal, "t", ToggleThemePicker);
     bind(kb, Normal, "?", ToggleHelp);
 
     // Clipboard
     bind(kb, Normal, "y", CopyContent);
+    bind(kb, Normal, "\"", SelectRegister);
+    bind(kb, Normal, "Alt+y", CopyContentHtml);
     bind(kb, Normal, "Y", CopyAnchor);
+    bind(kb, Normal, "Alt+Y", CopyFileAnchor);
 
     // File operations
     bind(kb, Normal, "b", GoBack);
@@ -120,6 +124,9 @@ fn add_normal_mode(kb: &mut Keybindings) {
     bind(kb, Normal, "e", OpenInEditor);
     bind(kb, Normal, "Ctrl+o", OpenFilePicker);
     bind(kb, Normal, "o", OpenFilePicker);
+    bind(kb, Normal, "O", OpenContainingDir);
+    bind(kb, Normal, "R", Reload);
+    bind(kb, Normal, "Alt+r", ToggleAutoReload);
 
     // Application
     bind(kb, Normal, "q", Quit);
@@ -206,6 +213,8 @@ fn add_interactive_mode(kb: &mut Keybindings) {
     bind(kb, Interactive, "Down", InteractiveNext);
     bind(kb, Interactive, "k", InteractivePrevious);
     bind(kb, Interactive, "Up", InteractivePrevious);
+    bind(kb, Interactive, "]", InteractiveNextOfType);
+    bind(kb, Interactive, "[", InteractivePrevOfType);
 
     // Link navigation within element
     bind(kb, Interactive, "Tab", InteractiveNextLink);
@@ -234,6 +243,7 @@ fn add_interactive_mode(kb: &mut Keybindings) {
 
     // Clipboard
     bind(kb, Interactive, "y", CopyContent);
+    bind(kb, Interactive, "\"", SelectRegister);
 
     // Undo last edit
     bind(kb, Interactive, "Ctrl+z", UndoEdit);
@@ -258,10 +268,13 @@ fn add_interactive_table_mode(kb: &mut Keybindings) {
     bind(kb, InteractiveTable, "Down", InteractiveNext);
     bind(kb, InteractiveTable, "k", InteractivePrevious);
     bind(kb, InteractiveTable, "Up", InteractivePrevious);
+    bind(kb, InteractiveTable, "PageDown", PageDown);
+    bind(kb, InteractiveTable, "PageUp", PageUp);
 
     // Clipboard
     bind(kb, InteractiveTable, "y", CopyContent);
     bind(kb, InteractiveTable, "Y", CopyAnchor);
+    bind(kb, InteractiveTable, "c", CopyTableCsv);
 
     // View toggle
     bind(kb, InteractiveTable, "r", ToggleRawSource);
@@ -293,6 +306,7 @@ fn add_link_follow_mode(kb: &mut Keybindings) {
 
     // Actions
     bind(kb, LinkFollow, "Enter", FollowLink);
+    bind(kb, LinkFollow, "e", EditLinkTarget);
     bind(kb, LinkFollow, "/", LinkSearch);
     bind(kb, LinkFollow, "p", JumpToParent);
 
@@ -309,6 +323,7 @@ fn add_link_follow_mode(kb: &mut Keybindings) {
 
     // Clipboard
     bind(kb, LinkFollow, "y", CopyContent);
+    bind(kb, LinkFollow, "\"", SelectRegister);
     bind(kb, LinkFollow, "Y", CopyAnchor);
 
     // Quit
@@ -348,6 +363,9 @@ fn add_search_mode(kb: &mut Keybindings) {
 
     // Toggle to doc search
     bind(kb, Search, "Tab", ToggleSearchMode);
+
+    // Also keep headings under a matching heading
+    bind(kb, Search, "Alt+p", ToggleSearchMatchPath);
 }
 
 fn add_doc_search_mode(kb: &mut Keybindings) {
@@ -373,6 +391,9 @@ fn add_doc_search_mode(kb: &mut Keybindings) {
     bind(kb, DocSearch, "Tab", ToggleSearchMode);
     bind(kb, DocSearch, "Shift+Tab", PrevMatch);
 
+    // Include or skip matches inside code blocks
+    bind(kb, DocSearch, "Alt+c", ToggleSearchInCode);
+
     // Re-enter search input with /
     bind(kb, DocSearch, "/", EnterDocSearch);
 }
@@ -546,6 +567,13 @@ mod tests {
             ),
             Some(Action::InteractiveNextLink)
         );
+        assert_eq!(
+            kb.dispatch(
+                KeybindingMode::Interactive,
+                make_key_event(KeyCode::Char(']'), KeyModifiers::NONE)
+            ),
+            Some(Action::InteractiveNextOfType)
+        );
     }
 
     #[test]
@@ -19,10 +19,23 @@
 //! "k" = "Previous"
 //! "Ctrl+c" = "Quit"
 //! "g g" = "First"  # Multi-key sequences supported!
+//! "q" = "None"     # Remove a default binding
 //!
 //! [keybindings.Interactive]
 //! "Escape" = "ExitInteractiveMode"
 //! ```
+//!
+//! Bindings may start with `<leader>`, which expands to the configured
+//! `leader` key (Space by default). The leader's own single-key binding is
+//! dropped only in modes that use `<leader>` bindings:
+//!
+//! ```toml
+//! [keybindings]
+//! leader = "Space"
+//!
+//! [keybindings.Normal]
+//! "<leader> f" = "ToggleFocusMode"
+//! ```
 
 mod action;
 mod defaults;
@@ -30,9 +43,10 @@ mod defaults;
 pub use action::Action;
 
 use crossterm::event::KeyEvent;
-use keybinds::Keybinds;
+use keybinds::{KeySeq, Keybind, Keybinds};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// Application modes that have their own keybinding sets
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -145,6 +159,13 @@ impl Keybindings {
         }
     }
 
+    /// Set how long a multi-key sequence waits for its next key
+    pub fn set_sequence_timeout(&mut self, timeout: Duration) {
+        for kb in self.bindings.values_mut() {
+            kb.set_timeout(timeout);
+        }
+    }
+
     /// Get the keybinds for a specific mode
     pub fn get_mode_keybinds(&self, mode: KeybindingMode) -> Option<&Keybinds<Action>> {
         self.bindings.get(&mode)
@@ -198,18 +219,71 @@ impl Keybindings {
     }
 
     /// Merge another keybindings set into this one (other takes precedence)
+    ///
+    /// Bindings for a key sequence replace any existing binding for it, and
+    /// [`Action::Unbind`] just removes it. `<leader>` in key sequences expands
+    /// to `other`'s leader key. Modes with leader bindings lose their
+    /// single-key binding for the leader, which would otherwise always match
+    /// first.
     pub fn merge(&mut self, other: &KeybindingsConfig) -> Result<(), String> {
-        for (mode, mode_bindings) in &other.0 {
-            let kb = self.bindings.entry(*mode).or_default();
+        let leader = other.leader();
+        for (mode, mode_bindings) in &other.modes {
+            let mut replaced = Vec::new();
+            let mut added = Vec::new();
+            let mut leader_used = false;
             for (key_str, action) in mode_bindings {
-                kb.bind(key_str, *action)
+                let (key_str, uses_leader) = expand_leader(key_str, leader);
+                if uses_leader && !leader_used {
+                    leader_used = true;
+                    replaced.push(
+                        leader
+                            .parse::<KeySeq>()
+                            .map_err(|e| format!("Invalid leader key '{}': {}", leader, e))?,
+                    );
+                }
+                let seq: KeySeq = key_str
+                    .parse()
                     .map_err(|e| format!("Invalid key '{}': {}", key_str, e))?;
+                if *action != Action::Unbind {
+                    added.push(Keybind::new(seq.clone(), *action));
+                }
+                replaced.push(seq);
             }
+
+            let kb = self.bindings.entry(*mode).or_default();
+            let mut binds = std::mem::take(kb).into_vec();
+            binds.retain(|bind| !replaced.contains(&bind.seq));
+            binds.extend(added);
+            *kb = Keybinds::new(binds);
         }
         Ok(())
     }
 }
 
+/// Replace `<leader>` tokens in a key sequence with `leader`.
+///
+/// Returns the expanded sequence and whether it contained a leader token.
+fn expand_leader(key_str: &str, leader: &str) -> (String, bool) {
+    let mut uses_leader = false;
+    let expanded = key_str
+        .split_whitespace()
+        .map(|token| {
+            if token.eq_ignore_ascii_case(LEADER_TOKEN) {
+                uses_leader = true;
+                leader
+            } else {
+                token
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    if uses_leader {
+        (expanded, true)
+    } else {
+        (key_str.to_string(), false)
+    }
+}
+
 /// Format a key sequence for display
 fn format_key_sequence(seq: &keybinds::KeySeq) -> String {
     seq.as_slice()
@@ -284,11 +358,29 @@ pub fn format_key_compact(key: &str) -> String {
     key.to_string()
 }
 
+/// Placeholder in key sequences for the configured leader key
+const LEADER_TOKEN: &str = "<leader>";
+
+/// Leader key used when none is configured
+pub const DEFAULT_LEADER: &str = "Space";
+
 /// Configuration format for keybindings (uses string keys for TOML compatibility)
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct KeybindingsConfig(pub HashMap<KeybindingMode, HashMap<String, Action>>);
+pub struct KeybindingsConfig {
+    /// Key that `<leader>` expands to (defaults to [`DEFAULT_LEADER`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader: Option<String>,
+    /// Bindings per mode
+    #[serde(flatten)]
+    pub modes: HashMap<KeybindingMode, HashMap<String, Action>>,
+}
 
 impl KeybindingsConfig {
+    /// The configured leader key
+    pub fn leader(&self) -> &str {
+        self.leader.as_deref().unwrap_or(DEFAULT_LEADER)
+    }
+
     /// Convert to Keybindings, using defaults for any missing bindings
     pub fn to_keybindings(&self) -> Keybindings {
         let mut keybindings = Keybindings::default();
@@ -301,7 +393,7 @@ impl KeybindingsConfig {
 
     /// Check if the config is empty
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.modes.is_empty()
     }
 }
 
@@ -362,6 +454,13 @@ mod tests {
             make_key_event(KeyCode::Char('x'), KeyModifiers::NONE),
         );
         assert!(action.is_none() || action == Some(Action::Next)); // May match or not
+
+        // Terminals report Shift along with Alt for an uppercase letter
+        let action = kb.dispatch(
+            KeybindingMode::Normal,
+            make_key_event(KeyCode::Char('Y'), KeyModifiers::ALT | KeyModifiers::SHIFT),
+        );
+        assert_eq!(action, Some(Action::CopyFileAnchor));
     }
 
     #[test]
@@ -404,4 +503,90 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_leader_bindings_expand() {
+        let space = || make_key_event(KeyCode::Char(' '), KeyModifiers::NONE);
+        let f = || make_key_event(KeyCode::Char('f'), KeyModifiers::NONE);
+
+        // Without leader bindings, Space keeps its default action
+        let mut kb = KeybindingsConfig::default().to_keybindings();
+        assert_eq!(
+            kb.dispatch(KeybindingMode::Normal, space()),
+            Some(Action::ToggleExpand)
+        );
+
+        let config: KeybindingsConfig = toml::from_str(
+            r#"
+            [Normal]
+            "<leader> f" = "ToggleFocusMode"
+            "<Leader> <leader>" = "First"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.leader(), DEFAULT_LEADER);
+        let mut kb = config.to_keybindings();
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, space()), None);
+        assert_eq!(
+            kb.dispatch(KeybindingMode::Normal, f()),
+            Some(Action::ToggleFocusMode)
+        );
+        kb.dispatch(KeybindingMode::Normal, space());
+        assert_eq!(
+            kb.dispatch(KeybindingMode::Normal, space()),
+            Some(Action::First)
+        );
+        // Modes without leader bindings are untouched
+        assert_eq!(
+            kb.dispatch(KeybindingMode::Interactive, space()),
+            Some(Action::InteractiveActivate)
+        );
+
+        let config: KeybindingsConfig = toml::from_str(
+            r#"
+            leader = "Ctrl+x"
+
+            [Normal]
+            "<leader> f" = "ToggleFocusMode"
+            "#,
+        )
+        .unwrap();
+        let mut kb = config.to_keybindings();
+        assert_eq!(
+            kb.dispatch(KeybindingMode::Normal, space()),
+            Some(Action::ToggleExpand)
+        );
+        kb.dispatch(
+            KeybindingMode::Normal,
+            make_key_event(KeyCode::Char('x'), KeyModifiers::CONTROL),
+        );
+        assert_eq!(
+            kb.dispatch(KeybindingMode::Normal, f()),
+            Some(Action::ToggleFocusMode)
+        );
+    }
+
+    #[test]
+    fn test_unbind_and_override_defaults() {
+        let q = || make_key_event(KeyCode::Char('q'), KeyModifiers::NONE);
+        let j = || make_key_event(KeyCode::Char('j'), KeyModifiers::NONE);
+
+        let config: KeybindingsConfig = toml::from_str(
+            r#"
+            [Normal]
+            "q" = "None"
+            "j" = "Previous"
+            "#,
+        )
+        .unwrap();
+        let mut kb = config.to_keybindings();
+
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, q()), None);
+        assert_eq!(
+            kb.dispatch(KeybindingMode::Normal, j()),
+            Some(Action::Previous)
+        );
+        // Other modes keep their defaults
+        assert_eq!(kb.dispatch(KeybindingMode::Help, q()), Some(Action::Quit));
+    }
 }
@@ -14,6 +14,17 @@
 //! - Extract sections by heading name
 //! - Interactive TUI with dual-pane interface
 //! - Syntax-highlighted code blocks (50+ languages)
+//! - Headless markdown rendering to ratatui text
+//!
+//! ## Cargo features
+//!
+//! - `native` (default): the TUI, ratatui and ANSI rendering, code
+//!   highlighting, config file, stdin and file input. Without it only
+//!   [`parser`], [`query`] and the HTML and man page renderers in [`render`]
+//!   are built, with no terminal, clipboard or file I/O dependencies. A
+//!   `wasm32-unknown-unknown` build is still blocked by `turbovault-core`,
+//!   which needs tokio's file system and multi-threaded runtime.
+//! - `interactivity` (default): clipboard and opening URLs in the browser.
 //!
 //! ## Example
 //!
@@ -50,11 +61,13 @@
 /// Configuration module for persisting user preferences.
 ///
 /// Provides configuration management for theme choices, UI settings, and terminal preferences.
+#[cfg(feature = "native")]
 pub mod config;
 
 /// Input handling module for stdin and file sources.
 ///
 /// Provides robust input reading, format detection, and tree output parsing.
+#[cfg(feature = "native")]
 pub mod input;
 
 /// Parser module for markdown documents.
@@ -66,8 +79,34 @@ pub mod parser;
 ///
 /// Provides the App and UI rendering functionality for building interactive
 /// markdown viewers.
+#[cfg(feature = "native")]
 pub mod tui;
 
+/// Headless rendering module for reusing treemd's markdown styling.
+///
+/// Renders markdown to HTML and man pages, and with the `native` feature to
+/// ratatui `Text`, without constructing an `App`.
+///
+/// ## Example
+///
+/// ```rust
+/// use treemd::render;
+///
+/// assert_eq!(render::markdown_to_html("- item\n"), "<ul>\n<li>item</li>\n</ul>\n");
+/// ```
+///
+/// ```rust
+/// # #[cfg(feature = "native")] {
+/// use treemd::render;
+/// use treemd::tui::theme::Theme;
+///
+/// let text = render::markdown_to_text("# Title\n\n- item", &Theme::ocean_dark());
+/// assert!(!text.lines.is_empty());
+/// assert!(render::markdown_to_string("- item").contains("• item"));
+/// # }
+/// ```
+pub mod render;
+
 /// Query language module for jq-like markdown querying.
 ///
 /// Provides a powerful query language for navigating and extracting
@@ -88,9 +127,15 @@ pub mod query;
 ///
 /// Provides a flexible keybinding system that allows users to customize
 /// keyboard shortcuts via configuration files.
+#[cfg(feature = "native")]
 pub mod keybindings;
 
 // Re-export commonly used types for convenience
+#[cfg(feature = "native")]
 pub use config::Config;
-pub use parser::{Document, Heading, HeadingNode, parse_file, parse_markdown};
+#[cfg(feature = "native")]
+pub use parser::parse_file;
+pub use parser::{Document, Heading, HeadingNode, parse_markdown};
+pub use render::{CodeHighlight, HtmlOptions, render_html_fragment};
+#[cfg(feature = "native")]
 pub use tui::App;
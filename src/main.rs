@@ -30,10 +30,12 @@
 
 mod cli;
 
-use clap::Parser as ClapParser;
-use cli::{Cli, OutputFormat};
+use clap::{Parser as ClapParser, ValueEnum};
+use cli::{Cli, ExportKind, OutputFormat};
 use color_eyre::Result;
 use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process;
 use treemd::{Document, parser};
 
@@ -69,6 +71,11 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Several files with --list, --count or -q: report on each in turn
+    if args.file.len() > 1 && (args.list || args.count || args.query.is_some()) {
+        return handle_multi_file_mode(&args);
+    }
+
     // For TUI mode with piped stdin, we'll read stdin first, then open TUI
     // This allows elegant piping: tree | treemd
     //
@@ -102,7 +109,10 @@ fn main() -> Result<()> {
                 })
                 .collect();
 
-            if md_files.is_empty() {
+            // Recently opened files are offered even when cwd has no markdown
+            let has_recent_files = !treemd::Config::load().recent_files().is_empty();
+
+            if md_files.is_empty() && !has_recent_files {
                 eprintln!("No markdown files found in current directory.");
                 eprintln!("\nUsage: treemd [OPTIONS] <FILE>");
                 eprintln!("       treemd [OPTIONS] -");
@@ -191,31 +201,73 @@ fn main() -> Result<()> {
     // Check if stdin was piped (before consuming input_source)
     let stdin_was_piped = matches!(input_source, treemd::input::InputSource::Stdin(_));
 
-    // Process input (handles tree format conversion, markdown passthrough, etc.)
-    let markdown_content = match treemd::input::process_input(input_source) {
-        Ok(content) => content,
-        Err(e) => {
-            eprintln!("Error processing input: {}", e);
-            process::exit(1);
-        }
-    };
-
-    // Parse the markdown content
-    let doc = parser::parse_markdown(&markdown_content);
-
-    // Handle query mode
-    if let Some(ref query_str) = args.query {
-        return handle_query_mode(&doc, query_str, args.query_output.as_deref());
-    }
+    // Process input (handles format conversion, markdown passthrough, etc.)
+    let input_path = args
+        .file
+        .first()
+        .filter(|path| !path.is_dir() && path.as_path() != Path::new("-"));
+    let markdown_content =
+        match treemd::input::process_input_from(input_source, input_path.map(PathBuf::as_path)) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Error processing input: {}", e);
+                process::exit(1);
+            }
+        };
 
     // If no flags, launch TUI
-    if !args.list
+    let launch_tui = args.query.is_none()
+        && !args.list
         && !args.tree
         && !args.count
+        && args.stats.is_none()
+        && args.split.is_none()
+        && args.export.is_none()
         && args.section.is_none()
+        && args.shift.is_none()
+        && args.diff.is_none()
         && args.command.is_none()
-        && !args.setup_completions
-    {
+        && !args.setup_completions;
+
+    // Print the rendered document when asked to, or when there is no terminal
+    // to draw the TUI on
+    let pager_mode =
+        launch_tui && !needs_file_picker && (args.pager || !std::io::stdout().is_terminal());
+    if args.pager && needs_file_picker {
+        eprintln!("Error: --pager needs a markdown file");
+        process::exit(1);
+    }
+    let launch_tui = launch_tui && !pager_mode;
+
+    // Parse the markdown content (large documents are parsed in the background
+    // once the TUI is up, so it can show progress)
+    let load_in_background =
+        launch_tui && markdown_content.len() >= treemd::tui::BACKGROUND_LOAD_BYTES;
+    let doc = if load_in_background {
+        Document::new(String::new(), Vec::new())
+    } else {
+        parser::parse_markdown(&markdown_content)
+    };
+
+    // Handle query mode
+    if let Some(ref query_str) = args.query {
+        let query_output = args
+            .query_output
+            .clone()
+            .or_else(|| treemd::Config::load().cli.default_query_output);
+        return handle_query_mode(
+            &doc,
+            query_str,
+            query_output.as_deref(),
+            args.query_validate,
+        );
+    }
+
+    if pager_mode {
+        return handle_pager_mode(&args, &markdown_content);
+    }
+
+    if launch_tui {
         // Load configuration
         let mut config = treemd::Config::load();
 
@@ -225,27 +277,8 @@ fn main() -> Result<()> {
         }
 
         // Detect terminal capabilities and determine color mode
-        // Priority: CLI args > config file > auto-detection
         let caps = treemd::tui::TerminalCapabilities::detect();
-        let color_mode = if let Some(ref mode_arg) = args.color_mode {
-            // CLI flag takes highest priority
-            use cli::ColorModeArg;
-            use treemd::tui::ColorMode;
-            match mode_arg {
-                ColorModeArg::Auto => caps.recommended_color_mode,
-                ColorModeArg::Rgb => ColorMode::Rgb,
-                ColorModeArg::Color256 => ColorMode::Indexed256,
-            }
-        } else {
-            // Check config file setting before falling back to auto-detection
-            use treemd::tui::ColorMode;
-            match config.terminal.color_mode.as_str() {
-                "rgb" => ColorMode::Rgb,
-                "256" => ColorMode::Indexed256,
-                // "auto" or any other value falls back to detection
-                _ => caps.recommended_color_mode,
-            }
-        };
+        let color_mode = resolve_color_mode(&args, &config, &caps);
 
         // Show compatibility warning if needed (before TUI init)
         // Skip the warning prompt if stdin was piped (already consumed)
@@ -311,10 +344,26 @@ fn main() -> Result<()> {
             config.images.enabled
         };
 
+        // Remember the opened file, and list recent files in the startup picker
+        // when no path was given
+        if file_path.is_file() {
+            let _ = config.push_recent(&file_path);
+        }
+        let show_recent_files =
+            needs_file_picker && args.file.is_empty() && !config.recent_files().is_empty();
+
         let mut app =
             treemd::App::new(doc, filename, file_path, config, color_mode, images_enabled);
+        app.remember_recent_files = true;
+        if args.emit_events {
+            app.set_event_writer(std::io::stderr());
+        }
+        if load_in_background {
+            app.load_in_background(markdown_content);
+        }
         if needs_file_picker {
             app.startup_needs_file_picker = true;
+            app.file_picker_recent = show_recent_files;
         }
         if let Some(dir) = file_picker_dir {
             app.file_picker_dir = Some(dir.canonicalize().unwrap_or(dir));
@@ -334,7 +383,118 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Pick the color mode.
+///
+/// Priority: CLI args > config file > auto-detection
+fn resolve_color_mode(
+    args: &Cli,
+    config: &treemd::Config,
+    caps: &treemd::tui::TerminalCapabilities,
+) -> treemd::tui::ColorMode {
+    use treemd::tui::ColorMode;
+    if let Some(ref mode_arg) = args.color_mode {
+        // CLI flag takes highest priority
+        use cli::ColorModeArg;
+        match mode_arg {
+            ColorModeArg::Auto => caps.recommended_color_mode,
+            ColorModeArg::Rgb => ColorMode::Rgb,
+            ColorModeArg::Color256 => ColorMode::Indexed256,
+            ColorModeArg::None => ColorMode::None,
+        }
+    } else {
+        // Check config file setting before falling back to auto-detection
+        match config.terminal.color_mode.as_str() {
+            "rgb" => ColorMode::Rgb,
+            "256" => ColorMode::Indexed256,
+            "none" => ColorMode::None,
+            // "auto" or any other value falls back to detection
+            _ => caps.recommended_color_mode,
+        }
+    }
+}
+
+/// Render the document and page it, or print it when stdout isn't a terminal
+fn handle_pager_mode(args: &Cli, markdown: &str) -> Result<()> {
+    use std::io::Write;
+    use treemd::tui::theme::Theme;
+
+    let mut config = treemd::Config::load();
+    if let Some(ref theme_name) = args.theme {
+        config.ui.theme = theme_name.clone();
+    }
+    let color_mode =
+        resolve_color_mode(args, &config, &treemd::tui::TerminalCapabilities::detect());
+    let theme_name = config.theme_name();
+    let theme = Theme::from_name(theme_name)
+        .with_color_mode(color_mode, theme_name)
+        .with_custom_colors(&config.theme, color_mode);
+
+    let to_terminal = std::io::stdout().is_terminal();
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let color = !no_color
+        && color_mode != treemd::tui::ColorMode::None
+        && (to_terminal || args.color_mode.is_some());
+
+    let mut output = if color {
+        treemd::render::text_to_ansi(&treemd::render::markdown_to_text(markdown, &theme))
+    } else {
+        treemd::render::markdown_to_string(markdown)
+    };
+    output.truncate(output.trim_end_matches('\n').len());
+    output.push('\n');
+
+    if to_terminal && let Some(mut pager) = spawn_pager() {
+        if let Some(mut stdin) = pager.stdin.take() {
+            // The reader may quit early; a broken pipe is not an error
+            let _ = stdin.write_all(output.as_bytes());
+        }
+        pager.wait()?;
+        return Ok(());
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    match stdout.write_all(output.as_bytes()) {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Start `$PAGER` (default: `less -R`) reading from a pipe
+fn spawn_pager() -> Option<process::Child> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next()?;
+    process::Command::new(program)
+        .args(parts)
+        .stdin(process::Stdio::piped())
+        .spawn()
+        .ok()
+}
+
+/// `--output`, or `cli.default_output` from the config file when the flag
+/// isn't given.
+fn resolve_output_format(args: &Cli) -> OutputFormat {
+    if let Some(output) = &args.output {
+        return output.clone();
+    }
+    match treemd::Config::load().cli.default_output {
+        Some(name) => OutputFormat::from_str(&name, true).unwrap_or_else(|_| {
+            eprintln!(
+                "Error: invalid cli.default_output \"{}\" in config (expected plain, json or tree)",
+                name
+            );
+            process::exit(1);
+        }),
+        None => OutputFormat::Plain,
+    }
+}
+
 fn handle_cli_mode(args: &Cli, doc: &Document) {
+    let shift = args.shift.unwrap_or(0);
+    let output = resolve_output_format(args);
     // Apply filters
     let headings: Vec<_> = if let Some(level) = args.level {
         doc.headings_at_level(level)
@@ -347,28 +507,98 @@ fn handle_cli_mode(args: &Cli, doc: &Document) {
     // Handle different modes
     if args.count {
         print_heading_counts(doc);
+    } else if let Some(ref section) = args.stats {
+        match format_stats(doc, section) {
+            Ok(report) => print!("{}", report),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+    } else if let Some(ref dir) = args.split {
+        let dir = if dir.is_empty() {
+            default_split_dir(&args.file)
+        } else {
+            PathBuf::from(dir)
+        };
+        match write_split(doc, args.split_level as usize, shift, &dir) {
+            Ok(count) => println!(
+                "Wrote index.md and {} section files to {}",
+                count,
+                dir.display()
+            ),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+    } else if let Some(ref old_path) = args.diff {
+        match parser::parse_file(old_path) {
+            Ok(old) => {
+                let diff = old.diff(doc);
+                if matches!(output, OutputFormat::Json) {
+                    println!("{}", serde_json::to_string_pretty(&diff).unwrap());
+                } else {
+                    print!("{}", diff);
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", old_path.display(), e);
+                process::exit(1);
+            }
+        }
+    } else if let Some(ref kind) = args.export {
+        match kind {
+            ExportKind::Linkreport => {
+                let entries = link_report(doc, &link_base_dir(&args.file));
+                print!("{}", format_link_report(&entries, &output));
+            }
+            ExportKind::Mermaid => print!("{}", parser::render_mermaid(doc)),
+            ExportKind::MermaidFlowchart => print!("{}", parser::render_mermaid_flowchart(doc)),
+            ExportKind::Man => print!("{}", treemd::render::render_man(doc)),
+        }
     } else if args.tree {
-        print_tree(doc, &args.output);
+        print_tree(doc, &output, args.max_level.map(|n| n as usize));
     } else if let Some(ref section_name) = args.section {
-        extract_section(doc, section_name);
+        extract_section(doc, section_name, shift);
     } else if args.list {
-        print_headings(&headings, &args.output, doc);
+        print_headings(&headings, &output, doc);
+    } else if args.shift.is_some() {
+        print!("{}", shift_markdown(&doc.content, shift));
+    }
+}
+
+/// `markdown` with its heading levels moved by `shift`, warning on stderr
+/// when levels had to be clamped.
+fn shift_markdown(markdown: &str, shift: i8) -> String {
+    if shift == 0 {
+        return markdown.to_string();
     }
+    let doc = parser::parse_markdown(markdown);
+    let clamped = doc.headings_clamped_by_shift(shift);
+    if clamped > 0 {
+        eprintln!(
+            "warning: --shift {} would move {} heading(s) past levels 1-6; clamped",
+            shift, clamped
+        );
+    }
+    doc.shift_headings(shift)
 }
 
 fn print_headings(headings: &[&parser::Heading], format: &OutputFormat, doc: &Document) {
+    print!("{}", format_headings(headings, format, doc));
+}
+
+fn format_headings(headings: &[&parser::Heading], format: &OutputFormat, doc: &Document) -> String {
     match format {
-        OutputFormat::Plain => {
-            for heading in headings {
-                let prefix = "#".repeat(heading.level);
-                println!("{} {}", prefix, heading.text);
-            }
-        }
+        OutputFormat::Plain => headings
+            .iter()
+            .map(|heading| format!("{} {}\n", "#".repeat(heading.level), heading.text))
+            .collect(),
         OutputFormat::Json => {
             // Use new nested JSON output with markdown intelligence
             let json_output = parser::build_json_output(doc, None);
-            let json = serde_json::to_string_pretty(&json_output).unwrap();
-            println!("{}", json);
+            serde_json::to_string_pretty(&json_output).unwrap() + "\n"
         }
         OutputFormat::Tree => {
             eprintln!("Use --tree for tree output");
@@ -377,45 +607,492 @@ fn print_headings(headings: &[&parser::Heading], format: &OutputFormat, doc: &Do
     }
 }
 
-fn print_tree(doc: &Document, format: &OutputFormat) {
-    let tree = doc.build_tree();
+/// `--list`, `--count` or `-q` over several files, each under a
+/// `==> path <==` header, in path order. JSON reports are combined into one
+/// object keyed by path instead, so the output stays a single document.
+fn handle_multi_file_mode(args: &Cli) -> Result<()> {
+    use treemd::query;
+
+    let query_format = args.query.as_ref().map(|_| {
+        let name = args
+            .query_output
+            .clone()
+            .or_else(|| treemd::Config::load().cli.default_query_output);
+        parse_query_format(name.as_deref())
+    });
+    let output = resolve_output_format(args);
+    // Some(pretty) when each file's report is one JSON document
+    let json = match &query_format {
+        Some(query::OutputFormat::Json) => Some(false),
+        Some(query::OutputFormat::JsonPretty) => Some(true),
+        Some(_) => None,
+        None if !args.count && matches!(output, OutputFormat::Json) => Some(true),
+        None => None,
+    };
+
+    let mut failed = false;
+    let mut first = true;
+    let mut json_reports = Vec::new();
+    for (path, doc) in parse_files(&args.file) {
+        let doc = match doc {
+            Ok(doc) => doc,
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", path.display(), e);
+                failed = true;
+                continue;
+            }
+        };
+
+        let report = if let (Some(query_str), Some(format)) = (&args.query, &query_format) {
+            match query::execute(&doc, query_str) {
+                Ok(results) if results.is_empty() => String::new(),
+                Ok(results) => query::format_output(&results, format.clone()) + "\n",
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            }
+        } else if args.count {
+            format_heading_counts(&doc)
+        } else {
+            let headings: Vec<_> = if let Some(level) = args.level {
+                doc.headings_at_level(level)
+            } else if let Some(ref filter) = args.filter {
+                doc.filter_headings(filter)
+            } else {
+                doc.headings.iter().collect()
+            };
+            format_headings(&headings, &output, &doc)
+        };
+
+        if json.is_some() {
+            json_reports.push((path, report));
+            continue;
+        }
+        if !first {
+            println!();
+        }
+        first = false;
+        println!("==> {} <==", path.display());
+        print!("{}", report);
+    }
+    if let Some(pretty) = json {
+        println!("{}", combine_json_reports(&json_reports, pretty));
+    }
+
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// One JSON object mapping each path to its report. Empty reports (queries
+/// with no results) become empty arrays.
+fn combine_json_reports(reports: &[(PathBuf, String)], pretty: bool) -> String {
+    let combined: serde_json::Map<String, serde_json::Value> = reports
+        .iter()
+        .map(|(path, report)| {
+            let value = if report.trim().is_empty() {
+                serde_json::Value::Array(Vec::new())
+            } else {
+                serde_json::from_str(report).unwrap_or_else(|_| report.trim().into())
+            };
+            (path.display().to_string(), value)
+        })
+        .collect();
+    let combined = serde_json::Value::Object(combined);
+    if pretty {
+        serde_json::to_string_pretty(&combined).unwrap()
+    } else {
+        combined.to_string()
+    }
+}
+
+/// Parse `paths` on a pool of worker threads. Results are sorted by path,
+/// so output doesn't depend on which file finished first.
+fn parse_files(paths: &[PathBuf]) -> Vec<(PathBuf, std::io::Result<Document>)> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(paths.len());
+    let next = AtomicUsize::new(0);
+    let mut parsed: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    while let Some(path) = paths.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        done.push((path.clone(), parser::parse_file(path)));
+                    }
+                    done
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("parser thread panicked"))
+            .collect()
+    });
+    parsed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    parsed
+}
+
+fn print_tree(doc: &Document, format: &OutputFormat, max_level: Option<usize>) {
     let config = treemd::Config::load();
-    let compact = config.is_compact_tree();
+    print!(
+        "{}",
+        format_tree(doc, format, config.is_compact_tree(), max_level)
+    );
+}
 
+fn format_tree(
+    doc: &Document,
+    format: &OutputFormat,
+    compact: bool,
+    max_level: Option<usize>,
+) -> String {
     match format {
         OutputFormat::Tree | OutputFormat::Plain => {
+            // An empty tree (no headings) prints nothing
+            let tree = doc.build_tree();
+            let mut out = String::new();
             for (i, node) in tree.iter().enumerate() {
-                let is_last = i == tree.len() - 1;
-                print!("{}", node.render_box_tree_styled("", is_last, compact));
+                let is_last = i + 1 == tree.len();
+                out.push_str(&node.render_box_tree_to_depth("", is_last, compact, max_level));
             }
+            out
         }
         OutputFormat::Json => {
             // For JSON, we'll serialize the flat headings list
             // (Tree serialization would need custom implementation)
-            let json = serde_json::to_string_pretty(&doc.headings).unwrap();
-            println!("{}", json);
+            let headings: Vec<_> = doc
+                .iter_tree()
+                .filter(|(depth, _)| max_level.is_none_or(|max| *depth < max))
+                .map(|(_, heading)| heading)
+                .collect();
+            format!("{}\n", serde_json::to_string_pretty(&headings).unwrap())
         }
     }
 }
 
 fn print_heading_counts(doc: &Document) {
+    print!("{}", format_heading_counts(doc));
+}
+
+fn format_heading_counts(doc: &Document) -> String {
+    // Nothing to break down for a document without headings
+    if doc.headings.is_empty() {
+        return "Total: 0\n".to_string();
+    }
+
     let mut counts: HashMap<usize, usize> = HashMap::new();
 
     for heading in &doc.headings {
         *counts.entry(heading.level).or_insert(0) += 1;
     }
 
-    println!("Heading counts:");
+    let mut output = String::from("Heading counts:\n");
     for level in 1..=6 {
         if let Some(count) = counts.get(&level) {
             let prefix = "#".repeat(level);
-            println!("  {}: {}", prefix, count);
+            output.push_str(&format!("  {}: {}\n", prefix, count));
         }
     }
-    println!("\nTotal: {}", doc.headings.len());
+    output.push_str(&format!("\nTotal: {}\n", doc.headings.len()));
+    output
 }
 
-fn extract_section(doc: &Document, section_name: &str) {
+/// Average reading speed used for reading time estimates.
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Build the `--stats` report for the whole document, or for the section
+/// named `section` (and its subsections) when it is non-empty.
+///
+/// The numbers come from the query aggregations (`stats`, `levels`, `langs`,
+/// `types`, `tasks`) run against the selected text.
+fn format_stats(doc: &Document, section: &str) -> std::result::Result<String, String> {
+    use treemd::query::{self, Value};
+
+    let (title, scoped);
+    let doc = if section.is_empty() {
+        title = "Document".to_string();
+        doc
+    } else {
+        let heading = doc
+            .find_heading(section)
+            .ok_or_else(|| format!("Section '{}' not found", section))?;
+        let heading_line = doc.content[heading.offset..].lines().next().unwrap_or("");
+        let content = doc.extract_section_at(heading.offset).unwrap_or_default();
+        title = heading.text.clone();
+        scoped = parser::parse_markdown(&format!("{}\n\n{}", heading_line, content));
+        &scoped
+    };
+
+    let aggregate = |name: &str| -> Vec<(String, usize)> {
+        let Ok(results) = query::execute(doc, &format!(". | {}", name)) else {
+            return Vec::new();
+        };
+        let mut counts: Vec<(String, usize)> = match results.first() {
+            Some(Value::Object(obj)) => obj
+                .iter()
+                .map(|(key, value)| (key.clone(), value.as_number().unwrap_or(0.0) as usize))
+                .collect(),
+            _ => Vec::new(),
+        };
+        counts.sort_by(|(a_key, a), (b_key, b)| b.cmp(a).then_with(|| a_key.cmp(b_key)));
+        counts
+    };
+    let count_of = |counts: &[(String, usize)], key: &str| {
+        counts
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, n)| *n)
+            .unwrap_or(0)
+    };
+    let breakdown = |counts: &[(String, usize)]| {
+        let parts: Vec<String> = counts.iter().map(|(k, n)| format!("{} {}", k, n)).collect();
+        if parts.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", parts.join(", "))
+        }
+    };
+
+    let stats = aggregate("stats");
+    let mut levels = aggregate("levels");
+    levels.sort();
+    let langs = aggregate("langs");
+    let types = aggregate("types");
+    let tasks = aggregate("tasks");
+
+    let words = count_of(&stats, "words");
+    let minutes = words.div_ceil(WORDS_PER_MINUTE);
+    let level_parts: Vec<(String, usize)> = levels
+        .into_iter()
+        .map(|(key, n)| {
+            let level = key.trim_start_matches('h').parse().unwrap_or(1);
+            ("#".repeat(level), n)
+        })
+        .collect();
+
+    let mut out = format!("Stats: {}\n", title);
+    out.push_str(&format!(
+        "  Words:        {} (~{} min read)\n",
+        words, minutes
+    ));
+    out.push_str(&format!(
+        "  Headings:     {}{}\n",
+        count_of(&stats, "headings"),
+        breakdown(&level_parts)
+    ));
+    out.push_str(&format!(
+        "  Code blocks:  {}{}\n",
+        count_of(&stats, "code_blocks"),
+        breakdown(&langs)
+    ));
+    out.push_str(&format!(
+        "  Links:        {}{}\n",
+        count_of(&stats, "links"),
+        breakdown(&types)
+    ));
+    out.push_str(&format!("  Images:       {}\n", count_of(&stats, "images")));
+    out.push_str(&format!("  Tables:       {}\n", count_of(&stats, "tables")));
+
+    let (done, total) = (count_of(&tasks, "done"), count_of(&tasks, "total"));
+    if total > 0 {
+        out.push_str(&format!(
+            "  Tasks:        {}/{} done ({}%)\n",
+            done,
+            total,
+            done * 100 / total
+        ));
+    } else {
+        out.push_str("  Tasks:        none\n");
+    }
+
+    Ok(out)
+}
+
+/// Directory for `--split` output: the input file's name without extension.
+fn default_split_dir(files: &[PathBuf]) -> PathBuf {
+    match files {
+        [file] if file.as_os_str() != "-" => file
+            .file_stem()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("sections")),
+        _ => PathBuf::from("sections"),
+    }
+}
+
+/// Files written by `--split`: one per section, then `index.md`.
+///
+/// The index keeps the content before the first section and links to each file.
+fn split_files(doc: &Document, level: usize, shift: i8) -> Result<Vec<(String, String)>, String> {
+    let parts = doc.split_by_level(level);
+    let Some(first) = doc.headings.iter().find(|h| h.level == level) else {
+        return Err(format!("No level {} headings to split on", level));
+    };
+
+    let mut index = String::new();
+    let preamble = doc.content[..first.offset].trim();
+    if !preamble.is_empty() {
+        index.push_str(preamble);
+        index.push_str("\n\n");
+    }
+    let titles = doc.headings.iter().filter(|h| h.level == level);
+    for ((name, _), heading) in parts.iter().zip(titles) {
+        index.push_str(&format!("- [{}]({}.md)\n", heading.text, name));
+    }
+
+    let mut files: Vec<(String, String)> = parts
+        .into_iter()
+        .map(|(name, markdown)| (format!("{}.md", name), shift_markdown(&markdown, shift)))
+        .collect();
+    files.push(("index.md".to_string(), index));
+    Ok(files)
+}
+
+fn write_split(doc: &Document, level: usize, shift: i8, dir: &Path) -> Result<usize, String> {
+    let files = split_files(doc, level, shift)?;
+
+    if let Some((name, _)) = files.iter().find(|(name, _)| dir.join(name).exists()) {
+        return Err(format!(
+            "{} already exists; refusing to overwrite",
+            dir.join(name).display()
+        ));
+    }
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    for (name, markdown) in &files {
+        let path = dir.join(name);
+        std::fs::write(&path, markdown)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    Ok(files.len() - 1)
+}
+
+/// One row of the `--export linkreport` output.
+#[derive(Debug, serde::Serialize)]
+struct LinkReportEntry {
+    text: String,
+    target: String,
+    resolved: Option<String>,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    line: usize,
+    status: &'static str,
+}
+
+/// Directory the document's relative links resolve against: the input
+/// file's directory, or the working directory for stdin.
+fn link_base_dir(files: &[PathBuf]) -> PathBuf {
+    match files {
+        [file] if file.as_os_str() != "-" => file
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from(".")),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// Resolve every link in the document the way the TUI follows them.
+///
+/// Anchors are checked against the document's headings and local files
+/// (plus their anchors) against the disk. External URLs are never fetched
+/// and are reported as `unchecked`.
+fn link_report(doc: &Document, base: &Path) -> Vec<LinkReportEntry> {
+    use treemd::parser::LinkTarget;
+    use treemd::tui::{DefaultLinkResolver, LinkResolver, ResolvedLink};
+
+    let display = |path: &Path| {
+        path.strip_prefix(base)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+
+    parser::extract_links(&doc.content)
+        .into_iter()
+        .map(|link| {
+            let kind = match link.target {
+                LinkTarget::Anchor(_) => "anchor",
+                LinkTarget::RelativeFile { .. } => "relative",
+                LinkTarget::WikiLink { .. } => "wiki",
+                LinkTarget::External(_) => "external",
+            };
+            let (resolved, status) = match DefaultLinkResolver.resolve(&link, base) {
+                ResolvedLink::Anchor(anchor) => {
+                    let found = doc.find_anchor(&anchor).is_some();
+                    (
+                        Some(format!("#{}", anchor)),
+                        if found { "ok" } else { "missing" },
+                    )
+                }
+                ResolvedLink::File { path, anchor } => {
+                    let found = path.is_file()
+                        && anchor.as_deref().is_none_or(|anchor| {
+                            std::fs::read_to_string(&path)
+                                .map(|content| {
+                                    parser::parse_markdown(&content)
+                                        .find_anchor(anchor)
+                                        .is_some()
+                                })
+                                .unwrap_or(false)
+                        });
+                    let resolved = match anchor {
+                        Some(anchor) => format!("{}#{}", display(&path), anchor),
+                        None => display(&path),
+                    };
+                    (Some(resolved), if found { "ok" } else { "missing" })
+                }
+                ResolvedLink::Editor(path) => (Some(display(&path)), "ok"),
+                ResolvedLink::External(url) => (Some(url), "unchecked"),
+                ResolvedLink::Rejected(_) => (None, "rejected"),
+            };
+            LinkReportEntry {
+                line: doc.content[..link.offset].matches('\n').count() + 1,
+                target: link.target.as_str(),
+                text: link.text,
+                resolved,
+                kind,
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Render the link report as JSON for `-o json`, otherwise as a markdown table.
+fn format_link_report(entries: &[LinkReportEntry], format: &OutputFormat) -> String {
+    if matches!(format, OutputFormat::Json) {
+        return format!("{}\n", serde_json::to_string_pretty(entries).unwrap());
+    }
+
+    let cell = |s: &str| s.replace('|', "\\|");
+    let mut out = String::from(
+        "| Line | Type | Text | Target | Resolved | Status |\n|---:|---|---|---|---|---|\n",
+    );
+    for entry in entries {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            entry.line,
+            entry.kind,
+            cell(&entry.text),
+            cell(&entry.target),
+            entry
+                .resolved
+                .as_deref()
+                .map(cell)
+                .unwrap_or_else(|| "-".to_string()),
+            entry.status
+        ));
+    }
+    out
+}
+
+fn extract_section(doc: &Document, section_name: &str, shift: i8) {
     let heading = match doc.find_heading(section_name) {
         Some(h) => h,
         None => {
@@ -424,53 +1101,59 @@ fn extract_section(doc: &Document, section_name: &str) {
         }
     };
 
-    // Find the section in content
-    // This is a simple implementation - could be improved
-    let search = format!("{} {}", "#".repeat(heading.level), heading.text);
-    if let Some(start) = doc.content.find(&search) {
-        // Find next heading at same or higher level
-        let after = &doc.content[start..];
-        let section_level = heading.level;
-
-        // Find end of section
-        let end_pos = doc
-            .headings
-            .iter()
-            .skip_while(|h| h.text != heading.text)
-            .skip(1)
-            .find(|h| h.level <= section_level)
-            .and_then(|next_heading| {
-                let search = format!("{} {}", "#".repeat(next_heading.level), next_heading.text);
-                after.find(&search)
-            })
-            .unwrap_or(after.len());
-
-        println!("{}", &after[..end_pos].trim());
+    // Slice by the heading's byte offset so repeated heading text can't
+    // resolve to the wrong section
+    let behavior = treemd::Config::load().behavior;
+    if let Some(content) = behavior.section_at(doc, heading.offset) {
+        let heading_line = doc.content[heading.offset..].lines().next().unwrap_or("");
+        let mut section = format!("{}\n", heading_line.trim_end());
+        if !content.is_empty() {
+            section.push_str(&format!("\n{}\n", content));
+        }
+        print!("{}", shift_markdown(&section, shift));
     }
 }
 
-fn handle_query_mode(doc: &Document, query_str: &str, output_format: Option<&str>) -> Result<()> {
-    use treemd::query::{self, OutputFormat};
-
-    // Parse output format
-    let format = output_format
-        .map(|s| s.parse::<OutputFormat>())
+/// The `--query-output` format, exiting on an unknown name.
+fn parse_query_format(output_format: Option<&str>) -> treemd::query::OutputFormat {
+    output_format
+        .map(|s| s.parse::<treemd::query::OutputFormat>())
         .transpose()
-        .map_err(|e| {
+        .unwrap_or_else(|e| {
             eprintln!("Error: {}", e);
             process::exit(1);
-        })?
-        .unwrap_or(OutputFormat::Plain);
+        })
+        .unwrap_or_default()
+}
+
+fn handle_query_mode(
+    doc: &Document,
+    query_str: &str,
+    output_format: Option<&str>,
+    validate: bool,
+) -> Result<()> {
+    use treemd::query;
+
+    let format = parse_query_format(output_format);
 
     // Execute query
     match query::execute(doc, query_str) {
         Ok(results) => {
-            if results.is_empty() {
-                // No results - exit silently like jq
-                return Ok(());
+            // No results print nothing, like jq
+            if !results.is_empty() {
+                let output = query::format_output(&results, format);
+                println!("{}", output);
+            }
+
+            if validate {
+                let warnings = query::validate(doc, query_str).unwrap_or_default();
+                for warning in &warnings {
+                    eprintln!("warning: {}", warning);
+                }
+                if !warnings.is_empty() {
+                    process::exit(1);
+                }
             }
-            let output = query::format_output(&results, format);
-            println!("{}", output);
             Ok(())
         }
         Err(e) => {
@@ -480,8 +1163,8 @@ fn handle_query_mode(doc: &Document, query_str: &str, output_format: Option<&str
     }
 }
 
-fn print_query_help() {
-    let help = r#"
+/// Text of `--query-help`
+const QUERY_HELP: &str = r#"
 treemd Query Language (tql)
 
 A jq-like query language for navigating and extracting markdown structure.
@@ -505,11 +1188,21 @@ FILTERS & INDEXING
     .h2[-1]             Last h2
     .h2[1:3]            h2s at index 1 and 2
     .h2[:3]             First 3 h2s
+    .h2[::2]            Every other h2 (start:end:step)
+    .h2[::-1]           All h2s in reverse
+    .h[line>=100]       Headings from source line 100 on (==, !=, <, <=, >)
+                        Negative bounds count from the end; out-of-range
+                        bounds are clamped, so slices never error
 
 HIERARCHY
     .h1 > .h2           Direct child h2s under h1s
     .h1 >> .code        Code blocks anywhere under h1s
 
+UNIONS
+    .h2, .code          h2s and code blocks together, in document order
+    .h2, .h3 | text     Commas bind tighter than pipes
+    [.h2, .code] | count  Count both kinds
+
 PIPES
     .h2 | text          Get heading text (strips ##)
     [.h2] | count       Count all h2s
@@ -522,6 +1215,8 @@ COLLECTION FUNCTIONS
     limit(n), take(n)   First n elements
     skip(n), drop(n)    Skip first n elements
     nth(n)              Get element at index
+    at_line(n)          Heading enclosing source line n; on an array,
+                        the nearest element at or before it
     reverse             Reverse order
     sort                Sort alphabetically
     sort_by(key)        Sort by property
@@ -530,6 +1225,9 @@ COLLECTION FUNCTIONS
     group_by(key)       Group elements by key
     min, max            Min/max numeric value
     add                 Sum numbers or concat strings
+    avg, mean           Average of numbers (not rounded)
+    median              Median (middle pair averaged for even counts)
+    map(f)              Apply f to each element (or object value)
 
 STRING FUNCTIONS
     text                Get text representation
@@ -543,7 +1241,9 @@ STRING FUNCTIONS
 
 FILTER FUNCTIONS
     select(cond)        Keep if condition true (alias: where, filter)
-    contains(s)         Contains substring (alias: includes)
+    contains(s)         Contains substring, ignoring case (alias: includes)
+    contains_cs(s)      Contains substring, case-sensitive
+    word(s)             Contains s as a whole word, ignoring case (not "apiary" for "api")
     startswith(s)       Starts with prefix
     endswith(s)         Ends with suffix
     matches(regex)      Matches regex pattern
@@ -556,11 +1256,21 @@ CONTENT FUNCTIONS
     url, href, src      Get URL/link/image source
     lang                Code block language
 
+TABLE FUNCTIONS
+    headers             Header cells
+    rows                Body rows, each an array of cells
+    column(c)           Cells of column c (header name or index)
+    cell(r, c)          Cell in body row r, column c (0-based)
+
 AGGREGATION FUNCTIONS
     stats               Document statistics
     levels              Heading count by level
     langs               Code block count by language
     types               Link types count
+    tasks               Task list items: {done, total}
+    word_frequency(n, skip_stopwords)
+                        {word, count} pairs, most frequent first; code,
+                        URLs and link targets are skipped (n: top-n or null)
 
 EXAMPLES
     # List all h2 headings
@@ -580,9 +1290,19 @@ EXAMPLES
     treemd -q '.h | where(contains("API"))' doc.md
     treemd -q '.h[API]' doc.md
 
+    # Exact case, or whole words only
+    treemd -q '.h | select(contains_cs("API"))' doc.md
+    treemd -q '.h | select(word("api"))' doc.md
+
     # All Rust code blocks
     treemd -q '.code[rust]' doc.md
 
+    # One column of the first table
+    treemd -q '.table[0] | column("Status")' doc.md
+
+    # Raw code of the third Rust block
+    treemd -q '.code[rust][2] | content' doc.md
+
     # External link URLs
     treemd -q '.link[external] | url' doc.md
 
@@ -592,12 +1312,25 @@ EXAMPLES
     # Group headings by level
     treemd -q '[.h] | group_by("level")' doc.md
 
+    # Average headings per level, median code block length
+    treemd -q '[.h] | group_by("level") | map(count) | avg' doc.md
+    treemd -q '[.code | lines] | median' doc.md
+
     # Document statistics
     treemd -q '. | stats' doc.md
 
     # JSON output
     treemd -q '.h2' --query-output json doc.md
 
+    # Ten most common words, without stopwords, as CSV
+    treemd -q '. | content | word_frequency(10, true)' --query-output csv doc.md
+
+    # Custom report lines
+    treemd -q '.link' --query-output 'template:- {text}: {url}' doc.md
+
+    # Warn when a filter matches nothing (typo check, exit status 1)
+    treemd -q '.h2[Instalation]' --query-validate doc.md
+
 OUTPUT FORMATS (--query-output)
     plain       Human-readable text (default)
     json        Compact JSON
@@ -605,8 +1338,197 @@ OUTPUT FORMATS (--query-output)
     jsonl       Line-delimited JSON (one per line)
     md          Raw markdown
     tree        Tree structure
+    csv         CSV; object and element fields become columns
+    template:T  Render T per result; {field} is replaced by the result's
+                property (text, level, url, lang, line, anchor, ...), unknown
+                fields are empty and {{ }} are literal braces
+
+    treemd -q '.h2' --query-output 'template:### {text} ({line})' doc.md
 
 For more details, see: https://github.com/epistates/treemd
 "#;
-    println!("{}", help.trim());
+
+fn print_query_help() {
+    println!("{}", QUERY_HELP.trim());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading_counts_for_headingless_documents() {
+        for content in ["", "  \n\t\n", "Only body text\n"] {
+            let doc = parser::parse_markdown(content);
+            assert_eq!(format_heading_counts(&doc), "Total: 0\n");
+        }
+    }
+
+    #[test]
+    fn test_stats_report_for_document_and_section() {
+        let doc = parser::parse_markdown(
+            "# Guide\n\nIntro words here.\n\n## Setup\n\n- [x] Install\n- [ ] Configure\n  - [x] Theme\n\n```sh\ncargo install treemd\n```\n\nSee [docs](https://example.com) and [usage](#usage).\n\n### Notes\n\n```rust\nfn main() {}\n```\n\n## Usage\n\n| a | b |\n|---|---|\n| 1 | 2 |\n",
+        );
+
+        let report = format_stats(&doc, "Setup").unwrap();
+        assert_eq!(
+            report,
+            "Stats: Setup\n  \
+             Words:        28 (~1 min read)\n  \
+             Headings:     2 (## 1, ### 1)\n  \
+             Code blocks:  2 (rust 1, sh 1)\n  \
+             Links:        2 (anchor 1, external 1)\n  \
+             Images:       0\n  \
+             Tables:       0\n  \
+             Tasks:        2/3 done (66%)\n"
+        );
+
+        let report = format_stats(&doc, "").unwrap();
+        assert!(report.starts_with("Stats: Document\n"));
+        assert!(report.contains("Headings:     4 (# 1, ## 2, ### 1)"));
+        assert!(report.contains("Tables:       1"));
+        assert!(format_stats(&doc, "Missing").is_err());
+    }
+
+    #[test]
+    fn test_tree_max_level_marks_collapsed_headings() {
+        let doc = parser::parse_markdown("# A\n## B\n### C\n## D\n# E\n## F\n");
+        let tree = format_tree(&doc, &OutputFormat::Tree, false, Some(2));
+        assert_eq!(
+            tree,
+            "├─ # A\n│   ├─ ▶ ## B\n│   └─ ## D\n└─ # E\n    └─ ## F\n"
+        );
+
+        let tree = format_tree(&doc, &OutputFormat::Tree, true, Some(1));
+        assert_eq!(tree, "├──▶ # A\n└──▶ # E\n");
+
+        let json = format_tree(&doc, &OutputFormat::Json, false, Some(1));
+        let headings: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(headings.len(), 2);
+    }
+
+    #[test]
+    fn test_split_files_with_index() {
+        let doc = parser::parse_markdown(
+            "Intro text\n\n# Setup\nInstall it.\n## Linux\napt\n# Setup\nAgain\n# Index\n",
+        );
+        let files = split_files(&doc, 1, 0).unwrap();
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["setup.md", "setup-2.md", "index-2.md", "index.md"]);
+        assert_eq!(files[0].1, "# Setup\n\nInstall it.\n## Linux\napt\n");
+        assert_eq!(
+            files[3].1,
+            "Intro text\n\n- [Setup](setup.md)\n- [Setup](setup-2.md)\n- [Index](index-2.md)\n"
+        );
+
+        assert!(split_files(&doc, 3, 0).is_err());
+
+        // Shifting applies to the section files, not the index
+        let files = split_files(&doc, 1, 1).unwrap();
+        assert_eq!(files[0].1, "## Setup\n\nInstall it.\n### Linux\napt\n");
+        assert!(files[3].1.starts_with("Intro text"));
+    }
+
+    #[test]
+    fn test_link_report_resolves_and_checks_links() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("guide.md"), "# Usage\n").unwrap();
+        let doc = parser::parse_markdown(
+            "# Intro\n\n[a](#intro) [b](#nope)\n\n[c](guide.md#usage) [d](guide.md#gone) [[guide]]\n\n[e|f](https://example.com) [g](../up.md)\n\nMail me@example.com\n",
+        );
+
+        let entries = link_report(&doc, dir.path());
+        let rows: Vec<_> = entries
+            .iter()
+            .map(|e| (e.line, e.kind, e.resolved.as_deref(), e.status))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (3, "anchor", Some("#intro"), "ok"),
+                (3, "anchor", Some("#nope"), "missing"),
+                (5, "relative", Some("guide.md#usage"), "ok"),
+                (5, "relative", Some("guide.md#gone"), "missing"),
+                (5, "wiki", Some("guide.md"), "ok"),
+                (7, "external", Some("https://example.com"), "unchecked"),
+                (7, "relative", None, "rejected"),
+                (9, "external", Some("mailto:me@example.com"), "unchecked"),
+            ]
+        );
+
+        let table = format_link_report(&entries, &OutputFormat::Plain);
+        assert!(table.contains(
+            "| 7 | external | e\\|f | https://example.com | https://example.com | unchecked |\n"
+        ));
+        let json: Vec<serde_json::Value> =
+            serde_json::from_str(&format_link_report(&entries, &OutputFormat::Json)).unwrap();
+        assert_eq!(json[0]["type"], "anchor");
+        assert_eq!(json[6]["resolved"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_heading_counts_by_level() {
+        let doc = parser::parse_markdown("# A\n## B\n## C\n");
+        assert_eq!(
+            format_heading_counts(&doc),
+            "Heading counts:\n  #: 1\n  ##: 2\n\nTotal: 3\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_files_ordered_by_path() {
+        let dir = tempfile::tempdir().unwrap();
+        // The first file alphabetically is by far the slowest to parse
+        let big = format!("# a\n{}", "## part\n\ntext\n\n".repeat(2_000));
+        let mut paths = Vec::new();
+        for (name, content) in [("a.md", big.as_str()), ("c.md", "# c\n"), ("b.md", "# b\n")] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            paths.push(path);
+        }
+        paths.push(dir.path().join("missing.md"));
+        paths.reverse();
+
+        for _ in 0..5 {
+            let parsed = parse_files(&paths);
+            let names: Vec<_> = parsed
+                .iter()
+                .map(|(path, _)| path.file_name().unwrap().to_str().unwrap())
+                .collect();
+            assert_eq!(names, ["a.md", "b.md", "c.md", "missing.md"]);
+            assert_eq!(parsed[0].1.as_ref().unwrap().headings.len(), 2_001);
+            assert_eq!(parsed[2].1.as_ref().unwrap().headings[0].text, "c");
+            assert!(parsed[3].1.is_err());
+        }
+    }
+
+    #[test]
+    fn test_combine_json_reports() {
+        let reports = vec![
+            (PathBuf::from("a.md"), "[{\"text\":\"A\"}]\n".to_string()),
+            (PathBuf::from("b.md"), String::new()),
+        ];
+        let combined = combine_json_reports(&reports, false);
+        assert_eq!(combined, r#"{"a.md":[{"text":"A"}],"b.md":[]}"#);
+
+        let pretty: serde_json::Value =
+            serde_json::from_str(&combine_json_reports(&reports, true)).unwrap();
+        assert_eq!(pretty["a.md"][0]["text"], "A");
+    }
+
+    #[test]
+    fn test_query_help_examples_parse() {
+        let examples: Vec<&str> = QUERY_HELP
+            .lines()
+            .filter_map(|line| line.split_once("treemd -q '"))
+            .filter_map(|(_, rest)| rest.split_once('\''))
+            .map(|(query, _)| query)
+            .collect();
+        assert!(examples.len() >= 20);
+        for example in examples {
+            if let Err(e) = treemd::query::parse(example) {
+                panic!("`{}` doesn't parse: {}", example, e);
+            }
+        }
+    }
 }
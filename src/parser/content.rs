@@ -9,8 +9,13 @@
 //! - Links with spaces: `[text](url with spaces)` → `[text](<url with spaces>)`
 //! - Details block extraction: `<details><summary>` HTML blocks
 //! - Full GFM support: tables, strikethrough, task lists
+//! - Bare URLs and email addresses in text become links
 
-use super::output::Block;
+use std::ops::Range;
+
+use super::links::find_autolinks;
+use super::output::{Block, InlineElement, ListItem};
+use super::utils::{detect_checkbox_in_text, frontmatter_len};
 
 /// Parse markdown content into structured blocks.
 ///
@@ -24,28 +29,961 @@ use super::output::Block;
 ///
 /// # Returns
 ///
-/// A vector of parsed content blocks.
+/// A vector of parsed content blocks. Leading YAML frontmatter (see
+/// [`frontmatter_len`]) becomes a `yaml` code block rather than a horizontal
+/// rule followed by a setext heading. Bare URLs and email addresses in
+/// text (not code spans) become [`InlineElement::Link`]s.
 pub fn parse_content(markdown: &str, start_line: usize) -> Vec<Block> {
-    turbovault_parser::parse_blocks_from_line(markdown, start_line)
+    let mut blocks = parse_blocks(markdown, start_line);
+    autolink_blocks(&mut blocks);
+    blocks
+}
+
+fn parse_blocks(markdown: &str, start_line: usize) -> Vec<Block> {
+    let Some(len) = frontmatter_len(markdown) else {
+        return turbovault_parser::parse_blocks_from_line(markdown, start_line);
+    };
+
+    let (frontmatter, body) = markdown.split_at(len);
+    let lines: Vec<&str> = frontmatter.lines().collect();
+    let mut blocks = vec![Block::Code {
+        language: Some("yaml".to_string()),
+        content: lines[1..lines.len() - 1].join("\n"),
+        start_line,
+        end_line: start_line + lines.len() - 1,
+    }];
+    blocks.extend(turbovault_parser::parse_blocks_from_line(
+        body,
+        start_line + lines.len(),
+    ));
+    blocks
+}
+
+/// Turn bare URLs and email addresses in the text runs of `blocks` into links.
+fn autolink_blocks(blocks: &mut [Block]) {
+    for block in blocks {
+        match block {
+            Block::Heading { inline, .. } | Block::Paragraph { inline, .. } => {
+                *inline = autolink_inline(std::mem::take(inline), None);
+            }
+            Block::List { items, .. } => {
+                for item in items {
+                    item.inline =
+                        autolink_inline(std::mem::take(&mut item.inline), Some(&item.content));
+                    autolink_blocks(&mut item.blocks);
+                }
+            }
+            Block::Blockquote { blocks, .. } | Block::Details { blocks, .. } => {
+                autolink_blocks(blocks);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Split the autolinks out of `inline`'s text runs. For list items, `source`
+/// is the item's content, used to give each link its line within the item.
+fn autolink_inline(inline: Vec<InlineElement>, source: Option<&str>) -> Vec<InlineElement> {
+    let mut out = Vec::with_capacity(inline.len());
+    let mut source_pos = 0;
+    let mut elements = inline.into_iter().peekable();
+
+    while let Some(element) = elements.next() {
+        let InlineElement::Text { mut value } = element else {
+            out.push(element);
+            continue;
+        };
+        // The parser can split a URL into several text runs
+        while let Some(InlineElement::Text { value: next }) = elements.peek() {
+            value.push_str(next);
+            elements.next();
+        }
+
+        let mut last = 0;
+        for (range, url) in find_autolinks(&value) {
+            if range.start > last {
+                out.push(InlineElement::Text {
+                    value: value[last..range.start].to_string(),
+                });
+            }
+            let text = value[range.clone()].to_string();
+            let line_offset = source.and_then(|source| {
+                let found = source_pos + source[source_pos..].find(&text)?;
+                source_pos = found + text.len();
+                Some(source[..found].matches('\n').count())
+            });
+            out.push(InlineElement::Link {
+                text,
+                url,
+                title: None,
+                line_offset,
+            });
+            last = range.end;
+        }
+        if last == 0 {
+            out.push(InlineElement::Text { value });
+        } else if last < value.len() {
+            out.push(InlineElement::Text {
+                value: value[last..].to_string(),
+            });
+        }
+    }
+    out
+}
+/// Blocks parsed from some markdown, with what they don't record about how
+/// their lists were written.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedContent {
+    pub blocks: Vec<Block>,
+    /// One per block, in the same order
+    pub layouts: Vec<SourceLayout>,
+}
+
+/// Parse `markdown` like [`parse_content`], and lay out its lists.
+///
+/// turbovault-parser's list blocks don't keep the number a list starts at,
+/// the numbers of nested items, or which content lines start nested items.
+/// They are worked out alongside the blocks: the parser's list handling is
+/// replayed over the same events, so every content line of an item gets
+/// the depth and marker of the source line that produced it.
+///
+/// ```
+/// use treemd::parser::content::parse_with_layout;
+///
+/// let parsed = parse_with_layout("Intro\n\n5. five\n6. six\n", 0);
+/// let starts: Vec<_> = parsed.layouts.iter().map(|layout| layout.list_start()).collect();
+/// assert_eq!(starts, vec![None, Some(5)]);
+/// ```
+pub fn parse_with_layout(markdown: &str, start_line: usize) -> ParsedContent {
+    let blocks = parse_content(markdown, start_line);
+    let layouts = match frontmatter_len(markdown) {
+        // The frontmatter is the first block, and the rest is parsed after it
+        Some(len) => std::iter::once(SourceLayout::None)
+            .chain(source_layouts(&markdown[len..], &blocks[1..]))
+            .collect(),
+        None => source_layouts(markdown, &blocks),
+    };
+    ParsedContent { blocks, layouts }
+}
+
+/// What a block doesn't record about how it was written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SourceLayout {
+    /// Nothing: the block isn't a list and holds none
+    #[default]
+    None,
+    /// A list's numbering and nesting
+    List(ListLayout),
+    /// Layouts of the blocks in a blockquote or `<details>` block
+    Blocks(Vec<SourceLayout>),
+}
+
+impl SourceLayout {
+    /// The layout of a list block
+    pub fn list(&self) -> Option<&ListLayout> {
+        match self {
+            Self::List(layout) => Some(layout),
+            _ => None,
+        }
+    }
+
+    /// Number an ordered list starts at; `None` for other blocks
+    pub fn list_start(&self) -> Option<u64> {
+        self.list().and_then(|layout| layout.start)
+    }
+
+    /// Layout of the `idx`th block nested in this one
+    pub fn nested(&self, idx: usize) -> &SourceLayout {
+        match self {
+            Self::Blocks(layouts) => layouts.get(idx).unwrap_or(&Self::None),
+            _ => &Self::None,
+        }
+    }
+}
+
+/// How a line of a list item is marked when rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListMarker {
+    /// A bullet
+    Bullet,
+    /// The item's number in an ordered list
+    Number(u64),
+    /// A task checkbox and whether it is checked
+    Task(bool),
+    /// No marker: the line continues the text above it
+    Continuation,
+}
+
+/// One display line of a list item: its first line, a nested item, or text
+/// continuing one of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemLine<'a> {
+    /// Nesting depth: 0 for the item itself, 1 for its nested items, ...
+    pub depth: usize,
+    pub marker: ListMarker,
+    /// Text of the line as parsed, without its task marker
+    pub text: &'a str,
+}
+
+/// Nesting and markers of a list block's lines.
+///
+/// The parser flattens nested items into their top-level item's content,
+/// indented two spaces per level, and starts a new content line for each
+/// piece of a nested item's text around a link or formatting. A layout from
+/// [`parse_with_layout`] knows which nested item each content line came
+/// from; one built with [`ListLayout::new`] places lines by their
+/// indentation and shows nested items as bullets or tasks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListLayout {
+    /// Number the list starts at; `None` for bullet lists
+    pub start: Option<u64>,
+    /// Per item, the depth and marker of each content line
+    items: Vec<Vec<(usize, ListMarker)>>,
+}
+
+impl ListLayout {
+    /// Layout for a list without source to read, such as one nested in a
+    /// list item.
+    pub fn new(ordered: bool) -> Self {
+        Self {
+            start: ordered.then_some(1),
+            items: Vec::new(),
+        }
+    }
+
+    /// Display lines of the list's `item_idx`th item, one per line of its
+    /// content.
+    ///
+    /// ```
+    /// use treemd::parser::content::{ListMarker, parse_with_layout};
+    /// use treemd::parser::output::Block;
+    ///
+    /// let parsed = parse_with_layout("- fruit\n  1. apple\n  2. [x] pear\n", 0);
+    /// let Block::List { items, .. } = &parsed.blocks[0] else {
+    ///     unreachable!()
+    /// };
+    /// let layout = parsed.layouts[0].list().unwrap();
+    /// let lines: Vec<_> = layout
+    ///     .lines(0, &items[0])
+    ///     .into_iter()
+    ///     .map(|line| (line.depth, line.marker, line.text))
+    ///     .collect();
+    /// assert_eq!(
+    ///     lines,
+    ///     vec![
+    ///         (0, ListMarker::Bullet, "fruit"),
+    ///         (1, ListMarker::Number(1), "apple"),
+    ///         (1, ListMarker::Task(true), "pear"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn lines<'a>(&self, item_idx: usize, item: &'a ListItem) -> Vec<ItemLine<'a>> {
+        let marker = match (item.checked, self.start) {
+            (Some(checked), _) => ListMarker::Task(checked),
+            (None, Some(start)) => ListMarker::Number(start + item_idx as u64),
+            (None, None) => ListMarker::Bullet,
+        };
+        let marks = self
+            .items
+            .get(item_idx)
+            .filter(|marks| marks.len() == item.content.lines().count());
+
+        let mut lines = Vec::new();
+        for (idx, raw) in item.content.lines().enumerate() {
+            let text = raw.trim_start();
+            if idx == 0 {
+                lines.push(ItemLine {
+                    depth: 0,
+                    marker,
+                    text,
+                });
+                continue;
+            }
+
+            let (is_task, checked, task_text) = detect_checkbox_in_text(text);
+            let (depth, marker) = match marks {
+                Some(marks) => marks[idx],
+                None => {
+                    let indent = (raw.len() - text.len()) / 2;
+                    match (indent, is_task) {
+                        (0, _) => (0, ListMarker::Continuation),
+                        (_, true) => (indent, ListMarker::Task(checked)),
+                        (_, false) => (indent, ListMarker::Bullet),
+                    }
+                }
+            };
+            let text = match marker {
+                ListMarker::Task(_) => task_text,
+                _ => text,
+            };
+            lines.push(ItemLine {
+                depth,
+                marker,
+                text,
+            });
+        }
+        if lines.is_empty() {
+            lines.push(ItemLine {
+                depth: 0,
+                marker,
+                text: "",
+            });
+        }
+        lines
+    }
+}
+
+/// [`SourceLayout`] of each of `blocks`, as parsed from `markdown`.
+fn source_layouts(markdown: &str, blocks: &[Block]) -> Vec<SourceLayout> {
+    let mut lists = replay_lists(markdown);
+    let list_blocks = blocks
+        .iter()
+        .filter(|block| matches!(block, Block::List { .. }))
+        .count();
+    // The replay follows the parser, so this only fails if the parser
+    // changes; its lists then fall back to `ListLayout::new`
+    if lists.len() != list_blocks {
+        lists.clear();
+    }
+
+    let mut lists = lists.into_iter();
+    blocks
+        .iter()
+        .map(|block| match block {
+            Block::List { .. } => lists.next().map_or(SourceLayout::None, SourceLayout::List),
+            // Both are parsed from their `content` on their own
+            Block::Blockquote { content, blocks }
+            | Block::Details {
+                content, blocks, ..
+            } => SourceLayout::Blocks(source_layouts(content, blocks)),
+            _ => SourceLayout::None,
+        })
+        .collect()
+}
+
+/// The layout of every list block turbovault-parser makes from `markdown`,
+/// outside `<details>` blocks.
+///
+/// Follows the parser's `parse_blocks_from_line` step by step: the same
+/// preprocessing and pulldown-cmark options, and the same state changes
+/// for every event that touches the text an item's content is built from.
+/// Rather than the text, only where its lines start is kept, with the
+/// nested item (or the continuation of one) each line belongs to.
+fn replay_lists(markdown: &str) -> Vec<ListLayout> {
+    use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+    let markdown = preprocess_like_parser(markdown);
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut replay = ListReplay::default();
+    for event in Parser::new_ext(&markdown, options) {
+        let state = &mut replay;
+        match event {
+            Event::Start(Tag::Paragraph) => state.in_paragraph = true,
+            Event::End(TagEnd::Paragraph) => {
+                if state.item_depth >= 1 && state.in_paragraph && !state.text.is_empty() {
+                    // The paragraph becomes one of the item's blocks
+                    let marks = std::mem::take(&mut state.marks);
+                    state.item_blocks.push(Some(marks));
+                    state.text.clear();
+                    state.in_paragraph = false;
+                } else {
+                    state.flush_paragraph();
+                }
+            }
+            Event::Start(Tag::CodeBlock(_)) => state.in_code = true,
+            Event::End(TagEnd::CodeBlock) if state.in_code && state.code_written => {
+                if state.item_depth >= 1 {
+                    state.item_blocks.push(None);
+                }
+                state.in_code = false;
+                state.code_written = false;
+            }
+            Event::Start(Tag::List(start)) => {
+                state.list_depth += 1;
+                if state.list_depth == 1 {
+                    state.in_list = true;
+                    state.start = start;
+                }
+                state.open.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                state.list_depth = state.list_depth.saturating_sub(1);
+                state.open.pop();
+                if state.list_depth == 0 {
+                    state.flush_list();
+                }
+            }
+            Event::Start(Tag::Item) => {
+                state.item_depth += 1;
+                let marker = match state.open.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        ListMarker::Number(*number - 1)
+                    }
+                    _ => ListMarker::Bullet,
+                };
+                state.nested.push((marker, false));
+                if state.item_depth > 1 {
+                    state.saved_tasks.push(state.task.take());
+                }
+                if state.item_depth == 1 {
+                    state.clear();
+                    state.item_blocks.clear();
+                }
+            }
+            Event::End(TagEnd::Item) => {
+                if state.item_depth > 1
+                    && let Some(saved) = state.saved_tasks.pop()
+                {
+                    state.task = saved;
+                }
+                if state.item_depth == 1 {
+                    // The content is the text left over, or else the
+                    // item's first block if that is a paragraph
+                    let marks = if !state.text.is_empty() {
+                        std::mem::take(&mut state.marks)
+                    } else {
+                        state
+                            .item_blocks
+                            .first()
+                            .cloned()
+                            .flatten()
+                            .unwrap_or_default()
+                    };
+                    state.items.push(marks);
+                    state.clear();
+                    state.item_blocks.clear();
+                    state.task = None;
+                }
+                state.nested.pop();
+                state.item_depth = state.item_depth.saturating_sub(1);
+            }
+            Event::TaskListMarker(checked) => state.task = Some(checked),
+            Event::Start(Tag::BlockQuote(_)) => state.in_blockquote = true,
+            Event::End(TagEnd::BlockQuote(_)) if state.in_blockquote && state.quote_written => {
+                state.in_blockquote = false;
+                state.quote_written = false;
+            }
+            Event::Start(Tag::Table(_)) => state.in_table = true,
+            Event::End(TagEnd::TableHead) => {
+                state.table_has_head = state.row_cells > 0;
+                state.row_cells = 0;
+            }
+            Event::End(TagEnd::TableRow) => state.row_cells = 0,
+            Event::Start(Tag::TableCell) => state.clear(),
+            Event::End(TagEnd::TableCell) => {
+                state.row_cells += 1;
+                state.clear();
+            }
+            Event::End(TagEnd::Table) if state.in_table && state.table_has_head => {
+                state.clear();
+                state.row_cells = 0;
+                state.in_table = false;
+                state.table_has_head = false;
+            }
+            Event::Code(code) => state.push(&code),
+            Event::Start(Tag::Link { .. }) => {
+                state.start_nested_line();
+                state.in_link = true;
+            }
+            Event::End(TagEnd::Link) => {
+                state.in_link = false;
+                // Written as `[text](url)`
+                state.push("[");
+                state.image_in_link = false;
+            }
+            Event::Start(Tag::Image { title, .. }) => {
+                if state.in_link {
+                    state.image_in_link = true;
+                }
+                state.in_image = true;
+                state.clear();
+                state.push(&title);
+            }
+            Event::End(TagEnd::Image) => {
+                state.in_image = false;
+                if !state.image_in_link {
+                    state.clear();
+                    if state.in_paragraph {
+                        // Written as `![alt](src)`
+                        state.push("!");
+                    }
+                }
+            }
+            Event::Text(text) => {
+                if state.in_code {
+                    state.code_written |= !text.is_empty();
+                } else if state.in_blockquote {
+                    state.quote_written |= !text.is_empty();
+                } else if !(state.in_heading || state.in_link || state.in_image) {
+                    state.start_nested_line();
+                    state.push(&text);
+                }
+            }
+            Event::SoftBreak if state.in_paragraph => state.push(" "),
+            Event::HardBreak if state.in_paragraph => state.push("\n"),
+            Event::Rule => state.flush_paragraph(),
+            Event::Start(Tag::Heading { .. }) => {
+                state.flush_paragraph();
+                state.in_heading = true;
+            }
+            Event::End(TagEnd::Heading(_)) => state.in_heading = false,
+            _ => {}
+        }
+    }
+    replay.flush_paragraph();
+    replay.flush_list();
+    replay.lists
+}
+
+/// The parser's state while [`replay_lists`] replays it, reduced to what
+/// decides where the lines of list item content start.
+#[derive(Default)]
+struct ListReplay {
+    /// The text being collected: item content, a paragraph or a table cell
+    text: String,
+    /// Depth and marker of each line of `text`
+    marks: Vec<(usize, ListMarker)>,
+    /// The blocks of the current top-level item: a paragraph's line marks,
+    /// or `None` for a code block
+    item_blocks: Vec<Option<Vec<(usize, ListMarker)>>>,
+    /// Per open list, the number of its next item (`None` for bullets)
+    open: Vec<Option<u64>>,
+    /// Per open item, its marker and whether it has started a line yet
+    nested: Vec<(ListMarker, bool)>,
+    /// Task marker not yet written out, and those of enclosing items
+    task: Option<bool>,
+    saved_tasks: Vec<Option<bool>>,
+    list_depth: usize,
+    item_depth: usize,
+    start: Option<u64>,
+    items: Vec<Vec<(usize, ListMarker)>>,
+    lists: Vec<ListLayout>,
+    in_list: bool,
+    in_paragraph: bool,
+    in_code: bool,
+    code_written: bool,
+    in_blockquote: bool,
+    quote_written: bool,
+    in_table: bool,
+    table_has_head: bool,
+    row_cells: usize,
+    in_heading: bool,
+    in_link: bool,
+    in_image: bool,
+    image_in_link: bool,
+}
+
+impl ListReplay {
+    /// Append `text`, marking each line it starts. A line started here
+    /// continues whatever item the text belongs to.
+    fn push(&mut self, text: &str) {
+        let depth = self.item_depth.saturating_sub(1);
+        for ch in text.chars() {
+            if self.text.is_empty() || self.text.ends_with('\n') {
+                self.marks.push((depth, ListMarker::Continuation));
+            }
+            self.text.push(ch);
+        }
+    }
+
+    /// Inside a nested item, the parser puts each text run and link on a
+    /// line of its own, indented by depth; the first starts the item.
+    fn start_nested_line(&mut self) {
+        if !self.in_list || self.item_depth <= 1 {
+            return;
+        }
+        if !self.text.is_empty() && !self.text.ends_with('\n') {
+            self.text.push('\n');
+        }
+        let depth = self.item_depth - 1;
+        let marker = match (self.task.take(), self.nested.last_mut()) {
+            (Some(checked), _) => ListMarker::Task(checked),
+            (None, Some((marker, false))) => *marker,
+            (None, _) => ListMarker::Continuation,
+        };
+        if let Some((_, started)) = self.nested.last_mut() {
+            *started = true;
+        }
+        self.marks.push((depth, marker));
+        self.text.push_str(&"  ".repeat(depth));
+    }
+
+    fn clear(&mut self) {
+        self.text.clear();
+        self.marks.clear();
+    }
+
+    fn flush_paragraph(&mut self) {
+        if self.in_paragraph && !self.text.is_empty() {
+            self.clear();
+            self.in_paragraph = false;
+        }
+    }
+
+    fn flush_list(&mut self) {
+        if self.in_list && !self.items.is_empty() {
+            self.lists.push(ListLayout {
+                start: self.start,
+                items: std::mem::take(&mut self.items),
+            });
+            self.in_list = false;
+        }
+    }
+}
+
+/// `markdown` as turbovault-parser rewrites it before parsing blocks:
+/// wikilinks and links with spaces become plain links, and `<details>`
+/// blocks become placeholder paragraphs.
+fn preprocess_like_parser(markdown: &str) -> String {
+    use regex::Regex;
+    use std::sync::OnceLock;
+
+    static WIKILINK: OnceLock<Regex> = OnceLock::new();
+    static LINK_WITH_SPACES: OnceLock<Regex> = OnceLock::new();
+    let wikilink =
+        WIKILINK.get_or_init(|| Regex::new(r"\[\[([^\]|]+)(?:\|([^\]]+))?\]\]").unwrap());
+    let link_with_spaces =
+        LINK_WITH_SPACES.get_or_init(|| Regex::new(r"\[([^\]]+)\]\(([^)<>]+\s[^)<>]*)\)").unwrap());
+
+    let markdown = wikilink.replace_all(markdown, |caps: &regex::Captures| {
+        let target = caps[1].trim();
+        let text = caps.get(2).map_or(target, |alias| alias.as_str().trim());
+        format!("[{}](wikilink:{})", text, target)
+    });
+    let markdown = link_with_spaces.replace_all(&markdown, |caps: &regex::Captures| {
+        if caps[2].contains(' ') {
+            format!("[{}](<{}>)", &caps[1], &caps[2])
+        } else {
+            caps[0].to_string()
+        }
+    });
+
+    let mut out = String::with_capacity(markdown.len());
+    let mut rest = &*markdown;
+    let mut count = 0;
+    while let Some(at) = rest.find("<details") {
+        let after = &rest[at..];
+        let Some(end) = after
+            .find('>')
+            .and_then(|open| Some(open + 1 + after[open + 1..].find("</details>")?))
+        else {
+            break;
+        };
+        out.push_str(&rest[..at]);
+        out.push_str(&format!("\n[DETAILS_BLOCK_{}]\n", count));
+        count += 1;
+        rest = &after[end + "</details>".len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Whether each block is a `<details>` block written with the `open` attribute.
+///
+/// [`Block::Details`] drops the opening tag's attributes, so they are read
+/// back from the source: `<details>` tags are found the way the block parser
+/// finds them and paired with the details blocks in order. If the counts
+/// differ, no block is reported open.
+///
+/// ```
+/// use treemd::parser::content::{details_open, parse_content};
+///
+/// let markdown = "<details open>\n<summary>A</summary>\n\nShown\n</details>\n\n\
+///                 <details>\n<summary>B</summary>\n\nHidden\n</details>\n";
+/// let blocks = parse_content(markdown, 0);
+/// assert_eq!(details_open(markdown, &blocks), vec![true, false]);
+/// ```
+pub fn details_open(markdown: &str, blocks: &[Block]) -> Vec<bool> {
+    let markdown = &markdown[frontmatter_len(markdown).unwrap_or(0)..];
+
+    // Opening tags in source order, skipping each block's body
+    let mut sources = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = markdown[pos..].find("<details") {
+        let start = pos + offset;
+        let tag_end = markdown[start..].find('>').map(|end| start + end);
+        let Some((tag_end, body_end)) =
+            tag_end.and_then(|end| Some((end, markdown[end..].find("</details>")?)))
+        else {
+            pos = start + 1;
+            continue;
+        };
+        let attributes = &markdown[start + "<details".len()..tag_end];
+        sources.push(attributes.split_whitespace().any(|attribute| {
+            let name = attribute.split('=').next().unwrap_or_default();
+            name.trim_end_matches('/').eq_ignore_ascii_case("open")
+        }));
+        pos = tag_end + body_end + "</details>".len();
+    }
+
+    let count = blocks
+        .iter()
+        .filter(|block| matches!(block, Block::Details { .. }))
+        .count();
+    let matched = count == sources.len();
+
+    let mut sources = sources.into_iter();
+    blocks
+        .iter()
+        .map(|block| match block {
+            Block::Details { .. } => sources.next().unwrap_or(false) && matched,
+            _ => false,
+        })
+        .collect()
 }
 
-/// Generate URL-friendly slug from heading text.
+/// Checkbox states of a list item's tasks, nested tasks included.
+///
+/// Nested tasks are folded into their parent item's content by the parser,
+/// so continuation lines starting with `[ ]` or `[x]` are counted too.
 ///
-/// Converts heading text to lowercase and replaces non-alphanumeric
-/// characters with hyphens.
+/// ```
+/// use treemd::parser::content::item_task_states;
+///
+/// let states: Vec<bool> = item_task_states(Some(false), "parent\n  [x] child").collect();
+/// assert_eq!(states, vec![false, true]);
+/// ```
+pub fn item_task_states(checked: Option<bool>, content: &str) -> impl Iterator<Item = bool> + '_ {
+    let nested = content.lines().skip(1).filter_map(|line| {
+        let line = line.trim_start();
+        if line.starts_with("[ ] ") {
+            Some(false)
+        } else if line.starts_with("[x] ") || line.starts_with("[X] ") {
+            Some(true)
+        } else {
+            None
+        }
+    });
+    checked.into_iter().chain(nested)
+}
+
+/// A task checkbox written inside a paragraph or table cell rather than as
+/// a list item's task marker, as found by [`inline_checkboxes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineCheckbox {
+    /// Byte range of the `[x]` or `[ ]` token in the markdown
+    pub range: Range<usize>,
+    /// `[x]` or `[X]`
+    pub checked: bool,
+    /// Position of the containing block among the top-level paragraphs and
+    /// tables, in document order
+    pub block: usize,
+    /// Source line of the token within that block, 0 for its first line
+    pub line: usize,
+    /// Whether the containing block is a table
+    pub in_table: bool,
+}
+
+/// Checkboxes written inline in top-level paragraphs and table cells.
+///
+/// Only a `[x]`, `[X]` or `[ ]` standing on its own counts: not inside code
+/// spans, link text or list items, not escaped, and not touching other
+/// words (`arr[x]`) or brackets (`[[x]]`).
+///
+/// ```
+/// use treemd::parser::content::inline_checkboxes;
+///
+/// let markdown = "| Task | Done |\n|---|---|\n| Ship | [x] |\n| Docs | `[ ]` |\n\nReview [ ] and arr[x]\n";
+/// let found = inline_checkboxes(markdown);
+/// assert_eq!(found.len(), 2);
+/// assert_eq!((&markdown[found[0].range.clone()], found[0].line, found[0].in_table), ("[x]", 2, true));
+/// assert_eq!((found[1].checked, found[1].block), (false, 1));
+/// ```
+pub fn inline_checkboxes(markdown: &str) -> Vec<InlineCheckbox> {
+    use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+    let skipped = frontmatter_len(markdown).unwrap_or(0);
+    let body = &markdown[skipped..];
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+    // Runs of adjacent text, with the index, start and kind of their block
+    let mut runs: Vec<(Range<usize>, usize, usize, bool)> = Vec::new();
+    let mut block: Option<(usize, usize, bool)> = None;
+    let mut blocks = 0;
+    let mut depth = 0usize;
+    let mut in_link = 0usize;
+
+    for (event, range) in Parser::new_ext(body, options).into_offset_iter() {
+        match event {
+            Event::Start(tag) => {
+                if depth == 0 && matches!(tag, Tag::Paragraph | Tag::Table(_)) {
+                    block = Some((blocks, range.start, matches!(tag, Tag::Table(_))));
+                    blocks += 1;
+                }
+                if matches!(tag, Tag::Link { .. } | Tag::Image { .. }) {
+                    in_link += 1;
+                }
+                depth += 1;
+            }
+            Event::End(tag) => {
+                if matches!(tag, TagEnd::Link | TagEnd::Image) {
+                    in_link = in_link.saturating_sub(1);
+                }
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    block = None;
+                }
+            }
+            Event::Text(_) if in_link == 0 => {
+                let Some((idx, start, in_table)) = block else {
+                    continue;
+                };
+                match runs.last_mut() {
+                    Some((run, run_idx, ..)) if *run_idx == idx && run.end == range.start => {
+                        run.end = range.end;
+                    }
+                    _ => runs.push((range, idx, start, in_table)),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut found = Vec::new();
+    for (run, idx, start, in_table) in runs {
+        let text = &body[run.clone()];
+        for (pos, _) in text.match_indices('[') {
+            let checked = match text.get(pos..pos + 3) {
+                Some("[x]" | "[X]") => true,
+                Some("[ ]") => false,
+                _ => continue,
+            };
+            let at = run.start + pos;
+            let before = body[..at].chars().next_back();
+            let after = body[at + 3..].chars().next();
+            let open = before.is_none_or(|c| c.is_whitespace() || matches!(c, '|' | '('));
+            let close = after.is_none_or(|c| {
+                c.is_whitespace() || matches!(c, '|' | '.' | ',' | ';' | ':' | '!' | '?' | ')')
+            });
+            if open && close {
+                found.push(InlineCheckbox {
+                    range: skipped + at..skipped + at + 3,
+                    checked,
+                    block: idx,
+                    line: body[start..at].matches('\n').count(),
+                    in_table,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// A footnote reference or the start of a footnote definition, as found by
+/// [`footnotes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FootnoteMark {
+    /// The label between `[^` and `]`
+    pub label: String,
+    /// Byte range of the `[^label]` token in the markdown; a definition's
+    /// colon is not included
+    pub range: Range<usize>,
+    /// Whether this token starts a definition (`[^label]: ...`)
+    pub definition: bool,
+}
+
+/// Footnote references and definitions, in document order.
 ///
-/// # Examples
+/// References to labels defined nowhere in `markdown` are found too, so an
+/// undefined reference can be told apart from a missing section. Code and
+/// link text are skipped, and so are definitions the block parser reads as
+/// link reference definitions (a single word such as `[^1]: https://...`),
+/// along with their references, since those render as links.
 ///
 /// ```
-/// use treemd::parser::content::slugify;
+/// use treemd::parser::content::footnotes;
 ///
-/// assert_eq!(slugify("Hello World"), "hello-world");
-/// assert_eq!(slugify("API Reference"), "api-reference");
+/// let markdown = "Claim[^1] and `[^2]` and[^gone].\n\n[^1]: The source.\n";
+/// let found: Vec<_> = footnotes(markdown)
+///     .into_iter()
+///     .map(|m| (m.label, m.definition, &markdown[m.range]))
+///     .collect();
+/// assert_eq!(
+///     found,
+///     [
+///         ("1".to_string(), false, "[^1]"),
+///         ("gone".to_string(), false, "[^gone]"),
+///         ("1".to_string(), true, "[^1]"),
+///     ]
+/// );
 /// ```
-pub fn slugify(text: &str) -> String {
-    turbovault_parser::slugify(text)
+pub fn footnotes(markdown: &str) -> Vec<FootnoteMark> {
+    use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+    let skipped = frontmatter_len(markdown).unwrap_or(0);
+    let body = &markdown[skipped..];
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let link_definitions = Parser::new_ext(body, options);
+    let is_link = |label: &str| {
+        link_definitions
+            .reference_definitions()
+            .get(&format!("^{}", label))
+            .is_some()
+    };
+
+    let mut found = Vec::new();
+    // Runs of adjacent text outside links: undefined references stay text
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    let mut in_link = 0usize;
+    let parser = Parser::new_ext(body, options | Options::ENABLE_FOOTNOTES);
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::FootnoteReference(label) => found.push(FootnoteMark {
+                label: label.to_string(),
+                range: skipped + range.start..skipped + range.end,
+                definition: false,
+            }),
+            Event::Start(Tag::FootnoteDefinition(label)) => {
+                let Some(close) = body[range.start..].find(']') else {
+                    continue;
+                };
+                found.push(FootnoteMark {
+                    label: label.to_string(),
+                    range: skipped + range.start..skipped + range.start + close + 1,
+                    definition: true,
+                });
+            }
+            Event::Start(Tag::Link { .. } | Tag::Image { .. }) => in_link += 1,
+            Event::End(TagEnd::Link | TagEnd::Image) => in_link = in_link.saturating_sub(1),
+            Event::Text(_) if in_link == 0 => match runs.last_mut() {
+                Some(run) if run.end == range.start => run.end = range.end,
+                _ => runs.push(range),
+            },
+            _ => {}
+        }
+    }
+
+    for run in runs {
+        let text = &body[run.clone()];
+        for (pos, _) in text.match_indices("[^") {
+            let rest = &text[pos + 2..];
+            let Some(len) = rest.find(']') else {
+                continue;
+            };
+            let label = &rest[..len];
+            if label.is_empty() || label.contains(|c: char| c.is_whitespace() || c == '[') {
+                continue;
+            }
+            let start = skipped + run.start + pos;
+            found.push(FootnoteMark {
+                label: label.to_string(),
+                range: start..start + len + 3,
+                definition: false,
+            });
+        }
+    }
+
+    found.retain(|mark| !is_link(&mark.label));
+    found.sort_by_key(|mark| mark.range.start);
+    found
 }
 
+pub use super::utils::slugify;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,6 +1069,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bare_urls_and_emails_become_links() {
+        let markdown = "See https://example.com/a_b. Mail me@example.com, not `https://code.example`.\n\n- one\n  - more at (https://example.org/x)\n";
+        let blocks = parse_content(markdown, 0);
+
+        let Block::Paragraph { inline, .. } = &blocks[0] else {
+            panic!("Expected Paragraph block");
+        };
+        let links: Vec<_> = inline
+            .iter()
+            .filter_map(|e| match e {
+                InlineElement::Link { text, url, .. } => Some((text.as_str(), url.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            links,
+            [
+                ("https://example.com/a_b", "https://example.com/a_b"),
+                ("me@example.com", "mailto:me@example.com"),
+            ]
+        );
+        assert!(matches!(&inline[0], InlineElement::Text { value } if value == "See "));
+
+        // Links in nested list items know which line of the item they are on
+        let Block::List { items, .. } = &blocks[1] else {
+            panic!("Expected List block");
+        };
+        let link = items[0].inline.iter().find_map(|e| match e {
+            InlineElement::Link {
+                text, line_offset, ..
+            } => Some((text.as_str(), *line_offset)),
+            _ => None,
+        });
+        assert_eq!(link, Some(("https://example.org/x", Some(1))));
+    }
+
     #[test]
     fn test_code_block_excludes_wikilinks() {
         // This is the key test - wikilinks inside code blocks should NOT be parsed
@@ -211,6 +1186,213 @@ Also [[Another Valid]]
         }
     }
 
+    #[test]
+    fn test_dashes_after_text_blank_line_and_frontmatter() {
+        let blocks = parse_content("text\n---\n", 0);
+        assert!(matches!(
+            &blocks[..],
+            [Block::Heading { level: 2, content, .. }] if content == "text"
+        ));
+
+        let blocks = parse_content("\n---\n", 0);
+        assert!(matches!(&blocks[..], [Block::HorizontalRule]));
+
+        let blocks = parse_content("---\n\nBody\n", 0);
+        assert!(matches!(
+            &blocks[..],
+            [Block::HorizontalRule, Block::Paragraph { .. }]
+        ));
+
+        let blocks = parse_content("---\ntitle: Notes\ntags:\n- a\n---\n\n1. one\n", 0);
+        let [
+            Block::Code {
+                language, content, ..
+            },
+            Block::List { ordered: true, .. },
+        ] = &blocks[..]
+        else {
+            panic!("Expected frontmatter code block and list, got {:?}", blocks);
+        };
+        assert_eq!(language.as_deref(), Some("yaml"));
+        assert_eq!(content, "title: Notes\ntags:\n- a");
+    }
+
+    #[test]
+    fn test_table_rows_match_header_width() {
+        let blocks = parse_content(
+            "| a | b | c |\n|---|---|---|\n| 1 | 2 |\n| x | y | z | w |\n",
+            0,
+        );
+        let Block::Table { rows, .. } = &blocks[0] else {
+            panic!("Expected Table block");
+        };
+        assert_eq!(rows, &[vec!["1", "2", ""], vec!["x", "y", "z"]]);
+    }
+
+    /// Depth and marker of every line of each item of the list at `block`.
+    fn list_marks(parsed: &ParsedContent, block: usize) -> Vec<Vec<(usize, ListMarker, &str)>> {
+        let Block::List { items, .. } = &parsed.blocks[block] else {
+            panic!("Expected List block");
+        };
+        let layout = parsed.layouts[block].list().expect("list layout");
+        items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                layout
+                    .lines(idx, item)
+                    .into_iter()
+                    .map(|line| (line.depth, line.marker, line.text))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_list_starts_follow_source_numbers() {
+        let markdown = "5. five\n6. six\n\n- bullet\n\n> quoted\n\nText\n\n0. zero\n   1. nested\n";
+        let parsed = parse_with_layout(markdown, 0);
+        assert_eq!(parsed.layouts.len(), parsed.blocks.len());
+
+        let lists: Vec<Option<u64>> = parsed
+            .blocks
+            .iter()
+            .zip(&parsed.layouts)
+            .filter(|(block, _)| matches!(block, Block::List { .. }))
+            .map(|(_, layout)| layout.list_start())
+            .collect();
+        assert_eq!(lists, vec![Some(5), None, Some(0)]);
+    }
+
+    #[test]
+    fn test_list_starts_in_quotes_details_and_after_frontmatter() {
+        let markdown = "---\ntitle: x\n---\n\n3. three\n\n> 7. quoted\n> 8. more\n\n\
+                        <details>\n<summary>S</summary>\n\n4. hidden\n5. also\n</details>\n\n\
+                        9. nine\n";
+        let parsed = parse_with_layout(markdown, 0);
+        let kinds: Vec<&str> = parsed
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Code { .. } => "code",
+                Block::List { .. } => "list",
+                Block::Blockquote { .. } => "quote",
+                Block::Details { .. } => "details",
+                _ => "other",
+            })
+            .collect();
+        // The parser keeps a quoted list's items but not their text
+        assert_eq!(kinds, ["code", "list", "list", "quote", "details", "list"]);
+
+        let starts: Vec<Option<u64>> = parsed
+            .layouts
+            .iter()
+            .map(SourceLayout::list_start)
+            .collect();
+        assert_eq!(starts, [None, Some(3), Some(7), None, None, Some(9)]);
+        assert_eq!(parsed.layouts[4].nested(0).list_start(), Some(4));
+    }
+
+    #[test]
+    fn test_list_layouts_follow_nested_items() {
+        let markdown = "- a\n  - b\n    lazy\n    1. **c** `d`\n- e\n\n> - quoted\n\n- f\n";
+        let parsed = parse_with_layout(markdown, 0);
+
+        // Lazy continuations don't start items, and the pieces the parser
+        // splits a formatted item into continue it
+        assert_eq!(
+            list_marks(&parsed, 0),
+            [
+                vec![
+                    (0, ListMarker::Bullet, "a"),
+                    (1, ListMarker::Bullet, "b"),
+                    (1, ListMarker::Continuation, "lazy"),
+                    (2, ListMarker::Number(1), "c"),
+                    (2, ListMarker::Continuation, "d"),
+                ],
+                vec![(0, ListMarker::Bullet, "e")],
+            ]
+        );
+        let last = parsed.blocks.len() - 1;
+        assert_eq!(
+            list_marks(&parsed, last),
+            [vec![(0, ListMarker::Bullet, "f")]]
+        );
+    }
+
+    #[test]
+    fn test_list_layouts_with_inline_code_and_links() {
+        let markdown = "- top\n  - run `cargo test` now\n  - [x] see [docs](https://example.com) too\n  - [[Page]]\n";
+        let parsed = parse_with_layout(markdown, 0);
+
+        // Code spans stay on their line; links get lines of their own
+        assert_eq!(
+            list_marks(&parsed, 0),
+            [vec![
+                (0, ListMarker::Bullet, "top"),
+                (1, ListMarker::Bullet, "run cargo test"),
+                (1, ListMarker::Continuation, "now"),
+                (1, ListMarker::Task(true), "see "),
+                (1, ListMarker::Continuation, "[docs](https://example.com)"),
+                (1, ListMarker::Continuation, "too"),
+                (1, ListMarker::Bullet, "[Page](wikilink:Page)"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_list_layouts_with_repeated_nested_text() {
+        let markdown = "- same\n  1. same\n  2. same\n     same\n  3. same\n- same\n  - same\n";
+        let parsed = parse_with_layout(markdown, 0);
+        assert_eq!(
+            list_marks(&parsed, 0),
+            [
+                vec![
+                    (0, ListMarker::Bullet, "same"),
+                    (1, ListMarker::Number(1), "same"),
+                    (1, ListMarker::Number(2), "same"),
+                    (1, ListMarker::Continuation, "same"),
+                    (1, ListMarker::Number(3), "same"),
+                ],
+                vec![
+                    (0, ListMarker::Bullet, "same"),
+                    (1, ListMarker::Bullet, "same"),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_layouts_cover_every_content_line() {
+        // Shapes where the parser's list content takes odd turns
+        let samples = [
+            "- a\n\n  b\n\n  - c\n- d\n",
+            "- a  \n  b\n  - c  \n    d\n",
+            "- a\n  ```\n  code\n  ```\n  - b\n",
+            "- a\n  - ![img](x.png) b\n  - c\n",
+            "- a\n\n  | h |\n  |---|\n  | v |\n\n  - b\n",
+            "- a\n  > quoted\n  - b\n",
+            "- a\n  # heading\n  - b\n",
+            "- [link](a b.md)\n  - [x] [[Wiki|alias]] **bold** _em_ ~~del~~\n",
+            "1. a\n   1. b\n      1. c\n         - d\n   2. e\n",
+            "- a\n  <details>\n  <summary>S</summary>\n\n  - x\n  </details>\n  - b\n",
+            "> - a\n>   - b\n\n- c\n  - d\n",
+        ];
+        for markdown in samples {
+            let parsed = parse_with_layout(markdown, 0);
+            for (block, layout) in parsed.blocks.iter().zip(&parsed.layouts) {
+                let Block::List { items, .. } = block else {
+                    continue;
+                };
+                let layout = layout.list().expect("list layout");
+                assert_eq!(layout.items.len(), items.len(), "{:?}", markdown);
+                for (marks, item) in layout.items.iter().zip(items) {
+                    assert_eq!(marks.len(), item.content.lines().count(), "{:?}", markdown);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_slugify() {
         assert_eq!(slugify("Hello World"), "hello-world");
@@ -253,4 +1435,27 @@ Also [[Another Valid]]
             panic!("Expected Details block, got {:?}", blocks[0]);
         }
     }
+
+    #[test]
+    fn test_inline_checkboxes_skip_code_links_and_lists() {
+        let markdown = "---\ntitle: x\n---\n[x] Done, [ ] open.\n\n\
+                        `[x]` \\[x] arr[x] [[x]] [x](url) [ ]x\n\n\
+                        - [ ] list task [x]\n\n\
+                        ```\n[x]\n```\n\n\
+                        | A | B |\n|---|---|\n|[ ]|[X] |\n";
+        let found = inline_checkboxes(markdown);
+        let tokens: Vec<_> = found
+            .iter()
+            .map(|c| (&markdown[c.range.clone()], c.block, c.line, c.in_table))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                ("[x]", 0, 0, false),
+                ("[ ]", 0, 0, false),
+                ("[ ]", 2, 2, true),
+                ("[X]", 2, 2, true),
+            ]
+        );
+    }
 }
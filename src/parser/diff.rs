@@ -0,0 +1,326 @@
+//! Heading structure comparison between two documents.
+//!
+//! [`Document::diff`] matches headings across versions instead of diffing
+//! lines: first by their full path of ancestor headings, then by text alone
+//! (a move), then by similar text under the same parent (a rename). Whatever
+//! is left over was removed from the old document or added to the new one.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::{Document, Heading};
+use crate::query::levenshtein;
+
+/// One structural difference, as found by [`Document::diff`].
+///
+/// Paths list the heading texts from the top-level ancestor down to the
+/// heading itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum HeadingChange {
+    /// Only in the new document
+    Added { path: Vec<String>, level: usize },
+    /// Only in the old document
+    Removed { path: Vec<String>, level: usize },
+    /// Same place in the tree, different text; `path` is the new one
+    Renamed {
+        from: String,
+        to: String,
+        path: Vec<String>,
+    },
+    /// Same heading under a different parent
+    Moved { from: Vec<String>, to: Vec<String> },
+    /// Same heading at a different level; `path` is the new one
+    LevelChanged {
+        path: Vec<String>,
+        from: usize,
+        to: usize,
+    },
+}
+
+/// Result of [`Document::diff`]: removals in old document order, then the
+/// other changes in new document order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StructureDiff {
+    pub changes: Vec<HeadingChange>,
+}
+
+impl StructureDiff {
+    /// True when both documents have the same heading structure.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for HeadingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = |p: &[String]| p.join(" > ");
+        match self {
+            HeadingChange::Added { path: p, level } => write!(f, "+ {} (h{})", path(p), level),
+            HeadingChange::Removed { path: p, level } => write!(f, "- {} (h{})", path(p), level),
+            HeadingChange::Renamed { from, to, path: p } => {
+                let parent = &p[..p.len() - 1];
+                if parent.is_empty() {
+                    write!(f, "~ {} → {}", from, to)
+                } else {
+                    write!(f, "~ {} > {} → {}", path(parent), from, to)
+                }
+            }
+            HeadingChange::Moved { from, to } => write!(f, "> {} → {}", path(from), path(to)),
+            HeadingChange::LevelChanged { path: p, from, to } => {
+                write!(f, "^ {} h{} → h{}", path(p), from, to)
+            }
+        }
+    }
+}
+
+impl fmt::Display for StructureDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No heading structure changes");
+        }
+        let count =
+            |pred: fn(&HeadingChange) -> bool| self.changes.iter().filter(|c| pred(c)).count();
+        writeln!(
+            f,
+            "{} added, {} removed, {} renamed, {} moved, {} level changed",
+            count(|c| matches!(c, HeadingChange::Added { .. })),
+            count(|c| matches!(c, HeadingChange::Removed { .. })),
+            count(|c| matches!(c, HeadingChange::Renamed { .. })),
+            count(|c| matches!(c, HeadingChange::Moved { .. })),
+            count(|c| matches!(c, HeadingChange::LevelChanged { .. })),
+        )?;
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+impl Document {
+    /// Compare the heading structure of `self` (the old version) with `other`.
+    ///
+    /// ```
+    /// use treemd::parse_markdown;
+    /// use treemd::parser::HeadingChange;
+    ///
+    /// let old = parse_markdown("# Guide\n## Instalation\n## Usage\n");
+    /// let new = parse_markdown("# Guide\n## Installation\n### Usage\n");
+    /// let diff = old.diff(&new);
+    /// assert!(matches!(&diff.changes[0], HeadingChange::Renamed { to, .. } if to == "Installation"));
+    /// assert_eq!(diff.changes.len(), 3); // Usage also moved under Installation, one level down
+    /// ```
+    pub fn diff(&self, other: &Document) -> StructureDiff {
+        let old = Tree::new(&self.headings);
+        let new = Tree::new(&other.headings);
+        let mut matched: Vec<Option<usize>> = vec![None; old.len()];
+        let mut taken = vec![false; new.len()];
+
+        // Same path, then same text anywhere, in document order
+        let mut pair = |same: &dyn Fn(usize, usize) -> bool| {
+            for (o, slot) in matched.iter_mut().enumerate() {
+                if slot.is_some() {
+                    continue;
+                }
+                if let Some(n) = (0..new.len()).find(|&n| !taken[n] && same(o, n)) {
+                    *slot = Some(n);
+                    taken[n] = true;
+                }
+            }
+        };
+        pair(&|o, n| old.paths[o] == new.paths[n]);
+        pair(&|o, n| old.text(o) == new.text(n));
+
+        // Renames: the closest similar text under the corresponding parent.
+        // Parents come first in document order, so theirs are already known.
+        for o in 0..old.len() {
+            if matched[o].is_some() {
+                continue;
+            }
+            let parent = old.parents[o].and_then(|p| matched[p]);
+            if old.parents[o].is_some() && parent.is_none() {
+                continue;
+            }
+            let best = (0..new.len())
+                .filter(|&n| !taken[n] && new.parents[n] == parent)
+                .filter_map(|n| similarity(old.text(o), new.text(n)).map(|d| (d, n)))
+                .min_by_key(|&(distance, _)| distance);
+            if let Some((_, n)) = best {
+                matched[o] = Some(n);
+                taken[n] = true;
+            }
+        }
+
+        let mut changes: Vec<HeadingChange> = (0..old.len())
+            .filter(|&o| matched[o].is_none())
+            .map(|o| HeadingChange::Removed {
+                path: old.paths[o].clone(),
+                level: old.headings[o].level,
+            })
+            .collect();
+
+        let mut from_old: Vec<Option<usize>> = vec![None; new.len()];
+        for (o, n) in matched.iter().enumerate() {
+            if let Some(n) = n {
+                from_old[*n] = Some(o);
+            }
+        }
+        for (n, o) in from_old.into_iter().enumerate() {
+            let path = new.paths[n].clone();
+            let Some(o) = o else {
+                changes.push(HeadingChange::Added {
+                    path,
+                    level: new.headings[n].level,
+                });
+                continue;
+            };
+            if old.text(o) != new.text(n) {
+                changes.push(HeadingChange::Renamed {
+                    from: old.text(o).to_string(),
+                    to: new.text(n).to_string(),
+                    path: path.clone(),
+                });
+            }
+            let moved = match old.parents[o] {
+                None => new.parents[n].is_some(),
+                Some(p) => matched[p].is_none() || matched[p] != new.parents[n],
+            };
+            if moved {
+                changes.push(HeadingChange::Moved {
+                    from: old.paths[o].clone(),
+                    to: path.clone(),
+                });
+            }
+            let (from, to) = (old.headings[o].level, new.headings[n].level);
+            if from != to {
+                changes.push(HeadingChange::LevelChanged { path, from, to });
+            }
+        }
+
+        StructureDiff { changes }
+    }
+}
+
+/// Headings with their parent indices and text paths.
+struct Tree<'a> {
+    headings: &'a [Heading],
+    parents: Vec<Option<usize>>,
+    paths: Vec<Vec<String>>,
+}
+
+impl<'a> Tree<'a> {
+    fn new(headings: &'a [Heading]) -> Self {
+        let mut parents = Vec::with_capacity(headings.len());
+        let mut paths: Vec<Vec<String>> = Vec::with_capacity(headings.len());
+        let mut stack: Vec<usize> = Vec::new();
+
+        for (idx, heading) in headings.iter().enumerate() {
+            while stack
+                .last()
+                .is_some_and(|&top| headings[top].level >= heading.level)
+            {
+                stack.pop();
+            }
+            let parent = stack.last().copied();
+            let mut path = parent.map(|p| paths[p].clone()).unwrap_or_default();
+            path.push(heading.text.clone());
+            parents.push(parent);
+            paths.push(path);
+            stack.push(idx);
+        }
+
+        Self {
+            headings,
+            parents,
+            paths,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.headings.len()
+    }
+
+    fn text(&self, idx: usize) -> &str {
+        &self.headings[idx].text
+    }
+}
+
+/// Edit distance between two heading texts when they are close enough to be
+/// a rename: a typo-sized edit, or one text containing the other.
+fn similarity(old: &str, new: &str) -> Option<usize> {
+    let (old, new) = (old.to_lowercase(), new.to_lowercase());
+    let distance = levenshtein(&old, &new);
+    let longest = old.chars().count().max(new.chars().count());
+    let contained =
+        !old.is_empty() && !new.is_empty() && (old.contains(&new) || new.contains(&old));
+    (distance <= longest / 3 || contained).then_some(distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_markdown;
+
+    fn diff(old: &str, new: &str) -> Vec<String> {
+        parse_markdown(old)
+            .diff(&parse_markdown(new))
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_renamed_versus_removed_and_added() {
+        let old = "# Guide\n## Instalation\n### Linux\n## Usage\n";
+
+        // A typo fix keeps the section and its children
+        assert_eq!(
+            diff(old, "# Guide\n## Installation\n### Linux\n## Usage\n"),
+            ["~ Guide > Instalation → Installation"]
+        );
+        assert_eq!(
+            diff("# Guide\n## Install\n", "# Guide\n## Install Guide\n"),
+            ["~ Guide > Install → Install Guide"]
+        );
+
+        // An unrelated heading in the same place is a removal and an addition
+        assert_eq!(
+            diff(old, "# Guide\n## Instalation\n### Linux\n## Deployment\n"),
+            ["- Guide > Usage (h2)", "+ Guide > Deployment (h2)"]
+        );
+
+        // Similar text under a different parent is not a rename
+        assert_eq!(
+            diff("# A\n## Setup\n# B\n", "# A\n# B\n## Setups\n"),
+            ["- A > Setup (h2)", "+ B > Setups (h2)"]
+        );
+    }
+
+    #[test]
+    fn test_diff_moves_and_level_changes() {
+        let old = "# Guide\n## Install\n## Usage\n### Tips\n";
+        let new = "# Guide\n## Install\n### Tips\n## Usage\n";
+        assert_eq!(
+            diff(old, new),
+            ["> Guide > Usage > Tips → Guide > Install > Tips"]
+        );
+
+        let changes = diff("# Guide\n## Notes\n", "# Guide\n### Notes\n");
+        assert_eq!(changes, ["^ Guide > Notes h2 → h3"]);
+
+        let changes = diff("# Guide\n## Notes\n", "# Notes\n# Guide\n");
+        assert_eq!(changes, ["> Guide > Notes → Notes", "^ Notes h2 → h1"]);
+
+        assert!(parse_markdown(old).diff(&parse_markdown(old)).is_empty());
+    }
+
+    #[test]
+    fn test_diff_serializes_with_change_tag() {
+        let diff = parse_markdown("# A\n").diff(&parse_markdown("# A\n## B\n"));
+        assert_eq!(
+            serde_json::to_string(&diff).unwrap(),
+            r#"{"changes":[{"change":"added","path":["A","B"],"level":2}]}"#
+        );
+    }
+}
@@ -4,31 +4,83 @@
 //! markdown documents and their heading hierarchy.
 
 use indextree::{Arena, NodeId};
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
 
 /// A markdown document with its content and structure.
 ///
 /// Contains the original markdown content and a list of extracted headings.
-#[derive(Debug, Clone)]
+/// Serializes as a whole (see [`Document::to_json`]), with heading offsets
+/// included so a deserialized document can be used without re-parsing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Document {
+    /// Full markdown source, including any frontmatter
     pub content: String,
+    #[serde(
+        serialize_with = "serialize_headings",
+        deserialize_with = "deserialize_headings"
+    )]
     pub headings: Vec<Heading>,
 }
 
+/// Task list completion for a section, from [`Document::task_stats_for_section`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskStats {
+    /// Checked tasks
+    pub done: usize,
+    /// All tasks, checked or not
+    pub total: usize,
+}
+
 /// A heading in a markdown document.
 ///
 /// Represents a single heading with its level (1-6), text content, and byte position.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Heading {
     /// Heading level (1 for #, 2 for ##, etc.)
     pub level: usize,
     /// Heading text content (stripped of inline markdown formatting)
     pub text: String,
     /// Byte offset where the heading starts in the source document
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     pub offset: usize,
 }
 
+/// Heading as stored inside a serialized [`Document`].
+///
+/// Unlike the flat heading list output, this keeps the byte offset.
+#[derive(Serialize, Deserialize)]
+struct HeadingRecord {
+    level: usize,
+    text: String,
+    offset: usize,
+}
+
+fn serialize_headings<S: Serializer>(
+    headings: &[Heading],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(headings.iter().map(|h| HeadingRecord {
+        level: h.level,
+        text: h.text.clone(),
+        offset: h.offset,
+    }))
+}
+
+fn deserialize_headings<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Heading>, D::Error> {
+    let records = Vec::<HeadingRecord>::deserialize(deserializer)?;
+    Ok(records
+        .into_iter()
+        .map(|r| Heading {
+            level: r.level,
+            text: r.text,
+            offset: r.offset,
+        })
+        .collect())
+}
+
 /// A node in the heading tree.
 ///
 /// Represents a heading and its child headings in a hierarchical structure.
@@ -43,6 +95,61 @@ impl Document {
         Self { content, headings }
     }
 
+    /// Start building a document programmatically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treemd::parser::{Document, parse_markdown};
+    ///
+    /// let doc = Document::builder()
+    ///     .heading(1, "Title")
+    ///     .text("Intro")
+    ///     .heading(2, "Usage")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(doc, parse_markdown("# Title\nIntro\n## Usage\n"));
+    /// ```
+    pub fn builder() -> DocumentBuilder {
+        DocumentBuilder::default()
+    }
+
+    /// Serialize the whole document (content and headings with offsets) to JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Deserialize a document previously written by [`Document::to_json`].
+    ///
+    /// Fails if any heading offset does not point at a character boundary
+    /// inside the content, so offsets can be trusted for section extraction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treemd::parser::{Document, parse_markdown};
+    ///
+    /// let doc = parse_markdown("# Title\n\n## Section\n");
+    /// let json = doc.to_json().unwrap();
+    /// assert_eq!(Document::from_json(&json).unwrap(), doc);
+    /// ```
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        use serde::de::Error;
+
+        let doc: Self = serde_json::from_str(json)?;
+        if let Some(heading) = doc
+            .headings
+            .iter()
+            .find(|h| h.offset >= doc.content.len() || !doc.content.is_char_boundary(h.offset))
+        {
+            return Err(serde_json::Error::custom(format!(
+                "heading '{}' has offset {} outside the document content",
+                heading.text, heading.offset
+            )));
+        }
+        Ok(doc)
+    }
+
     /// Build a hierarchical tree from flat heading list
     pub fn build_tree(&self) -> Vec<HeadingNode> {
         let mut arena = Arena::new();
@@ -77,6 +184,31 @@ impl Document {
             .collect()
     }
 
+    /// Iterate over the heading hierarchy depth-first, in document order.
+    ///
+    /// Yields `(depth, heading)` pairs where depth 0 is a root of
+    /// [`Document::build_tree`], without building the tree. Use
+    /// [`TreeIter::new`] to walk a tree you already hold as nodes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use treemd::parse_markdown;
+    ///
+    /// let doc = parse_markdown("# A\n## B\n### C\n# D\n");
+    /// let outline: Vec<String> = doc
+    ///     .iter_tree()
+    ///     .map(|(depth, h)| format!("{}{}", "  ".repeat(depth), h.text))
+    ///     .collect();
+    /// assert_eq!(outline, ["A", "  B", "    C", "D"]);
+    /// ```
+    pub fn iter_tree(&self) -> DocumentTreeIter<'_> {
+        DocumentTreeIter {
+            headings: self.headings.iter(),
+            levels: Vec::new(),
+        }
+    }
+
     /// Get headings at a specific level
     pub fn headings_at_level(&self, level: usize) -> Vec<&Heading> {
         self.headings.iter().filter(|h| h.level == level).collect()
@@ -90,6 +222,47 @@ impl Document {
             .find(|h| h.text.to_lowercase() == search)
     }
 
+    /// Find the heading a link anchor points to.
+    ///
+    /// Matches GitHub-style slugs (`#getting-started`) as well as the heading
+    /// text itself, which wikilinks like `[[#Getting Started]]` use.
+    ///
+    /// ```
+    /// use treemd::parse_markdown;
+    ///
+    /// let doc = parse_markdown("# Getting Started\n");
+    /// assert!(doc.find_anchor("getting-started").is_some());
+    /// assert!(doc.find_anchor("Getting Started").is_some());
+    /// assert!(doc.find_anchor("missing").is_none());
+    /// ```
+    pub fn find_anchor(&self, anchor: &str) -> Option<&Heading> {
+        let anchor_lower = anchor.to_lowercase();
+        self.headings.iter().find(|h| {
+            super::utils::slugify(&h.text) == anchor_lower || h.text.eq_ignore_ascii_case(anchor)
+        })
+    }
+
+    /// The heading whose section contains 1-based source `line`: the last
+    /// heading at or before it.
+    ///
+    /// ```
+    /// use treemd::parse_markdown;
+    ///
+    /// let doc = parse_markdown("intro\n# Guide\ntext\n## Install\nsteps\n");
+    /// assert!(doc.heading_at_line(1).is_none());
+    /// assert_eq!(doc.heading_at_line(3).unwrap().text, "Guide");
+    /// assert_eq!(doc.heading_at_line(4).unwrap().text, "Install");
+    /// ```
+    pub fn heading_at_line(&self, line: usize) -> Option<&Heading> {
+        // Where the line after `line` starts
+        let end = self
+            .content
+            .match_indices('\n')
+            .nth(line.checked_sub(1)?)
+            .map_or(self.content.len(), |(idx, _)| idx + 1);
+        self.headings.iter().take_while(|h| h.offset < end).last()
+    }
+
     /// Get all headings matching a filter
     pub fn filter_headings(&self, filter: &str) -> Vec<&Heading> {
         let search = filter.to_lowercase();
@@ -101,40 +274,220 @@ impl Document {
 
     /// Extract the content of a section by heading text.
     ///
-    /// Uses stored byte offsets for fast, accurate extraction without string searching.
+    /// When several headings share the same text, the first one wins. Use
+    /// [`Document::extract_section_at`] to target a specific occurrence.
     pub fn extract_section(&self, heading_text: &str) -> Option<String> {
-        // Find the heading (O(n) scan of headings list)
-        let heading_idx = self
+        let heading = self.find_heading(heading_text)?;
+        self.extract_section_at(heading.offset)
+    }
+
+    /// Extract the content of the section whose heading starts at `offset`.
+    ///
+    /// Uses stored byte offsets for fast, accurate extraction without string searching,
+    /// so sections with duplicate heading text are resolved correctly.
+    pub fn extract_section_at(&self, offset: usize) -> Option<String> {
+        let heading = self.headings.iter().find(|h| h.offset == offset)?;
+
+        // Find end: next heading at same or higher level after this one
+        let end = self
             .headings
             .iter()
-            .position(|h| h.text.to_lowercase() == heading_text.to_lowercase())?;
+            .find(|h| h.offset > offset && h.level <= heading.level)
+            .map(|h| h.offset);
 
-        let heading = &self.headings[heading_idx];
-
-        // Start from the heading's stored byte offset
-        let start = heading.offset;
+        Some(self.section_body(offset, end))
+    }
 
-        // Find content start (skip the heading line itself)
-        let after_heading = &self.content[start..];
-        let content_start = after_heading
-            .find('\n')
-            .map(|i| start + i + 1)
-            .unwrap_or(start);
+    /// Extract the content of the section whose heading starts at `offset`,
+    /// without its subsections: it ends at the next heading of any level.
+    ///
+    /// ```
+    /// use treemd::parse_markdown;
+    ///
+    /// let doc = parse_markdown("# Guide\nIntro\n## Setup\nSteps\n# Notes\n");
+    /// assert_eq!(doc.extract_section_at(0).unwrap(), "Intro\n## Setup\nSteps");
+    /// assert_eq!(doc.extract_section_shallow_at(0).unwrap(), "Intro");
+    /// ```
+    pub fn extract_section_shallow_at(&self, offset: usize) -> Option<String> {
+        self.headings.iter().find(|h| h.offset == offset)?;
 
-        // Find end: next heading at same or higher level
         let end = self
             .headings
             .iter()
-            .skip(heading_idx + 1)
-            .find(|h| h.level <= heading.level)
-            .map(|h| h.offset)
+            .find(|h| h.offset > offset)
+            .map(|h| h.offset);
+
+        Some(self.section_body(offset, end))
+    }
+
+    /// The trimmed text after the heading line at `offset`, up to `end` (or
+    /// the end of the document).
+    fn section_body(&self, offset: usize, end: Option<usize>) -> String {
+        // Find content start (skip the heading line itself)
+        let after_heading = &self.content[offset..];
+        let content_start = after_heading
+            .find('\n')
+            .map(|i| offset + i + 1)
             .unwrap_or(self.content.len());
+        let end = end.unwrap_or(self.content.len()).max(content_start);
 
-        // Extract section content
-        Some(self.content[content_start..end].trim().to_string())
+        self.content[content_start..end].trim().to_string()
+    }
+
+    /// Count the task list items in `heading`'s section, subsections included.
+    ///
+    /// Returns `None` when the section has no tasks.
+    ///
+    /// ```
+    /// use treemd::parse_markdown;
+    /// use treemd::parser::TaskStats;
+    ///
+    /// let doc = parse_markdown("# Todo\n- [x] a\n- [ ] b\n## Later\n- [x] c\n# Notes\ntext\n");
+    /// let stats = doc.task_stats_for_section(&doc.headings[0]);
+    /// assert_eq!(stats, Some(TaskStats { done: 2, total: 3 }));
+    /// assert_eq!(doc.task_stats_for_section(&doc.headings[2]), None);
+    /// ```
+    pub fn task_stats_for_section(&self, heading: &Heading) -> Option<TaskStats> {
+        use super::content::{item_task_states, parse_content};
+        use super::output::Block;
+
+        fn count(blocks: &[Block], stats: &mut TaskStats) {
+            for block in blocks {
+                if let Block::List { items, .. } = block {
+                    for item in items {
+                        for checked in item_task_states(item.checked, &item.content) {
+                            stats.total += 1;
+                            stats.done += usize::from(checked);
+                        }
+                        count(&item.blocks, stats);
+                    }
+                }
+            }
+        }
+
+        let section = self.extract_section_at(heading.offset)?;
+        let mut stats = TaskStats::default();
+        count(&parse_content(&section, 0), &mut stats);
+        (stats.total > 0).then_some(stats)
+    }
+
+    /// Split the document into one markdown file per heading at `level`.
+    ///
+    /// Returns `(name, markdown)` pairs in document order. Each markdown holds
+    /// the heading line followed by its whole section, subsections included.
+    /// Names are slugs of the heading text, made unique with numeric suffixes
+    /// (`setup`, `setup-2`). `index` is never used as a name so callers can
+    /// write an index alongside the sections.
+    ///
+    /// ```
+    /// use treemd::parse_markdown;
+    ///
+    /// let doc = parse_markdown("# Intro\nHello\n## Details\nMore\n# Intro\nAgain\n");
+    /// let parts = doc.split_by_level(1);
+    /// assert_eq!(parts[0], ("intro".to_string(), "# Intro\n\nHello\n## Details\nMore\n".to_string()));
+    /// assert_eq!(parts[1].0, "intro-2");
+    /// ```
+    pub fn split_by_level(&self, level: usize) -> Vec<(String, String)> {
+        let mut used: HashSet<String> = HashSet::from(["index".to_string()]);
+        let mut parts = Vec::new();
+
+        for heading in self.headings.iter().filter(|h| h.level == level) {
+            let mut base = super::utils::slugify(&heading.text);
+            if base.is_empty() {
+                base = "section".to_string();
+            }
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while !used.insert(name.clone()) {
+                name = format!("{}-{}", base, suffix);
+                suffix += 1;
+            }
+
+            let heading_line = self.content[heading.offset..]
+                .lines()
+                .next()
+                .unwrap_or("")
+                .trim_end();
+            let body = self.extract_section_at(heading.offset).unwrap_or_default();
+            let markdown = if body.is_empty() {
+                format!("{}\n", heading_line)
+            } else {
+                format!("{}\n\n{}\n", heading_line, body)
+            };
+            parts.push((name, markdown));
+        }
+
+        parts
+    }
+
+    /// The markdown source with every heading level moved by `delta`.
+    ///
+    /// Levels are clamped to 1-6; non-heading lines, including `#` lines in
+    /// code blocks, are copied unchanged. Setext headings that change level
+    /// are rewritten as `#` headings, since underlines only express levels 1
+    /// and 2.
+    ///
+    /// ```
+    /// use treemd::parse_markdown;
+    ///
+    /// let doc = parse_markdown("## Setup\n```sh\n# comment\n```\n### Linux\n");
+    /// assert_eq!(doc.shift_headings(1), "### Setup\n```sh\n# comment\n```\n#### Linux\n");
+    /// assert_eq!(doc.shift_headings(-4), "# Setup\n```sh\n# comment\n```\n# Linux\n");
+    /// ```
+    pub fn shift_headings(&self, delta: i8) -> String {
+        let mut out = String::with_capacity(self.content.len() + self.headings.len());
+        let mut copied = 0;
+
+        for heading in &self.headings {
+            let level = shifted_level(heading.level, delta);
+            if level == heading.level {
+                continue;
+            }
+            let rest = &self.content[heading.offset..];
+            let line = rest.split_inclusive('\n').next().unwrap_or("");
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            let hashes = line[indent..].bytes().take_while(|&b| b == b'#').count();
+
+            if hashes > 0 {
+                out.push_str(&self.content[copied..heading.offset + indent]);
+                out.push_str(&"#".repeat(level));
+                copied = heading.offset + indent + hashes;
+            } else {
+                let underline = rest[line.len()..]
+                    .split_inclusive('\n')
+                    .next()
+                    .unwrap_or("");
+                let marker = underline.trim();
+                if marker.is_empty() || !marker.chars().all(|c| c == '=' || c == '-') {
+                    // Multi-line setext heading; leave it as written
+                    continue;
+                }
+                let eol = &underline[underline.trim_end_matches(['\r', '\n']).len()..];
+                out.push_str(&self.content[copied..heading.offset]);
+                out.push_str(&format!("{} {}{}", "#".repeat(level), line.trim(), eol));
+                copied = heading.offset + line.len() + underline.len();
+            }
+        }
+
+        out.push_str(&self.content[copied..]);
+        out
+    }
+
+    /// Number of headings that [`shift_headings`](Self::shift_headings)
+    /// would have to clamp to stay within levels 1-6.
+    pub fn headings_clamped_by_shift(&self, delta: i8) -> usize {
+        self.headings
+            .iter()
+            .filter(|h| !(1..=6).contains(&(h.level as isize + delta as isize)))
+            .count()
     }
 }
 
+/// `level` moved by `delta`, clamped to valid heading levels.
+fn shifted_level(level: usize, delta: i8) -> usize {
+    level.saturating_add_signed(delta as isize).clamp(1, 6)
+}
+
 fn build_heading_node(node_id: NodeId, arena: &Arena<Heading>) -> HeadingNode {
     let heading = arena[node_id].get().clone();
     let children = node_id
@@ -145,7 +498,208 @@ fn build_heading_node(node_id: NodeId, arena: &Arena<Heading>) -> HeadingNode {
     HeadingNode { heading, children }
 }
 
+/// Builder for [`Document`]s, created by [`Document::builder`].
+///
+/// Headings are either appended together with their markdown line
+/// ([`heading`](Self::heading)) or recorded at an explicit byte offset into
+/// content set with [`content`](Self::content). [`build`](Self::build)
+/// validates that every offset points at a heading line of the right level.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentBuilder {
+    content: String,
+    headings: Vec<Heading>,
+}
+
+impl DocumentBuilder {
+    /// Replace the document content.
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = content.into();
+        self
+    }
+
+    /// Append a `#` heading line and record it as a heading.
+    pub fn heading(mut self, level: usize, text: impl Into<String>) -> Self {
+        let text = text.into();
+        self.start_line();
+        let offset = self.content.len();
+        self.content
+            .push_str(&format!("{} {}\n", "#".repeat(level), text));
+        self.headings.push(Heading {
+            level,
+            text,
+            offset,
+        });
+        self
+    }
+
+    /// Append body text as its own line(s).
+    pub fn text(mut self, text: &str) -> Self {
+        self.start_line();
+        self.content.push_str(text);
+        if !text.ends_with('\n') {
+            self.content.push('\n');
+        }
+        self
+    }
+
+    /// Record a heading whose line starts at `offset` in the content.
+    pub fn heading_at(mut self, level: usize, text: impl Into<String>, offset: usize) -> Self {
+        self.headings.push(Heading {
+            level,
+            text: text.into(),
+            offset,
+        });
+        self
+    }
+
+    /// Finish the document.
+    ///
+    /// Fails if a heading level is outside 1-6, headings are not in increasing
+    /// offset order, or an offset does not start a `#` line of the heading's level.
+    pub fn build(self) -> Result<Document, String> {
+        let mut previous: Option<usize> = None;
+
+        for heading in &self.headings {
+            if !(1..=6).contains(&heading.level) {
+                return Err(format!(
+                    "Heading '{}' has invalid level {}",
+                    heading.text, heading.level
+                ));
+            }
+            if previous.is_some_and(|prev| heading.offset <= prev) {
+                return Err(format!(
+                    "Heading '{}' at offset {} is not after the previous heading",
+                    heading.text, heading.offset
+                ));
+            }
+
+            let line = self
+                .content
+                .get(heading.offset..)
+                .filter(|_| heading.offset == 0 || self.content[..heading.offset].ends_with('\n'))
+                .ok_or_else(|| {
+                    format!(
+                        "Heading '{}' offset {} is not at the start of a line",
+                        heading.text, heading.offset
+                    )
+                })?;
+            let hashes = line.chars().take_while(|&c| c == '#').count();
+            if hashes != heading.level {
+                return Err(format!(
+                    "Heading '{}' at offset {} is not a level {} heading line",
+                    heading.text, heading.offset, heading.level
+                ));
+            }
+
+            previous = Some(heading.offset);
+        }
+
+        Ok(Document::new(self.content, self.headings))
+    }
+
+    /// Make sure appended text starts on a new line.
+    fn start_line(&mut self) {
+        if !self.content.is_empty() && !self.content.ends_with('\n') {
+            self.content.push('\n');
+        }
+    }
+}
+
+/// Depth-first iterator over the headings of a [`Document`].
+///
+/// Created by [`Document::iter_tree`].
+pub struct DocumentTreeIter<'a> {
+    headings: std::slice::Iter<'a, Heading>,
+    /// Levels of the current heading's ancestors, outermost first
+    levels: Vec<usize>,
+}
+
+impl<'a> Iterator for DocumentTreeIter<'a> {
+    type Item = (usize, &'a Heading);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let heading = self.headings.next()?;
+
+        // Same parent rule as build_tree: nearest preceding shallower heading
+        while self
+            .levels
+            .last()
+            .is_some_and(|&level| level >= heading.level)
+        {
+            self.levels.pop();
+        }
+        let depth = self.levels.len();
+        self.levels.push(heading.level);
+
+        Some((depth, heading))
+    }
+}
+
+/// Depth-first iterator over [`HeadingNode`] trees.
+///
+/// Yields `(depth, node)` pairs in document order, where depth 0 is a node
+/// the iterator was started from.
+pub struct TreeIter<'a> {
+    stack: Vec<(usize, &'a HeadingNode)>,
+}
+
+impl<'a> TreeIter<'a> {
+    /// Iterate over a forest of root nodes, such as the result of
+    /// [`Document::build_tree`].
+    pub fn new(roots: &'a [HeadingNode]) -> Self {
+        Self {
+            stack: roots.iter().rev().map(|node| (0, node)).collect(),
+        }
+    }
+}
+
+impl<'a> Iterator for TreeIter<'a> {
+    type Item = (usize, &'a HeadingNode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.stack.pop()?;
+        // Push children in reverse so the first child is visited next
+        self.stack
+            .extend(node.children.iter().rev().map(|child| (depth + 1, child)));
+        Some((depth, node))
+    }
+}
+
 impl HeadingNode {
+    /// Iterate over this node and all its descendants depth-first.
+    ///
+    /// This node is yielded first at depth 0.
+    pub fn iter(&self) -> TreeIter<'_> {
+        TreeIter::new(std::slice::from_ref(self))
+    }
+
+    /// Iterate over all descendants of this node depth-first, excluding itself.
+    ///
+    /// Depths are relative to this node, so direct children have depth 1.
+    pub fn descendants(&self) -> impl Iterator<Item = (usize, &HeadingNode)> {
+        self.iter().skip(1)
+    }
+
+    /// Find the ancestors of the first heading matching `text` (case-insensitive).
+    ///
+    /// Returns the path from this node down to the matching node's parent,
+    /// outermost first. Returns an empty path when this node itself matches,
+    /// and `None` when no heading in the subtree matches.
+    pub fn ancestors_of(&self, text: &str) -> Option<Vec<&HeadingNode>> {
+        let search = text.to_lowercase();
+        let mut path: Vec<&HeadingNode> = Vec::new();
+
+        for (depth, node) in self.iter() {
+            path.truncate(depth);
+            if node.heading.text.to_lowercase() == search {
+                return Some(path);
+            }
+            path.push(node);
+        }
+
+        None
+    }
+
     /// Render as tree with box-drawing characters
     /// If compact is true, uses gapless box characters without trailing spaces
     pub fn render_box_tree(&self, prefix: &str, is_last: bool) -> String {
@@ -154,6 +708,20 @@ impl HeadingNode {
 
     /// Render as tree with box-drawing characters, with optional compact style
     pub fn render_box_tree_styled(&self, prefix: &str, is_last: bool, compact: bool) -> String {
+        self.render_box_tree_to_depth(prefix, is_last, compact, None)
+    }
+
+    /// Render as tree with box-drawing characters, showing at most `max_depth`
+    /// levels (this node is level 1).
+    ///
+    /// Nodes whose children are hidden are marked with `▶`.
+    pub fn render_box_tree_to_depth(
+        &self,
+        prefix: &str,
+        is_last: bool,
+        compact: bool,
+        max_depth: Option<usize>,
+    ) -> String {
         let mut result = String::new();
 
         let (connector, space, continuation) = if compact {
@@ -172,17 +740,32 @@ impl HeadingNode {
             }
         };
 
+        let collapsed = max_depth.is_some_and(|depth| depth <= 1) && !self.children.is_empty();
         let marker = "#".repeat(self.heading.level);
         result.push_str(&format!(
-            "{}{}{}{} {}\n",
-            prefix, connector, space, marker, self.heading.text
+            "{}{}{}{}{} {}\n",
+            prefix,
+            connector,
+            space,
+            if collapsed { "▶ " } else { "" },
+            marker,
+            self.heading.text
         ));
+        if collapsed {
+            return result;
+        }
 
         let child_prefix = format!("{}{}", prefix, continuation);
+        let child_depth = max_depth.map(|depth| depth - 1);
 
         for (i, child) in self.children.iter().enumerate() {
             let is_last_child = i == self.children.len() - 1;
-            result.push_str(&child.render_box_tree_styled(&child_prefix, is_last_child, compact));
+            result.push_str(&child.render_box_tree_to_depth(
+                &child_prefix,
+                is_last_child,
+                compact,
+                child_depth,
+            ));
         }
 
         result
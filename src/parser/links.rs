@@ -7,6 +7,7 @@
 //! All parsing is delegated to `turbovault-parser` for unified, code-block-aware
 //! link extraction.
 
+use std::ops::Range;
 use std::path::PathBuf;
 use turbovault_parser::LinkType;
 
@@ -19,6 +20,14 @@ pub struct Link {
     pub target: LinkTarget,
     /// Byte offset in the source content where the link starts
     pub offset: usize,
+    /// Byte offset in the source content just past the end of the link syntax
+    pub end: usize,
+    /// Label of the reference definition the target came from, for
+    /// `[text][label]`, `[label][]` and `[label]` links
+    pub reference: Option<String>,
+    /// Whether this is an image (`![alt](src)`), with the alt text as its
+    /// text and the image source as its target
+    pub image: bool,
 }
 
 /// The different types of link targets supported.
@@ -44,6 +53,46 @@ pub enum LinkTarget {
 }
 
 impl LinkTarget {
+    /// Classify the URL of a parsed inline link, as found in
+    /// [`InlineElement::Link`](crate::parser::output::InlineElement).
+    ///
+    /// Wikilinks arrive as `wikilink:target` URLs; `text` becomes the alias
+    /// when it differs from the target. Any URL with a scheme (`https://`,
+    /// `mailto:`) is external, and everything else is a relative file.
+    ///
+    /// ```
+    /// use treemd::parser::LinkTarget;
+    ///
+    /// assert_eq!(LinkTarget::from_url("Setup", "#setup"), LinkTarget::Anchor("setup".into()));
+    /// assert!(matches!(
+    ///     LinkTarget::from_url("API", "docs/api.md#usage"),
+    ///     LinkTarget::RelativeFile { anchor: Some(a), .. } if a == "usage"
+    /// ));
+    /// assert!(matches!(LinkTarget::from_url("me", "mailto:me@example.com"), LinkTarget::External(_)));
+    /// ```
+    pub fn from_url(text: &str, url: &str) -> LinkTarget {
+        if let Some(target) = url.strip_prefix("wikilink:") {
+            LinkTarget::WikiLink {
+                target: target.to_string(),
+                alias: (text != target).then(|| text.to_string()),
+            }
+        } else if let Some(anchor) = url.strip_prefix('#') {
+            LinkTarget::Anchor(anchor.to_string())
+        } else if url.contains("://") || url.starts_with("mailto:") {
+            LinkTarget::External(url.to_string())
+        } else if let Some((path, anchor)) = url.split_once('#') {
+            LinkTarget::RelativeFile {
+                path: path.into(),
+                anchor: Some(anchor.to_string()),
+            }
+        } else {
+            LinkTarget::RelativeFile {
+                path: url.into(),
+                anchor: None,
+            }
+        }
+    }
+
     /// Get a string representation of the link target for display/search
     pub fn as_str(&self) -> String {
         match self {
@@ -68,14 +117,56 @@ impl LinkTarget {
 }
 
 impl Link {
-    /// Create a new link.
+    /// Create a new link with no known source span.
     pub fn new(text: String, target: LinkTarget, offset: usize) -> Self {
+        Self::with_span(text, target, offset..offset)
+    }
+
+    /// Create a new link covering the given byte range of the source content.
+    pub fn with_span(text: String, target: LinkTarget, span: Range<usize>) -> Self {
         Self {
             text,
             target,
-            offset,
+            offset: span.start,
+            end: span.end,
+            reference: None,
+            image: false,
         }
     }
+
+    /// The byte range of the full link syntax in the source content.
+    pub fn span(&self) -> Range<usize> {
+        self.offset..self.end
+    }
+
+    /// Check whether the link's source span overlaps the given byte range.
+    pub fn overlaps(&self, range: Range<usize>) -> bool {
+        range.start < self.end && range.end > self.offset
+    }
+
+    /// The byte range this link's rendered text occupies in the plain-text form
+    /// of `content` (as produced by `turbovault_parser::to_plain_text`).
+    ///
+    /// `content` is the slice of the link's source that starts at byte `base`
+    /// (pass `0` for the full source). Search runs over plain text, so this maps
+    /// the exact source span into search coordinates.
+    pub fn plain_text_span(&self, content: &str, base: usize) -> Range<usize> {
+        let start = self.offset.saturating_sub(base).min(content.len());
+        let end = self.end.saturating_sub(base).clamp(start, content.len());
+        let before = turbovault_parser::to_plain_text(&content[..start]);
+        let through = turbovault_parser::to_plain_text(&content[..end]);
+
+        // Length of the shared prefix, kept on a char boundary
+        let shared = before
+            .char_indices()
+            .zip(through.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(before.len().min(through.len()), |((i, _), _)| i);
+
+        let rest = &through[shared..];
+        let start = shared + (rest.len() - rest.trim_start().len());
+        start..through.len().max(start)
+    }
 }
 
 /// Extract all links from markdown content.
@@ -89,6 +180,7 @@ impl Link {
 /// - Wikilinks: `[[target]]` or `[[target|alias]]`
 /// - Anchor links: `[text](#section)`
 /// - External links: `[text](https://...)`
+/// - Bare URLs and email addresses in text (see [`find_autolinks`])
 ///
 /// # Arguments
 ///
@@ -108,7 +200,11 @@ pub fn extract_links(content: &str) -> Vec<Link> {
             .unwrap_or_else(|| md_link.target.clone());
         let target = convert_link_type(&md_link.type_, &md_link.target);
 
-        links.push(Link::new(text, target, md_link.position.offset));
+        let start = md_link.position.offset;
+        let end = start + md_link.position.length;
+        let mut link = Link::with_span(text, target, start..end);
+        link.reference = content.get(start..end).and_then(reference_label);
+        links.push(link);
     }
 
     // Extract wikilinks via turbovault-parser
@@ -117,19 +213,253 @@ pub fn extract_links(content: &str) -> Vec<Link> {
         let alias = wikilink.display_text.clone();
         let display_text = alias.clone().unwrap_or_else(|| target.clone());
 
-        links.push(Link::new(
+        let start = wikilink.position.offset;
+        let end = start + wikilink.position.length;
+        links.push(Link::with_span(
             display_text,
             LinkTarget::WikiLink { target, alias },
-            wikilink.position.offset,
+            start..end,
         ));
     }
 
+    // Bare URLs and emails, outside code and the links found above
+    for (range, url) in text_autolinks(content) {
+        if links.iter().any(|link| link.overlaps(range.clone())) {
+            continue;
+        }
+        let text = content[range.clone()].to_string();
+        links.push(Link::with_span(text, LinkTarget::External(url), range));
+    }
+
     // Sort by offset for consistent ordering
     links.sort_by_key(|l| l.offset);
 
     links
 }
 
+/// Extract links and images from markdown content, ordered by offset.
+///
+/// Images are included as [`Link`]s with [`Link::image`] set, so they can
+/// be navigated and followed alongside the links from [`extract_links`].
+///
+/// ```
+/// use treemd::parser::links::extract_links_and_images;
+///
+/// let links = extract_links_and_images("![Logo](logo.png) and [docs](docs.md)");
+/// assert_eq!(links.len(), 2);
+/// assert!(links[0].image && links[0].text == "Logo");
+/// assert!(!links[1].image);
+/// ```
+pub fn extract_links_and_images(content: &str) -> Vec<Link> {
+    let mut links = extract_links(content);
+    links.extend(extract_images(content));
+    links.sort_by_key(|l| l.offset);
+    links
+}
+
+/// Images in `content`, outside code, with their alt text (or source when
+/// the alt text is empty) as the link text.
+fn extract_images(content: &str) -> Vec<Link> {
+    use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut images = Vec::new();
+    // Source, span and alt text of the image being read
+    let mut current: Option<(String, Range<usize>, String)> = None;
+
+    for (event, range) in Parser::new_ext(content, options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Image { dest_url, .. }) if current.is_none() => {
+                current = Some((dest_url.to_string(), range, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, alt)) = &mut current {
+                    alt.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Image) => {
+                if let Some((src, span, alt)) = current.take() {
+                    let text = if alt.trim().is_empty() {
+                        src.clone()
+                    } else {
+                        alt
+                    };
+                    let mut image = Link::with_span(text, LinkTarget::from_url("", &src), span);
+                    image.image = true;
+                    images.push(image);
+                }
+            }
+            _ => {}
+        }
+    }
+    images
+}
+
+/// Autolinks in the text of `content`: code spans, code blocks, HTML and
+/// the text of explicit links are skipped.
+fn text_autolinks(content: &str) -> Vec<(Range<usize>, String)> {
+    use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut found = Vec::new();
+    // Inside explicit links, images or code blocks
+    let mut skip_depth = 0usize;
+    // Adjacent text events are joined, since the parser can split a URL at
+    // characters like `_` that might have started emphasis
+    let mut run: Option<Range<usize>> = None;
+    let mut flush = |run: &mut Option<Range<usize>>| {
+        if let Some(range) = run.take() {
+            for (found_range, url) in find_autolinks(&content[range.clone()]) {
+                let start = range.start + found_range.start;
+                found.push((start..range.start + found_range.end, url));
+            }
+        }
+    };
+
+    for (event, range) in Parser::new_ext(content, options).into_offset_iter() {
+        match event {
+            Event::Text(_) if skip_depth == 0 => match &mut run {
+                Some(current) if current.end == range.start => current.end = range.end,
+                _ => {
+                    flush(&mut run);
+                    run = Some(range);
+                }
+            },
+            Event::Start(Tag::Link { .. } | Tag::Image { .. } | Tag::CodeBlock(_)) => {
+                flush(&mut run);
+                skip_depth += 1;
+            }
+            Event::End(TagEnd::Link | TagEnd::Image | TagEnd::CodeBlock) => {
+                skip_depth = skip_depth.saturating_sub(1);
+            }
+            _ => flush(&mut run),
+        }
+    }
+    flush(&mut run);
+    found
+}
+
+/// Bare `http://`/`https://` URLs and email addresses in plain text, with
+/// the URL each links to (`mailto:` for emails).
+///
+/// Trailing sentence punctuation and unbalanced closing parentheses are
+/// left out of URLs. A `mailto:` written before an address is included.
+///
+/// ```
+/// use treemd::parser::links::find_autolinks;
+///
+/// let text = "Docs at https://example.com/guide. Mail me@example.com!";
+/// let found = find_autolinks(text);
+/// assert_eq!(&text[found[0].0.clone()], "https://example.com/guide");
+/// assert_eq!(found[1].1, "mailto:me@example.com");
+/// ```
+pub fn find_autolinks(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut found: Vec<(Range<usize>, String)> = Vec::new();
+
+    for (start, _) in text.match_indices("http") {
+        if found.last().is_some_and(|(range, _)| range.end > start)
+            || text[..start]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_alphanumeric)
+        {
+            continue;
+        }
+        let rest = &text[start..];
+        let Some(scheme) = ["https://", "http://"]
+            .into_iter()
+            .find(|scheme| rest.starts_with(scheme))
+        else {
+            continue;
+        };
+        if !rest[scheme.len()..]
+            .chars()
+            .next()
+            .is_some_and(char::is_alphanumeric)
+        {
+            continue;
+        }
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '<')
+            .unwrap_or(rest.len());
+        let url = trim_url_end(&rest[..end]);
+        found.push((start..start + url.len(), url.to_string()));
+    }
+
+    let is_local = |c: char| c.is_ascii_alphanumeric() || ".+-_".contains(c);
+    let is_domain = |c: char| c.is_ascii_alphanumeric() || "-_.".contains(c);
+    let mut emails = Vec::new();
+    for (at, _) in text.match_indices('@') {
+        let local_start = text[..at]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| is_local(c))
+            .last()
+            .map_or(at, |(i, _)| i);
+        let domain_len = text[at + 1..]
+            .find(|c: char| !is_domain(c))
+            .unwrap_or(text.len() - at - 1);
+        let domain = text[at + 1..at + 1 + domain_len].trim_end_matches('.');
+        let valid_domain = domain.contains('.')
+            && !domain.ends_with(['-', '_'])
+            && domain
+                .rsplit('.')
+                .next()
+                .is_some_and(|tld| tld.chars().any(|c| c.is_ascii_alphabetic()));
+        if local_start == at || !valid_domain {
+            continue;
+        }
+        let start = if text[..local_start].ends_with("mailto:") {
+            local_start - "mailto:".len()
+        } else {
+            local_start
+        };
+        let end = at + 1 + domain.len();
+        if found
+            .iter()
+            .any(|(range, _)| range.start < end && start < range.end)
+        {
+            continue;
+        }
+        let address = &text[local_start..end];
+        emails.push((start..end, format!("mailto:{}", address)));
+    }
+
+    found.extend(emails);
+    found.sort_by_key(|(range, _)| range.start);
+    found
+}
+
+/// Drop trailing punctuation that ends the sentence rather than the URL.
+fn trim_url_end(mut url: &str) -> &str {
+    loop {
+        let Some(last) = url.chars().next_back() else {
+            return url;
+        };
+        let unbalanced_paren = last == ')' && url.matches(')').count() > url.matches('(').count();
+        if "?!.,:;*_~'\"".contains(last) || unbalanced_paren {
+            url = &url[..url.len() - last.len_utf8()];
+        } else {
+            return url;
+        }
+    }
+}
+
+/// The reference label used by reference-style link syntax, or `None` for
+/// inline links and autolinks.
+fn reference_label(source: &str) -> Option<String> {
+    // Collapsed `[label][]` uses the text as the label, like a shortcut
+    let source = source.strip_suffix("[]").unwrap_or(source);
+    let inner = source.strip_prefix('[')?.strip_suffix(']')?;
+    let label = match inner.rfind("][") {
+        Some(idx) => &inner[idx + 2..],
+        None => inner,
+    };
+    Some(label.to_string())
+}
+
 /// Convert turbovault LinkType to treemd LinkTarget.
 fn convert_link_type(link_type: &LinkType, target: &str) -> LinkTarget {
     match link_type {
@@ -265,6 +595,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_aliased_and_bare_wikilinks() {
+        let md = "See [[notes/setup|Setup Guide]] and [[glossary]].";
+        let links = extract_links(md);
+
+        assert_eq!(links.len(), 2);
+
+        // Aliased: display the alias, navigate to the target
+        assert_eq!(links[0].text, "Setup Guide");
+        assert_eq!(
+            links[0].target,
+            LinkTarget::WikiLink {
+                target: "notes/setup".to_string(),
+                alias: Some("Setup Guide".to_string()),
+            }
+        );
+
+        // Bare: display and navigate to the target
+        assert_eq!(links[1].text, "glossary");
+        assert_eq!(
+            links[1].target,
+            LinkTarget::WikiLink {
+                target: "glossary".to_string(),
+                alias: None,
+            }
+        );
+    }
+
     #[test]
     fn test_extract_multiple_links() {
         let md = r#"
@@ -292,6 +650,69 @@ Visit [GitHub](https://github.com/user/repo) for source.
         assert!(matches!(links[3].target, LinkTarget::External(_)));
     }
 
+    #[test]
+    fn test_link_spans_cover_source_syntax() {
+        let md = "Read [a](https://example.com/a/very/long/path/to/somewhere) now";
+        let links = extract_links(md);
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(
+            &md[links[0].span()],
+            "[a](https://example.com/a/very/long/path/to/somewhere)"
+        );
+    }
+
+    #[test]
+    fn test_link_spans_with_long_display_text() {
+        let md = "See [a very long description of a tiny target](#x) and [[t|an alias]].";
+        let links = extract_links(md);
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(
+            &md[links[0].span()],
+            "[a very long description of a tiny target](#x)"
+        );
+        assert_eq!(&md[links[1].span()], "[[t|an alias]]");
+    }
+
+    #[test]
+    fn test_adjacent_link_spans_do_not_overlap() {
+        let md = "[one](#one)[two](#two)[[three]]";
+        let links = extract_links(md);
+
+        assert_eq!(links.len(), 3);
+        assert_eq!(&md[links[0].span()], "[one](#one)");
+        assert_eq!(&md[links[1].span()], "[two](#two)");
+        assert_eq!(&md[links[2].span()], "[[three]]");
+        assert_eq!(links[0].end, links[1].offset);
+        assert_eq!(links[1].end, links[2].offset);
+
+        // A range inside the second link only overlaps the second link
+        let inside_two = 12..15;
+        let hits: Vec<_> = links
+            .iter()
+            .filter(|l| l.overlaps(inside_two.clone()))
+            .collect();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].text, "two");
+
+        let plain = turbovault_parser::to_plain_text(md);
+        assert_eq!(&plain[links[0].plain_text_span(md, 0)], "one");
+        assert_eq!(&plain[links[1].plain_text_span(md, 0)], "two");
+    }
+
+    #[test]
+    fn test_plain_text_span_matches_rendered_text() {
+        let md =
+            "Intro [short](https://example.com/a/much/longer/url) and [[page|Alias]] end\n\nNext";
+        let plain = turbovault_parser::to_plain_text(md);
+        let links = extract_links(md);
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(&plain[links[0].plain_text_span(md, 0)], "short");
+        assert_eq!(&plain[links[1].plain_text_span(md, 0)], "Alias");
+    }
+
     #[test]
     fn test_empty_content() {
         let md = "";
@@ -426,4 +847,74 @@ let x = "[[Fake Inside Code]]";
             "Expected WikiLink"
         );
     }
+
+    #[test]
+    fn test_extract_bare_urls_and_emails() {
+        let md = "Visit https://example.com/docs. Or (see https://en.wikipedia.org/wiki/Rust_(language)).\n\nWrite to mailto:team@example.com or ops@example.org!\n\n`https://code.example` and [https://x.com](https://x.com)\n\n```\nhttps://fenced.example\n```\n";
+        let links = extract_links(md);
+        let found: Vec<_> = links
+            .iter()
+            .map(|l| (l.text.as_str(), l.target.as_str(), &md[l.span()]))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "https://example.com/docs",
+                    "https://example.com/docs".to_string(),
+                    "https://example.com/docs"
+                ),
+                (
+                    "https://en.wikipedia.org/wiki/Rust_(language)",
+                    "https://en.wikipedia.org/wiki/Rust_(language)".to_string(),
+                    "https://en.wikipedia.org/wiki/Rust_(language)"
+                ),
+                (
+                    "mailto:team@example.com",
+                    "mailto:team@example.com".to_string(),
+                    "mailto:team@example.com"
+                ),
+                (
+                    "ops@example.org",
+                    "mailto:ops@example.org".to_string(),
+                    "ops@example.org"
+                ),
+                (
+                    "https://x.com",
+                    "https://x.com".to_string(),
+                    "[https://x.com](https://x.com)"
+                ),
+            ]
+        );
+
+        // Not addresses or URLs
+        assert!(find_autolinks("user@localhost, xhttps://a.b, https://, a@b.c-").is_empty());
+    }
+
+    #[test]
+    fn test_extract_links_and_images() {
+        let md = "[Guide](guide.md)\n\n![Diagram](img/arch.png) ![](https://example.com/b.gif)\n\n```\n![code](no.png)\n```\n";
+
+        // Images are left out of plain link extraction
+        assert_eq!(extract_links(md).len(), 1);
+
+        let links = extract_links_and_images(md);
+        let found: Vec<_> = links
+            .iter()
+            .map(|l| (l.text.as_str(), l.target.as_str(), l.image))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Guide", "guide.md".to_string(), false),
+                ("Diagram", "img/arch.png".to_string(), true),
+                (
+                    "https://example.com/b.gif",
+                    "https://example.com/b.gif".to_string(),
+                    true
+                ),
+            ]
+        );
+        assert_eq!(&md[links[1].span()], "![Diagram](img/arch.png)");
+    }
 }
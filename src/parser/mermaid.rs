@@ -0,0 +1,118 @@
+//! Mermaid diagrams of a document's heading tree.
+//!
+//! [`render_mermaid`] produces a `mindmap` and [`render_mermaid_flowchart`] a
+//! top-down `graph TD`, both nesting headings the way
+//! [`Document::build_tree`] does. Labels are always quoted so heading text
+//! with brackets or parentheses cannot be mistaken for node shapes.
+
+use super::Document;
+
+/// Label for the synthetic mindmap root when the document has no single top heading.
+const DEFAULT_ROOT: &str = "Document";
+
+/// Render the heading tree as a Mermaid `mindmap`.
+///
+/// A mindmap needs exactly one root: a document with a single top-level
+/// heading uses it, otherwise the top-level headings hang off a `Document` node.
+///
+/// ```
+/// use treemd::parse_markdown;
+/// use treemd::parser::render_mermaid;
+///
+/// let doc = parse_markdown("# Guide\n## Install\n## Usage\n");
+/// assert_eq!(
+///     render_mermaid(&doc),
+///     "mindmap\n  root((\"Guide\"))\n    n2[\"Install\"]\n    n3[\"Usage\"]\n"
+/// );
+/// ```
+pub fn render_mermaid(doc: &Document) -> String {
+    let tree: Vec<_> = doc.iter_tree().collect();
+    let single_root = tree.iter().filter(|(depth, _)| *depth == 0).count() == 1;
+
+    let mut out = String::from("mindmap\n");
+    let mut nodes = tree.iter().enumerate();
+    let indent_offset = if single_root {
+        let (_, (_, heading)) = nodes.next().expect("single root exists");
+        out.push_str(&format!("  root((\"{}\"))\n", escape_label(&heading.text)));
+        1
+    } else {
+        out.push_str(&format!("  root((\"{}\"))\n", DEFAULT_ROOT));
+        0
+    };
+
+    for (index, (depth, heading)) in nodes {
+        out.push_str(&format!(
+            "{}n{}[\"{}\"]\n",
+            "  ".repeat(depth + 2 - indent_offset),
+            index + 1,
+            escape_label(&heading.text)
+        ));
+    }
+    out
+}
+
+/// Render the heading tree as a Mermaid `graph TD` flowchart.
+///
+/// ```
+/// use treemd::parse_markdown;
+/// use treemd::parser::render_mermaid_flowchart;
+///
+/// let doc = parse_markdown("# Guide\n## Install\n");
+/// assert_eq!(
+///     render_mermaid_flowchart(&doc),
+///     "graph TD\n  n1[\"Guide\"]\n  n2[\"Install\"]\n  n1 --> n2\n"
+/// );
+/// ```
+pub fn render_mermaid_flowchart(doc: &Document) -> String {
+    let mut out = String::from("graph TD\n");
+    // Node id of the most recent heading at each depth
+    let mut parents: Vec<usize> = Vec::new();
+
+    for (index, (depth, heading)) in doc.iter_tree().enumerate() {
+        let id = index + 1;
+        out.push_str(&format!("  n{}[\"{}\"]\n", id, escape_label(&heading.text)));
+        parents.truncate(depth);
+        if let Some(parent) = parents.last() {
+            out.push_str(&format!("  n{} --> n{}\n", parent, id));
+        }
+        parents.push(id);
+    }
+    out
+}
+
+/// Escape text for use inside a quoted Mermaid label.
+///
+/// Mermaid has no backslash escapes; quotes and `#` are written as entity codes.
+fn escape_label(text: &str) -> String {
+    text.replace('#', "#35;").replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_markdown;
+
+    #[test]
+    fn test_mermaid_nesting_and_escaping() {
+        let doc = parse_markdown(
+            "# A (intro)\n## \"Quoted\" [x]\n### C# notes\n# B\n### Skipped level\n",
+        );
+
+        assert_eq!(
+            render_mermaid(&doc),
+            "mindmap\n  root((\"Document\"))\n    n1[\"A (intro)\"]\n      \
+             n2[\"#quot;Quoted#quot; [x]\"]\n        n3[\"C#35; notes\"]\n    \
+             n4[\"B\"]\n      n5[\"Skipped level\"]\n"
+        );
+        assert_eq!(
+            render_mermaid_flowchart(&doc),
+            "graph TD\n  n1[\"A (intro)\"]\n  n2[\"#quot;Quoted#quot; [x]\"]\n  n1 --> n2\n  \
+             n3[\"C#35; notes\"]\n  n2 --> n3\n  n4[\"B\"]\n  n5[\"Skipped level\"]\n  \
+             n4 --> n5\n"
+        );
+
+        let empty = parse_markdown("No headings\n");
+        assert_eq!(render_mermaid(&empty), "mindmap\n  root((\"Document\"))\n");
+        assert_eq!(render_mermaid_flowchart(&empty), "graph TD\n");
+    }
+}
@@ -8,17 +8,25 @@
 
 pub mod builder;
 pub mod content;
+mod diff;
 mod document;
 pub mod links;
+pub mod mermaid;
 pub mod output;
 pub mod utils;
 
 pub use builder::build_json_output;
-pub use document::{Document, Heading, HeadingNode};
+pub use diff::{HeadingChange, StructureDiff};
+pub use document::{
+    Document, DocumentBuilder, DocumentTreeIter, Heading, HeadingNode, TaskStats, TreeIter,
+};
 pub use links::{Link, LinkTarget, extract_links};
-pub use output::{Block, DocumentOutput, InlineElement, Section};
+pub use mermaid::{render_mermaid, render_mermaid_flowchart};
+pub use output::{Block, DocumentOutput, InlineElement, Section, json_schema};
 pub use utils::{parse_inline_html, strip_markdown_inline};
 
+use std::ops::Range;
+#[cfg(feature = "native")]
 use std::path::Path;
 
 /// Parse a markdown file and extract its structure.
@@ -34,6 +42,7 @@ use std::path::Path;
 /// # Errors
 ///
 /// Returns an error if the file cannot be read.
+#[cfg(feature = "native")]
 pub fn parse_file(path: &Path) -> std::io::Result<Document> {
     let content = std::fs::read_to_string(path)?;
     Ok(parse_markdown(&content))
@@ -44,6 +53,10 @@ pub fn parse_file(path: &Path) -> std::io::Result<Document> {
 /// Uses turbovault-parser for unified markdown parsing with proper
 /// code-block awareness and OFM support.
 ///
+/// Windows (`\r\n`) line endings are normalized to `\n` so that all byte
+/// offsets and line arithmetic work on a single newline byte. Line numbers are
+/// unaffected by the normalization.
+///
 /// # Arguments
 ///
 /// * `content` - Markdown content as a string
@@ -52,7 +65,8 @@ pub fn parse_file(path: &Path) -> std::io::Result<Document> {
 ///
 /// A `Document` containing the content and extracted headings with byte offsets.
 pub fn parse_markdown(content: &str) -> Document {
-    let headings = turbovault_parser::parse_headings(content)
+    let content = normalize_line_endings(content);
+    let headings = turbovault_parser::parse_headings(&content)
         .into_iter()
         .map(|h| Heading {
             level: h.level as usize,
@@ -61,7 +75,140 @@ pub fn parse_markdown(content: &str) -> Document {
         })
         .collect();
 
-    Document::new(content.to_string(), headings)
+    Document::new(content, headings)
+}
+
+/// Approximate number of bytes parsed between progress reports.
+const PROGRESS_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Parse markdown content like [`parse_markdown`], reporting progress.
+///
+/// Large documents are parsed in chunks; after each chunk `progress` is called
+/// with the number of headings found so far. The result is identical to
+/// [`parse_markdown`].
+///
+/// # Example
+///
+/// ```
+/// use treemd::parser::parse_markdown_with_progress;
+///
+/// let mut reports = Vec::new();
+/// let doc = parse_markdown_with_progress("# A\n## B\n", |n| reports.push(n));
+/// assert_eq!(doc.headings.len(), 2);
+/// assert_eq!(reports.last(), Some(&2));
+/// ```
+pub fn parse_markdown_with_progress(content: &str, progress: impl FnMut(usize)) -> Document {
+    parse_in_chunks(content, PROGRESS_CHUNK_BYTES, progress)
+}
+
+fn parse_in_chunks(content: &str, min_bytes: usize, mut progress: impl FnMut(usize)) -> Document {
+    let content = normalize_line_endings(content);
+    let mut headings = Vec::new();
+
+    for chunk in heading_chunks(&content, min_bytes) {
+        let start = chunk.start;
+        headings.extend(
+            turbovault_parser::parse_headings(&content[chunk])
+                .into_iter()
+                .map(|h| Heading {
+                    level: h.level as usize,
+                    text: h.text,
+                    offset: start + h.position.offset,
+                }),
+        );
+        progress(headings.len());
+    }
+
+    Document::new(content, headings)
+}
+
+/// Split content into chunks of at least `min_bytes` that parse independently.
+///
+/// Chunks only break before an ATX heading that follows a blank line outside
+/// fenced code, comments and raw HTML blocks, where no block can continue
+/// across the boundary.
+fn heading_chunks(content: &str, min_bytes: usize) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+    let mut fence: Option<(char, usize)> = None;
+    let mut html_end: Option<&str> = None;
+    let mut previous_blank = true;
+
+    for line in content.split_inclusive('\n') {
+        let line_text = line.trim_end_matches('\n');
+        let indent = line_text.len() - line_text.trim_start_matches(' ').len();
+        let trimmed = line_text.trim_start_matches(' ');
+
+        if fence.is_none()
+            && html_end.is_none()
+            && previous_blank
+            && offset - start >= min_bytes
+            && indent < 4
+            && is_atx_heading(trimmed)
+        {
+            chunks.push(start..offset);
+            start = offset;
+        }
+
+        if let Some(end) = html_end {
+            if line_text.to_lowercase().contains(end) {
+                html_end = None;
+            }
+        } else if indent < 4 {
+            let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~');
+            let marker_len = marker.map_or(0, |c| trimmed.chars().take_while(|&x| x == c).count());
+            match (fence, marker) {
+                (None, Some(c)) if marker_len >= 3 => fence = Some((c, marker_len)),
+                (Some((c, len)), Some(m))
+                    if m == c && marker_len >= len && trimmed[marker_len..].trim().is_empty() =>
+                {
+                    fence = None
+                }
+                (None, None) => html_end = raw_html_block_end(trimmed),
+                _ => {}
+            }
+        }
+
+        previous_blank = line_text.trim().is_empty();
+        offset += line.len();
+    }
+
+    chunks.push(start..content.len());
+    chunks
+}
+
+fn is_atx_heading(line: &str) -> bool {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    (1..=6).contains(&hashes)
+        && line[hashes..]
+            .chars()
+            .next()
+            .is_none_or(|c| c == ' ' || c == '\t')
+}
+
+/// For HTML blocks that may contain blank lines, the text that closes them.
+fn raw_html_block_end(line: &str) -> Option<&'static str> {
+    let lower = line.to_lowercase();
+    let (start, end) = [
+        ("<!--", "-->"),
+        ("<pre", "</pre>"),
+        ("<script", "</script>"),
+        ("<style", "</style>"),
+        ("<textarea", "</textarea>"),
+    ]
+    .into_iter()
+    .find(|(start, _)| lower.starts_with(start))?;
+    (!lower[start.len()..].contains(end)).then_some(end)
+}
+
+/// Convert `\r\n` line endings to `\n`.
+fn normalize_line_endings(content: &str) -> String {
+    if content.contains('\r') {
+        content.replace("\r\n", "\n")
+    } else {
+        content.to_string()
+    }
 }
 
 #[cfg(test)]
@@ -187,4 +334,285 @@ End of doc"#;
         assert!(content.contains("More lines"));
         assert!(content.contains("End of doc"));
     }
+
+    #[test]
+    fn test_extract_section_with_duplicate_heading_text() {
+        let md = r#"# Tool A
+
+## Usage
+Run tool A with --fast
+
+# Tool B
+
+## Usage
+Run tool B with --slow
+"#;
+
+        let doc = parse_markdown(md);
+        let usages: Vec<_> = doc.headings.iter().filter(|h| h.text == "Usage").collect();
+        assert_eq!(usages.len(), 2);
+
+        let first = doc.extract_section_at(usages[0].offset).unwrap();
+        assert_eq!(first, "Run tool A with --fast");
+
+        let second = doc.extract_section_at(usages[1].offset).unwrap();
+        assert_eq!(second, "Run tool B with --slow");
+
+        // Lookup by text resolves to the first occurrence
+        assert_eq!(doc.extract_section("Usage").unwrap(), first);
+    }
+
+    #[test]
+    fn test_extract_section_at_unknown_offset() {
+        let doc = parse_markdown(
+            "# Title
+
+Body
+",
+        );
+        assert!(doc.extract_section_at(3).is_none());
+    }
+
+    #[test]
+    fn test_crlf_content_is_normalized() {
+        let lf = "# Title\n\nIntro [link](#x)\n\n## Section\n\nBody\n";
+        let crlf = lf.replace('\n', "\r\n");
+
+        let doc = parse_markdown(&crlf);
+        let expected = parse_markdown(lf);
+
+        assert_eq!(doc.content, lf);
+        let offsets = |d: &Document| d.headings.iter().map(|h| h.offset).collect::<Vec<_>>();
+        assert_eq!(offsets(&doc), offsets(&expected));
+        assert_eq!(&doc.content[doc.headings[1].offset..][..10], "## Section");
+        assert_eq!(doc.extract_section("Section").unwrap(), "Body");
+        assert_eq!(extract_links(&doc.content), extract_links(lf));
+    }
+
+    #[test]
+    fn test_headingless_documents() {
+        for content in ["", "  \n\t\n", "Just a paragraph.\n\nAnd another.\n"] {
+            let doc = parse_markdown(content);
+            assert!(doc.headings.is_empty());
+            assert!(doc.build_tree().is_empty());
+            assert!(doc.extract_section("Anything").is_none());
+
+            let output = build_json_output(&doc, None);
+            assert_eq!(output.document.metadata.heading_count, 0);
+            assert_eq!(output.document.metadata.max_depth, 0);
+            assert!(output.document.sections.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_document_json_round_trip() {
+        let pieces = [
+            "---\ntitle: Notes\ntags: [a, b]\n---\n",
+            "# Café ☕\n\nIntro with **bold** and [link](#x).\n",
+            "## Usage\n\n```sh\n# not a heading\n```\n",
+            "## Usage\n\n- [ ] todo\n",
+            "### Deep 日本語\n\nText\n",
+            "Plain paragraph without heading.\n",
+        ];
+
+        // Every ordered combination of up to three pieces
+        let mut cases = vec![String::new()];
+        for a in &pieces {
+            for b in &pieces {
+                for c in &pieces {
+                    cases.push(format!("{a}{b}{c}"));
+                }
+            }
+        }
+
+        for content in cases {
+            let doc = parse_markdown(&content);
+            let restored = Document::from_json(&doc.to_json().unwrap()).unwrap();
+            assert_eq!(restored, doc);
+            for heading in &restored.headings {
+                assert_eq!(
+                    restored.extract_section_at(heading.offset),
+                    doc.extract_section_at(heading.offset)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_document_from_json_rejects_invalid_offsets() {
+        let json = r##"{"content":"# é","headings":[{"level":1,"text":"é","offset":3}]}"##;
+        assert!(Document::from_json(json).is_err());
+
+        let json = r##"{"content":"# A","headings":[{"level":1,"text":"A","offset":9}]}"##;
+        assert!(Document::from_json(json).is_err());
+    }
+
+    #[test]
+    fn test_tree_iterators_visit_headings_in_document_order() {
+        let doc = parse_markdown("# A\n## A1\n### A1a\n## A2\n# B\n### B-deep\n## B1\n");
+        let tree = doc.build_tree();
+
+        let from_nodes: Vec<(usize, &str)> = TreeIter::new(&tree)
+            .map(|(depth, node)| (depth, node.heading.text.as_str()))
+            .collect();
+        let from_doc: Vec<(usize, &str)> = doc
+            .iter_tree()
+            .map(|(depth, heading)| (depth, heading.text.as_str()))
+            .collect();
+
+        let expected = vec![
+            (0, "A"),
+            (1, "A1"),
+            (2, "A1a"),
+            (1, "A2"),
+            (0, "B"),
+            (1, "B-deep"),
+            (1, "B1"),
+        ];
+        assert_eq!(from_nodes, expected);
+        assert_eq!(from_doc, expected);
+
+        let subtree: Vec<(usize, &str)> = tree[0]
+            .iter()
+            .map(|(depth, node)| (depth, node.heading.text.as_str()))
+            .collect();
+        assert_eq!(subtree, vec![(0, "A"), (1, "A1"), (2, "A1a"), (1, "A2")]);
+
+        let descendants: Vec<&str> = tree[0]
+            .descendants()
+            .map(|(_, node)| node.heading.text.as_str())
+            .collect();
+        assert_eq!(descendants, vec!["A1", "A1a", "A2"]);
+    }
+
+    #[test]
+    fn test_ancestors_of() {
+        let doc = parse_markdown("# A\n## A1\n### A1a\n## A2\n");
+        let tree = doc.build_tree();
+        let texts = |path: Vec<&HeadingNode>| -> Vec<String> {
+            path.iter().map(|node| node.heading.text.clone()).collect()
+        };
+
+        assert_eq!(texts(tree[0].ancestors_of("a1a").unwrap()), vec!["A", "A1"]);
+        assert_eq!(texts(tree[0].ancestors_of("A2").unwrap()), vec!["A"]);
+        assert!(tree[0].ancestors_of("A").unwrap().is_empty());
+        assert!(tree[0].ancestors_of("Missing").is_none());
+    }
+
+    #[test]
+    fn test_builder_matches_parser() {
+        let doc = Document::builder()
+            .heading(1, "Guide")
+            .text("\nIntro text.\n")
+            .heading(2, "Install")
+            .text("Run it.")
+            .heading(3, "Linux")
+            .heading(2, "Usage")
+            .build()
+            .unwrap();
+
+        assert_eq!(doc, parse_markdown(&doc.content));
+        assert_eq!(doc.build_tree()[0].children.len(), 2);
+        assert_eq!(
+            doc.extract_section("Install").unwrap(),
+            "Run it.\n### Linux"
+        );
+    }
+
+    #[test]
+    fn test_builder_with_explicit_offsets() {
+        let content = "# A\nbody\n## B\n";
+        let doc = Document::builder()
+            .content(content)
+            .heading_at(1, "A", 0)
+            .heading_at(2, "B", 9)
+            .build()
+            .unwrap();
+        assert_eq!(doc, parse_markdown(content));
+
+        let build = |level, offset| {
+            Document::builder()
+                .content(content)
+                .heading_at(level, "B", offset)
+                .build()
+        };
+        assert!(build(2, 9).is_ok());
+        assert!(build(1, 9).is_err(), "level must match the # count");
+        assert!(build(2, 10).is_err(), "offset must start a line");
+        assert!(build(2, 99).is_err(), "offset must be inside the content");
+        assert!(build(7, 9).is_err(), "level must be 1-6");
+
+        let unordered = Document::builder()
+            .content(content)
+            .heading_at(2, "B", 9)
+            .heading_at(1, "A", 0)
+            .build();
+        assert!(unordered.is_err());
+    }
+
+    #[test]
+    fn test_chunked_parse_matches_parse_markdown() {
+        let md = "---\ntitle: x\n---\n\n# Intro\n\nText\n\n```sh\n\n# not a heading\n\n```\n\n\
+                  <!--\n\n# commented out\n\n-->\n\n<pre>\n\n# preformatted\n\n</pre>\n\n\
+                  Setext\n======\n\n> quote\n\n## Next\n\n~~~~\n```\n\n# still code\n~~~~\n\n\
+                  - item\n\n      # indented code\n\n### Last #\n";
+        let expected = parse_markdown(md);
+        assert_eq!(
+            expected
+                .headings
+                .iter()
+                .map(|h| h.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Intro", "Setext", "Next", "Last"]
+        );
+
+        // Split at every possible boundary
+        let mut reports = Vec::new();
+        let chunked = parse_in_chunks(md, 0, |n| reports.push(n));
+        assert_eq!(chunked, expected);
+        assert_eq!(reports, vec![0, 2, 3, 4]);
+
+        // Offsets are relative to the whole document
+        let with_crlf = parse_markdown_with_progress(&md.replace('\n', "\r\n"), |_| {});
+        assert_eq!(with_crlf, expected);
+    }
+
+    #[test]
+    fn test_heading_chunks_only_split_before_atx_headings() {
+        let md = "# A\n\n```\n\n# fenced\n\n```\n\nSetext\n------\n\n# B\n# C\n\n~~~\n# x\n~~~\n";
+        let starts: Vec<&str> = heading_chunks(md, 0)
+            .into_iter()
+            .map(|chunk| md[chunk].lines().next().unwrap_or(""))
+            .collect();
+        // Not inside the fences, not at the setext heading, and not at a
+        // heading that directly follows another line
+        assert_eq!(starts, vec!["", "# A", "# B"]);
+
+        // Chunks cover the document without gaps
+        let chunks = heading_chunks(md, 0);
+        assert_eq!(chunks.first().unwrap().start, 0);
+        assert_eq!(chunks.last().unwrap().end, md.len());
+        assert!(chunks.windows(2).all(|pair| pair[0].end == pair[1].start));
+
+        // A minimum size keeps small documents in one chunk
+        assert_eq!(heading_chunks(md, md.len()), vec![0..md.len()]);
+    }
+
+    #[test]
+    fn test_shift_headings() {
+        let md = "Title\n=====\n\n  ## Setup ##\n\n~~~\n## not a heading\n~~~\n###### Deep\n";
+        let doc = parse_markdown(md);
+
+        assert_eq!(
+            doc.shift_headings(1),
+            "## Title\n\n  ### Setup ##\n\n~~~\n## not a heading\n~~~\n###### Deep\n"
+        );
+        assert_eq!(doc.headings_clamped_by_shift(1), 1);
+        assert_eq!(doc.shift_headings(0), md);
+        assert_eq!(doc.headings_clamped_by_shift(-1), 1);
+
+        let shifted = parse_markdown(&doc.shift_headings(-1));
+        let levels: Vec<_> = shifted.headings.iter().map(|h| h.level).collect();
+        assert_eq!(levels, vec![1, 1, 5]);
+    }
 }
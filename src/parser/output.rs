@@ -12,6 +12,25 @@ pub use turbovault_parser::{
     ContentBlock as Block, InlineElement, ListItem, TableAlignment as Alignment,
 };
 
+/// JSON Schema (draft 2020-12) describing [`DocumentOutput`].
+///
+/// This is the contract for `treemd --output json`: every object lists its
+/// fields and rejects unknown ones, so a shape change fails validation.
+const JSON_SCHEMA: &str = include_str!("output.schema.json");
+
+/// Return the JSON Schema for [`build_json_output`](super::build_json_output)
+/// output.
+///
+/// # Example
+///
+/// ```
+/// let schema: serde_json::Value = serde_json::from_str(treemd::parser::json_schema()).unwrap();
+/// assert_eq!(schema["title"], "treemd document output");
+/// ```
+pub fn json_schema() -> &'static str {
+    JSON_SCHEMA
+}
+
 /// Root document structure with metadata and nested sections
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocumentOutput {
@@ -69,3 +88,276 @@ pub struct Content {
     /// Parsed content blocks
     pub blocks: Vec<Block>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// Validate `value` against the subset of JSON Schema used by our schema:
+    /// `type`, `const`, `enum`, `minimum`/`maximum`, `properties`,
+    /// `required`, `additionalProperties`, `items`, `oneOf` and `$ref`.
+    fn validate(value: &Value, schema: &Value, root: &Value, path: &str) -> Result<(), String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/$defs/");
+            return validate(value, &root["$defs"][name], root, path);
+        }
+
+        if let Some(variants) = schema["oneOf"].as_array() {
+            let matching = variants
+                .iter()
+                .filter(|variant| validate(value, variant, root, path).is_ok())
+                .count();
+            if matching != 1 {
+                return Err(format!(
+                    "{}: {} oneOf variants match {}",
+                    path, matching, value
+                ));
+            }
+        }
+
+        if let Some(types) = schema.get("type") {
+            let types: Vec<&str> = match types {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+                _ => vec![],
+            };
+            let matches = types.iter().any(|t| match *t {
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "string" => value.is_string(),
+                "integer" => value.is_u64() || value.is_i64(),
+                "boolean" => value.is_boolean(),
+                "null" => value.is_null(),
+                _ => false,
+            });
+            if !matches {
+                return Err(format!("{}: expected {:?}, got {}", path, types, value));
+            }
+        }
+
+        if let Some(expected) = schema.get("const")
+            && value != expected
+        {
+            return Err(format!("{}: expected {}, got {}", path, expected, value));
+        }
+        if let Some(allowed) = schema["enum"].as_array()
+            && !allowed.contains(value)
+        {
+            return Err(format!("{}: {} is not one of {:?}", path, value, allowed));
+        }
+        if let (Some(min), Some(n)) = (schema["minimum"].as_f64(), value.as_f64())
+            && n < min
+        {
+            return Err(format!("{}: {} is below {}", path, n, min));
+        }
+        if let (Some(max), Some(n)) = (schema["maximum"].as_f64(), value.as_f64())
+            && n > max
+        {
+            return Err(format!("{}: {} is above {}", path, n, max));
+        }
+
+        if let Some(object) = value.as_object() {
+            let properties = schema["properties"].as_object();
+            for required in schema["required"].as_array().into_iter().flatten() {
+                let key = required.as_str().unwrap_or_default();
+                if !object.contains_key(key) {
+                    return Err(format!("{}: missing {}", path, key));
+                }
+            }
+            for (key, field) in object {
+                match properties.and_then(|p| p.get(key)) {
+                    Some(field_schema) => {
+                        validate(field, field_schema, root, &format!("{}.{}", path, key))?
+                    }
+                    None if schema["additionalProperties"] == Value::Bool(false) => {
+                        return Err(format!("{}: unexpected field {}", path, key));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+            for (i, item) in items.iter().enumerate() {
+                validate(item, item_schema, root, &format!("{}[{}]", path, i))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_output_matches_schema() {
+        let md = r#"# Guide
+
+Intro with **bold**, *em*, `code`, ~~old~~, [a link](https://example.com "Title") and ![icon](icon.png).
+
+![Diagram](diagram.png)
+
+```rust
+fn main() {}
+```
+
+- [x] Done task
+- [ ] Open task
+  - Nested with [link](other.md)
+
+  ```sh
+  cargo test
+  ```
+
+1. First
+2. Second
+
+> Quoted text
+>
+> ```
+> quoted code
+> ```
+
+| Name | Value |
+|:-----|------:|
+| a    | 1     |
+
+---
+
+<details>
+<summary>More</summary>
+
+Hidden text
+
+</details>
+
+## Child
+
+### Grandchild
+
+Text
+"#;
+        let doc = crate::parser::parse_markdown(md);
+        let output = crate::parser::build_json_output(&doc, Some(std::path::Path::new("guide.md")));
+        let json = serde_json::to_value(&output).unwrap();
+
+        let schema: Value = serde_json::from_str(json_schema()).unwrap();
+        validate(&json, &schema, &schema, "$").unwrap();
+
+        // Every block kind in the document is covered
+        let text = json.to_string();
+        for kind in [
+            "paragraph",
+            "code",
+            "list",
+            "blockquote",
+            "table",
+            "image",
+            "horizontalrule",
+            "details",
+        ] {
+            assert!(
+                text.contains(&format!(r#""type":"{}""#, kind)),
+                "missing {} block",
+                kind
+            );
+        }
+
+        // Shape changes are caught
+        let mut renamed = json.clone();
+        renamed["document"]["metadata"]["heading_count"] = Value::from(1);
+        assert!(validate(&renamed, &schema, &schema, "$").is_err());
+        let mut retyped = json.clone();
+        retyped["document"]["sections"][0]["level"] = Value::from("1");
+        assert!(validate(&retyped, &schema, &schema, "$").is_err());
+    }
+
+    #[test]
+    fn test_schema_only_uses_validated_keywords() {
+        // Keywords `validate` checks, plus annotations with no effect
+        const KNOWN: &[&str] = &[
+            "type",
+            "const",
+            "enum",
+            "minimum",
+            "maximum",
+            "properties",
+            "required",
+            "additionalProperties",
+            "items",
+            "oneOf",
+            "$ref",
+            "$defs",
+            "$schema",
+            "$id",
+            "title",
+            "description",
+        ];
+        fn check(schema: &Value) {
+            match schema {
+                Value::Object(object) => {
+                    for (key, value) in object {
+                        assert!(KNOWN.contains(&key.as_str()), "unchecked keyword {key}");
+                        match key.as_str() {
+                            // Maps of names to schemas
+                            "properties" | "$defs" => {
+                                value.as_object().unwrap().values().for_each(check)
+                            }
+                            "items" | "additionalProperties" => check(value),
+                            "oneOf" => value.as_array().unwrap().iter().for_each(check),
+                            _ => {}
+                        }
+                    }
+                }
+                Value::Bool(_) => {}
+                other => panic!("not a schema: {other}"),
+            }
+        }
+        check(&serde_json::from_str(json_schema()).unwrap());
+    }
+
+    #[test]
+    fn test_schema_rejects_invalid_output() {
+        let doc = crate::parser::parse_markdown("# Title\n\nText\n\n- item\n");
+        let output = crate::parser::build_json_output(&doc, None);
+        let json = serde_json::to_value(&output).unwrap();
+        let schema: Value = serde_json::from_str(json_schema()).unwrap();
+        validate(&json, &schema, &schema, "$").unwrap();
+
+        let rejected = |edit: &dyn Fn(&mut Value)| {
+            let mut invalid = json.clone();
+            edit(&mut invalid);
+            validate(&invalid, &schema, &schema, "$").unwrap_err()
+        };
+        fn section(json: &mut Value) -> &mut Value {
+            &mut json["document"]["sections"][0]
+        }
+
+        // Extra fields
+        let error = rejected(&|json| section(json)["extra"] = Value::from(1));
+        assert!(error.contains("unexpected field extra"), "{error}");
+        let error = rejected(&|json| json["extra"] = Value::Null);
+        assert!(error.contains("unexpected field extra"), "{error}");
+
+        // Missing required fields
+        let error = rejected(&|json| {
+            section(json).as_object_mut().unwrap().remove("slug");
+        });
+        assert!(error.contains("missing slug"), "{error}");
+        let error = rejected(&|json| {
+            json["document"].as_object_mut().unwrap().remove("metadata");
+        });
+        assert!(error.contains("missing metadata"), "{error}");
+
+        // Wrong types and out-of-range values
+        let error = rejected(&|json| section(json)["title"] = Value::from(1));
+        assert!(error.contains("expected [\"string\"]"), "{error}");
+        let error = rejected(&|json| section(json)["children"] = Value::from("none"));
+        assert!(error.contains("expected [\"array\"]"), "{error}");
+        let error = rejected(&|json| section(json)["level"] = Value::from(7));
+        assert!(error.contains("above 6"), "{error}");
+
+        // Unknown block kinds match no variant
+        let error =
+            rejected(&|json| section(json)["content"]["blocks"][0]["type"] = Value::from("widget"));
+        assert!(error.contains("0 oneOf variants"), "{error}");
+    }
+}
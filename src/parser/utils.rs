@@ -243,10 +243,211 @@ pub fn get_heading_level(line: &str) -> Option<usize> {
     None
 }
 
+/// Generate a GitHub-compatible anchor slug from heading text.
+///
+/// Mirrors GitHub's algorithm: inline formatting is reduced to its visible
+/// text, the result is lowercased, every character other than letters,
+/// numbers, `_`, `-` and spaces is removed, and each space becomes `-`.
+/// Consecutive spaces are not collapsed, and dashes left behind by removed
+/// characters (such as a leading emoji) are kept, exactly as GitHub does.
+///
+/// # Examples
+///
+/// ```
+/// # use treemd::parser::utils::slugify;
+/// assert_eq!(slugify("Hello World"), "hello-world");
+/// assert_eq!(slugify("1. Item (x)"), "1-item-x");
+/// assert_eq!(slugify("snake_case-name"), "snake_case-name");
+/// ```
+pub fn slugify(text: &str) -> String {
+    use regex::Regex;
+    use std::sync::OnceLock;
+
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| Regex::new(r"!?\[([^\]]*)\]\([^)]*\)").unwrap());
+
+    let visible = link.replace_all(text, "$1");
+    let visible = strip_markdown_inline(&visible);
+
+    visible
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Byte length of the YAML frontmatter block at the start of `content`,
+/// both fences and the closing fence's newline included.
+///
+/// Frontmatter opens with `---` on the very first line, followed by a
+/// non-blank line, and closes with `---` or `...` on its own line. Anything
+/// else starting with `---` is left to the markdown parser: a `---` followed
+/// by a blank line is a horizontal rule, and `---` under paragraph text is a
+/// setext heading underline.
+///
+/// # Examples
+///
+/// ```
+/// # use treemd::parser::utils::frontmatter_len;
+/// assert_eq!(frontmatter_len("---\ntitle: x\n---\n# Doc\n"), Some(17));
+/// assert_eq!(frontmatter_len("---\n\nText\n---\n"), None);
+/// assert_eq!(frontmatter_len("Text\n---\n"), None);
+/// ```
+pub fn frontmatter_len(content: &str) -> Option<usize> {
+    let mut lines = content.split_inclusive('\n');
+    let opening = lines.next()?;
+    if opening.trim_end() != "---" {
+        return None;
+    }
+
+    let mut len = opening.len();
+    for (idx, line) in lines.enumerate() {
+        let text = line.trim_end();
+        if idx == 0 && text.trim().is_empty() {
+            return None;
+        }
+        len += line.len();
+        if idx > 0 && (text == "---" || text == "...") {
+            return Some(len);
+        }
+    }
+    None
+}
+
+/// Find the line ranges of fenced code blocks (` ``` ` or `~~~`), fences included.
+///
+/// Line numbers are 0-indexed and ranges are half-open. A closing fence must use
+/// the same character and be at least as long as the opening fence; an unclosed
+/// block runs to the end of the content.
+///
+/// # Examples
+///
+/// ```
+/// # use treemd::parser::utils::code_block_lines;
+/// let md = "text\n```rust\nlet x = 1;\n```\nmore";
+/// assert_eq!(code_block_lines(md), vec![1..4]);
+/// ```
+pub fn code_block_lines(content: &str) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut open: Option<(usize, char, usize)> = None; // (start line, fence char, fence len)
+    let mut line_count = 0;
+
+    for (idx, line) in content.lines().enumerate() {
+        line_count = idx + 1;
+        let trimmed = line.trim_start();
+        let Some(fence_char) = trimmed.chars().next().filter(|c| *c == '`' || *c == '~') else {
+            continue;
+        };
+        let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+        if fence_len < 3 {
+            continue;
+        }
+
+        match open {
+            None => open = Some((idx, fence_char, fence_len)),
+            Some((start, open_char, open_len))
+                if fence_char == open_char
+                    && fence_len >= open_len
+                    && trimmed[fence_len..].trim().is_empty() =>
+            {
+                ranges.push(start..idx + 1);
+                open = None;
+            }
+            Some(_) => {}
+        }
+    }
+
+    if let Some((start, _, _)) = open {
+        ranges.push(start..line_count);
+    }
+
+    ranges
+}
+
+/// Detect checkbox markers in text for task list items.
+///
+/// Parses text to identify task list checkbox patterns (`[x]`, `[X]`, `[ ]`).
+///
+/// # Arguments
+/// * `text` - The text to parse
+///
+/// # Returns
+/// A tuple of `(is_task, is_checked, remaining_text)`:
+/// - `is_task`: true if a checkbox pattern was found
+/// - `is_checked`: true if the checkbox is checked (`[x]` or `[X]`)
+/// - `remaining_text`: the text after the checkbox marker (or original text if no checkbox)
+pub fn detect_checkbox_in_text(text: &str) -> (bool, bool, &str) {
+    let trimmed = text.trim_start();
+
+    // Check for [x] or [X] (checked)
+    if let Some(stripped) = trimmed
+        .strip_prefix("[x]")
+        .or_else(|| trimmed.strip_prefix("[X]"))
+    {
+        return (true, true, stripped.trim_start());
+    }
+
+    // Check for [ ] (unchecked)
+    if let Some(stripped) = trimmed.strip_prefix("[ ]") {
+        return (true, false, stripped.trim_start());
+    }
+
+    // Not a task list item
+    (false, false, text)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_code_block_lines() {
+        let md = "intro\n\n```rust\nlet a = 1;\n```\n\n- item\n\n  ~~~\n  nested\n  ~~~\n\nend";
+        assert_eq!(code_block_lines(md), vec![2..5, 8..11]);
+    }
+
+    #[test]
+    fn test_code_block_lines_nested_fences_and_unclosed() {
+        // A shorter or different fence inside a block doesn't close it
+        let md = "````md\n```\ninner\n```\n````\n~~~\nopen";
+        assert_eq!(code_block_lines(md), vec![0..5, 5..7]);
+        assert!(code_block_lines("no code here").is_empty());
+    }
+
+    #[test]
+    fn test_slugify_matches_github_anchors() {
+        let cases = [
+            ("Hello World", "hello-world"),
+            ("API Reference", "api-reference"),
+            ("1. Item (x)", "1-item-x"),
+            ("1. Getting Started", "1-getting-started"),
+            ("What's new?", "whats-new"),
+            ("v2.0 Release", "v20-release"),
+            ("Foo & Bar", "foo--bar"),
+            ("Multiple   spaces", "multiple---spaces"),
+            ("  Padded heading  ", "padded-heading"),
+            ("Keep-dashes_and_underscores", "keep-dashes_and_underscores"),
+            ("🚀 Launch", "-launch"),
+            ("Done ✅", "done-"),
+            ("Café Olé", "café-olé"),
+            ("C++ / Rust", "c--rust"),
+            ("**Bold** heading", "bold-heading"),
+            ("`code_name` usage", "code_name-usage"),
+            ("See [the docs](https://example.com)", "see-the-docs"),
+            ("Émoji 🎉 Party", "émoji--party"),
+        ];
+
+        for (heading, expected) in cases {
+            assert_eq!(slugify(heading), expected, "heading: {:?}", heading);
+        }
+    }
+
     #[test]
     fn test_strip_markdown_inline() {
         assert_eq!(strip_markdown_inline("**bold**"), "bold");
@@ -331,4 +532,64 @@ mod tests {
         assert_eq!(elements.len(), 1);
         assert!(matches!(&elements[0], InlineElement::Text { value } if value == "No HTML here"));
     }
+
+    mod detect_checkbox_tests {
+        use super::*;
+
+        #[test]
+        fn test_checked_lowercase() {
+            let (is_task, is_checked, text) = detect_checkbox_in_text("[x] Task done");
+            assert!(is_task);
+            assert!(is_checked);
+            assert_eq!(text, "Task done");
+        }
+
+        #[test]
+        fn test_checked_uppercase() {
+            let (is_task, is_checked, text) = detect_checkbox_in_text("[X] Also done");
+            assert!(is_task);
+            assert!(is_checked);
+            assert_eq!(text, "Also done");
+        }
+
+        #[test]
+        fn test_unchecked() {
+            let (is_task, is_checked, text) = detect_checkbox_in_text("[ ] Not done yet");
+            assert!(is_task);
+            assert!(!is_checked);
+            assert_eq!(text, "Not done yet");
+        }
+
+        #[test]
+        fn test_not_a_task() {
+            let (is_task, is_checked, text) = detect_checkbox_in_text("Regular text");
+            assert!(!is_task);
+            assert!(!is_checked);
+            assert_eq!(text, "Regular text");
+        }
+
+        #[test]
+        fn test_with_leading_whitespace() {
+            let (is_task, is_checked, text) = detect_checkbox_in_text("  [x] Indented task");
+            assert!(is_task);
+            assert!(is_checked);
+            assert_eq!(text, "Indented task");
+        }
+
+        #[test]
+        fn test_empty_task() {
+            let (is_task, is_checked, text) = detect_checkbox_in_text("[x]");
+            assert!(is_task);
+            assert!(is_checked);
+            assert_eq!(text, "");
+        }
+
+        #[test]
+        fn test_bracket_but_not_checkbox() {
+            let (is_task, is_checked, text) = detect_checkbox_in_text("[a] Not a checkbox");
+            assert!(!is_task);
+            assert!(!is_checked);
+            assert_eq!(text, "[a] Not a checkbox");
+        }
+    }
 }
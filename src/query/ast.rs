@@ -1,13 +1,34 @@
 //! Abstract Syntax Tree types for the query language.
 //!
-//! The AST represents the parsed structure of a query expression.
+//! The AST represents the parsed structure of a query expression. The
+//! types are `#[non_exhaustive]`: new expression kinds and fields may be
+//! added in minor releases, so matches outside treemd need a wildcard arm.
 
 use std::fmt;
 
 /// A complete query consisting of one or more piped expressions.
+///
+/// Returned by [`parse`](super::parse). The query holds one [`PipedExpr`]
+/// whose stages are [`Expr`] nodes run left to right, so
+/// `.h2[Install] | text` becomes:
+///
+/// ```text
+/// Query
+/// └─ PipedExpr
+///    ├─ Expr::Element { kind: Heading(Some(2)), filters: [Filter::Text], index: None }
+///    └─ Expr::Function { name: "text", args: [] }
+/// ```
+///
+/// Commas at the top level become an [`Expr::Union`] stage, and `>`/`>>`
+/// an [`Expr::Hierarchy`]. Every node but [`Expr::Identity`] carries the
+/// [`Span`] of its source text, for pointing tools back at the query.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Query {
-    /// The expressions connected by commas (multiple outputs)
+    /// Top-level expressions whose outputs are concatenated
+    ///
+    /// The parser produces a single expression; top-level commas become
+    /// [`Expr::Union`] stages.
     pub expressions: Vec<PipedExpr>,
 }
 
@@ -19,6 +40,7 @@ impl Query {
 
 /// Expressions connected by pipes (`|`).
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct PipedExpr {
     /// Pipeline stages executed left-to-right
     pub stages: Vec<Expr>,
@@ -36,6 +58,7 @@ impl PipedExpr {
 
 /// A single expression in the query language.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum Expr {
     /// Identity selector: `.`
     Identity,
@@ -83,6 +106,9 @@ pub enum Expr {
         span: Span,
     },
 
+    /// Union: `.h2, .code` - results of every branch in document order
+    Union { exprs: Vec<Expr>, span: Span },
+
     /// Literal value
     Literal { value: Literal, span: Span },
 
@@ -117,6 +143,7 @@ impl Expr {
             Expr::Array { span, .. } => *span,
             Expr::Conditional { span, .. } => *span,
             Expr::Hierarchy { span, .. } => *span,
+            Expr::Union { span, .. } => *span,
             Expr::Literal { span, .. } => *span,
             Expr::Binary { span, .. } => *span,
             Expr::Unary { span, .. } => *span,
@@ -127,6 +154,7 @@ impl Expr {
 
 /// Element type for selectors.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ElementKind {
     /// Any heading: `.h`
     Heading(Option<u8>),
@@ -151,6 +179,8 @@ pub enum ElementKind {
 impl ElementKind {
     /// Parse an element kind from a string.
     /// Supports multiple aliases for discoverability and convenience.
+    // Unknown names are `None` rather than an error, unlike `FromStr`
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             // Headings - multiple conventions
@@ -223,6 +253,7 @@ impl fmt::Display for ElementKind {
 
 /// Filter for element selection.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum Filter {
     /// Text filter: `[text]` or `["exact text"]`
     Text {
@@ -236,18 +267,39 @@ pub enum Filter {
 
     /// Type filter: `[anchor]`, `[external]` for links
     Type { type_name: String, span: Span },
+
+    /// Source line filter: `[line>=100]`, `[line<50]`
+    Line {
+        op: BinaryOp,
+        line: usize,
+        span: Span,
+    },
+}
+
+impl Filter {
+    /// Get the span of this filter, brackets included.
+    pub fn span(&self) -> Span {
+        match self {
+            Filter::Text { span, .. }
+            | Filter::Regex { span, .. }
+            | Filter::Type { span, .. }
+            | Filter::Line { span, .. } => *span,
+        }
+    }
 }
 
 /// Index operation for element access.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum IndexOp {
     /// Single index: `[0]`, `[-1]`
     Single(i64),
 
-    /// Slice: `[0:3]`, `[:3]`, `[2:]`
+    /// Slice: `[0:3]`, `[:3]`, `[2:]`, `[::2]`, `[::-1]`
     Slice {
         start: Option<i64>,
         end: Option<i64>,
+        step: Option<i64>,
     },
 
     /// Iterate (no index): `[]`
@@ -256,6 +308,7 @@ pub enum IndexOp {
 
 /// Literal values.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum Literal {
     String(String),
     Number(f64),
@@ -276,6 +329,7 @@ impl fmt::Display for Literal {
 
 /// Binary operators.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum BinaryOp {
     // Comparison
     Eq,
@@ -346,6 +400,7 @@ impl fmt::Display for BinaryOp {
 
 /// Unary operators.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum UnaryOp {
     Not,
     Neg,
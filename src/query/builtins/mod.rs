@@ -8,7 +8,7 @@ mod string;
 use super::error::QueryError;
 use super::eval::EvalContext;
 use super::registry::{Function, Registry};
-use super::value::Value;
+use super::value::{TableValue, Value};
 
 /// Register all built-in functions.
 pub fn register_all(registry: &mut Registry) {
@@ -44,6 +44,8 @@ pub fn register_all(registry: &mut Registry) {
         Function::new(fn_select, 1..=1).with_takes_input(true),
     );
     registry.register_function("contains", Function::new(fn_contains, 1..=1));
+    registry.register_function("contains_cs", Function::new(fn_contains_cs, 1..=1));
+    registry.register_function("word", Function::new(fn_word, 1..=1));
     registry.register_function("startswith", Function::new(fn_startswith, 1..=1));
     registry.register_function("endswith", Function::new(fn_endswith, 1..=1));
     registry.register_function("matches", Function::new(fn_matches, 1..=1));
@@ -56,21 +58,32 @@ pub fn register_all(registry: &mut Registry) {
     registry.register_function("url", Function::new(fn_url, 0..=0));
     registry.register_function("lang", Function::new(fn_lang, 0..=0));
 
+    // Table functions
+    registry.register_function("headers", Function::new(fn_headers, 0..=0));
+    registry.register_function("rows", Function::new(fn_rows, 0..=0));
+    registry.register_function("cell", Function::new(fn_cell, 2..=2));
+    registry.register_function("column", Function::new(fn_column, 1..=1));
+
     // Aggregation functions
     registry.register_function("stats", Function::new(fn_stats, 0..=0));
     registry.register_function("levels", Function::new(fn_levels, 0..=0));
     registry.register_function("langs", Function::new(fn_langs, 0..=0));
     registry.register_function("types", Function::new(fn_types, 0..=0));
+    registry.register_function("tasks", Function::new(fn_tasks, 0..=0));
+    registry.register_function("word_frequency", Function::new(fn_word_frequency, 0..=2));
 
     // Utility functions
     registry.register_function("limit", Function::new(fn_limit, 1..=1));
     registry.register_function("skip", Function::new(fn_skip, 1..=1));
     registry.register_function("nth", Function::new(fn_nth, 1..=1));
+    registry.register_function("at_line", Function::new(fn_at_line, 1..=1));
     registry.register_function("any", Function::new(fn_any, 1..=1).with_takes_input(true));
     registry.register_function("all", Function::new(fn_all, 1..=1).with_takes_input(true));
     registry.register_function("min", Function::new(fn_min, 0..=0));
     registry.register_function("max", Function::new(fn_max, 0..=0));
     registry.register_function("add", Function::new(fn_add, 0..=0));
+    registry.register_function("avg", Function::new(fn_avg, 0..=0));
+    registry.register_function("median", Function::new(fn_median, 0..=0));
     registry.register_function("not", Function::new(fn_not, 0..=0));
     registry.register_function("null", Function::new(fn_null, 0..=0));
     registry.register_function("debug", Function::new(fn_debug, 0..=0));
@@ -99,6 +112,9 @@ pub fn register_all(registry: &mut Registry) {
     registry.register_alias("take", "limit");
     registry.register_alias("drop", "skip");
 
+    // Statistics
+    registry.register_alias("mean", "avg");
+
     // jq compatibility
     registry.register_alias("ascii_downcase", "lower");
     registry.register_alias("ascii_upcase", "upper");
@@ -369,6 +385,28 @@ fn fn_contains(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryEr
     Ok(vec![Value::Bool(result)])
 }
 
+fn fn_contains_cs(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let input = args.first().unwrap_or(&Value::Null);
+    let pattern = args.get(1).map(|v| v.to_text()).unwrap_or_default();
+    Ok(vec![Value::Bool(input.to_text().contains(&pattern))])
+}
+
+/// Case-insensitive match of `pattern` as a whole word: not touching letters,
+/// digits or `_` on either side, so `word("api")` skips "apiary".
+fn fn_word(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let input = args.first().unwrap_or(&Value::Null);
+    let pattern = args.get(1).map(|v| v.to_text()).unwrap_or_default();
+    let (text, pattern) = (input.to_text().to_lowercase(), pattern.to_lowercase());
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let result = !pattern.is_empty()
+        && text.match_indices(&pattern).any(|(at, _)| {
+            let before = text[..at].chars().next_back();
+            let after = text[at + pattern.len()..].chars().next();
+            !before.is_some_and(is_word) && !after.is_some_and(is_word)
+        });
+    Ok(vec![Value::Bool(result)])
+}
+
 fn fn_startswith(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
     let pattern = args.get(1).map(|v| v.to_text()).unwrap_or_default();
@@ -456,6 +494,76 @@ fn fn_lang(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError>
     }
 }
 
+// ============================================================================
+// Table functions
+// ============================================================================
+
+fn fn_headers(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    match args.first() {
+        Some(Value::Table(t)) => Ok(t.get_property("headers").into_iter().collect()),
+        _ => Ok(vec![Value::Null]),
+    }
+}
+
+/// Body rows, each an array of cell strings (the header row is left out)
+fn fn_rows(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    match args.first() {
+        Some(Value::Table(t)) => Ok(t.get_property("rows").into_iter().collect()),
+        _ => Ok(vec![Value::Null]),
+    }
+}
+
+/// Column index for a number, or for a header name (exact, then ignoring case)
+fn column_index(table: &TableValue, column: Option<&Value>) -> Option<usize> {
+    match column? {
+        Value::Number(n) if *n >= 0.0 => Some(*n as usize),
+        Value::String(name) => table
+            .headers
+            .iter()
+            .position(|h| h.trim() == name)
+            .or_else(|| {
+                table
+                    .headers
+                    .iter()
+                    .position(|h| h.trim().eq_ignore_ascii_case(name))
+            }),
+        _ => None,
+    }
+}
+
+/// `cell(row, column)`: a body cell by row index and column index or header
+fn fn_cell(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let Some(Value::Table(t)) = args.first() else {
+        return Ok(vec![Value::Null]);
+    };
+    let row = match args.get(1) {
+        Some(Value::Number(n)) if *n >= 0.0 => t.rows.get(*n as usize),
+        _ => None,
+    };
+    let cell = row
+        .zip(column_index(t, args.get(2)))
+        .and_then(|(row, c)| row.get(c));
+    Ok(vec![
+        cell.cloned().map(Value::String).unwrap_or(Value::Null),
+    ])
+}
+
+/// `column(name)`: the body cells under a header (or column index)
+fn fn_column(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let Some(Value::Table(t)) = args.first() else {
+        return Ok(vec![Value::Null]);
+    };
+    let Some(c) = column_index(t, args.get(1)).filter(|&c| c < t.headers.len()) else {
+        return Ok(vec![Value::Null]);
+    };
+    Ok(vec![Value::Array(
+        t.rows
+            .iter()
+            .map(|row| Value::String(row.get(c).cloned().unwrap_or_default()))
+            .collect(),
+    )])
+}
+
 // ============================================================================
 // Aggregation functions
 // ============================================================================
@@ -529,6 +637,130 @@ fn fn_types(args: &[Value], ctx: &EvalContext) -> Result<Vec<Value>, QueryError>
     Ok(vec![Value::Object(obj)])
 }
 
+/// Count task list items: `{done, total}`.
+///
+/// Nested tasks are counted too (see [`item_task_states`]).
+///
+/// [`item_task_states`]: crate::parser::content::item_task_states
+fn fn_tasks(args: &[Value], ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let _ = args;
+    let mut done = 0;
+    let mut total = 0;
+    for item in ctx.lists.iter().flat_map(|list| &list.items) {
+        for checked in crate::parser::content::item_task_states(item.checked, &item.content) {
+            total += 1;
+            if checked {
+                done += 1;
+            }
+        }
+    }
+
+    let mut obj = indexmap::IndexMap::new();
+    obj.insert("done".to_string(), Value::Number(done as f64));
+    obj.insert("total".to_string(), Value::Number(total as f64));
+    Ok(vec![Value::Object(obj)])
+}
+
+/// Common English words skipped by `word_frequency(n, true)`.
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "been",
+    "but", "by", "can", "do", "does", "for", "from", "had", "has", "have", "he", "her", "his",
+    "how", "i", "if", "in", "into", "is", "it", "its", "more", "no", "not", "of", "on", "one",
+    "or", "our", "she", "so", "some", "such", "than", "that", "the", "their", "them", "then",
+    "there", "these", "they", "this", "to", "up", "us", "was", "we", "were", "what", "when",
+    "which", "who", "will", "with", "would", "you", "your",
+];
+
+/// Count words in the input's text, most frequent first.
+///
+/// Usage: `word_frequency`, `word_frequency(10)` for the top 10, and
+/// `word_frequency(10, true)` to skip stopwords (`null` for no limit).
+/// Returns an array of `{word, count}` objects; ties sort alphabetically.
+fn fn_word_frequency(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let input = args.first().unwrap_or(&Value::Null);
+    let limit = args.get(1).and_then(|v| v.as_number()).map(|n| n as usize);
+    let skip_stopwords = args.get(2).is_some_and(|v| v.is_truthy());
+
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for word in prose_words(&input.to_text()) {
+        if !(skip_stopwords && STOPWORDS.contains(&word.as_str())) {
+            *counts.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|(a_word, a), (b_word, b)| b.cmp(a).then_with(|| a_word.cmp(b_word)));
+    if let Some(limit) = limit {
+        counts.truncate(limit);
+    }
+
+    let pairs = counts
+        .into_iter()
+        .map(|(word, count)| {
+            let mut obj = indexmap::IndexMap::new();
+            obj.insert("word".to_string(), Value::String(word));
+            obj.insert("count".to_string(), Value::Number(count as f64));
+            Value::Object(obj)
+        })
+        .collect();
+    Ok(vec![Value::Array(pairs)])
+}
+
+/// Lowercased words of markdown prose.
+///
+/// Fenced code, link destinations and URLs are skipped, and markdown
+/// punctuation splits words. Apostrophes inside words are kept (`don't`);
+/// tokens without letters (numbers, `---`) are dropped.
+fn prose_words(markdown: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        for chunk in strip_link_destinations(line).split_whitespace() {
+            if chunk.contains("://") {
+                continue;
+            }
+            for token in chunk.split(|c: char| !(c.is_alphanumeric() || c == '\'')) {
+                let token = token.trim_matches('\'');
+                if token.chars().any(char::is_alphabetic) {
+                    words.push(token.to_lowercase());
+                }
+            }
+        }
+    }
+
+    words
+}
+
+/// Remove `(destination)` after `]` so link and image targets aren't counted.
+fn strip_link_destinations(line: &str) -> std::borrow::Cow<'_, str> {
+    if !line.contains("](") {
+        return std::borrow::Cow::Borrowed(line);
+    }
+
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(pos) = rest.find("](") {
+        out.push_str(&rest[..=pos]);
+        let after = &rest[pos + 2..];
+        rest = match after.find(')') {
+            Some(end) => &after[end + 1..],
+            None => after,
+        };
+    }
+    out.push_str(rest);
+    std::borrow::Cow::Owned(out)
+}
+
 // ============================================================================
 // Utility functions
 // ============================================================================
@@ -610,6 +842,37 @@ fn fn_nth(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError>
     }
 }
 
+/// The element nearest at or before a source line. An array picks among
+/// its elements; a single heading is kept if it is the document's heading
+/// enclosing that line.
+fn fn_at_line(args: &[Value], ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    use super::eval::document_line;
+
+    let input = args.first().unwrap_or(&Value::Null);
+    let line = match args.get(1) {
+        Some(Value::Number(n)) => *n as usize,
+        _ => 0,
+    };
+
+    match input {
+        Value::Array(a) => Ok(a
+            .iter()
+            .filter_map(|v| document_line(v).filter(|&l| l <= line).map(|l| (l, v)))
+            .max_by_key(|(l, _)| *l)
+            .map(|(_, v)| vec![v.clone()])
+            .unwrap_or_default()),
+        Value::Heading(h) => {
+            let enclosing = ctx.headings.iter().rfind(|other| other.line <= line);
+            Ok(if enclosing.is_some_and(|e| e.index == h.index) {
+                vec![input.clone()]
+            } else {
+                vec![]
+            })
+        }
+        _ => Ok(vec![]),
+    }
+}
+
 fn fn_any(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
     let condition = args.get(1).unwrap_or(&Value::Bool(false));
@@ -714,6 +977,54 @@ fn fn_add(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError>
     }
 }
 
+/// Numbers in an array, skipping other values (like `min` and `max`).
+fn numbers(values: &[Value]) -> Vec<f64> {
+    values.iter().filter_map(Value::as_number).collect()
+}
+
+/// Arithmetic mean. Results are not rounded, so integer inputs can average to
+/// a fraction (`[1, 2] | avg` is `1.5`).
+fn fn_avg(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let input = args.first().unwrap_or(&Value::Null);
+    match input {
+        Value::Array(a) => {
+            let nums = numbers(a);
+            if nums.is_empty() {
+                Ok(vec![Value::Null])
+            } else {
+                let avg = nums.iter().sum::<f64>() / nums.len() as f64;
+                Ok(vec![Value::Number(avg)])
+            }
+        }
+        Value::Number(n) => Ok(vec![Value::Number(*n)]),
+        _ => Ok(vec![Value::Null]),
+    }
+}
+
+/// Median. For an even count this is the mean of the two middle values, so
+/// integer inputs can yield `.5` (`[1, 2, 3, 4] | median` is `2.5`).
+fn fn_median(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let input = args.first().unwrap_or(&Value::Null);
+    match input {
+        Value::Array(a) => {
+            let mut nums = numbers(a);
+            if nums.is_empty() {
+                return Ok(vec![Value::Null]);
+            }
+            nums.sort_by(f64::total_cmp);
+            let mid = nums.len() / 2;
+            let median = if nums.len().is_multiple_of(2) {
+                (nums[mid - 1] + nums[mid]) / 2.0
+            } else {
+                nums[mid]
+            };
+            Ok(vec![Value::Number(median)])
+        }
+        Value::Number(n) => Ok(vec![Value::Number(*n)]),
+        _ => Ok(vec![Value::Null]),
+    }
+}
+
 fn fn_not(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
     Ok(vec![Value::Bool(!input.is_truthy())])
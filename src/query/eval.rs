@@ -138,6 +138,8 @@ impl<'a> Engine<'a> {
                 span,
             } => self.eval_hierarchy(parent, child, *direct, *span),
 
+            Expr::Union { exprs, .. } => self.eval_union(exprs),
+
             Expr::Binary {
                 op,
                 left,
@@ -164,15 +166,9 @@ impl<'a> Engine<'a> {
         }
     }
 
-    fn eval_element(
-        &mut self,
-        kind: &ElementKind,
-        filters: &[Filter],
-        index: Option<&IndexOp>,
-        _span: Span,
-    ) -> Result<Vec<Value>, QueryError> {
-        // Get all elements of the requested kind
-        let mut elements: Vec<Value> = match kind {
+    /// Every element of `kind` in the document, before filters and indexing.
+    pub(crate) fn elements_of(&self, kind: &ElementKind) -> Vec<Value> {
+        match kind {
             ElementKind::Heading(level) => self
                 .context
                 .headings
@@ -228,7 +224,18 @@ impl<'a> Engine<'a> {
                 // TODO: parse front matter
                 Vec::new()
             }
-        };
+        }
+    }
+
+    fn eval_element(
+        &mut self,
+        kind: &ElementKind,
+        filters: &[Filter],
+        index: Option<&IndexOp>,
+        _span: Span,
+    ) -> Result<Vec<Value>, QueryError> {
+        // Get all elements of the requested kind
+        let mut elements = self.elements_of(kind);
 
         // Apply filters
         for filter in filters {
@@ -243,12 +250,21 @@ impl<'a> Engine<'a> {
         Ok(elements)
     }
 
-    fn apply_filter(
+    pub(crate) fn apply_filter(
         &self,
         elements: Vec<Value>,
         filter: &Filter,
     ) -> Result<Vec<Value>, QueryError> {
         match filter {
+            // `.code[rust]` names a language when the blocks have that one
+            Filter::Text {
+                pattern,
+                exact: false,
+                ..
+            } if elements.iter().any(|v| code_language_is(v, pattern)) => Ok(elements
+                .into_iter()
+                .filter(|v| code_language_is(v, pattern))
+                .collect()),
             Filter::Text { pattern, exact, .. } => {
                 let pattern_lower = pattern.to_lowercase();
                 Ok(elements
@@ -279,6 +295,19 @@ impl<'a> Engine<'a> {
                     .filter(|v| re.is_match(&v.to_text()))
                     .collect())
             }
+            Filter::Line { op, line, .. } => Ok(elements
+                .into_iter()
+                .filter(|v| {
+                    document_line(v).is_some_and(|l| match op {
+                        BinaryOp::Eq => l == *line,
+                        BinaryOp::Ne => l != *line,
+                        BinaryOp::Lt => l < *line,
+                        BinaryOp::Le => l <= *line,
+                        BinaryOp::Gt => l > *line,
+                        _ => l >= *line,
+                    })
+                })
+                .collect()),
             Filter::Type { type_name, .. } => Ok(elements
                 .into_iter()
                 .filter(|v| {
@@ -332,6 +361,34 @@ impl<'a> Engine<'a> {
                 }
                 return Ok(current);
             }
+            "map" => {
+                // Apply the argument to each element: [.h] | map(text)
+                // Objects map over their values, like jq's `[.[] | f]`
+                let [func] = args else {
+                    return Err(QueryError::new(
+                        QueryErrorKind::InvalidArity {
+                            function: name.to_string(),
+                            expected: "1..=1".to_string(),
+                            found: args.len(),
+                        },
+                        span,
+                        String::new(),
+                    ));
+                };
+                let input = self.context.current.clone();
+                let elements = match &input {
+                    Value::Array(a) => a.clone(),
+                    Value::Object(o) => o.values().cloned().collect(),
+                    other => vec![other.clone()],
+                };
+                let mut mapped = Vec::new();
+                for element in elements {
+                    self.context.current = element;
+                    mapped.extend(self.eval_expr(func)?);
+                }
+                self.context.current = input;
+                return Ok(vec![Value::Array(mapped)]);
+            }
             "_index" => {
                 // Internal index handling
                 if args.len() >= 2 {
@@ -395,6 +452,31 @@ impl<'a> Engine<'a> {
         }
     }
 
+    /// Evaluate each branch against the same input and merge the results.
+    ///
+    /// Elements with a known position (headings, code blocks, links) are
+    /// ordered by their line in the document, ties keeping selector order.
+    /// If any result has no position (tables, lists, computed values), the
+    /// branches' results are simply concatenated in selector order.
+    fn eval_union(&mut self, exprs: &[Expr]) -> Result<Vec<Value>, QueryError> {
+        let input = self.context.current.clone();
+        let mut results = Vec::new();
+        for expr in exprs {
+            self.context.current = input.clone();
+            results.extend(self.eval_expr(expr)?);
+        }
+        self.context.current = input;
+
+        let lines: Option<Vec<usize>> = results.iter().map(document_line).collect();
+        if let Some(lines) = lines {
+            let mut keyed: Vec<(usize, Value)> = lines.into_iter().zip(results).collect();
+            keyed.sort_by_key(|(line, _)| *line);
+            results = keyed.into_iter().map(|(_, value)| value).collect();
+        }
+
+        Ok(results)
+    }
+
     fn eval_hierarchy(
         &mut self,
         parent: &Expr,
@@ -608,12 +690,15 @@ impl<'a> Engine<'a> {
 // Helper functions
 
 fn extract_headings(doc: &Document) -> Vec<HeadingValue> {
+    // Headings are in offset order, so lines are counted in one pass
+    let mut counted = 0;
+    let mut line = 1;
     doc.headings
         .iter()
         .enumerate()
         .map(|(idx, h)| {
-            // Calculate line number
-            let line = doc.content[..h.offset].lines().count() + 1;
+            line += doc.content[counted..h.offset].matches('\n').count();
+            counted = h.offset;
 
             // Extract content (simplified - until next heading)
             let content_start = doc.content[h.offset..]
@@ -654,11 +739,14 @@ fn extract_blocks(
     Vec<TableValue>,
     Vec<ListValue>,
 ) {
-    use crate::parser::content::parse_content;
+    use crate::parser::content::parse_with_layout;
     use crate::parser::links::extract_links;
     use crate::parser::output::Block;
+    use crate::parser::utils::frontmatter_len;
 
-    let blocks = parse_content(&doc.content, 1);
+    // Frontmatter is metadata, not a code block
+    let body = &doc.content[frontmatter_len(&doc.content).unwrap_or(0)..];
+    let parsed = parse_with_layout(body, 1);
     let links = extract_links(&doc.content);
 
     let mut code_blocks = Vec::new();
@@ -722,7 +810,7 @@ fn extract_blocks(
         }
     }
 
-    for block in blocks {
+    for (block, layout) in parsed.blocks.into_iter().zip(&parsed.layouts) {
         match block {
             Block::Code {
                 language,
@@ -762,6 +850,7 @@ fn extract_blocks(
 
                 lists.push(ListValue {
                     ordered,
+                    start: layout.list_start(),
                     items: items
                         .into_iter()
                         .map(|i| ListItemValue {
@@ -805,13 +894,65 @@ fn extract_blocks(
                 url,
                 link_type,
                 offset: l.offset,
+                line: doc.content[..l.offset].matches('\n').count() + 1,
             }
         })
         .collect();
 
+    locate_code_blocks(&doc.content, &mut code_blocks);
+
     (code_blocks, link_values, images, tables, lists)
 }
 
+/// Fill in the source lines of code blocks.
+///
+/// The block parser does not track positions, so each block is found by
+/// scanning forward for its first content line, ignoring indentation and
+/// blockquote markers. Blocks whose content cannot be found keep the line
+/// of the previous block.
+fn locate_code_blocks(content: &str, code_blocks: &mut [CodeValue]) {
+    let lines: Vec<&str> = content
+        .lines()
+        .map(|line| line.trim_start_matches(|c: char| c.is_whitespace() || c == '>'))
+        .collect();
+    let is_fence = |line: &str| line.starts_with("```") || line.starts_with("~~~");
+
+    let mut cursor = 0;
+    for code in code_blocks {
+        let first = code.content.lines().next().unwrap_or("").trim();
+        let found = (!first.is_empty())
+            .then(|| (cursor..lines.len()).find(|&i| lines[i].trim_end() == first))
+            .flatten();
+        let Some(idx) = found else {
+            code.start_line = cursor + 1;
+            code.end_line = cursor + 1;
+            continue;
+        };
+
+        let fenced = idx > 0 && is_fence(lines[idx - 1]);
+        let body_lines = code.content.lines().count();
+        // 1-based line of the opening fence, or of the first indented line
+        code.start_line = if fenced { idx } else { idx + 1 };
+        code.end_line = idx + body_lines + usize::from(fenced);
+        cursor = idx + body_lines;
+    }
+}
+
+/// Line on which an element starts, if it has a known position.
+pub(crate) fn document_line(value: &Value) -> Option<usize> {
+    match value {
+        Value::Heading(h) => Some(h.line),
+        Value::Code(c) => Some(c.start_line),
+        Value::Link(l) => Some(l.line),
+        _ => None,
+    }
+}
+
+/// Whether `value` is a code block in `language`, ignoring case.
+fn code_language_is(value: &Value, language: &str) -> bool {
+    matches!(value, Value::Code(c) if c.language.as_deref().is_some_and(|l| l.eq_ignore_ascii_case(language)))
+}
+
 fn literal_to_value(lit: &Literal) -> Value {
     match lit {
         Literal::String(s) => Value::String(s.clone()),
@@ -833,28 +974,50 @@ fn apply_index(mut values: Vec<Value>, index: &IndexOp) -> Result<Vec<Value>, Qu
                 Ok(vec![])
             }
         }
-        IndexOp::Slice { start, end } => {
-            let len = values.len() as i64;
-            let start_idx = start
-                .map(|s| if s < 0 { (len + s).max(0) } else { s })
-                .unwrap_or(0) as usize;
-            let end_idx = end
-                .map(|e| if e < 0 { (len + e).max(0) } else { e })
-                .unwrap_or(len as i64) as usize;
-
-            let start_idx = start_idx.min(values.len());
-            let end_idx = end_idx.min(values.len());
-
-            if start_idx < end_idx {
-                Ok(values.drain(start_idx..end_idx).collect())
-            } else {
-                Ok(vec![])
-            }
+        IndexOp::Slice { start, end, step } => {
+            let indices = slice_indices(values.len(), *start, *end, step.unwrap_or(1));
+            let mut values: Vec<Option<Value>> = values.into_iter().map(Some).collect();
+            Ok(indices
+                .into_iter()
+                .filter_map(|i| values[i].take())
+                .collect())
         }
         IndexOp::Iterate => Ok(values),
     }
 }
 
+/// Indices selected by a Python-style slice over `len` items.
+///
+/// Negative bounds count from the end, out-of-range bounds are clamped, and a
+/// negative `step` walks backwards (with `start` defaulting to the last item).
+fn slice_indices(len: usize, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<usize> {
+    let len = len as i64;
+    let resolve = |bound: i64, low: i64, high: i64| {
+        let bound = if bound < 0 { bound + len } else { bound };
+        bound.clamp(low, high)
+    };
+
+    if step > 0 {
+        let start = start.map_or(0, |s| resolve(s, 0, len));
+        let end = end.map_or(len, |e| resolve(e, 0, len));
+        (start..end.max(start))
+            .step_by(step as usize)
+            .map(|i| i as usize)
+            .collect()
+    } else {
+        // -1 stands for "before the first item" when walking backwards
+        let start = start.map_or(len - 1, |s| resolve(s, -1, len - 1));
+        let end = end.map_or(-1, |e| resolve(e, -1, len - 1));
+        let mut indices = Vec::new();
+        let mut i = start;
+        while i > end {
+            indices.push(i as usize);
+            i += step;
+        }
+        indices
+    }
+}
+
 fn values_equal(a: &Value, b: &Value) -> bool {
     match (a, b) {
         (Value::Null, Value::Null) => true,
@@ -969,6 +1132,151 @@ mod tests {
         assert!(matches!(results[0], Value::Document(_)));
     }
 
+    #[test]
+    fn test_table_functions() {
+        let md = "| Task | Status |\n|------|--------|\n| Docs | done |\n| Tests | open |\n";
+        let texts =
+            |query: &str| -> Vec<String> { eval(md, query).iter().map(|v| v.to_text()).collect() };
+
+        let column = eval(md, ".table[0] | column(\"Status\")");
+        assert!(matches!(&column[..], [Value::Array(cells)] if cells.len() == 2));
+        assert_eq!(
+            texts(".table[0] | column(\"status\") | join(\",\")"),
+            ["done,open"]
+        );
+        assert_eq!(texts(".table[0] | cell(1, 0)"), ["Tests"]);
+        assert_eq!(texts(".table[0] | cell(0, \"Status\")"), ["done"]);
+        assert_eq!(texts(".table[0] | headers | join(\",\")"), ["Task,Status"]);
+        assert_eq!(
+            texts(".table[0] | rows | nth(1) | join(\",\")"),
+            ["Tests,open"]
+        );
+
+        // Out of range and unknown columns give null
+        assert!(matches!(
+            eval(md, ".table[0] | cell(5, 0)")[..],
+            [Value::Null]
+        ));
+        assert!(matches!(
+            eval(md, ".table[0] | column(\"Owner\")")[..],
+            [Value::Null]
+        ));
+
+        let json = crate::query::output::format(
+            &eval(md, ".table[0] | rows"),
+            crate::query::OutputFormat::Json,
+        );
+        assert!(json.contains(r#"["Docs","done"]"#), "{json}");
+    }
+
+    #[test]
+    fn test_contains_cs_and_word() {
+        let md = "# API\n# api keys\n# Apiary\n# rapid_api";
+        let texts =
+            |query: &str| -> Vec<String> { eval(md, query).iter().map(|v| v.to_text()).collect() };
+
+        assert_eq!(texts(".h | select(contains(\"api\")) | text").len(), 4);
+        assert_eq!(texts(".h | select(contains_cs(\"API\")) | text"), ["API"]);
+        // Case is ignored, but "Apiary" and "rapid_api" don't match as words
+        assert_eq!(
+            texts(".h | select(word(\"Api\")) | text"),
+            ["API", "api keys"]
+        );
+        assert!(texts(".h | select(word(\"\")) | text").is_empty());
+    }
+
+    #[test]
+    fn test_avg_and_median() {
+        let num = |md: &str, query: &str| eval(md, query)[0].as_number();
+
+        assert_eq!(num("", "[1, 2, 3, 4] | avg"), Some(2.5));
+        assert_eq!(num("", "[1, 2, 3, 4] | mean"), Some(2.5));
+        assert_eq!(num("", "[3, 1, 2] | median"), Some(2.0));
+        // Even counts average the middle pair; integers are not rounded
+        assert_eq!(num("", "[4, 1, 3, 2] | median"), Some(2.5));
+        // Non-numbers are skipped like min/max; nothing numeric gives null
+        assert_eq!(num("", "[1, \"x\", 3] | avg"), Some(2.0));
+        assert!(matches!(eval("", "[\"x\"] | median")[..], [Value::Null]));
+        assert!(matches!(eval("", "[] | avg")[..], [Value::Null]));
+    }
+
+    #[test]
+    fn test_word_frequency() {
+        let md = "# Notes\n\nThe fox and the [Dog](https://dog.example/the).\n\n```\nthe code\n```\n\nThe fox doesn't stop: 2024!\n";
+        let pairs = |query: &str| -> Vec<(String, f64)> {
+            match &eval(md, query)[..] {
+                [Value::Array(items)] => items
+                    .iter()
+                    .map(|item| {
+                        let word = item.get_property("word").unwrap().to_text();
+                        let count = item.get_property("count").unwrap().as_number().unwrap();
+                        (word, count)
+                    })
+                    .collect(),
+                other => panic!("Expected one array, got {:?}", other),
+            }
+        };
+        let pair = |word: &str, count: f64| (word.to_string(), count);
+
+        // Code, URLs and numbers are skipped; ties sort alphabetically
+        assert_eq!(
+            pairs(". | content | word_frequency(3)"),
+            [pair("the", 3.0), pair("fox", 2.0), pair("and", 1.0)]
+        );
+        assert_eq!(
+            pairs(". | content | word_frequency(null, true)"),
+            [
+                pair("fox", 2.0),
+                pair("doesn't", 1.0),
+                pair("dog", 1.0),
+                pair("notes", 1.0),
+                pair("stop", 1.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_numeric_pipelines_over_document() {
+        let md =
+            "# A\n## B\n## C\n### D\n\n```sh\na\n```\n\n```sh\na\nb\nc\n```\n\n```sh\na\nb\n```\n";
+
+        // Headings per level: 1, 2, 1
+        let avg = eval(md, "[.h] | group_by(\"level\") | map(count) | avg");
+        assert_eq!(avg[0].as_number(), Some(4.0 / 3.0));
+
+        let median = eval(md, "[.code | lines] | median");
+        assert_eq!(median[0].as_number(), Some(2.0));
+    }
+
+    #[test]
+    fn test_union_in_document_order() {
+        let md = "# Top\n\nSee [a](a.md).\n\n```rust\nfn a() {}\n```\n\n## Two\n\n    indented\n\n### Three\n";
+        let texts =
+            |query: &str| -> Vec<String> { eval(md, query).iter().map(|v| v.to_text()).collect() };
+
+        assert_eq!(texts(".h3, .h2 | .text"), ["Two", "Three"]);
+        assert_eq!(
+            texts(".code, .h, .link | .text"),
+            ["Top", "a", "fn a() {}", "Two", "indented", "Three"]
+        );
+        assert_eq!(texts(".h2, .code | count"), ["1", "1", "1"]);
+        assert_eq!(texts("[.h2, .code] | length"), ["3"]);
+        // Computed values have no position and keep selector order
+        assert_eq!(texts("\"b\", \"a\""), ["b", "a"]);
+        // Commas inside argument lists still separate arguments
+        assert_eq!(texts(".h2 | .text | replace(\"Two\", \"2\")"), ["2"]);
+
+        let code = eval(md, ".code");
+        let lines: Vec<(usize, usize)> = code
+            .iter()
+            .map(|v| match v {
+                Value::Code(c) => (c.start_line, c.end_line),
+                _ => panic!("Expected Code value"),
+            })
+            .collect();
+        assert_eq!(lines, [(5, 7), (11, 11)]);
+    }
+
     #[test]
     fn test_heading_selection() {
         let results = eval("# H1\n## H2\n### H3", ".h2");
@@ -996,6 +1304,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_heading_slice_with_step() {
+        let md = "# T\n## A\n## B\n## C\n## D\n## E";
+        let texts = |query: &str| -> Vec<String> {
+            eval(md, query)
+                .iter()
+                .map(|v| match v {
+                    Value::Heading(h) => h.text.clone(),
+                    other => panic!("expected heading, got {:?}", other),
+                })
+                .collect()
+        };
+
+        assert_eq!(texts(".h2[::2]"), ["A", "C", "E"]);
+        assert_eq!(texts(".h2[1::2]"), ["B", "D"]);
+        assert_eq!(texts(".h2[::-1]"), ["E", "D", "C", "B", "A"]);
+        assert_eq!(texts(".h2[-2::-2]"), ["D", "B"]);
+        assert_eq!(texts(".h2[3:0:-1]"), ["D", "C", "B"]);
+        assert_eq!(texts(".h2[1:3]"), ["B", "C"]);
+        // Out-of-range bounds are clamped rather than an error
+        assert_eq!(texts(".h2[10::-2]"), ["E", "C", "A"]);
+        assert!(texts(".h2[-10::-1]").is_empty());
+    }
+
     #[test]
     fn test_heading_filter() {
         let results = eval("# Hello\n## World\n## Goodbye", ".h2[World]");
@@ -1005,6 +1337,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_line_filter_and_at_line() {
+        let md = "# Guide\n\nintro\n\n> ## Quoted\n\n## Install\n\n```sh\n# not a heading\n```\n\n### Tips\n";
+        let texts =
+            |query: &str| -> Vec<String> { eval(md, query).iter().map(|v| v.to_text()).collect() };
+
+        assert_eq!(texts(".h | .line"), ["1", "5", "7", "13"]);
+        assert_eq!(texts(".h[line>=7]"), ["Install", "Tips"]);
+        assert_eq!(texts(".h[line<5]"), ["Guide"]);
+        assert_eq!(texts(".h[line==13]"), ["Tips"]);
+        assert_eq!(texts(".code[line>7]").len(), 1);
+
+        assert_eq!(texts(".h | at_line(10)"), ["Install"]);
+        assert_eq!(texts(".h | at_line(13)"), ["Tips"]);
+        assert_eq!(texts("[.h2] | at_line(20)"), ["Install"]);
+        assert!(texts("[.h2] | at_line(2)").is_empty());
+    }
+
+    #[test]
+    fn test_ordered_list_start_round_trips_to_markdown() {
+        use crate::query::{OutputFormat, format_output};
+
+        let results = eval("# Steps\n\n5. five\n6. six\n\n- bullet\n", ".list");
+        let Value::List(list) = &results[0] else {
+            panic!("Expected List value");
+        };
+        assert_eq!(list.start, Some(5));
+        assert_eq!(
+            format_output(&results[..1], OutputFormat::Markdown),
+            "5. five\n6. six"
+        );
+        let Value::List(bullets) = &results[1] else {
+            panic!("Expected List value");
+        };
+        assert_eq!(bullets.start, None);
+    }
+
+    #[test]
+    fn test_list_start_after_frontmatter_details_and_quotes() {
+        let markdown = "---\ntitle: x\n---\n# Steps\n\n<details>\n<summary>S</summary>\n\n\
+                        2. hidden\n</details>\n\n> 4. quoted\n\n7. seven\n8. eight\n";
+        let results = eval(markdown, ".list");
+        let Some(Value::List(list)) = results.last() else {
+            panic!("Expected List value");
+        };
+        assert_eq!(list.start, Some(7));
+        assert_eq!(list.items[0].content, "seven");
+    }
+
     #[test]
     fn test_code_blocks_in_list_items() {
         // Regression test: code blocks nested inside list items should be extracted
@@ -1049,8 +1430,7 @@ mod tests {
 
     #[test]
     fn test_code_blocks_with_content_filter_in_list() {
-        // Note: Language filtering via .code[rust] currently uses text filter (matches content)
-        // For content-based filtering, we can test with content patterns
+        // No block is in a language called "main", so this matches content
         let md = r#"## Examples
 
 1. Python example:
@@ -1075,4 +1455,26 @@ mod tests {
             assert!(c.content.contains("fn main"));
         }
     }
+
+    #[test]
+    fn test_code_language_filter_then_index() {
+        let md = "# Snippets\n\n```rust\nfn a() {}\n```\n\n```python\nprint('rust')\n```\n\n\
+                  ```Rust\nfn b() {}\n```\n\n```rust\nfn c() {\n\n    let x = 1;\n\n}\n```\n";
+
+        // Language filter first, then the position among those blocks
+        let results = eval(md, ".code[rust][2] | content");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].to_text(), "fn c() {\n\n    let x = 1;\n\n}");
+
+        // The python block mentions rust but isn't one of them
+        assert_eq!(eval(md, ".code[rust]").len(), 3);
+        assert_eq!(
+            eval(md, ".code[rust][-2] | content")[0].to_text(),
+            "fn b() {}"
+        );
+        assert_eq!(
+            eval(md, ".code[python] | content")[0].to_text(),
+            "print('rust')"
+        );
+    }
 }
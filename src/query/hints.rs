@@ -0,0 +1,273 @@
+//! Feedback for exploratory queries.
+//!
+//! [`validate`] tells an empty result apart from a probable typo by finding
+//! filters that match nothing although their elements exist, and
+//! [`suggest`] offers selector completions built from the document.
+
+use std::fmt;
+
+use super::ast::{ElementKind, Expr, Filter, Span};
+use super::eval::Engine;
+use super::registry::levenshtein;
+use super::value::Value;
+use crate::parser::Document;
+
+/// A filter that matched none of the elements it was applied to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryWarning {
+    /// The selector as written, e.g. `.h2[Instalation]`
+    pub selector: String,
+    /// Number of elements of that kind in the document
+    pub candidates: usize,
+    /// Closest existing text, when one is near enough to be a typo
+    pub suggestion: Option<String>,
+    /// Location of the selector in the query
+    pub span: Span,
+}
+
+impl fmt::Display for QueryWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` matches none of the {} elements it filters",
+            self.selector, self.candidates
+        )?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean \"{}\"?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Report text and regex filters that match no element of their kind.
+///
+/// Selectors whose kind doesn't occur in the document at all are not
+/// reported; their result is simply empty.
+///
+/// # Example
+///
+/// ```rust
+/// use treemd::{parse_markdown, query};
+///
+/// let doc = parse_markdown("# Guide\n## Installation\n## Usage");
+/// let warnings = query::validate(&doc, ".h2[Instalation]").unwrap();
+/// assert_eq!(warnings[0].suggestion.as_deref(), Some("Installation"));
+/// assert!(query::validate(&doc, ".h2[usage]").unwrap().is_empty());
+/// ```
+pub fn validate(doc: &Document, query_str: &str) -> Result<Vec<QueryWarning>, super::QueryError> {
+    let query = super::parse(query_str)?;
+    let engine = Engine::new(doc);
+
+    let mut selectors = Vec::new();
+    for piped in &query.expressions {
+        for stage in &piped.stages {
+            collect_selectors(stage, &mut selectors);
+        }
+    }
+
+    let mut warnings = Vec::new();
+    for (kind, filters, span) in selectors {
+        let mut elements = engine.elements_of(kind);
+        for filter in filters {
+            if elements.is_empty() {
+                break;
+            }
+            let remaining = engine.apply_filter(elements.clone(), filter)?;
+            if remaining.is_empty() && !matches!(filter, Filter::Type { .. }) {
+                let suggestion = match filter {
+                    Filter::Text { pattern, .. } => closest_text(&elements, pattern),
+                    _ => None,
+                };
+                warnings.push(QueryWarning {
+                    selector: query_str[span.start..span.end].to_string(),
+                    candidates: elements.len(),
+                    suggestion,
+                    span,
+                });
+            }
+            elements = remaining;
+        }
+    }
+    Ok(warnings)
+}
+
+/// Element selectors with filters anywhere in `expr`
+fn collect_selectors<'q>(expr: &'q Expr, out: &mut Vec<(&'q ElementKind, &'q [Filter], Span)>) {
+    match expr {
+        Expr::Element {
+            kind,
+            filters,
+            span,
+            ..
+        } => {
+            // The element's own span stops before its filters
+            if let Some(last) = filters.last() {
+                out.push((kind, filters, span.merge(last.span())));
+            }
+        }
+        Expr::Function { args, .. } => args.iter().for_each(|e| collect_selectors(e, out)),
+        Expr::Object { pairs, .. } => pairs.iter().for_each(|(_, e)| collect_selectors(e, out)),
+        Expr::Array { elements, .. } => elements.iter().for_each(|e| collect_selectors(e, out)),
+        Expr::Union { exprs, .. } => exprs.iter().for_each(|e| collect_selectors(e, out)),
+        Expr::Conditional {
+            condition,
+            then_branch,
+            else_branch,
+            ..
+        } => {
+            collect_selectors(condition, out);
+            collect_selectors(then_branch, out);
+            if let Some(else_branch) = else_branch {
+                collect_selectors(else_branch, out);
+            }
+        }
+        Expr::Hierarchy { parent, child, .. } => {
+            collect_selectors(parent, out);
+            collect_selectors(child, out);
+        }
+        Expr::Binary { left, right, .. } => {
+            collect_selectors(left, out);
+            collect_selectors(right, out);
+        }
+        Expr::Unary { expr, .. } | Expr::Group { expr, .. } => collect_selectors(expr, out),
+        Expr::Identity | Expr::Property { .. } | Expr::Literal { .. } => {}
+    }
+}
+
+/// The element text closest to `pattern`, if it is within typo distance
+fn closest_text(elements: &[Value], pattern: &str) -> Option<String> {
+    let pattern = pattern.to_lowercase();
+    let max_distance = (pattern.chars().count() / 3).max(2);
+    elements
+        .iter()
+        .map(|v| v.to_text())
+        .map(|text| (levenshtein(&text.to_lowercase(), &pattern), text))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, text)| text)
+}
+
+/// Element names offered when completing a bare `.` selector
+const ELEMENT_NAMES: &[&str] = &[
+    "h", "h1", "h2", "h3", "h4", "h5", "h6", "code", "link", "img", "table", "list",
+];
+
+/// Completions for a partially typed query, for building shell completion.
+///
+/// Inside an open filter (`.h2[Ins`) the candidates are the texts of that
+/// kind's headings or links containing what was typed so far, as quoted
+/// exact filters; after a `.` they are element names.
+///
+/// # Example
+///
+/// ```rust
+/// use treemd::{parse_markdown, query};
+///
+/// let doc = parse_markdown("# Guide\n## Installation\n## Usage");
+/// assert_eq!(query::suggest(&doc, ".h2[ins"), [r#".h2["Installation"]"#]);
+/// assert_eq!(query::suggest(&doc, ".co"), [".code"]);
+/// ```
+pub fn suggest(doc: &Document, partial: &str) -> Vec<String> {
+    // Completing the text of an unclosed filter
+    if let Some(open) = partial.rfind('[')
+        && !partial[open..].contains(']')
+    {
+        let head = &partial[..open];
+        let typed = partial[open + 1..]
+            .trim_start_matches(['"', '\''])
+            .to_lowercase();
+        let kind = head
+            .rfind('.')
+            .and_then(|dot| ElementKind::from_str(&head[dot + 1..]));
+        let Some(kind @ (ElementKind::Heading(_) | ElementKind::Link)) = kind else {
+            return Vec::new();
+        };
+
+        let mut texts: Vec<String> = Engine::new(doc)
+            .elements_of(&kind)
+            .iter()
+            .map(Value::to_text)
+            .filter(|text| text.to_lowercase().contains(&typed))
+            .collect();
+        let mut seen = std::collections::HashSet::new();
+        texts.retain(|text| seen.insert(text.clone()));
+        return texts
+            .iter()
+            .map(|text| {
+                let quoted = text.replace('\\', "\\\\").replace('"', "\\\"");
+                format!("{}[\"{}\"]", head, quoted)
+            })
+            .collect();
+    }
+
+    // Completing an element name
+    let Some(dot) = partial.rfind('.') else {
+        return Vec::new();
+    };
+    let typed = &partial[dot + 1..];
+    if !typed.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Vec::new();
+    }
+    ELEMENT_NAMES
+        .iter()
+        .filter(|name| name.starts_with(typed))
+        .map(|name| format!("{}.{}", &partial[..dot], name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    fn doc() -> Document {
+        parse_markdown(
+            "# Guide\n\n## Installation\n\n[docs](https://example.com)\n\n## Usage\n\n### Tips\n",
+        )
+    }
+
+    #[test]
+    fn test_validate_tells_typos_from_empty_results() {
+        let doc = doc();
+
+        let warnings = validate(&doc, ".h2[Instalation] | .text").unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].selector, ".h2[Instalation]");
+        assert_eq!(warnings[0].candidates, 2);
+        assert_eq!(
+            warnings[0].to_string(),
+            "`.h2[Instalation]` matches none of the 2 elements it filters (did you mean \"Installation\"?)"
+        );
+
+        // Nothing close enough to suggest
+        let warnings = validate(&doc, "[.h2[Nonexistent]]").unwrap();
+        assert_eq!(warnings[0].suggestion, None);
+
+        // Matches, and kinds missing from the document, are not reported
+        assert!(validate(&doc, ".h2[inst], .h3[tips]").unwrap().is_empty());
+        assert!(validate(&doc, ".h5[anything]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_suggest_completes_filters_and_element_names() {
+        let doc = doc();
+        assert_eq!(
+            suggest(&doc, ".h2["),
+            [r#".h2["Installation"]"#, r#".h2["Usage"]"#]
+        );
+        assert_eq!(suggest(&doc, ".h > .link[\"do"), [r#".h > .link["docs"]"#]);
+        assert_eq!(
+            suggest(&doc, ".h2 | .h"),
+            [
+                ".h2 | .h",
+                ".h2 | .h1",
+                ".h2 | .h2",
+                ".h2 | .h3",
+                ".h2 | .h4",
+                ".h2 | .h5",
+                ".h2 | .h6"
+            ]
+        );
+        assert!(suggest(&doc, ".code[").is_empty());
+    }
+}
@@ -24,6 +24,7 @@
 mod ast;
 mod error;
 mod eval;
+mod hints;
 mod lexer;
 mod parser;
 mod registry;
@@ -33,10 +34,13 @@ pub mod builtins;
 pub mod extractors;
 
 // Re-exports for public API
-pub use ast::Span;
-pub use ast::{Expr, Query};
+pub use ast::{
+    BinaryOp, ElementKind, Expr, Filter, IndexOp, Literal, PipedExpr, Query, Span, UnaryOp,
+};
 pub use error::{QueryError, QueryErrorKind};
 pub use eval::{Engine, EvalContext};
+pub use hints::{QueryWarning, suggest, validate};
+pub(crate) use registry::levenshtein;
 pub use registry::{ExtractorFn, Function, FunctionRegistry, Registry};
 pub use value::{Value, ValueKind};
 
@@ -61,9 +65,26 @@ pub fn execute(doc: &Document, query_str: &str) -> Result<Vec<Value>, QueryError
     engine.execute(&query)
 }
 
-/// Parse a query string into an AST.
+/// Parse a query string into an AST without running it.
 ///
-/// Useful when you want to parse once and execute multiple times.
+/// Useful when you want to parse once and execute multiple times, or to
+/// inspect a query's selectors, pipes and functions from other tools; see
+/// [`Query`] for the shape of the tree. [`execute`] is this followed by
+/// [`Engine::execute`].
+///
+/// # Example
+///
+/// ```rust
+/// use treemd::query::{self, ElementKind, Expr};
+///
+/// let ast = query::parse(".h2[Install] | text").unwrap();
+/// let stages = &ast.expressions[0].stages;
+/// assert!(matches!(
+///     &stages[0],
+///     Expr::Element { kind: ElementKind::Heading(Some(2)), filters, .. } if filters.len() == 1
+/// ));
+/// assert!(matches!(&stages[1], Expr::Function { name, .. } if name == "text"));
+/// ```
 pub fn parse(query_str: &str) -> Result<Query, QueryError> {
     let tokens = lexer::tokenize(query_str)?;
     parser::parse(&tokens, query_str)
@@ -109,7 +130,7 @@ pub fn format_output(values: &[Value], format: OutputFormat) -> String {
 mod output;
 
 /// Output format for query results.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub enum OutputFormat {
     /// Plain text, one result per line
     #[default]
@@ -124,12 +145,23 @@ pub enum OutputFormat {
     Markdown,
     /// Tree structure with box-drawing
     Tree,
+    /// RFC 4180 CSV; object and element fields become columns
+    Csv,
+    /// User template rendered once per result, e.g. `### {text} ({line})`
+    ///
+    /// `{field}` is replaced by the result's property (unknown fields render
+    /// empty) and `{{`/`}}` produce literal braces.
+    Template(String),
 }
 
 impl std::str::FromStr for OutputFormat {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(template) = s.strip_prefix("template:") {
+            return Ok(Self::Template(template.to_string()));
+        }
+
         match s.to_lowercase().as_str() {
             "plain" | "text" => Ok(Self::Plain),
             "json" => Ok(Self::Json),
@@ -137,6 +169,7 @@ impl std::str::FromStr for OutputFormat {
             "jsonl" | "jsonlines" | "ndjson" => Ok(Self::JsonLines),
             "md" | "markdown" => Ok(Self::Markdown),
             "tree" => Ok(Self::Tree),
+            "csv" => Ok(Self::Csv),
             _ => Err(format!("Unknown output format: {}", s)),
         }
     }
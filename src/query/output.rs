@@ -12,6 +12,8 @@ pub fn format(values: &[Value], format: OutputFormat) -> String {
         OutputFormat::JsonLines => format_json_lines(values),
         OutputFormat::Markdown => format_markdown(values),
         OutputFormat::Tree => format_tree(values),
+        OutputFormat::Csv => format_csv(values),
+        OutputFormat::Template(template) => format_template(values, &template),
     }
 }
 
@@ -80,7 +82,7 @@ fn format_plain_value(value: &Value) -> String {
             .enumerate()
             .map(|(i, item)| {
                 let prefix = if l.ordered {
-                    format!("{}.", i + 1)
+                    format!("{}.", l.start.unwrap_or(1) as usize + i)
                 } else {
                     "-".to_string()
                 };
@@ -110,6 +112,50 @@ fn format_plain_value(value: &Value) -> String {
     }
 }
 
+fn format_template(values: &[Value], template: &str) -> String {
+    values
+        .iter()
+        .map(|v| render_template(template, v))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Substitute `{field}` placeholders with properties of `value`.
+///
+/// `{{` and `}}` are literal braces; an unclosed `{` is kept as-is.
+fn render_template(template: &str, value: &Value) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+        } else if let Some(end) = tail.strip_prefix('{').and_then(|t| t.find('}')) {
+            out.push_str(&template_field(value, tail[1..=end].trim()));
+            rest = &tail[end + 2..];
+        } else {
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+fn template_field(value: &Value, name: &str) -> String {
+    match value.get_property(name) {
+        Some(property) => property.to_text(),
+        // Plain values (strings, numbers) have no properties but still have text
+        None if name == "text" => value.to_text(),
+        None => String::new(),
+    }
+}
+
 fn format_json(values: &[Value], pretty: bool) -> String {
     // Convert to JSON-compatible structure
     let json_values: Vec<serde_json::Value> = values.iter().map(|v| value_to_json(v)).collect();
@@ -135,10 +181,72 @@ fn format_json_lines(values: &[Value]) -> String {
         .join("\n")
 }
 
+/// Format results as CSV rows.
+///
+/// Array results contribute one row per element. When the first row is an
+/// object (or element), its keys form the header and later rows are matched
+/// by key; otherwise each row is a single column without a header.
+fn format_csv(values: &[Value]) -> String {
+    let rows: Vec<serde_json::Value> = values
+        .iter()
+        .flat_map(|v| match v {
+            Value::Array(items) => items.iter().map(value_to_json).collect(),
+            _ => vec![value_to_json(v)],
+        })
+        .collect();
+
+    let header: Option<Vec<String>> = rows
+        .first()
+        .and_then(|row| row.as_object())
+        .map(|obj| obj.keys().cloned().collect());
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    if let Some(header) = &header {
+        lines.push(csv_line(header.iter().map(String::as_str)));
+    }
+    for row in &rows {
+        let cells: Vec<String> = match (header.as_ref(), row.as_object()) {
+            (Some(header), Some(obj)) => header
+                .iter()
+                .map(|key| obj.get(key).map(csv_cell).unwrap_or_default())
+                .collect(),
+            _ => vec![csv_cell(row)],
+        };
+        lines.push(csv_line(cells.iter().map(String::as_str)));
+    }
+    lines.join("\n")
+}
+
+fn csv_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => n.to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_line<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    cells.map(csv_escape).collect::<Vec<_>>().join(",")
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
 fn value_to_json(value: &Value) -> serde_json::Value {
     match value {
         Value::Null => serde_json::Value::Null,
         Value::Bool(b) => serde_json::Value::Bool(*b),
+        // Whole numbers serialize as integers (`3`, not `3.0`)
+        Value::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+            serde_json::json!(*n as i64)
+        }
         Value::Number(n) => serde_json::json!(n),
         Value::String(s) => serde_json::Value::String(s.clone()),
         Value::Array(a) => serde_json::Value::Array(a.iter().map(value_to_json).collect()),
@@ -250,9 +358,11 @@ fn format_markdown_value(value: &Value) -> String {
 }
 
 fn format_tree(values: &[Value]) -> String {
-    use crate::Config;
-    let config = Config::load();
-    let compact = config.is_compact_tree();
+    #[cfg(feature = "native")]
+    let compact = crate::Config::load().is_compact_tree();
+    // Without a config file, use the default compact style
+    #[cfg(not(feature = "native"))]
+    let compact = true;
 
     let mut output = String::new();
 
@@ -352,6 +462,55 @@ mod tests {
         assert_eq!(output, "## Test");
     }
 
+    #[test]
+    fn test_format_template_headings_and_links() {
+        let doc = crate::parser::parse_markdown(
+            "# Intro\n\nRead [the guide](guide.md#setup) first.\n\n## Next Steps\n",
+        );
+        let run = |query: &str, template: &str| {
+            let values = crate::query::execute(&doc, query).unwrap();
+            let format: OutputFormat = format!("template:{}", template).parse().unwrap();
+            super::format(&values, format)
+        };
+
+        assert_eq!(
+            run(".h", "{{h{level}}} {text} (line {line}) #{anchor}"),
+            "{h1} Intro (line 1) #intro\n{h2} Next Steps (line 5) #next-steps"
+        );
+        assert_eq!(
+            run(
+                ".link",
+                "- [{text}]({url}) L{line} anchor={anchor}{unknown} {open"
+            ),
+            "- [the guide](guide.md#setup) L3 anchor=setup {open"
+        );
+        // Plain values expose their text
+        assert_eq!(run(".h2 | .text | upper", "* {text}"), "* NEXT STEPS");
+    }
+
+    #[test]
+    fn test_format_csv() {
+        let doc = crate::parser::parse_markdown("# Intro, \"quoted\"\n\nword word other\n");
+        let run = |query: &str, format: OutputFormat| {
+            let values = crate::query::execute(&doc, query).unwrap();
+            super::format(&values, format)
+        };
+
+        assert_eq!(
+            run(".h", OutputFormat::Csv),
+            "type,level,text,line\nheading,1,\"Intro, \"\"quoted\"\"\",1"
+        );
+        let frequency = ". | content | word_frequency(2)";
+        assert_eq!(
+            run(frequency, OutputFormat::Csv),
+            "word,count\nword,2\nintro,1"
+        );
+        assert_eq!(
+            run(frequency, OutputFormat::Json),
+            r#"[{"word":"word","count":2},{"word":"intro","count":1}]"#
+        );
+    }
+
     #[test]
     fn test_format_json() {
         let values = vec![Value::Number(42.0), Value::String("hello".to_string())];
@@ -88,12 +88,8 @@ pub fn parse(tokens: &[Token], source: &str) -> Result<Query, QueryError> {
 }
 
 fn parse_query(p: &mut Parser) -> Result<Query, QueryError> {
-    let mut expressions = vec![parse_piped_expr(p)?];
-
-    // Handle multiple expressions separated by commas
-    while p.matches(&[TokenKind::Comma]) {
-        expressions.push(parse_piped_expr(p)?);
-    }
+    // Top-level commas are unions inside the pipeline: `.h2, .h3 | text`
+    let expressions = vec![parse_piped_expr(p)?];
 
     if !p.is_at_end() {
         return Err(QueryError::new(
@@ -110,16 +106,52 @@ fn parse_query(p: &mut Parser) -> Result<Query, QueryError> {
 }
 
 fn parse_piped_expr(p: &mut Parser) -> Result<PipedExpr, QueryError> {
-    let mut stages = vec![parse_hierarchy_expr(p)?];
+    parse_pipeline(p, true)
+}
+
+/// Parse a pipeline whose stages cannot be comma unions.
+///
+/// Used where commas separate items: function arguments, array elements
+/// and object values.
+fn parse_list_item_expr(p: &mut Parser) -> Result<Expr, QueryError> {
+    parse_pipeline(p, false).map(Expr::from)
+}
+
+fn parse_pipeline(p: &mut Parser, allow_union: bool) -> Result<PipedExpr, QueryError> {
+    let parse_stage = if allow_union {
+        parse_union_expr
+    } else {
+        parse_hierarchy_expr
+    };
+    let mut stages = vec![parse_stage(p)?];
 
     // Handle pipes
     while p.matches(&[TokenKind::Pipe]) {
-        stages.push(parse_hierarchy_expr(p)?);
+        stages.push(parse_stage(p)?);
     }
 
     Ok(PipedExpr::new(stages))
 }
 
+fn parse_union_expr(p: &mut Parser) -> Result<Expr, QueryError> {
+    let first = parse_hierarchy_expr(p)?;
+    if !p.check(&TokenKind::Comma) {
+        return Ok(first);
+    }
+
+    let start_span = first.span();
+    let mut exprs = vec![first];
+    while p.matches(&[TokenKind::Comma]) {
+        exprs.push(parse_hierarchy_expr(p)?);
+    }
+    let end_span = exprs[exprs.len() - 1].span();
+
+    Ok(Expr::Union {
+        exprs,
+        span: start_span.merge(end_span),
+    })
+}
+
 fn parse_hierarchy_expr(p: &mut Parser) -> Result<Expr, QueryError> {
     let mut expr = parse_or_expr(p)?;
 
@@ -411,7 +443,7 @@ fn parse_postfix_expr(p: &mut Parser) -> Result<Expr, QueryError> {
                                 value: Literal::Number(n as f64),
                                 span,
                             },
-                            IndexOp::Slice { start, end } => Expr::Array {
+                            IndexOp::Slice { start, end, .. } => Expr::Array {
                                 elements: vec![
                                     Expr::Literal {
                                         value: start
@@ -637,6 +669,16 @@ fn parse_identifier(p: &mut Parser) -> Result<(String, Span), QueryError> {
     }
 }
 
+/// Optional number in a slice (`[start:end:step]`).
+fn parse_slice_bound(p: &mut Parser) -> Option<i64> {
+    if let TokenKind::Number(n) = p.current_kind().clone() {
+        p.advance();
+        Some(n as i64)
+    } else {
+        None
+    }
+}
+
 enum FilterOrIndex {
     Filter(Filter),
     Index(IndexOp),
@@ -656,30 +698,11 @@ fn parse_filter_or_index(p: &mut Parser) -> Result<(FilterOrIndex, Span), QueryE
         ));
     }
 
-    // Check for number (index or slice)
-    if let TokenKind::Number(n) = p.current_kind().clone() {
-        p.advance();
-        let n = n as i64;
-
-        // Check for slice
-        if p.matches(&[TokenKind::Colon]) {
-            let end = if let TokenKind::Number(e) = p.current_kind().clone() {
-                p.advance();
-                Some(e as i64)
-            } else {
-                None
-            };
-            let end_span = p.current_span();
-            p.expect(&TokenKind::RBracket)?;
-            return Ok((
-                FilterOrIndex::Index(IndexOp::Slice {
-                    start: Some(n),
-                    end,
-                }),
-                start_span.merge(end_span),
-            ));
-        }
-
+    // Index or slice: [n], [start:end], [start:end:step]
+    let start = parse_slice_bound(p);
+    if let Some(n) = start
+        && !p.check(&TokenKind::Colon)
+    {
         let end_span = p.current_span();
         p.expect(&TokenKind::RBracket)?;
         return Ok((
@@ -687,19 +710,26 @@ fn parse_filter_or_index(p: &mut Parser) -> Result<(FilterOrIndex, Span), QueryE
             start_span.merge(end_span),
         ));
     }
-
-    // Slice starting with :
     if p.matches(&[TokenKind::Colon]) {
-        let end = if let TokenKind::Number(e) = p.current_kind().clone() {
-            p.advance();
-            Some(e as i64)
+        let end = parse_slice_bound(p);
+        let step = if p.matches(&[TokenKind::Colon]) {
+            let step_span = p.current_span();
+            let step = parse_slice_bound(p);
+            if step == Some(0) {
+                return Err(QueryError::new(
+                    QueryErrorKind::InvalidFilter("slice step cannot be zero".to_string()),
+                    step_span,
+                    p.source.to_string(),
+                ));
+            }
+            step
         } else {
             None
         };
         let end_span = p.current_span();
         p.expect(&TokenKind::RBracket)?;
         return Ok((
-            FilterOrIndex::Index(IndexOp::Slice { start: None, end }),
+            FilterOrIndex::Index(IndexOp::Slice { start, end, step }),
             start_span.merge(end_span),
         ));
     }
@@ -749,6 +779,42 @@ fn parse_filter_or_index(p: &mut Parser) -> Result<(FilterOrIndex, Span), QueryE
     // Identifier filter (fuzzy match or type filter)
     if let TokenKind::Ident(name) = p.current_kind().clone() {
         p.advance();
+
+        // Source line comparison: [line>=100]
+        if name == "line" {
+            let op = match p.current_kind() {
+                TokenKind::Eq => Some(BinaryOp::Eq),
+                TokenKind::Ne => Some(BinaryOp::Ne),
+                TokenKind::Lt => Some(BinaryOp::Lt),
+                TokenKind::Le => Some(BinaryOp::Le),
+                TokenKind::Gt => Some(BinaryOp::Gt),
+                TokenKind::Ge => Some(BinaryOp::Ge),
+                _ => None,
+            };
+            if let Some(op) = op {
+                p.advance();
+                let TokenKind::Number(n) = p.current_kind().clone() else {
+                    return Err(QueryError::new(
+                        QueryErrorKind::InvalidFilter("expected a line number".to_string()),
+                        p.current_span(),
+                        p.source.to_string(),
+                    ));
+                };
+                p.advance();
+                let end_span = p.current_span();
+                p.expect(&TokenKind::RBracket)?;
+                let span = start_span.merge(end_span);
+                return Ok((
+                    FilterOrIndex::Filter(Filter::Line {
+                        op,
+                        line: n as usize,
+                        span,
+                    }),
+                    span,
+                ));
+            }
+        }
+
         let end_span = p.current_span();
         p.expect(&TokenKind::RBracket)?;
 
@@ -795,10 +861,10 @@ fn parse_function_args(p: &mut Parser) -> Result<Vec<Expr>, QueryError> {
     let mut args = Vec::new();
 
     if !p.check(&TokenKind::RParen) {
-        args.push(parse_piped_expr(p).map(Expr::from)?);
+        args.push(parse_list_item_expr(p)?);
 
         while p.matches(&[TokenKind::Comma]) {
-            args.push(parse_piped_expr(p).map(Expr::from)?);
+            args.push(parse_list_item_expr(p)?);
         }
     }
 
@@ -832,7 +898,7 @@ fn parse_object_literal(p: &mut Parser, start_span: Span) -> Result<Expr, QueryE
             p.expect(&TokenKind::Colon)?;
 
             // Value
-            let value = parse_piped_expr(p).map(Expr::from)?;
+            let value = parse_list_item_expr(p)?;
             pairs.push((key, value));
 
             if !p.matches(&[TokenKind::Comma]) {
@@ -855,7 +921,7 @@ fn parse_array_literal(p: &mut Parser, start_span: Span) -> Result<Expr, QueryEr
 
     if !p.check(&TokenKind::RBracket) {
         loop {
-            elements.push(parse_piped_expr(p).map(Expr::from)?);
+            elements.push(parse_list_item_expr(p)?);
 
             if !p.matches(&[TokenKind::Comma]) {
                 break;
@@ -981,12 +1047,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_slice_with_step() {
+        let query = parse_str(".h2[1::-2]").unwrap();
+        if let Expr::Element { index, .. } = &query.expressions[0].stages[0] {
+            assert!(matches!(
+                index,
+                Some(IndexOp::Slice {
+                    start: Some(1),
+                    end: None,
+                    step: Some(-2)
+                })
+            ));
+        } else {
+            panic!("Expected Element with slice");
+        }
+        assert!(parse_str(".h2[::0]").is_err());
+    }
+
     #[test]
     fn test_pipe() {
         let query = parse_str(".h2 | text").unwrap();
         assert_eq!(query.expressions[0].stages.len(), 2);
     }
 
+    #[test]
+    fn test_union_binds_tighter_than_pipe() {
+        let query = parse_str(".h2, .code | count").unwrap();
+        let stages = &query.expressions[0].stages;
+        assert_eq!(stages.len(), 2);
+        assert!(matches!(&stages[0], Expr::Union { exprs, .. } if exprs.len() == 2));
+
+        // Commas in argument lists and arrays are not unions
+        let query = parse_str("[.h2, .h3] | replace(\"a\", \"b\")").unwrap();
+        let stages = &query.expressions[0].stages;
+        assert!(matches!(&stages[0], Expr::Array { elements, .. } if elements.len() == 2));
+        assert!(matches!(&stages[1], Expr::Function { args, .. } if args.len() == 2));
+    }
+
     #[test]
     fn test_function_call() {
         let query = parse_str("select(contains(\"API\"))").unwrap();
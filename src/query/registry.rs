@@ -217,7 +217,7 @@ pub trait FunctionRegistry {
 }
 
 /// Simple Levenshtein distance for suggestions.
-fn levenshtein(a: &str, b: &str) -> usize {
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
     let a_chars: Vec<char> = a.chars().collect();
     let b_chars: Vec<char> = b.chars().collect();
     let a_len = a_chars.len();
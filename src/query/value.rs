@@ -343,7 +343,7 @@ impl HeadingValue {
             "line" => Some(Value::Number(self.line as f64)),
             "content" => Some(Value::String(self.content.clone())),
             "md" | "markdown" => Some(Value::String(self.raw_md.clone())),
-            "slug" => Some(Value::String(slugify(&self.text))),
+            "slug" | "anchor" => Some(Value::String(slugify(&self.text))),
             _ => None,
         }
     }
@@ -368,7 +368,7 @@ impl CodeValue {
                 .map(Value::String)
                 .or(Some(Value::Null)),
             "text" | "content" => Some(Value::String(self.content.clone())),
-            "start_line" => Some(Value::Number(self.start_line as f64)),
+            "start_line" | "line" => Some(Value::Number(self.start_line as f64)),
             "end_line" => Some(Value::Number(self.end_line as f64)),
             "lines" => Some(Value::Number(self.content.lines().count() as f64)),
             _ => None,
@@ -384,6 +384,7 @@ pub struct LinkValue {
     #[serde(rename = "type")]
     pub link_type: LinkType,
     pub offset: usize,
+    pub line: usize,
 }
 
 impl LinkValue {
@@ -393,6 +394,14 @@ impl LinkValue {
             "url" => Some(Value::String(self.url.clone())),
             "type" => Some(Value::String(self.link_type.as_str().to_string())),
             "offset" => Some(Value::Number(self.offset as f64)),
+            "line" => Some(Value::Number(self.line as f64)),
+            // Fragment of the target, e.g. `setup` for `guide.md#setup`
+            "anchor" => Some(
+                self.url
+                    .split_once('#')
+                    .map(|(_, anchor)| Value::String(anchor.to_string()))
+                    .unwrap_or(Value::Null),
+            ),
             _ => None,
         }
     }
@@ -478,6 +487,9 @@ impl TableValue {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListValue {
     pub ordered: bool,
+    /// Number of the first item, for ordered lists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
     pub items: Vec<ListItemValue>,
 }
 
@@ -485,6 +497,7 @@ impl ListValue {
     pub fn get_property(&self, name: &str) -> Option<Value> {
         match name {
             "ordered" => Some(Value::Bool(self.ordered)),
+            "start" => self.start.map(|start| Value::Number(start as f64)),
             "items" => Some(Value::Array(
                 self.items
                     .iter()
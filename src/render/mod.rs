@@ -0,0 +1,944 @@
+//! Headless markdown rendering.
+//!
+//! Exposes the content pane's markdown renderer (headings, lists, tables,
+//! syntax-highlighted code blocks, inline formatting) so other TUI apps and
+//! tests can reuse it without an [`App`](crate::App). The TUI itself renders
+//! with a superset of this that also draws interactive selection state.
+//!
+//! The ratatui renderers and [`text_to_ansi`] need the `native` feature; the
+//! HTML and man page renderers are part of the core build.
+//!
+//! [`markdown_to_html`] renders the same blocks as an HTML fragment, used for
+//! copying sections as rich text. [`render_html_fragment`] renders a document
+//! that way with [`HtmlOptions`], for embedding in your own pages.
+//! [`text_to_ansi`] turns rendered text into terminal escape sequences for
+//! `--pager`, and [`render_man`] writes a document as groff man page source
+//! for `--export man`.
+
+use crate::parser::content::{ItemLine, ListLayout, ListMarker, SourceLayout, parse_with_layout};
+use crate::parser::output::{Alignment, Block, InlineElement, ListItem};
+use crate::parser::utils::{slugify, strip_markdown_inline};
+#[cfg(feature = "native")]
+use crate::tui::SyntaxHighlighter;
+#[cfg(feature = "native")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "native")]
+mod text;
+#[cfg(feature = "native")]
+pub use text::{markdown_to_string, markdown_to_text, markdown_to_text_with, text_to_ansi};
+
+/// Shared highlighter, since loading syntax definitions is expensive.
+#[cfg(feature = "native")]
+fn highlighter() -> &'static SyntaxHighlighter {
+    static HIGHLIGHTER: OnceLock<SyntaxHighlighter> = OnceLock::new();
+    HIGHLIGHTER.get_or_init(SyntaxHighlighter::new)
+}
+
+/// How [`render_html_fragment`] highlights code blocks. Highlighting needs
+/// the `native` feature; without it code blocks are always left plain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CodeHighlight {
+    /// Escaped code with only a `language-*` class on `<code>`
+    #[default]
+    None,
+    /// Spans with the syntax's scope names as classes, for your own CSS
+    Classes,
+    /// Spans colored with inline `style` attributes from the
+    /// `base16-ocean.dark` theme
+    InlineStyles,
+}
+
+/// Options for [`render_html_fragment`].
+///
+/// The defaults produce the same HTML as [`markdown_to_html`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlOptions {
+    /// Give headings an `id` from their slug, so `#anchor` links reach them
+    pub heading_anchors: bool,
+    /// Prepended to every class the renderer writes, e.g. `md-` for
+    /// `md-language-rust`
+    pub class_prefix: String,
+    /// How code blocks are highlighted
+    pub code_highlight: CodeHighlight,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        Self {
+            heading_anchors: true,
+            class_prefix: String::new(),
+            code_highlight: CodeHighlight::None,
+        }
+    }
+}
+
+/// Render a document's body as an HTML fragment, without the
+/// `<html>`/`<head>`/`<style>` wrapper, to embed in your own templates.
+///
+/// ```
+/// use treemd::parse_markdown;
+/// use treemd::render::{CodeHighlight, HtmlOptions, render_html_fragment};
+///
+/// let doc = parse_markdown("# Title\n\n```rust\nfn main() {}\n```\n");
+/// let options = HtmlOptions {
+///     heading_anchors: false,
+///     class_prefix: "md-".to_string(),
+///     code_highlight: CodeHighlight::Classes,
+/// };
+/// let html = render_html_fragment(&doc, &options);
+/// assert!(html.starts_with("<h1>Title</h1>\n<pre><code class=\"md-language-rust\">"));
+/// assert!(html.contains("<span class=\"md-source md-rust\">"));
+/// ```
+pub fn render_html_fragment(doc: &crate::parser::Document, options: &HtmlOptions) -> String {
+    html_fragment(&doc.content, options)
+}
+
+/// Render markdown to an HTML fragment.
+///
+/// Covers the blocks the content pane understands: headings, paragraphs,
+/// lists (including task lists and nested items), code blocks, blockquotes,
+/// tables, images, rules and `<details>`. Text is HTML-escaped, and links are
+/// only kept for `http(s)`, `mailto` and relative URLs.
+///
+/// # Example
+///
+/// ```
+/// let html = treemd::render::markdown_to_html("## Setup\n\nRun **this**.\n");
+/// assert_eq!(
+///     html,
+///     "<h2 id=\"setup\">Setup</h2>\n<p>Run <strong>this</strong>.</p>\n"
+/// );
+/// ```
+pub fn markdown_to_html(content: &str) -> String {
+    html_fragment(content, &HtmlOptions::default())
+}
+
+fn html_fragment(content: &str, options: &HtmlOptions) -> String {
+    let mut html = String::new();
+    let parsed = parse_with_layout(content, 0);
+    for (block, layout) in parsed.blocks.iter().zip(&parsed.layouts) {
+        push_block_html(&mut html, block, layout, options);
+    }
+    html
+}
+
+/// Append `block` as HTML, laid out as `layout` says.
+fn push_block_html(html: &mut String, block: &Block, layout: &SourceLayout, options: &HtmlOptions) {
+    match block {
+        Block::Heading {
+            level,
+            content,
+            inline,
+            ..
+        } => {
+            let level = (*level).clamp(1, 6);
+            let id = if options.heading_anchors {
+                format!(" id=\"{}\"", escape_html(&slugify(content)))
+            } else {
+                String::new()
+            };
+            html.push_str(&format!(
+                "<h{}{}>{}</h{}>\n",
+                level,
+                id,
+                inline_html(inline),
+                level
+            ));
+        }
+        Block::Paragraph { inline, .. } => {
+            html.push_str(&format!("<p>{}</p>\n", inline_html(inline)));
+        }
+        Block::Code {
+            language, content, ..
+        } => {
+            let language = language.as_deref().filter(|lang| !lang.is_empty());
+            match language {
+                Some(lang) => html.push_str(&format!(
+                    "<pre><code class=\"{}language-{}\">",
+                    escape_html(&options.class_prefix),
+                    escape_html(lang)
+                )),
+                None => html.push_str("<pre><code>"),
+            }
+            html.push_str(&code_html(content, language, options));
+            html.push_str("</code></pre>\n");
+        }
+        Block::List { ordered, items } => {
+            let fallback = ListLayout::new(*ordered);
+            let layout = layout.list().unwrap_or(&fallback);
+            let tag = if *ordered { "ol" } else { "ul" };
+            match layout.start.filter(|start| *start != 1) {
+                Some(start) => html.push_str(&format!("<ol start=\"{}\">\n", start)),
+                None => html.push_str(&format!("<{}>\n", tag)),
+            }
+            for (idx, item) in items.iter().enumerate() {
+                push_list_item_html(html, item, &layout.lines(idx, item), options);
+            }
+            html.push_str(&format!("</{}>\n", tag));
+        }
+        Block::Blockquote { blocks, .. } => {
+            html.push_str("<blockquote>\n");
+            for (idx, block) in blocks.iter().enumerate() {
+                push_block_html(html, block, layout.nested(idx), options);
+            }
+            html.push_str("</blockquote>\n");
+        }
+        Block::Table {
+            headers,
+            alignments,
+            rows,
+        } => {
+            let cell = |tag: &str, col: usize, text: &str| {
+                let align = match alignments.get(col) {
+                    Some(Alignment::Left) => " style=\"text-align: left\"",
+                    Some(Alignment::Center) => " style=\"text-align: center\"",
+                    Some(Alignment::Right) => " style=\"text-align: right\"",
+                    _ => "",
+                };
+                format!(
+                    "<{}{}>{}</{}>",
+                    tag,
+                    align,
+                    escape_html(&strip_markdown_inline(text)),
+                    tag
+                )
+            };
+            html.push_str("<table>\n<thead>\n<tr>");
+            for (col, header) in headers.iter().enumerate() {
+                html.push_str(&cell("th", col, header));
+            }
+            html.push_str("</tr>\n</thead>\n<tbody>\n");
+            for row in rows {
+                html.push_str("<tr>");
+                for (col, value) in row.iter().enumerate() {
+                    html.push_str(&cell("td", col, value));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</tbody>\n</table>\n");
+        }
+        Block::Image { alt, src, title } => {
+            html.push_str(&format!(
+                "<p>{}</p>\n",
+                image_html(alt, src, title.as_deref())
+            ));
+        }
+        Block::HorizontalRule => html.push_str("<hr>\n"),
+        Block::Details {
+            summary, blocks, ..
+        } => {
+            html.push_str(&format!(
+                "<details>\n<summary>{}</summary>\n",
+                escape_html(summary)
+            ));
+            for (idx, block) in blocks.iter().enumerate() {
+                push_block_html(html, block, layout.nested(idx), options);
+            }
+            html.push_str("</details>\n");
+        }
+    }
+}
+
+/// Code block contents, highlighted as `options` asks.
+#[cfg(feature = "native")]
+fn code_html(content: &str, language: Option<&str>, options: &HtmlOptions) -> String {
+    let token = language.map(SyntaxHighlighter::detect_language);
+    match (options.code_highlight, token) {
+        (CodeHighlight::Classes, Some(token)) => prefix_classes(
+            &highlighter().highlight_html_classed(content, &token),
+            &options.class_prefix,
+        ),
+        (CodeHighlight::InlineStyles, Some(token)) => {
+            highlighter().highlight_html_styled(content, &token)
+        }
+        _ => escape_html(content),
+    }
+}
+
+/// Code block contents; highlighting needs syntect from the `native` feature.
+#[cfg(not(feature = "native"))]
+fn code_html(content: &str, _language: Option<&str>, _options: &HtmlOptions) -> String {
+    escape_html(content)
+}
+
+/// Render a list item and its nested items, as laid out by [`ListLayout`].
+///
+/// The parser concatenates the inline elements of an item and its nested
+/// items, so they are split back into lines to keep formatting.
+fn push_list_item_html(
+    html: &mut String,
+    item: &ListItem,
+    lines: &[ItemLine],
+    options: &HtmlOptions,
+) {
+    let texts: Vec<&str> = lines.iter().map(|line| line.text).collect();
+    let line_html: Vec<String> = match split_inline_lines(&item.inline, &texts) {
+        Some(groups) => groups.iter().map(|inline| inline_html(inline)).collect(),
+        None if texts.len() <= 1 => vec![inline_html(&item.inline)],
+        None => texts.iter().map(|text| escape_html(text)).collect(),
+    };
+
+    html.push_str("<li>");
+    html.push_str(task_marker(lines[0].marker));
+    html.push_str(line_html.first().map_or("", String::as_str));
+
+    // Depth and tag of the nested lists currently open
+    let mut open: Vec<(usize, &str)> = Vec::new();
+    for (line, text) in lines.iter().zip(&line_html).skip(1) {
+        if line.marker == ListMarker::Continuation {
+            html.push_str("<br>");
+            html.push_str(text);
+            continue;
+        }
+
+        let tag = match line.marker {
+            ListMarker::Number(_) => "ol",
+            _ => "ul",
+        };
+        while open.last().is_some_and(|&(depth, _)| depth > line.depth) {
+            let (_, open_tag) = open.pop().unwrap_or_default();
+            html.push_str(&format!("</li></{}>", open_tag));
+        }
+        if open.last() == Some(&(line.depth, tag)) {
+            html.push_str("</li>");
+        } else {
+            // A list of the other kind at the same depth ends this one
+            if open.last().is_some_and(|&(depth, _)| depth == line.depth) {
+                let (_, open_tag) = open.pop().unwrap_or_default();
+                html.push_str(&format!("</li></{}>", open_tag));
+            }
+            match line.marker {
+                ListMarker::Number(start) if start != 1 => {
+                    html.push_str(&format!("<ol start=\"{}\">", start))
+                }
+                _ => html.push_str(&format!("<{}>", tag)),
+            }
+            open.push((line.depth, tag));
+        }
+        html.push_str("<li>");
+        html.push_str(task_marker(line.marker));
+        html.push_str(text);
+    }
+    for (_, tag) in open.into_iter().rev() {
+        html.push_str(&format!("</li></{}>", tag));
+    }
+
+    for block in &item.blocks {
+        html.push('\n');
+        push_block_html(html, block, &SourceLayout::None, options);
+    }
+    html.push_str("</li>\n");
+}
+
+fn task_marker(marker: ListMarker) -> &'static str {
+    match marker {
+        ListMarker::Task(true) => "☑ ",
+        ListMarker::Task(false) => "☐ ",
+        _ => "",
+    }
+}
+
+/// Split inline elements into one group per line of text.
+///
+/// Returns `None` when the elements' text doesn't match the lines or a link
+/// or image would have to be split.
+fn split_inline_lines(inline: &[InlineElement], texts: &[&str]) -> Option<Vec<Vec<InlineElement>>> {
+    let joined: String = inline.iter().map(InlineElement::to_plain_text).collect();
+    if joined != texts.concat() {
+        return None;
+    }
+
+    let mut groups = Vec::new();
+    let mut elements = inline.iter().cloned();
+    let mut pending: Option<InlineElement> = None;
+    for text in texts {
+        let mut group = Vec::new();
+        let mut remaining = text.len();
+        while remaining > 0 {
+            let element = pending.take().or_else(|| elements.next())?;
+            let len = element.to_plain_text().len();
+            if len <= remaining {
+                remaining -= len;
+                group.push(element);
+            } else {
+                let (head, tail) = split_inline_element(&element, remaining)?;
+                group.push(head);
+                pending = Some(tail);
+                remaining = 0;
+            }
+        }
+        groups.push(group);
+    }
+    Some(groups)
+}
+
+/// Split a text-like element at byte offset `at`.
+fn split_inline_element(
+    element: &InlineElement,
+    at: usize,
+) -> Option<(InlineElement, InlineElement)> {
+    let split = |value: &str, make: fn(String) -> InlineElement| {
+        let (head, tail) = value.split_at_checked(at)?;
+        Some((make(head.to_string()), make(tail.to_string())))
+    };
+    match element {
+        InlineElement::Text { value } => split(value, |value| InlineElement::Text { value }),
+        InlineElement::Strong { value } => split(value, |value| InlineElement::Strong { value }),
+        InlineElement::Emphasis { value } => {
+            split(value, |value| InlineElement::Emphasis { value })
+        }
+        InlineElement::Code { value } => split(value, |value| InlineElement::Code { value }),
+        InlineElement::Strikethrough { value } => {
+            split(value, |value| InlineElement::Strikethrough { value })
+        }
+        InlineElement::Link { .. } | InlineElement::Image { .. } => None,
+    }
+}
+
+/// Width that paragraph text is filled to in man page source.
+const MAN_FILL_WIDTH: usize = 78;
+
+/// Render a document as groff man page source (`man(7)` macros).
+///
+/// The first `#` heading becomes the `.TH` title line; a trailing `(N)`, as
+/// in `treemd(1)`, sets the manual section (default 1). Other `#` and `##`
+/// headings become `.SH` sections, `###` headings `.SS` subsections, and
+/// deeper headings bold paragraphs. Code blocks are kept verbatim in
+/// `.nf`/`.fi`, and emphasis maps to `\fB`/`\fI`.
+///
+/// ```
+/// use treemd::parse_markdown;
+/// use treemd::render::render_man;
+///
+/// let doc = parse_markdown("# treemd(1)\n## NAME\ntreemd - a **markdown** navigator\n");
+/// assert_eq!(
+///     render_man(&doc),
+///     ".TH \"TREEMD\" \"1\"\n.SH \"NAME\"\ntreemd - a \\fBmarkdown\\fR navigator\n"
+/// );
+/// ```
+pub fn render_man(doc: &crate::parser::Document) -> String {
+    let mut out = String::new();
+    let parsed = parse_with_layout(&doc.content, 0);
+    let mut titled = false;
+    for (block, layout) in parsed.blocks.iter().zip(&parsed.layouts) {
+        if let Block::Heading {
+            level: 1, inline, ..
+        } = block
+            && !titled
+        {
+            titled = true;
+            let text = plain_inline(inline);
+            let (name, section) = man_title(&text);
+            out.insert_str(
+                0,
+                &format!(
+                    ".TH {} {}\n",
+                    quote_man(&name.to_uppercase()),
+                    quote_man(section)
+                ),
+            );
+            continue;
+        }
+        push_block_man(&mut out, block, layout);
+    }
+    if !titled {
+        out.insert_str(0, ".TH \"UNTITLED\" \"1\"\n");
+    }
+    out
+}
+
+/// Split a title like `treemd(1)` into its name and manual section.
+fn man_title(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    text.strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once('('))
+        .filter(|(name, section)| {
+            !name.trim().is_empty()
+                && !section.is_empty()
+                && section.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        .map_or((text, "1"), |(name, section)| (name.trim(), section))
+}
+
+/// Append `block` as man page source, laid out as `layout` says.
+fn push_block_man(out: &mut String, block: &Block, layout: &SourceLayout) {
+    match block {
+        Block::Heading { level, inline, .. } => {
+            let text = plain_inline(inline);
+            match level {
+                1 | 2 => out.push_str(&format!(".SH {}\n", quote_man(&text))),
+                3 => out.push_str(&format!(".SS {}\n", quote_man(&text))),
+                _ => {
+                    start_paragraph(out);
+                    push_man_text(out, &format!("\\fB{}\\fR", escape_man(&text)));
+                }
+            }
+        }
+        Block::Paragraph { inline, .. } => {
+            start_paragraph(out);
+            push_man_text(out, &fill_man(&inline_man(inline)));
+        }
+        Block::Code { content, .. } => {
+            start_paragraph(out);
+            out.push_str(".RS 4\n.nf\n");
+            push_man_text(out, &escape_man(content.trim_end_matches('\n')));
+            out.push_str(".fi\n.RE\n");
+        }
+        Block::List { ordered, items } => {
+            let fallback = ListLayout::new(*ordered);
+            let layout = layout.list().unwrap_or(&fallback);
+            for (idx, item) in items.iter().enumerate() {
+                let tag = match layout.start {
+                    Some(start) => format!("{}. 4", start + idx as u64),
+                    None => "\\(bu 2".to_string(),
+                };
+                push_list_item_man(out, item, &layout.lines(idx, item), &tag);
+            }
+        }
+        Block::Blockquote { blocks, .. } => {
+            out.push_str(".RS 4\n");
+            for (idx, block) in blocks.iter().enumerate() {
+                push_block_man(out, block, layout.nested(idx));
+            }
+            out.push_str(".RE\n");
+        }
+        Block::Table { headers, rows, .. } => {
+            let cells = |row: &[String]| -> Vec<String> {
+                row.iter().map(|cell| strip_markdown_inline(cell)).collect()
+            };
+            let mut table = vec![cells(headers)];
+            table.extend(rows.iter().map(|row| cells(row)));
+            let mut widths = vec![0; headers.len()];
+            for row in &table {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+
+            start_paragraph(out);
+            out.push_str(".nf\n");
+            for (idx, row) in table.iter().enumerate() {
+                let line = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                let line = escape_man(line.trim_end());
+                if idx == 0 {
+                    push_man_text(out, &format!("\\fB{}\\fR", line));
+                } else {
+                    push_man_text(out, &line);
+                }
+            }
+            out.push_str(".fi\n");
+        }
+        Block::Image { alt, .. } => {
+            if !alt.trim().is_empty() {
+                start_paragraph(out);
+                push_man_text(out, &fill_man(&escape_man(alt)));
+            }
+        }
+        Block::HorizontalRule => out.push_str(".sp\n"),
+        Block::Details {
+            summary, blocks, ..
+        } => {
+            start_paragraph(out);
+            push_man_text(out, &format!("\\fB{}\\fR", escape_man(summary)));
+            for (idx, block) in blocks.iter().enumerate() {
+                push_block_man(out, block, layout.nested(idx));
+            }
+        }
+    }
+}
+
+/// Render a list item as an indented paragraph tagged with `tag` (the
+/// `.IP` arguments), with nested items indented below it.
+fn push_list_item_man(out: &mut String, item: &ListItem, lines: &[ItemLine], tag: &str) {
+    let texts: Vec<&str> = lines.iter().map(|line| line.text).collect();
+    let line_man: Vec<String> = match split_inline_lines(&item.inline, &texts) {
+        Some(groups) => groups.iter().map(|inline| inline_man(inline)).collect(),
+        None if texts.len() <= 1 => vec![inline_man(&item.inline)],
+        None => texts.iter().map(|text| escape_man(text)).collect(),
+    };
+
+    out.push_str(&format!(".IP {}\n", tag));
+    let first = line_man.first().map_or("", String::as_str);
+    push_man_text(
+        out,
+        &fill_man(&format!("{}{}", task_marker(lines[0].marker), first)),
+    );
+
+    // Nested list depth currently open
+    let mut open = 0;
+    for (line, text) in lines.iter().zip(&line_man).skip(1) {
+        if line.marker == ListMarker::Continuation {
+            out.push_str(".br\n");
+            push_man_text(out, &fill_man(text));
+            continue;
+        }
+        while open > line.depth {
+            out.push_str(".RE\n");
+            open -= 1;
+        }
+        while open < line.depth {
+            out.push_str(".RS 2\n");
+            open += 1;
+        }
+        match line.marker {
+            ListMarker::Number(number) => out.push_str(&format!(".IP {}. 4\n", number)),
+            _ => out.push_str(".IP \\(bu 2\n"),
+        }
+        push_man_text(
+            out,
+            &fill_man(&format!("{}{}", task_marker(line.marker), text)),
+        );
+    }
+    for _ in 0..open {
+        out.push_str(".RE\n");
+    }
+
+    if !item.blocks.is_empty() {
+        out.push_str(".RS 2\n");
+        for block in &item.blocks {
+            push_block_man(out, block, &SourceLayout::None);
+        }
+        out.push_str(".RE\n");
+    }
+}
+
+fn inline_man(inline: &[InlineElement]) -> String {
+    inline
+        .iter()
+        .map(|element| match element {
+            InlineElement::Text { value } | InlineElement::Strikethrough { value } => {
+                escape_man(value)
+            }
+            InlineElement::Strong { value } | InlineElement::Code { value } => {
+                format!("\\fB{}\\fR", escape_man(value))
+            }
+            InlineElement::Emphasis { value } => format!("\\fI{}\\fR", escape_man(value)),
+            InlineElement::Link { text, url, .. } => {
+                let url = url.split_whitespace().next().unwrap_or("");
+                let bare = url.strip_prefix("mailto:").unwrap_or(url);
+                if url.is_empty() || url.starts_with('#') || text == url || text == bare {
+                    escape_man(text)
+                } else {
+                    format!("{} <\\fI{}\\fR>", escape_man(text), escape_man(url))
+                }
+            }
+            InlineElement::Image { alt, .. } => escape_man(alt),
+        })
+        .collect()
+}
+
+fn plain_inline(inline: &[InlineElement]) -> String {
+    inline.iter().map(InlineElement::to_plain_text).collect()
+}
+
+/// Start a paragraph, unless one was just started by a section heading.
+fn start_paragraph(out: &mut String) {
+    let last = out.lines().last().unwrap_or("");
+    if !(out.is_empty() || last.starts_with(".SH") || last.starts_with(".SS")) {
+        out.push_str(".PP\n");
+    }
+}
+
+/// Append text lines, protecting lines that would read as requests.
+fn push_man_text(out: &mut String, text: &str) {
+    for line in text.lines() {
+        if line.starts_with(['.', '\'']) {
+            out.push_str("\\&");
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+/// Wrap text at word boundaries for readable source; groff refills it.
+fn fill_man(text: &str) -> String {
+    let mut filled = String::new();
+    let mut width = 0;
+    for word in text.split_whitespace() {
+        let len = word.chars().count();
+        if width > 0 && width + 1 + len > MAN_FILL_WIDTH {
+            filled.push('\n');
+            width = 0;
+        } else if width > 0 {
+            filled.push(' ');
+            width += 1;
+        }
+        filled.push_str(word);
+        width += len;
+    }
+    filled
+}
+
+/// A quoted macro argument.
+fn quote_man(text: &str) -> String {
+    format!("\"{}\"", escape_man(text).replace('"', "\\(dq"))
+}
+
+fn escape_man(text: &str) -> String {
+    text.replace('\\', "\\e")
+}
+
+fn inline_html(inline: &[InlineElement]) -> String {
+    inline
+        .iter()
+        .map(|element| match element {
+            InlineElement::Text { value } => escape_html(value),
+            InlineElement::Strong { value } => format!("<strong>{}</strong>", escape_html(value)),
+            InlineElement::Emphasis { value } => format!("<em>{}</em>", escape_html(value)),
+            InlineElement::Code { value } => format!("<code>{}</code>", escape_html(value)),
+            InlineElement::Strikethrough { value } => format!("<del>{}</del>", escape_html(value)),
+            InlineElement::Link {
+                text, url, title, ..
+            } => {
+                // Titles can end up in the URL (`url "title"`)
+                let (url, title) = match url.trim().split_once(char::is_whitespace) {
+                    Some((url, rest)) if title.is_none() => {
+                        (url, Some(rest.trim().trim_matches(['"', '\'']).to_string()))
+                    }
+                    _ => (url.as_str(), title.clone()),
+                };
+                if !is_safe_url(url) {
+                    return escape_html(text);
+                }
+                let title = title
+                    .map(|t| format!(" title=\"{}\"", escape_html(&t)))
+                    .unwrap_or_default();
+                format!(
+                    "<a href=\"{}\"{}>{}</a>",
+                    escape_html(url),
+                    title,
+                    escape_html(text)
+                )
+            }
+            InlineElement::Image {
+                alt, src, title, ..
+            } => image_html(alt, src, title.as_deref()),
+        })
+        .collect()
+}
+
+fn image_html(alt: &str, src: &str, title: Option<&str>) -> String {
+    if !is_safe_url(src) {
+        return escape_html(alt);
+    }
+    let title = title
+        .map(|t| format!(" title=\"{}\"", escape_html(t)))
+        .unwrap_or_default();
+    format!(
+        "<img src=\"{}\" alt=\"{}\"{}>",
+        escape_html(src),
+        escape_html(alt),
+        title
+    )
+}
+
+/// Whether a URL can be emitted as a link target: `http(s)`, `mailto`, or a
+/// relative URL (no scheme).
+fn is_safe_url(url: &str) -> bool {
+    let lower = url.trim().to_lowercase();
+    let has_scheme = lower
+        .find(':')
+        .is_some_and(|colon| !lower[..colon].contains(['/', '?', '#']));
+    !has_scheme
+        || lower.starts_with("http://")
+        || lower.starts_with("https://")
+        || lower.starts_with("mailto:")
+}
+
+/// Prepend `prefix` to each class in the `class` attributes of generated
+/// HTML. Code text is escaped, so every `class="` is an attribute.
+#[cfg(feature = "native")]
+fn prefix_classes(html: &str, prefix: &str) -> String {
+    if prefix.is_empty() {
+        return html.to_string();
+    }
+    let prefix = escape_html(prefix);
+    let mut parts = html.split("class=\"");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let (classes, rest) = part.split_once('"').unwrap_or((part, ""));
+        let prefixed: Vec<String> = classes
+            .split_whitespace()
+            .map(|class| format!("{}{}", prefix, class))
+            .collect();
+        out.push_str(&format!("class=\"{}\"{}", prefixed.join(" "), rest));
+    }
+    out
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_html_blocks() {
+        let md = "# Title & more\n\nText with *em*, `code` and [a link](https://example.com \"T\").\n\n\
+                  ```rust\nlet x = a < b;\n```\n\n> quoted\n\n| Name | Qty |\n|:-----|----:|\n| **a** | 1 |\n\n---\n";
+        let html = markdown_to_html(md);
+        assert!(html.contains(">Title &amp; more</h1>"));
+        assert!(html.contains(
+            "<p>Text with <em>em</em>, <code>code</code> and \
+             <a href=\"https://example.com\" title=\"T\">a link</a>.</p>"
+        ));
+        assert!(html.contains("<pre><code class=\"language-rust\">let x = a &lt; b;</code></pre>"));
+        assert!(html.contains("<blockquote>\n<p>quoted</p>\n</blockquote>"));
+        assert!(html.contains("<th style=\"text-align: left\">Name</th>"));
+        assert!(html.contains("<td style=\"text-align: right\">1</td>"));
+        assert!(html.contains("<td style=\"text-align: left\">a</td>"));
+        assert!(html.contains("<hr>"));
+    }
+
+    #[test]
+    fn test_markdown_to_html_nested_lists() {
+        let html =
+            markdown_to_html("- a **b**\n  - nested `c`\n    - [x] deep\n  - sibling\n- d\n");
+        assert_eq!(
+            html,
+            "<ul>\n\
+             <li>a <strong>b</strong><ul><li>nested <code>c</code><ul><li>☑ deep</li></ul>\
+             </li><li>sibling</li></ul></li>\n\
+             <li>d</li>\n\
+             </ul>\n"
+        );
+
+        let tasks = markdown_to_html("1. [ ] todo\n2. [x] done\n");
+        assert!(tasks.contains("<ol>\n<li>☐ todo</li>\n<li>☑ done</li>\n</ol>"));
+    }
+
+    #[test]
+    fn test_render_man_snapshot() {
+        let doc = crate::parse_markdown(
+            "# treemd(1)\n\n## NAME\n\ntreemd - a *markdown* navigator\n\n\
+             ## OPTIONS\n\n### Output\n\n- `--tree` prints the tree\n- [docs](https://example.com)\n\n\
+             Paths like C:\\docs work.\n.Not a request.\n\n\
+             ```sh\n.hidden\necho \"a\\n\"\n```\n",
+        );
+        assert_eq!(
+            render_man(&doc),
+            ".TH \"TREEMD\" \"1\"\n\
+             .SH \"NAME\"\n\
+             treemd - a \\fImarkdown\\fR navigator\n\
+             .SH \"OPTIONS\"\n\
+             .SS \"Output\"\n\
+             .IP \\(bu 2\n\
+             \\fB--tree\\fR prints the tree\n\
+             .IP \\(bu 2\n\
+             docs <\\fIhttps://example.com\\fR>\n\
+             .PP\n\
+             Paths like C:\\edocs work. .Not a request.\n\
+             .PP\n\
+             .RS 4\n\
+             .nf\n\
+             \\&.hidden\n\
+             echo \"a\\en\"\n\
+             .fi\n\
+             .RE\n"
+        );
+
+        // Long paragraphs are filled, and a wrapped line can't start a request
+        let doc = crate::parse_markdown(&format!("# t\n\n{} .dot\n", "word ".repeat(15)));
+        let man = render_man(&doc);
+        assert!(man.contains("\nword word"));
+        assert!(man.lines().all(|line| line.len() <= 78));
+        assert!(man.lines().any(|line| line == "\\&.dot"));
+    }
+
+    #[test]
+    fn test_ordered_lists_keep_start_number() {
+        let md = "5. five\n6. six\n\nBreak\n\n1. one\n";
+        #[cfg(feature = "native")]
+        {
+            let rendered = markdown_to_string(md);
+            assert!(rendered.contains("5. five"));
+            assert!(rendered.contains("6. six"));
+            assert!(rendered.contains("1. one"));
+        }
+
+        let html = markdown_to_html(md);
+        assert!(html.contains("<ol start=\"5\">\n<li>five</li>\n<li>six</li>\n</ol>"));
+        assert!(html.contains("<ol>\n<li>one</li>\n</ol>"));
+    }
+
+    #[test]
+    fn test_nested_ordered_lists_keep_numbers() {
+        let md = "- fruit\n  1. apple\n  2. pear\n     - [x] ripe\n";
+        assert_eq!(
+            markdown_to_html(md),
+            "<ul>\n<li>fruit<ol><li>apple</li><li>pear<ul><li>☑ ripe</li></ul>\
+             </li></ol></li>\n</ul>\n"
+        );
+
+        let man = render_man(&crate::parse_markdown(md));
+        assert!(
+            man.contains(".RS 2\n.IP 1. 4\napple\n.IP 2. 4\npear\n.RS 2\n.IP \\(bu 2\n☑ ripe\n")
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_html_drops_unsafe_urls() {
+        let html = markdown_to_html("[click](javascript:alert(1)) [rel](docs/a.md#x)\n");
+        assert!(html.contains("click"));
+        assert!(!html.contains("javascript"));
+        assert!(html.contains("<a href=\"docs/a.md#x\">rel</a>"));
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn test_render_html_fragment_options() {
+        let doc = crate::parse_markdown(
+            "## Setup\n\n```rust\nlet s = \"<b>\";\n```\n\n```\nplain\n```\n",
+        );
+        let default = render_html_fragment(&doc, &HtmlOptions::default());
+        assert_eq!(default, markdown_to_html(&doc.content));
+
+        let classes = render_html_fragment(
+            &doc,
+            &HtmlOptions {
+                heading_anchors: false,
+                class_prefix: "md-".to_string(),
+                code_highlight: CodeHighlight::Classes,
+            },
+        );
+        assert!(classes.starts_with("<h2>Setup</h2>\n"));
+        assert!(
+            classes.contains("<code class=\"md-language-rust\"><span class=\"md-source md-rust\">")
+        );
+        assert!(classes.contains("&lt;b&gt;"));
+        assert!(!classes.contains("class=\"source"));
+        // Code without a language stays unhighlighted
+        assert!(classes.contains("<pre><code>plain</code></pre>"));
+
+        let styled = render_html_fragment(
+            &doc,
+            &HtmlOptions {
+                code_highlight: CodeHighlight::InlineStyles,
+                ..HtmlOptions::default()
+            },
+        );
+        assert!(styled.starts_with("<h2 id=\"setup\">Setup</h2>\n"));
+        assert!(styled.contains("<span style=\"color:#"));
+        assert!(styled.contains("&lt;b&gt;"));
+        assert!(!styled.contains("background"));
+    }
+}
@@ -0,0 +1,208 @@
+//! Rendering to styled ratatui text, as the content pane draws it.
+
+use super::highlighter;
+use crate::tui::theme::Theme;
+use crate::tui::{Highlighter, render_markdown_enhanced};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Text;
+
+/// Render markdown to styled ratatui text using the given theme.
+///
+/// Produces the same output as the TUI content pane outside interactive mode.
+/// Tables are laid out at their natural width.
+pub fn markdown_to_text(content: &str, theme: &Theme) -> Text<'static> {
+    markdown_to_text_with(content, theme, highlighter())
+}
+
+/// Render markdown like [`markdown_to_text`], highlighting code blocks with
+/// the given highlighter (e.g. [`PlainHighlighter`](crate::tui::PlainHighlighter)).
+pub fn markdown_to_text_with(
+    content: &str,
+    theme: &Theme,
+    highlighter: &dyn Highlighter,
+) -> Text<'static> {
+    render_markdown_enhanced(content, highlighter, theme, None, None, None)
+}
+
+/// Render markdown to plain text, one rendered line per output line.
+///
+/// Uses the same layout as [`markdown_to_text`] (bullets, checkboxes, table
+/// borders) with all styling dropped.
+pub fn markdown_to_string(content: &str) -> String {
+    let text = markdown_to_text(content, &Theme::ocean_dark());
+    text.lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Convert styled text to a string with ANSI escape sequences.
+///
+/// Colors are written as they are: RGB as true color, indexed colors as
+/// 256-color codes, so pick the theme's color mode before rendering. Each
+/// styled span resets afterwards, so lines can be printed independently.
+///
+/// ```
+/// use ratatui::style::{Color, Stylize};
+/// use ratatui::text::{Line, Text};
+///
+/// let text = Text::from(Line::from(vec!["plain ".into(), "red".fg(Color::Red).bold()]));
+/// assert_eq!(treemd::render::text_to_ansi(&text), "plain \x1b[1;31mred\x1b[0m");
+/// ```
+pub fn text_to_ansi(text: &Text) -> String {
+    let mut out = String::new();
+    for (i, line) in text.lines.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        for span in &line.spans {
+            let codes = sgr_codes(text.style.patch(line.style).patch(span.style));
+            if codes.is_empty() {
+                out.push_str(&span.content);
+            } else {
+                out.push_str(&format!("\x1b[{}m{}\x1b[0m", codes.join(";"), span.content));
+            }
+        }
+    }
+    out
+}
+
+/// SGR parameters for a style; empty when it has nothing to apply.
+fn sgr_codes(style: Style) -> Vec<String> {
+    const MODIFIERS: [(Modifier, &str); 6] = [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ];
+
+    let mut codes: Vec<String> = MODIFIERS
+        .iter()
+        .filter(|(modifier, _)| style.add_modifier.contains(*modifier))
+        .map(|(_, code)| code.to_string())
+        .collect();
+    codes.extend(style.fg.and_then(|color| color_code(color, false)));
+    codes.extend(style.bg.and_then(|color| color_code(color, true)));
+    codes
+}
+
+/// SGR parameter selecting `color` as foreground or background.
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let named = |offset: u8| Some((base + offset as u16).to_string());
+    let bright = |offset: u8| Some((base + 60 + offset as u16).to_string());
+    let layer = if background { 48 } else { 38 };
+    match color {
+        Color::Reset => None,
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => bright(0),
+        Color::LightRed => bright(1),
+        Color::LightGreen => bright(2),
+        Color::LightYellow => bright(3),
+        Color::LightBlue => bright(4),
+        Color::LightMagenta => bright(5),
+        Color::LightCyan => bright(6),
+        Color::White => bright(7),
+        Color::Indexed(index) => Some(format!("{};5;{}", layer, index)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", layer, r, g, b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_string_lays_out_blocks() {
+        let rendered = markdown_to_string("Some **bold** text\n\n- [x] done\n- [ ] todo\n");
+        assert!(rendered.contains("Some bold text"));
+        assert!(rendered.contains("  ☑ done"));
+        assert!(rendered.contains("  ☐ todo"));
+        assert!(!rendered.contains("**"));
+    }
+
+    #[test]
+    fn test_markdown_to_text_applies_theme_styles() {
+        let theme = Theme::ocean_dark();
+        let text = markdown_to_text("Some **bold** text", &theme);
+        let bold = text.lines[0]
+            .spans
+            .iter()
+            .find(|span| span.content == "bold")
+            .unwrap();
+        assert!(bold.style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_code_blocks_keep_their_source() {
+        let rendered = markdown_to_string("```rust\nfn main() {}\n```\n");
+        assert!(rendered.contains("fn main() {}"));
+    }
+
+    #[test]
+    fn test_custom_highlighter_renders_code_blocks() {
+        use crate::tui::PlainHighlighter;
+        use ratatui::style::Style;
+
+        let text = markdown_to_text_with(
+            "```rust\nfn main() {}\n```\n",
+            &Theme::ocean_dark(),
+            &PlainHighlighter,
+        );
+        let code_line = text
+            .lines
+            .iter()
+            .find(|line| line.spans.iter().any(|s| s.content.contains("fn main")))
+            .unwrap();
+        let code_span = code_line
+            .spans
+            .iter()
+            .find(|s| s.content.contains("fn main"))
+            .unwrap();
+        assert_eq!(code_span.style, Style::default());
+    }
+
+    #[test]
+    fn test_text_to_ansi_colors_and_modifiers() {
+        use ratatui::text::{Line, Span};
+
+        let text = Text::from(vec![
+            Line::from(vec![
+                Span::styled("rgb", Style::new().fg(Color::Rgb(1, 2, 3))),
+                Span::styled(
+                    "idx",
+                    Style::new()
+                        .fg(Color::Indexed(42))
+                        .bg(Color::Black)
+                        .add_modifier(Modifier::ITALIC | Modifier::UNDERLINED),
+                ),
+            ]),
+            Line::styled("whole line", Style::new().fg(Color::LightCyan)),
+            Line::from("plain"),
+        ]);
+
+        assert_eq!(
+            text_to_ansi(&text),
+            "\x1b[38;2;1;2;3mrgb\x1b[0m\x1b[3;4;38;5;42;40midx\x1b[0m\n\
+             \x1b[96mwhole line\x1b[0m\nplain"
+        );
+
+        let rendered = text_to_ansi(&markdown_to_text("# Title\n", &Theme::ocean_dark()));
+        assert!(rendered.starts_with("\x1b[1;4;38;2;100;200;255mTitle\x1b[0m"));
+    }
+}
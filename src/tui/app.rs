@@ -1,18 +1,25 @@
-use crate::config::Config;
+use crate::config::{Config, FilePosition};
 use crate::keybindings::{Action, KeybindingMode, Keybindings};
-use crate::parser::{Document, HeadingNode, Link, extract_links};
+use crate::parser::links::extract_links_and_images;
+use crate::parser::{Document, Heading, HeadingNode, Link, TaskStats};
+use crate::tui::clipboard::{self, ClipboardBackend};
+use crate::tui::events::{EventKind, EventSink};
 use crate::tui::help_text;
 use crate::tui::image_cache::ImageCache;
 use crate::tui::interactive::InteractiveState;
 use crate::tui::kitty_animation::{self, KittyAnimation};
-use crate::tui::syntax::SyntaxHighlighter;
+use crate::tui::link_resolver::{DefaultLinkResolver, LinkResolver, ResolvedLink};
+use crate::tui::loader::DocumentLoader;
+use crate::tui::syntax::{Highlighter, SyntaxHighlighter};
 use crate::tui::terminal_compat::ColorMode;
 use crate::tui::theme::{Theme, ThemeName};
 use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::widgets::{ListState, ScrollbarState};
-use std::collections::HashSet;
-use std::path::PathBuf;
-use std::time::{Duration, Instant};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
 
 /// Special marker for the document overview entry (shows entire file content)
 pub const DOCUMENT_OVERVIEW: &str = "(Document)";
@@ -49,10 +56,31 @@ pub enum AppMode {
     ConfirmSaveWidth,      // Modal confirmation for saving outline width
     ConfirmSaveBeforeQuit, // Prompt to save unsaved changes before quitting
     ConfirmSaveBeforeNav,  // Prompt to save unsaved changes before navigating
+    ConfirmOverwrite,      // Prompt before writing over a file changed on disk
     FilePicker,            // File picker modal for switching files
     FileSearch,            // File picker search/filter mode
 }
 
+/// A write held back because the file changed on disk since it was loaded
+#[derive(Debug, Clone)]
+pub enum PendingOverwrite {
+    /// Save the buffered cell edits, then quit if `quit` is set or carry out
+    /// the pending navigation if there is one
+    Edits { quit: bool },
+    /// Toggle a task list checkbox
+    Checkbox {
+        block_idx: usize,
+        item_idx: usize,
+        checked: bool,
+    },
+    /// Toggle a `[x]`/`[ ]` in a paragraph or table
+    InlineCheckbox {
+        offset: usize,
+        context: String,
+        checked: bool,
+    },
+}
+
 /// Type of pending navigation when user has unsaved changes
 #[derive(Debug, Clone)]
 pub enum PendingNavigation {
@@ -60,7 +88,7 @@ pub enum PendingNavigation {
     Back,
     /// Navigate forward in file history
     Forward,
-    /// Load a file (relative path, optional anchor)
+    /// Load a file (resolved path, optional anchor)
     LoadFile(PathBuf, Option<String>),
 }
 
@@ -73,6 +101,17 @@ pub enum CommandAction {
     ToggleOutline,
     ToggleHelp,
     ToggleRawSource,
+    ToggleFocusMode,
+    CopyContentHtml,
+    /// Copy `[Heading](path#anchor)` for linking from another document
+    CopyHeadingLink,
+    OpenContainingDir,
+    Reload,
+    ToggleAutoReload,
+    /// Copy or write the expanded sections (`:export FILE` writes a file)
+    ExportVisible,
+    /// List registers, or copy them to the clipboard (`:reg ab`)
+    Registers,
     JumpToTop,
     JumpToBottom,
     CollapseAll,
@@ -127,6 +166,11 @@ impl PaletteCommand {
             }
         }
 
+        // Alias followed by an argument (e.g. "export notes.md")
+        if self.alias_with_argument(&query_lower) {
+            return true;
+        }
+
         // Fuzzy match: check if all query chars appear in order in name
         let name_lower = self.name.to_lowercase();
         let mut name_chars = name_lower.chars().peekable();
@@ -156,6 +200,11 @@ impl PaletteCommand {
             }
         }
 
+        // Alias with an argument, ranked below exact aliases like "collapse 2"
+        if self.alias_with_argument(&query_lower) {
+            return 900;
+        }
+
         // Alias prefix match
         for alias in self.aliases {
             if alias.to_lowercase().starts_with(&query_lower) {
@@ -176,6 +225,25 @@ impl PaletteCommand {
         // Fuzzy match score based on how compact the match is
         100
     }
+
+    /// Whether `query` is one of the aliases followed by an argument
+    fn alias_with_argument(&self, query_lower: &str) -> bool {
+        command_argument(query_lower).is_some()
+            && query_lower.split_whitespace().next().is_some_and(|word| {
+                self.aliases
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(word))
+            })
+    }
+}
+
+/// The argument after the first word of a command query, if any
+fn command_argument(query: &str) -> Option<&str> {
+    query
+        .trim()
+        .split_once(char::is_whitespace)
+        .map(|(_, rest)| rest.trim())
+        .filter(|rest| !rest.is_empty())
 }
 
 /// All available commands
@@ -216,6 +284,54 @@ pub const PALETTE_COMMANDS: &[PaletteCommand] = &[
         "Switch between rendered and raw markdown",
         CommandAction::ToggleRawSource,
     ),
+    PaletteCommand::new(
+        "Toggle focus mode",
+        &["focus", "zen"],
+        "Show the whole document with only the selected section expanded",
+        CommandAction::ToggleFocusMode,
+    ),
+    PaletteCommand::new(
+        "Copy as HTML",
+        &["html", "copyhtml"],
+        "Copy current section as rich text for pasting into docs or email",
+        CommandAction::CopyContentHtml,
+    ),
+    PaletteCommand::new(
+        "Copy heading link",
+        &["link", "mdlink"],
+        "Copy [Heading](path/to/file.md#anchor) for linking from another document",
+        CommandAction::CopyHeadingLink,
+    ),
+    PaletteCommand::new(
+        "Open containing folder",
+        &["reveal", "folder", "dir"],
+        "Show the current file's folder in the file manager",
+        CommandAction::OpenContainingDir,
+    ),
+    PaletteCommand::new(
+        "Reload file",
+        &["reload", "refresh", "e!"],
+        "Re-read the current file from disk",
+        CommandAction::Reload,
+    ),
+    PaletteCommand::new(
+        "Toggle auto-reload",
+        &["autoreload", "auto"],
+        "Reload automatically when the file changes on disk",
+        CommandAction::ToggleAutoReload,
+    ),
+    PaletteCommand::new(
+        "Export visible as markdown",
+        &["export", "visible"],
+        "Copy expanded sections to the clipboard (:export FILE writes a file)",
+        CommandAction::ExportVisible,
+    ),
+    PaletteCommand::new(
+        "Registers",
+        &["registers", "reg"],
+        "List copy registers (:reg a copies register a, :reg ab joins several)",
+        CommandAction::Registers,
+    ),
     PaletteCommand::new(
         "Jump to top",
         &["top", "first", "gg"],
@@ -300,6 +416,7 @@ pub struct App {
     pub outline_scroll_state: ScrollbarState,
     pub focus: Focus,
     pub outline_items: Vec<OutlineItem>,
+    outline_task_stats: HashMap<usize, TaskStats>, // Task completion by heading offset (ui.outline_task_progress)
     pub content_scroll: u16,
     pub content_scroll_state: ScrollbarState,
     pub content_height: u16,
@@ -309,9 +426,11 @@ pub struct App {
     pub show_search: bool,
     pub outline_search_active: bool, // Whether search input is active (cursor visible)
     pub search_query: String,
-    pub highlighter: SyntaxHighlighter,
+    pub outline_search_path: bool, // Whether headings under a matching heading are kept too
+    pub highlighter: Box<dyn Highlighter>,
     pub show_outline: bool,
-    pub outline_width: u16, // Percentage: 20, 30, or 40
+    pub outline_width: u16,    // Percentage: 20, 30, or 40
+    pub status_format: String, // Status bar layout (ui.status_format)
     /// Whether the config file had a custom (non-standard) outline width at startup.
     /// Used to protect power users' custom config values from being overwritten.
     /// Standard values are 20, 30, 40; anything else is considered custom.
@@ -324,20 +443,32 @@ pub struct App {
     pub show_theme_picker: bool,
     pub theme_picker_selected: usize,
     pub theme_picker_original: Option<ThemeName>, // Original theme before picker opened (for cancel)
-    previous_selection: Option<String>,           // Track previous selection to detect changes
+    previous_selection: Option<(String, Option<usize>)>, // Track previous selection to detect changes
+    pub sync_outline: bool, // Outline selection follows the content scroll position
+    pub auto_reload: bool,  // Reload when the file changes on disk
+    pub content_heading_lines: Vec<u16>, // Rendered line of each heading in the content pane
+    synced_outline: Option<SyncedOutline>,
 
     // Link following state
     pub mode: AppMode,
     /// Vim-style count prefix for motion commands (e.g., 5j moves down 5)
     pub count_prefix: Option<usize>,
+    /// Named registers filled by `"a` before a copy, keyed by lowercase letter
+    pub registers: HashMap<char, String>,
+    awaiting_register: bool, // `"` pressed; the next key names a register
+    pending_register: Option<char>, // Register named for the next action
+    yank_register: Option<char>, // Register the running copy writes to
     pub current_file_path: PathBuf, // Path to current file for resolving relative links
-    pub file_path_changed: bool,    // Flag to signal file watcher needs update
-    pub suppress_file_watch: bool,  // Skip next file watch check (after internal save)
-    pub links_in_view: Vec<Link>,   // Links in currently displayed content
+    pub file_path_changed: bool, // Flag to signal file watcher needs update
+    pub suppress_file_watch: bool, // Skip next file watch check (after internal save)
+    file_mtime: Option<SystemTime>, // Modification time when the file was loaded or saved
+    pub disk_changed: bool,  // File changed on disk and the view wasn't reloaded
+    pub pending_overwrite: Option<PendingOverwrite>, // Write waiting for ConfirmOverwrite
+    pub links_in_view: Vec<Link>, // Links in currently displayed content
     pub filtered_link_indices: Vec<usize>, // Indices into links_in_view after filtering
     pub selected_link_idx: Option<usize>, // Currently selected index in filtered list
-    pub link_search_query: String,  // Search query for filtering links
-    pub link_search_active: bool,   // Whether search input is active
+    pub link_search_query: String, // Search query for filtering links
+    pub link_search_active: bool, // Whether search input is active
 
     // File picker state
     pub files_in_directory: Vec<PathBuf>, // All .md files in directory
@@ -347,6 +478,8 @@ pub struct App {
     pub file_search_active: bool,         // Whether search input is active
     pub startup_needs_file_picker: bool,  // True if started without file arg
     pub file_picker_dir: Option<PathBuf>, // Custom directory for file picker
+    pub file_picker_recent: bool,         // Picker lists recent files instead of a directory
+    pub remember_recent_files: bool,      // Record opened files in the config's recent list
 
     pub file_history: Vec<FileState>,   // Back navigation stack
     pub file_future: Vec<FileState>,    // Forward navigation stack (for undo back)
@@ -366,9 +499,9 @@ pub struct App {
     pub pending_edits: Vec<PendingEdit>, // Stack of uncommitted edits
     pub has_unsaved_changes: bool,       // True if pending_edits is non-empty
 
-    // Persistent clipboard for Linux X11 compatibility
-    // On Linux, the clipboard instance must stay alive to serve paste requests
-    clipboard: Option<arboard::Clipboard>,
+    // Persistent clipboard backend chosen by `ui.clipboard`
+    // On Linux, the system clipboard instance must stay alive to serve paste requests
+    clipboard: Option<Box<dyn ClipboardBackend>>,
 
     // Configuration persistence
     config: Config,
@@ -376,9 +509,18 @@ pub struct App {
 
     // Pending file to open in external editor (set by link following, consumed by main loop)
     pub pending_editor_file: Option<PathBuf>,
+    /// Background parse of a large document (see `load_in_background`)
+    loader: Option<DocumentLoader>,
+
+    // Decides where followed links lead
+    link_resolver: Box<dyn LinkResolver>,
+
+    // JSON-lines navigation events for scripting (see `set_event_writer`)
+    event_sink: Option<EventSink>,
 
     // Raw source view toggle
     pub show_raw_source: bool,
+    pub focus_mode: bool, // Content shows the whole document with only the selected section expanded
 
     // Pending file creation (for confirm dialog)
     pub pending_file_create: Option<PathBuf>,
@@ -387,10 +529,12 @@ pub struct App {
     // Document search state (for in-document / search with n/N navigation)
     pub doc_search_query: String,
     pub doc_search_matches: Vec<SearchMatch>,
+    pub doc_search_truncated: bool, // Whether matches stopped at ui.max_search_matches
     pub doc_search_current_idx: Option<usize>,
     pub doc_search_active: bool, // Whether search input is active
     pub doc_search_from_interactive: bool, // Whether search was started from interactive mode
     pub doc_search_selected_link_idx: Option<usize>, // Index into links_in_view if match is in a link
+    pub doc_search_in_code: bool, // Whether matches inside code blocks are included
 
     // Command palette state
     pub command_query: String,
@@ -399,6 +543,8 @@ pub struct App {
 
     // Customizable keybindings
     pub keybindings: Keybindings,
+    chord_timeout: Duration,
+    last_key_at: Option<Instant>, // When the last key was dispatched (for chord timeouts)
 
     // Pending navigation (for confirm save dialog when navigating with unsaved changes)
     pub pending_navigation: Option<PendingNavigation>,
@@ -406,6 +552,14 @@ pub struct App {
     // Image cache for lazy-loaded images
     pub image_cache: ImageCache,
 
+    // Extracted section text by heading offset, so rendering and metrics
+    // don't re-extract the selected section every frame. Cleared whenever
+    // the document changes.
+    section_cache: RefCell<HashMap<usize, Rc<str>>>,
+
+    // The content pane's last parse, reused while the section is unchanged
+    pub(crate) content_parse: crate::tui::ui::ParseCache,
+
     // Terminal graphics protocol picker (with fallback font size)
     pub picker: Option<ratatui_image::picker::Picker>,
 
@@ -444,12 +598,54 @@ pub struct FileState {
     pub outline_state_selected: Option<usize>,
 }
 
+/// Push onto a back or forward stack, dropping the oldest entries beyond
+/// `depth` so long link-hopping sessions don't keep every document alive
+fn push_bounded(stack: &mut Vec<FileState>, state: FileState, depth: usize) {
+    stack.push(state);
+    if stack.len() > depth {
+        stack.drain(..stack.len() - depth);
+    }
+}
+
+/// `path` relative to its project root, the nearest enclosing directory with
+/// a `.git`, or else to `cwd`; `/`-separated for use in markdown links.
+/// Paths outside both are returned whole.
+fn project_relative_path(path: &Path, cwd: Option<&Path>) -> String {
+    let root = path
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join(".git").exists())
+        .or(cwd);
+    match root.and_then(|root| path.strip_prefix(root).ok()) {
+        Some(relative) => relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        None => path.to_string_lossy().into_owned(),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OutlineItem {
     pub level: usize,
     pub text: String,
     pub expanded: bool,
     pub has_children: bool, // Track if this heading has children in the tree
+    pub offset: Option<usize>, // Byte offset of the heading (None for document overview)
+}
+
+/// Outline selection that follows the content scroll position.
+///
+/// While the selection sits on `index`, the content pane keeps showing the
+/// section it was scrolled in rather than the selected heading's section.
+#[derive(Debug, Clone)]
+struct SyncedOutline {
+    index: usize,
+    /// Offset of the heading whose section is displayed (None for the overview)
+    anchor: Option<usize>,
+    /// Selection key of the displayed section
+    anchor_key: Option<(String, Option<usize>)>,
 }
 
 impl App {
@@ -464,6 +660,7 @@ impl App {
         let tree = document.build_tree();
         let collapsed_headings = HashSet::new();
         let mut outline_items = Self::flatten_tree(&tree, &collapsed_headings);
+        let file_mtime = modified_time(&file_path);
 
         // Add document overview entry if there's preamble content or no headings
         let has_preamble = Self::has_preamble_content(&document);
@@ -475,6 +672,7 @@ impl App {
                     text: DOCUMENT_OVERVIEW.to_string(),
                     expanded: true,
                     has_children: !outline_items.is_empty(),
+                    offset: None,
                 },
             );
         }
@@ -487,6 +685,9 @@ impl App {
         let content_lines = document.content.lines().count();
 
         // Load theme from config, apply color mode, then apply custom colors
+        let outline_task_stats =
+            Self::collect_task_stats(&document, config.ui.outline_task_progress);
+
         let current_theme = config.theme_name();
         let theme = Theme::from_name(current_theme)
             .with_color_mode(color_mode, current_theme)
@@ -501,9 +702,15 @@ impl App {
             outline_width != 20 && outline_width != 30 && outline_width != 40;
 
         // Load keybindings from config (before config is moved)
-        let keybindings = config.keybindings();
-
-        Self {
+        let chord_timeout = Duration::from_millis(config.ui.chord_timeout_ms);
+        let mut keybindings = config.keybindings();
+        keybindings.set_sequence_timeout(chord_timeout);
+        let doc_search_in_code = config.ui.search_in_code;
+        let sync_outline = config.ui.sync_outline;
+        let auto_reload = config.ui.auto_reload;
+        let clipboard = clipboard::backend(&config.ui.clipboard);
+
+        let mut app = Self {
             document,
             filename,
             tree,
@@ -511,6 +718,7 @@ impl App {
             outline_scroll_state: ScrollbarState::new(outline_items.len()),
             focus: Focus::Outline,
             outline_items,
+            outline_task_stats,
             content_scroll: 0,
             content_scroll_state: ScrollbarState::new(content_lines),
             content_height: content_lines as u16,
@@ -520,9 +728,11 @@ impl App {
             show_search: false,
             outline_search_active: false,
             search_query: String::new(),
-            highlighter: SyntaxHighlighter::new(),
+            outline_search_path: config.ui.search_match_path,
+            highlighter: Box::new(SyntaxHighlighter::new().with_tab_width(config.ui.tab_width)),
             show_outline: true,
             outline_width,
+            status_format: config.ui.status_format.clone(),
             config_has_custom_outline_width,
             bookmark_position: None,
             collapsed_headings,
@@ -533,13 +743,24 @@ impl App {
             theme_picker_selected: 0,
             theme_picker_original: None,
             previous_selection: None,
+            sync_outline,
+            auto_reload,
+            content_heading_lines: Vec::new(),
+            synced_outline: None,
 
             // Link following state
             mode: AppMode::Normal,
             count_prefix: None,
+            registers: HashMap::new(),
+            awaiting_register: false,
+            pending_register: None,
+            yank_register: None,
             current_file_path: file_path,
             file_path_changed: false,
             suppress_file_watch: false,
+            file_mtime,
+            disk_changed: false,
+            pending_overwrite: None,
             links_in_view: Vec::new(),
             filtered_link_indices: Vec::new(),
             selected_link_idx: None,
@@ -554,6 +775,8 @@ impl App {
             file_search_active: false,
             startup_needs_file_picker: false,
             file_picker_dir: None,
+            file_picker_recent: false,
+            remember_recent_files: false,
 
             file_history: Vec::new(),
             file_future: Vec::new(),
@@ -573,8 +796,8 @@ impl App {
             pending_edits: Vec::new(),
             has_unsaved_changes: false,
 
-            // Initialize persistent clipboard (None if unavailable)
-            clipboard: arboard::Clipboard::new().ok(),
+            // Persistent clipboard (None if unavailable)
+            clipboard,
 
             // Configuration persistence
             config,
@@ -582,9 +805,13 @@ impl App {
 
             // Pending editor file
             pending_editor_file: None,
+            loader: None,
+            link_resolver: Box::new(DefaultLinkResolver),
+            event_sink: None,
 
             // Raw source view (off by default)
             show_raw_source: false,
+            focus_mode: false,
 
             // Pending file creation (for confirm dialog)
             pending_file_create: None,
@@ -593,10 +820,12 @@ impl App {
             // Document search state
             doc_search_query: String::new(),
             doc_search_matches: Vec::new(),
+            doc_search_truncated: false,
             doc_search_current_idx: None,
             doc_search_active: false,
             doc_search_from_interactive: false,
             doc_search_selected_link_idx: None,
+            doc_search_in_code,
 
             // Command palette state
             command_query: String::new(),
@@ -606,12 +835,16 @@ impl App {
             // Customizable keybindings (loaded from config)
             // Note: keybindings() called before config is moved into struct
             keybindings,
+            chord_timeout,
+            last_key_at: None,
 
             // Pending navigation (for confirm save dialog)
             pending_navigation: None,
 
             // Image cache (initialized later after entering alternate screen)
             image_cache: ImageCache::new(),
+            section_cache: RefCell::new(HashMap::new()),
+            content_parse: Default::default(),
 
             // Terminal graphics protocol picker with fallback (like figif)
             // Only initialize if images are enabled
@@ -643,7 +876,15 @@ impl App {
 
             // Image rendering control
             images_enabled,
-        }
+        };
+        app.interactive_state
+            .set_block_spacing(app.config.ui.block_spacing);
+        app.interactive_state
+            .set_fold_data_blocks(app.config.ui.fold_data_blocks);
+
+        // Pick up where this file was left last time
+        app.restore_file_position();
+        app
     }
 
     /// Initialize graphics protocol picker with fallback font size (like figif).
@@ -677,10 +918,15 @@ impl App {
     /// (for GIFs), and creates a stateful protocol for rendering.
     pub fn load_first_image(&mut self, content: &str) {
         use crate::parser::content::parse_content;
-        use crate::parser::output::Block as ContentBlock;
 
         // Parse content to find first image
         let blocks = parse_content(content, 0);
+        self.load_first_image_from_blocks(&blocks);
+    }
+
+    /// Load the first image found in already-parsed content blocks.
+    fn load_first_image_from_blocks(&mut self, blocks: &[crate::parser::output::Block]) {
+        use crate::parser::output::Block as ContentBlock;
 
         for block in blocks {
             // Check for block-level images
@@ -783,6 +1029,31 @@ impl App {
         }
     }
 
+    /// View a local image in the image modal, or copy its source when it is
+    /// remote, can't be loaded or images are disabled.
+    fn view_or_copy_image(&mut self, alt: &str, src: &str) {
+        let remote = src.contains("://") || src.starts_with("data:");
+        if !remote {
+            self.open_image_modal(src);
+        }
+        if self.viewing_image_state.is_some() {
+            self.status_message = Some(format!("📸 Viewing: {} (Esc:Close)", alt));
+            return;
+        }
+
+        let reason = if remote {
+            "remote image"
+        } else if !self.images_enabled {
+            "images disabled"
+        } else {
+            "image not loaded"
+        };
+        self.status_message = Some(match self.copy_to_clipboard(src) {
+            Ok(()) => format!("✓ Image source copied ({}): {}", reason, src),
+            Err(e) => format!("✗ {}", e),
+        });
+    }
+
     /// Close the image modal
     pub fn close_image_modal(&mut self) {
         // Delete Kitty animation if active
@@ -985,7 +1256,8 @@ impl App {
             AppMode::ConfirmFileCreate
             | AppMode::ConfirmSaveWidth
             | AppMode::ConfirmSaveBeforeQuit
-            | AppMode::ConfirmSaveBeforeNav => KeybindingMode::ConfirmDialog,
+            | AppMode::ConfirmSaveBeforeNav
+            | AppMode::ConfirmOverwrite => KeybindingMode::ConfirmDialog,
             AppMode::DocSearch => KeybindingMode::DocSearch,
             AppMode::CommandPalette => KeybindingMode::CommandPalette,
             AppMode::FilePicker => {
@@ -1012,9 +1284,29 @@ impl App {
             state: KeyEventState::NONE,
         };
 
+        self.last_key_at = Some(Instant::now());
         self.keybindings.dispatch(mode, event)
     }
 
+    /// Discard a half-typed key sequence once the chord timeout has passed.
+    ///
+    /// Called while idle so a late second key starts a new sequence instead
+    /// of completing the stale one.
+    pub fn expire_pending_chord(&mut self) {
+        let Some(last_key_at) = self.last_key_at else {
+            return;
+        };
+        if last_key_at.elapsed() >= self.chord_timeout {
+            self.last_key_at = None;
+            if self
+                .keybindings
+                .is_sequence_ongoing(self.current_keybinding_mode())
+            {
+                self.keybindings.reset_sequences();
+            }
+        }
+    }
+
     /// Execute an action, returning the result type
     ///
     /// Returns:
@@ -1024,6 +1316,10 @@ impl App {
     pub fn execute_action(&mut self, action: Action) -> ActionResult {
         use Action::*;
 
+        if let Some(register) = self.pending_register.take() {
+            return self.execute_into_register(action, register);
+        }
+
         match action {
             // === Application ===
             Quit => {
@@ -1071,13 +1367,23 @@ impl App {
                 self.first();
             }
             Last => {
-                self.clear_count();
-                self.last();
+                // `42G` goes to the 42nd heading, like a line number in vim
+                match self.count_prefix.take() {
+                    Some(number) if self.mode == AppMode::Normal => {
+                        self.jump_to_heading_number(number)
+                    }
+                    _ => self.last(),
+                }
             }
             PageDown => {
                 self.clear_count();
                 if self.show_help {
                     self.scroll_help_page_down();
+                } else if self.interactive_state.is_in_table_mode() {
+                    let (rows, cols) = self.get_table_dimensions();
+                    self.interactive_state.table_page_down(rows);
+                    self.status_message =
+                        Some(self.interactive_state.table_status_text(rows + 1, cols));
                 } else {
                     self.scroll_page_down();
                 }
@@ -1086,6 +1392,11 @@ impl App {
                 self.clear_count();
                 if self.show_help {
                     self.scroll_help_page_up();
+                } else if self.interactive_state.is_in_table_mode() {
+                    let (rows, cols) = self.get_table_dimensions();
+                    self.interactive_state.table_page_up();
+                    self.status_message =
+                        Some(self.interactive_state.table_status_text(rows + 1, cols));
                 } else {
                     self.scroll_page_up();
                 }
@@ -1098,7 +1409,10 @@ impl App {
             // === Outline ===
             Expand => self.expand(),
             Collapse => self.collapse(),
-            ToggleExpand => self.toggle_expand(),
+            ToggleExpand => match self.count_prefix.take() {
+                Some(number) if self.mode == AppMode::Normal => self.jump_to_heading_number(number),
+                _ => self.toggle_expand(),
+            },
             ToggleFocus => self.toggle_focus(),
             ToggleFocusBack => self.toggle_focus_back(),
             ToggleOutline => self.toggle_outline(),
@@ -1117,6 +1431,8 @@ impl App {
             EnterSearchMode => self.toggle_search(),
             EnterDocSearch => self.enter_doc_search(),
             ToggleSearchMode => self.toggle_search_mode(),
+            ToggleSearchInCode => self.toggle_doc_search_in_code(),
+            ToggleSearchMatchPath => self.toggle_outline_search_path(),
             ExitMode => self.exit_current_mode(),
             OpenCommandPalette => self.open_command_palette(),
 
@@ -1138,6 +1454,13 @@ impl App {
                 }
                 _ => {}
             },
+            EditLinkTarget => match self.selected_link_edit_target() {
+                Ok((path, line)) => {
+                    self.exit_link_follow_mode();
+                    return ActionResult::RunEditor(path, Some(line));
+                }
+                Err(e) => self.status_message = Some(format!("✗ Error: {}", e)),
+            },
             LinkSearch => match self.mode {
                 AppMode::LinkFollow => self.start_link_search(),
                 AppMode::FilePicker => {
@@ -1185,6 +1508,18 @@ impl App {
                     self.status_message = Some(self.interactive_state.status_text());
                 }
             }
+            InteractiveNextOfType | InteractivePrevOfType => {
+                let count = self.take_count();
+                for _ in 0..count {
+                    if action == InteractiveNextOfType {
+                        self.interactive_state.next_of_type();
+                    } else {
+                        self.interactive_state.previous_of_type();
+                    }
+                }
+                self.scroll_to_interactive_element(self.content_viewport_height);
+                self.status_message = Some(self.interactive_state.status_text());
+            }
             InteractiveActivate => {
                 self.clear_count();
                 // In table mode, Enter edits the cell; otherwise activate the element
@@ -1228,13 +1563,25 @@ impl App {
 
             // === View ===
             ToggleRawSource => self.toggle_raw_source(),
+            ToggleFocusMode => self.toggle_focus_mode(),
             ToggleHelp => self.toggle_help(),
             ToggleThemePicker => self.toggle_theme_picker(),
             ApplyTheme => self.apply_selected_theme(),
 
             // === Clipboard ===
             CopyContent => self.copy_content(),
+            SelectRegister => {
+                self.awaiting_register = true;
+                self.status_message = Some("\"".to_string());
+            }
+            CopyContentHtml => self.copy_content_html(),
             CopyAnchor => self.copy_anchor(),
+            CopyFileAnchor => self.copy_file_anchor(false),
+            CopyTableCsv => {
+                if let Err(e) = self.copy_table_csv() {
+                    self.status_message = Some(format!("✗ {}", e));
+                }
+            }
 
             // === File Operations ===
             GoBack => {
@@ -1276,6 +1623,9 @@ impl App {
             OpenFilePicker => {
                 self.enter_file_picker();
             }
+            OpenContainingDir => self.open_containing_dir(),
+            Reload => self.reload_on_demand(),
+            ToggleAutoReload => self.toggle_auto_reload(),
 
             // === Dialog Actions ===
             ConfirmAction => {
@@ -1354,6 +1704,9 @@ impl App {
             // === Doc Search Navigation ===
             NextMatch => self.next_doc_match(),
             PrevMatch => self.prev_doc_match(),
+
+            // Removed from the keymap when config is loaded
+            Unbind => {}
         }
 
         ActionResult::Continue
@@ -1433,6 +1786,7 @@ impl App {
                 } else {
                     // Exit file picker entirely
                     self.mode = AppMode::Normal;
+                    self.file_picker_recent = false;
                     self.file_search_query.clear();
                     self.file_search_active = false;
                 }
@@ -1441,7 +1795,8 @@ impl App {
             | AppMode::ConfirmFileCreate
             | AppMode::ConfirmSaveWidth
             | AppMode::ConfirmSaveBeforeQuit
-            | AppMode::ConfirmSaveBeforeNav => {
+            | AppMode::ConfirmSaveBeforeNav
+            | AppMode::ConfirmOverwrite => {
                 // In normal mode, show hint for quitting
                 self.set_status_message("Press q to quit • : for commands • ? for help");
             }
@@ -1485,6 +1840,10 @@ impl App {
             }
             AppMode::ConfirmSaveWidth => self.confirm_save_outline_width(),
             AppMode::ConfirmSaveBeforeQuit => {
+                if self.check_disk_changed() {
+                    self.request_overwrite(PendingOverwrite::Edits { quit: true });
+                    return None;
+                }
                 // Save pending changes and quit
                 if let Err(e) = self.save_pending_edits_to_file() {
                     self.status_message = Some(format!("✗ Save failed: {}", e));
@@ -1494,6 +1853,10 @@ impl App {
                 }
             }
             AppMode::ConfirmSaveBeforeNav => {
+                if self.check_disk_changed() {
+                    self.request_overwrite(PendingOverwrite::Edits { quit: false });
+                    return None;
+                }
                 // Save pending changes and then navigate
                 if let Err(e) = self.save_pending_edits_to_file() {
                     self.status_message = Some(format!("✗ Save failed: {}", e));
@@ -1504,6 +1867,7 @@ impl App {
                     self.execute_pending_navigation();
                 }
             }
+            AppMode::ConfirmOverwrite => return self.confirm_overwrite(),
             AppMode::Search => self.show_search = false,
             AppMode::DocSearch => self.accept_doc_search(),
             AppMode::CommandPalette => {
@@ -1541,6 +1905,15 @@ impl App {
                 self.pending_navigation = None;
                 self.status_message = Some("Navigation cancelled".to_string());
             }
+            AppMode::ConfirmOverwrite => {
+                self.mode = match self.pending_overwrite.take() {
+                    Some(PendingOverwrite::Edits { .. }) | None => AppMode::Normal,
+                    Some(_) => AppMode::Interactive,
+                };
+                self.pending_navigation = None;
+                self.status_message =
+                    Some("Not saved: file changed on disk (R reloads it)".to_string());
+            }
             _ => self.exit_current_mode(),
         }
     }
@@ -1582,6 +1955,63 @@ impl App {
         }
     }
 
+    /// Whether the file was modified on disk since it was loaded or last
+    /// saved; also updates the `[disk-changed]` indicator.
+    pub fn check_disk_changed(&mut self) -> bool {
+        let current = modified_time(&self.current_file_path);
+        self.disk_changed = self.file_mtime.is_some() && current != self.file_mtime;
+        self.disk_changed
+    }
+
+    /// Ask before carrying out a write that would overwrite external changes
+    fn request_overwrite(&mut self, pending: PendingOverwrite) {
+        self.pending_overwrite = Some(pending);
+        self.mode = AppMode::ConfirmOverwrite;
+    }
+
+    /// Carry out the write held back by the overwrite confirmation
+    fn confirm_overwrite(&mut self) -> Option<ActionResult> {
+        let pending = self.pending_overwrite.take()?;
+        let result = match pending {
+            PendingOverwrite::Edits { quit } => {
+                self.mode = AppMode::Normal;
+                match self.save_pending_edits_to_file() {
+                    Ok(()) if quit => return Some(ActionResult::Quit),
+                    Ok(()) => {
+                        if self.pending_navigation.is_some() {
+                            self.execute_pending_navigation();
+                        }
+                        Ok(())
+                    }
+                    Err(e) => {
+                        self.pending_navigation = None;
+                        Err(e)
+                    }
+                }
+            }
+            PendingOverwrite::Checkbox {
+                block_idx,
+                item_idx,
+                checked,
+            } => {
+                self.mode = AppMode::Interactive;
+                self.toggle_checkbox_and_save(block_idx, item_idx, checked)
+            }
+            PendingOverwrite::InlineCheckbox {
+                offset,
+                context,
+                checked,
+            } => {
+                self.mode = AppMode::Interactive;
+                self.toggle_inline_checkbox_and_save(offset, &context, checked)
+            }
+        };
+        if let Err(e) = result {
+            self.status_message = Some(format!("✗ Save failed: {}", e));
+        }
+        None
+    }
+
     /// Execute the pending navigation action
     fn execute_pending_navigation(&mut self) {
         let nav = self.pending_navigation.take();
@@ -1622,9 +2052,7 @@ impl App {
             AppMode::LinkFollow if self.link_search_active => self.link_search_pop(),
             AppMode::FileSearch => self.file_search_pop(),
             AppMode::CommandPalette => self.command_palette_backspace(),
-            AppMode::CellEdit => {
-                self.cell_edit_value.pop();
-            }
+            AppMode::CellEdit => self.cell_edit_backspace(),
             _ => {}
         }
     }
@@ -1678,6 +2106,7 @@ impl App {
             self.content_scroll = new_scroll;
             self.content_scroll_state = self.content_scroll_state.position(new_scroll as usize);
         }
+        self.sync_outline_to_scroll();
     }
 
     /// Scroll content up by one line
@@ -1685,6 +2114,87 @@ impl App {
         let new_scroll = self.content_scroll.saturating_sub(1);
         self.content_scroll = new_scroll;
         self.content_scroll_state = self.content_scroll_state.position(new_scroll as usize);
+        self.sync_outline_to_scroll();
+    }
+
+    /// Move the outline selection to the heading at the top of the content view.
+    ///
+    /// Only active with `ui.sync_outline`. The displayed section stays put while
+    /// the selection follows, so scrolling reads on without jumping.
+    fn sync_outline_to_scroll(&mut self) {
+        if !self.sync_outline {
+            return;
+        }
+        let anchor = self
+            .content_anchor()
+            .unwrap_or_else(|| self.selected_heading_offset());
+
+        // Headings shown in the content pane, in the order they were rendered
+        let shown: Vec<usize> = match anchor {
+            Some(offset) => {
+                let Some(level) = self
+                    .document
+                    .headings
+                    .iter()
+                    .find(|h| h.offset == offset)
+                    .map(|h| h.level)
+                else {
+                    return;
+                };
+                self.document
+                    .headings
+                    .iter()
+                    .skip_while(|h| h.offset <= offset)
+                    .take_while(|h| h.level > level)
+                    .map(|h| h.offset)
+                    .collect()
+            }
+            None => self
+                .visible_heading_indices()
+                .into_iter()
+                .map(|i| self.document.headings[i].offset)
+                .collect(),
+        };
+        // Filtered content can render a different set of headings; don't guess
+        if shown.len() != self.content_heading_lines.len() {
+            return;
+        }
+
+        // Last heading scrolled to or past; collapsed headings fall back to
+        // the nearest visible one above them
+        let passed = shown
+            .iter()
+            .zip(&self.content_heading_lines)
+            .filter(|&(_, &line)| line <= self.content_scroll)
+            .map(|(&offset, _)| Some(offset));
+        let Some(idx) = passed
+            .rev()
+            .chain(std::iter::once(anchor))
+            .find_map(|offset| self.outline_items.iter().position(|i| i.offset == offset))
+        else {
+            return;
+        };
+        if self.outline_state.selected() == Some(idx) {
+            return;
+        }
+
+        let anchor_key = self.selection_key();
+        self.select_outline_index(idx);
+        self.synced_outline = (self.outline_items[idx].offset != anchor).then_some(SyncedOutline {
+            index: idx,
+            anchor,
+            anchor_key,
+        });
+    }
+
+    /// Offset of the section kept on screen by [`Self::sync_outline_to_scroll`].
+    ///
+    /// Returns `None` when the content simply follows the outline selection.
+    fn content_anchor(&self) -> Option<Option<usize>> {
+        self.synced_outline
+            .as_ref()
+            .filter(|synced| self.outline_state.selected() == Some(synced.index))
+            .map(|synced| synced.anchor)
     }
 
     /// Jump to link by index in filtered list
@@ -1705,6 +2215,20 @@ impl App {
         self.set_status_message(msg);
     }
 
+    /// Toggle focus mode: the whole document with every section except the
+    /// selected one collapsed to its heading line
+    pub fn toggle_focus_mode(&mut self) {
+        self.focus_mode = !self.focus_mode;
+        // The content changed; re-index and re-position as for a new selection
+        self.previous_selection = None;
+        let msg = if self.focus_mode {
+            "Focus mode enabled"
+        } else {
+            "Focus mode disabled"
+        };
+        self.set_status_message(msg);
+    }
+
     /// Set a status message with automatic timeout tracking
     pub fn set_status_message(&mut self, msg: &str) {
         self.status_message = Some(msg.to_string());
@@ -1745,6 +2269,132 @@ impl App {
         self.count_prefix.take().unwrap_or(1)
     }
 
+    /// Name the register for the next copy from the key pressed after `"`.
+    ///
+    /// Returns true if the key was consumed. `a`-`z` replace a register's
+    /// contents and `A`-`Z` append to it; any other key cancels.
+    pub fn handle_register_key(&mut self, code: KeyCode) -> bool {
+        if !std::mem::take(&mut self.awaiting_register) {
+            return false;
+        }
+        match code {
+            KeyCode::Char(c) if c.is_ascii_alphabetic() => {
+                self.pending_register = Some(c);
+                self.status_message = Some(format!("\"{}", c));
+            }
+            KeyCode::Char(c) => {
+                self.status_message = Some(format!("✗ Invalid register: {}", c));
+            }
+            _ => self.status_message = None,
+        }
+        true
+    }
+
+    /// Run `action` with `register` named: copy actions write to the
+    /// register instead of the clipboard, and other actions ignore it.
+    fn execute_into_register(&mut self, action: Action, register: char) -> ActionResult {
+        use Action::*;
+
+        if !matches!(
+            action,
+            CopyContent | CopyAnchor | CopyFileAnchor | CopyTableCsv
+        ) {
+            return self.execute_action(action);
+        }
+
+        self.yank_register = Some(register);
+        let result = self.execute_action(action);
+        let yanked = self.yank_register.take().is_none();
+        if yanked
+            && self
+                .status_message
+                .as_deref()
+                .is_some_and(|m| m.starts_with('✓'))
+        {
+            let name = register.to_ascii_lowercase();
+            let lines = self
+                .registers
+                .get(&name)
+                .map_or(0, |text| text.lines().count());
+            let verb = if register.is_ascii_uppercase() {
+                "Appended to"
+            } else {
+                "Copied to"
+            };
+            self.status_message = Some(format!(
+                "✓ {} register \"{} ({} line{})",
+                verb,
+                name,
+                lines,
+                if lines == 1 { "" } else { "s" }
+            ));
+        }
+        result
+    }
+
+    /// Store copied text in a register, appending for an uppercase name
+    fn store_register(&mut self, register: char, text: &str) {
+        let contents = self
+            .registers
+            .entry(register.to_ascii_lowercase())
+            .or_default();
+        if register.is_ascii_uppercase() && !contents.is_empty() {
+            contents.push_str("\n\n");
+        } else {
+            contents.clear();
+        }
+        contents.push_str(text);
+    }
+
+    /// `:reg` lists the registers; `:reg ab` copies registers a and b,
+    /// joined by blank lines, to the clipboard.
+    fn show_or_copy_registers(&mut self, names: Option<&str>) {
+        let Some(names) = names else {
+            let mut registers: Vec<_> = self.registers.iter().collect();
+            registers.sort();
+            self.status_message = Some(if registers.is_empty() {
+                "No registers (\"ay copies into register a)".to_string()
+            } else {
+                let listed: Vec<String> = registers
+                    .iter()
+                    .map(|(name, text)| {
+                        let first = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+                        let preview: String = first.trim().chars().take(24).collect();
+                        format!("\"{} {}", name, preview)
+                    })
+                    .collect();
+                format!("Registers: {}", listed.join(" · "))
+            });
+            return;
+        };
+
+        let mut parts = Vec::new();
+        for name in names.chars().filter(|c| !c.is_whitespace()) {
+            let name = name.to_ascii_lowercase();
+            match self.registers.get(&name) {
+                Some(text) => parts.push(text.clone()),
+                None => {
+                    self.status_message = Some(format!("✗ Register \"{} is empty", name));
+                    return;
+                }
+            }
+        }
+        let copied = parts.join("\n\n");
+        self.status_message = Some(match self.copy_to_clipboard(&copied) {
+            Ok(()) => format!(
+                "✓ Copied register{} {} to clipboard",
+                if parts.len() == 1 { "" } else { "s" },
+                names
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .map(|c| format!("\"{}", c.to_ascii_lowercase()))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Err(e) => format!("✗ {}", e),
+        });
+    }
+
     /// Clear the count prefix without consuming it
     pub fn clear_count(&mut self) {
         self.count_prefix = None;
@@ -1774,8 +2424,8 @@ impl App {
     }
 
     /// Check if a heading's section contains open todos (- [ ])
-    fn heading_has_open_todos(&self, heading_text: &str) -> bool {
-        if let Some(content) = self.document.extract_section(heading_text) {
+    fn heading_has_open_todos(&self, heading: &Heading) -> bool {
+        if let Some(content) = self.document.extract_section_at(heading.offset) {
             // Check for unchecked todo pattern: - [ ] or * [ ]
             content.contains("- [ ]") || content.contains("* [ ]")
         } else {
@@ -1786,7 +2436,7 @@ impl App {
     /// Check if a heading or any of its descendants have open todos
     fn heading_tree_has_open_todos(&self, node: &HeadingNode) -> bool {
         // Check this heading's direct content
-        if self.heading_has_open_todos(&node.heading.text) {
+        if self.heading_has_open_todos(&node.heading) {
             return true;
         }
         // Recursively check children
@@ -1821,6 +2471,9 @@ impl App {
 
     /// Rebuild outline items from the tree, optionally adding document overview
     fn rebuild_outline_items(&mut self) {
+        self.synced_outline = None;
+        self.outline_task_stats =
+            Self::collect_task_stats(&self.document, self.config.ui.outline_task_progress);
         let mut items = Self::flatten_tree(&self.tree, &self.collapsed_headings);
 
         // Apply todo filter if enabled
@@ -1851,6 +2504,7 @@ impl App {
                     text: DOCUMENT_OVERVIEW.to_string(),
                     expanded: true,
                     has_children: !self.outline_items.is_empty(),
+                    offset: None,
                 },
             );
         } else if self.filter_by_todos && preamble_has_todos {
@@ -1861,11 +2515,98 @@ impl App {
                     text: DOCUMENT_OVERVIEW.to_string(),
                     expanded: true,
                     has_children: !self.outline_items.is_empty(),
+                    offset: None,
                 },
             );
         }
     }
 
+    /// Bring the outline in line with the collapsed state of `text`.
+    ///
+    /// Splices subtrees in or out of `outline_items` in place instead of
+    /// re-flattening the whole tree, and selects the heading that was at
+    /// `selected`. Filtered outlines are rebuilt and the selection restored by
+    /// text.
+    fn apply_collapsed_change(&mut self, text: &str, selected: usize) {
+        if self.filter_by_todos || !self.search_query.is_empty() {
+            self.rebuild_outline_items();
+            if !self.select_by_text(text) {
+                // If heading not found (shouldn't happen), clamp to valid index
+                let safe_idx = selected.min(self.outline_items.len().saturating_sub(1));
+                self.outline_state.select(Some(safe_idx));
+                self.outline_scroll_state =
+                    ScrollbarState::new(self.outline_items.len()).position(safe_idx);
+            }
+            return;
+        }
+
+        self.synced_outline = None;
+        let expanded = !self.collapsed_headings.contains(text);
+        let mut selected = selected;
+        let mut i = 0;
+        while i < self.outline_items.len() {
+            // Headings sharing the text collapse together; the overview never does
+            let item = &self.outline_items[i];
+            let offset = match item.offset {
+                Some(offset) if item.text == text => offset,
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+            let was_expanded = std::mem::replace(&mut self.outline_items[i].expanded, expanded);
+            if !self.outline_items[i].has_children || was_expanded == expanded {
+                i += 1;
+                continue;
+            }
+
+            if expanded {
+                let children = Self::find_node(&self.tree, offset)
+                    .map(|node| Self::flatten_tree(&node.children, &self.collapsed_headings))
+                    .unwrap_or_default();
+                let count = children.len();
+                self.outline_items.splice(i + 1..i + 1, children);
+                if selected > i {
+                    selected += count;
+                }
+                i += count + 1;
+            } else {
+                let level = self.outline_items[i].level;
+                let end = i
+                    + 1
+                    + self.outline_items[i + 1..]
+                        .iter()
+                        .take_while(|item| item.level > level)
+                        .count();
+                self.outline_items.drain(i + 1..end);
+                if selected >= end {
+                    selected -= end - (i + 1);
+                } else if selected > i {
+                    selected = i;
+                }
+                i += 1;
+            }
+        }
+
+        let selected = selected.min(self.outline_items.len().saturating_sub(1));
+        self.outline_state.select(Some(selected));
+        self.outline_scroll_state =
+            ScrollbarState::new(self.outline_items.len()).position(selected);
+    }
+
+    /// Find the tree node for the heading at `offset`.
+    fn find_node(tree: &[HeadingNode], offset: usize) -> Option<&HeadingNode> {
+        // Siblings are in document order, so the node is in the subtree of the
+        // last sibling starting at or before `offset`
+        let idx = tree.partition_point(|node| node.heading.offset <= offset);
+        let node = tree.get(idx.checked_sub(1)?)?;
+        if node.heading.offset == offset {
+            Some(node)
+        } else {
+            Self::find_node(&node.children, offset)
+        }
+    }
+
     fn flatten_tree(
         tree: &[HeadingNode],
         collapsed_headings: &HashSet<String>,
@@ -1886,6 +2627,7 @@ impl App {
                 text: node.heading.text.clone(),
                 expanded,
                 has_children,
+                offset: Some(node.heading.offset),
             });
 
             // Only show children if this node is expanded
@@ -1922,7 +2664,11 @@ impl App {
 
     /// Update content height based on current selection and reset scroll if selection changed
     pub fn update_content_metrics(&mut self) {
-        let current_selection = self.selected_heading_text().map(|s| s.to_string());
+        // Moving the outline selection releases a synced section
+        if self.synced_outline.is_some() && self.content_anchor().is_none() {
+            self.synced_outline = None;
+        }
+        let current_selection = self.selection_key();
 
         // Check if selection changed
         if current_selection != self.previous_selection {
@@ -1930,37 +2676,37 @@ impl App {
             self.content_scroll = 0;
             self.previous_selection = current_selection.clone();
 
+            // In focus mode, start at the selected heading; every heading above
+            // it renders as a heading line plus a blank line
+            if self.focus_mode
+                && let Some(offset) = self.selected_heading_offset()
+            {
+                let above = self
+                    .document
+                    .headings
+                    .iter()
+                    .take_while(|h| h.offset < offset)
+                    .count();
+                self.content_scroll = (above * 2) as u16;
+            }
+
             // Reindex interactive elements for the new section
-            let content_text = if let Some(heading_text) = &current_selection {
-                if let Some(_heading) = self.document.find_heading(heading_text) {
-                    self.document
-                        .extract_section(heading_text)
-                        .unwrap_or_else(|| self.document.content.clone())
-                } else {
-                    self.document.content.clone()
-                }
-            } else {
-                self.document.content.clone()
-            };
+            let content_text = self.selected_section_content();
 
             use crate::parser::content::parse_content;
             let blocks = parse_content(&content_text, 0);
+            self.interactive_state.open_details(&content_text, &blocks);
             self.interactive_state.index_elements(&blocks);
+            self.interactive_state
+                .index_inline_checkboxes(&content_text, &blocks);
+            self.interactive_state
+                .index_footnotes(&content_text, &blocks);
+
+            self.emit(EventKind::SectionSelected);
         }
 
         // Update content height based on current section
-        let content_text = if let Some(heading_text) = &current_selection {
-            if let Some(_heading) = self.document.find_heading(heading_text) {
-                // Use extract_section_content to get the actual displayed content
-                self.document
-                    .extract_section(heading_text)
-                    .unwrap_or_else(|| self.document.content.clone())
-            } else {
-                self.document.content.clone()
-            }
-        } else {
-            self.document.content.clone()
-        };
+        let content_text = self.selected_section_content();
 
         let content_lines = content_text.lines().count();
         self.content_height = content_lines as u16;
@@ -1988,6 +2734,7 @@ impl App {
                 self.content_scroll = new_scroll;
                 self.content_scroll_state = self.content_scroll_state.position(new_scroll as usize);
             }
+            self.sync_outline_to_scroll();
         }
     }
 
@@ -2004,6 +2751,7 @@ impl App {
             self.content_scroll_state = self
                 .content_scroll_state
                 .position(self.content_scroll as usize);
+            self.sync_outline_to_scroll();
         }
     }
 
@@ -2013,6 +2761,7 @@ impl App {
         } else {
             self.content_scroll = 0;
             self.content_scroll_state = self.content_scroll_state.position(0);
+            self.sync_outline_to_scroll();
         }
     }
 
@@ -2027,6 +2776,7 @@ impl App {
                 .saturating_sub(self.content_viewport_height);
             self.content_scroll = max_scroll;
             self.content_scroll_state = self.content_scroll_state.position(max_scroll as usize);
+            self.sync_outline_to_scroll();
         }
     }
 
@@ -2181,9 +2931,22 @@ impl App {
             let query_lower = self.search_query.to_lowercase();
             let has_preamble = Self::has_preamble_content(&self.document);
 
+            // Levels of the matching headings above the current item, for
+            // keeping whole subtrees when matching on the heading path
+            let mut matched_levels: Vec<usize> = Vec::new();
+            let match_path = self.outline_search_path;
             self.outline_items = Self::flatten_tree(&self.tree, &self.collapsed_headings)
                 .into_iter()
-                .filter(|item| item.text.to_lowercase().contains(&query_lower))
+                .filter(|item| {
+                    while matched_levels.last().is_some_and(|&l| l >= item.level) {
+                        matched_levels.pop();
+                    }
+                    let matches = item.text.to_lowercase().contains(&query_lower);
+                    if matches {
+                        matched_levels.push(item.level);
+                    }
+                    matches || (match_path && !matched_levels.is_empty())
+                })
                 .collect();
 
             // Add overview entry if it matches the search or if document has preamble
@@ -2197,6 +2960,7 @@ impl App {
                         text: DOCUMENT_OVERVIEW.to_string(),
                         expanded: true,
                         has_children: !self.tree.is_empty(),
+                        offset: None,
                     },
                 );
             }
@@ -2246,6 +3010,26 @@ impl App {
         self.doc_search_current_idx = None;
     }
 
+    /// Toggle whether document search includes matches inside code blocks
+    pub fn toggle_doc_search_in_code(&mut self) {
+        self.doc_search_in_code = !self.doc_search_in_code;
+        self.update_doc_search_matches();
+        let state = if self.doc_search_in_code { "on" } else { "off" };
+        self.status_message = Some(format!("Search in code blocks: {}", state));
+    }
+
+    /// Toggle whether outline search keeps headings under a matching heading
+    pub fn toggle_outline_search_path(&mut self) {
+        self.outline_search_path = !self.outline_search_path;
+        self.filter_outline();
+        let state = if self.outline_search_path {
+            "on"
+        } else {
+            "off"
+        };
+        self.status_message = Some(format!("Match heading paths: {}", state));
+    }
+
     /// Add a character to the document search query
     pub fn doc_search_input(&mut self, c: char) {
         // Limit search query length
@@ -2269,6 +3053,7 @@ impl App {
     /// Update search matches based on current query (supports fuzzy and exact matching)
     pub fn update_doc_search_matches(&mut self) {
         self.doc_search_matches.clear();
+        self.doc_search_truncated = false;
 
         if self.doc_search_query.is_empty() {
             self.doc_search_current_idx = None;
@@ -2276,25 +3061,32 @@ impl App {
         }
 
         // Get current section content
-        let content = if let Some(heading_text) = self.selected_heading_text() {
-            self.document
-                .extract_section(heading_text)
-                .unwrap_or_else(|| self.document.content.clone())
-        } else {
-            self.document.content.clone()
-        };
+        let content = self.selected_section_content();
 
-        // Convert to plain text using parser (strips links, formatting, etc.)
-        // This ensures search matches what's visible when rendered
-        let plain_content = turbovault_parser::to_plain_text(&content);
         let query = self.doc_search_query.to_lowercase();
 
-        // Find all exact substring matches (case-insensitive)
-        for (line_num, line) in plain_content.lines().enumerate() {
-            let line_lower = line.to_lowercase();
+        // Line ranges of fenced code blocks to skip when code search is off
+        let code_lines = if self.doc_search_in_code {
+            Vec::new()
+        } else {
+            crate::parser::utils::code_block_lines(&content)
+        };
+
+        // Search the visible text of each source line (strips links, formatting, etc.)
+        // so match line numbers stay aligned with the section's lines for scrolling
+        let max_matches = self.config.ui.max_search_matches.max(1);
+        'lines: for (line_num, line) in content.lines().enumerate() {
+            if code_lines.iter().any(|range| range.contains(&line_num)) {
+                continue;
+            }
+            let line_lower = turbovault_parser::to_plain_text(line).to_lowercase();
 
             let mut search_start = 0;
             while let Some(pos) = line_lower[search_start..].find(&query) {
+                if self.doc_search_matches.len() == max_matches {
+                    self.doc_search_truncated = true;
+                    break 'lines;
+                }
                 let col_start = search_start + pos;
                 self.doc_search_matches.push(SearchMatch {
                     line: line_num,
@@ -2348,47 +3140,37 @@ impl App {
         match_col: usize,
         match_len: usize,
     ) {
-        use crate::parser::links::extract_links;
-
         // Get current section content
-        let content = if let Some(heading_text) = self.selected_heading_text() {
-            self.document
-                .extract_section(heading_text)
-                .unwrap_or_else(|| self.document.content.clone())
-        } else {
-            self.document.content.clone()
-        };
-
-        // Convert line/col to byte offset
-        let mut byte_offset = 0;
-        for (line_num, line) in content.lines().enumerate() {
-            if line_num == match_line {
-                byte_offset += match_col;
-                break;
-            }
-            byte_offset += line.len() + 1; // +1 for newline
-        }
-
-        let match_end = byte_offset + match_len;
+        let content = self.selected_section_content();
+
+        // Locate the source line the match is on
+        let line_start: usize = content
+            .lines()
+            .take(match_line)
+            .map(|line| line.len() + 1) // +1 for newline
+            .sum();
+        let line = content
+            .get(line_start..)
+            .and_then(|rest| rest.lines().next())
+            .unwrap_or("");
+        let line_end = line_start + line.len();
+        let match_range = match_col..match_col + match_len;
 
         // Extract links and populate links_in_view for potential following
-        self.links_in_view = extract_links(&content);
+        self.links_in_view = extract_links_and_images(&content);
         self.filtered_link_indices = (0..self.links_in_view.len()).collect();
 
-        // Find if match overlaps with any link
-        for (idx, link) in self.links_in_view.iter().enumerate() {
-            let link_start = link.offset;
-            // Estimate link end based on its display text length + some syntax overhead
-            // For markdown: [text](url) - we care about the text portion
-            // For wikilinks: [[target|text]] - we care about the display text
-            let link_end = link_start + link.text.len() + 20; // generous estimate for syntax
-
-            // Check if match overlaps with link region
-            if byte_offset < link_end && match_end > link_start {
-                self.doc_search_selected_link_idx = Some(idx);
-                self.selected_link_idx = Some(idx); // Also set link mode selection
-                break;
+        // Find the link on this line whose rendered text overlaps the match
+        let hit = self.links_in_view.iter().position(|link| {
+            if link.offset < line_start || link.offset >= line_end {
+                return false;
             }
+            let span = link.plain_text_span(line, line_start);
+            match_range.start < span.end && match_range.end > span.start
+        });
+        if let Some(idx) = hit {
+            self.doc_search_selected_link_idx = Some(idx);
+            self.selected_link_idx = Some(idx); // Also set link mode selection
         }
     }
 
@@ -2453,10 +3235,17 @@ impl App {
             return;
         }
 
-        self.doc_search_current_idx = Some(match self.doc_search_current_idx {
-            Some(idx) => (idx + 1) % self.doc_search_matches.len(),
+        let len = self.doc_search_matches.len();
+        let next = match self.doc_search_current_idx {
+            Some(idx) => {
+                if idx + 1 >= len {
+                    self.set_status_message("Search wrapped to top");
+                }
+                (idx + 1) % len
+            }
             None => 0,
-        });
+        };
+        self.doc_search_current_idx = Some(next);
 
         self.scroll_to_doc_search_match();
     }
@@ -2479,10 +3268,16 @@ impl App {
         }
 
         let len = self.doc_search_matches.len();
-        self.doc_search_current_idx = Some(match self.doc_search_current_idx {
-            Some(idx) => (idx + len - 1) % len,
+        let prev = match self.doc_search_current_idx {
+            Some(idx) => {
+                if idx == 0 {
+                    self.set_status_message("Search wrapped to bottom");
+                }
+                (idx + len - 1) % len
+            }
             None => len - 1,
-        });
+        };
+        self.doc_search_current_idx = Some(prev);
 
         self.scroll_to_doc_search_match();
     }
@@ -2498,7 +3293,10 @@ impl App {
         } else {
             let current = self.doc_search_current_idx.unwrap_or(0) + 1;
             let total = self.doc_search_matches.len();
-            let base = format!("Search: {} ({}/{})", self.doc_search_query, current, total);
+            let mut base = format!("Search: {} ({}/{})", self.doc_search_query, current, total);
+            if self.doc_search_truncated {
+                base.push_str(&format!(" (first {} matches)", total));
+            }
 
             // Add link indicator if match is inside a link
             if let Some(link_idx) = self.doc_search_selected_link_idx {
@@ -2524,6 +3322,7 @@ impl App {
             self.content_scroll_state = self
                 .content_scroll_state
                 .position(self.content_scroll as usize);
+            self.sync_outline_to_scroll();
         }
     }
 
@@ -2533,6 +3332,7 @@ impl App {
             self.content_scroll_state = self
                 .content_scroll_state
                 .position(self.content_scroll as usize);
+            self.sync_outline_to_scroll();
         }
     }
 
@@ -2603,17 +3403,7 @@ impl App {
                         self.collapsed_headings.insert(heading_text.clone());
                     }
 
-                    // Rebuild the flattened list with overview entry
-                    self.rebuild_outline_items();
-
-                    // Restore selection by text (not by index)
-                    if !self.select_by_text(&heading_text) {
-                        // If heading not found (shouldn't happen), clamp to valid index
-                        let safe_idx = i.min(self.outline_items.len().saturating_sub(1));
-                        self.outline_state.select(Some(safe_idx));
-                        self.outline_scroll_state =
-                            ScrollbarState::new(self.outline_items.len()).position(safe_idx);
-                    }
+                    self.apply_collapsed_change(&heading_text, i);
                 }
             }
         }
@@ -2628,17 +3418,7 @@ impl App {
                     // Remove from collapsed set to expand
                     self.collapsed_headings.remove(&heading_text);
 
-                    // Rebuild the flattened list with overview entry
-                    self.rebuild_outline_items();
-
-                    // Restore selection by text (not by index)
-                    if !self.select_by_text(&heading_text) {
-                        // If heading not found (shouldn't happen), clamp to valid index
-                        let safe_idx = i.min(self.outline_items.len().saturating_sub(1));
-                        self.outline_state.select(Some(safe_idx));
-                        self.outline_scroll_state =
-                            ScrollbarState::new(self.outline_items.len()).position(safe_idx);
-                    }
+                    self.apply_collapsed_change(&heading_text, i);
                 }
             }
         }
@@ -2654,45 +3434,19 @@ impl App {
                     // If current heading has children, collapse it
                     if self.outline_items[i].has_children {
                         self.collapsed_headings.insert(current_text.clone());
-
-                        // Rebuild the flattened list with overview entry
-                        self.rebuild_outline_items();
-
-                        // Restore selection by text
-                        if !self.select_by_text(&current_text) {
-                            let safe_idx = i.min(self.outline_items.len().saturating_sub(1));
-                            self.outline_state.select(Some(safe_idx));
-                            self.outline_scroll_state =
-                                ScrollbarState::new(self.outline_items.len()).position(safe_idx);
-                        }
+                        self.apply_collapsed_change(&current_text, i);
                     } else {
                         // If no children, find parent and collapse it
                         // Look backwards for first heading with lower level
-                        let mut parent_text: Option<String> = None;
-                        for idx in (0..i).rev() {
-                            if self.outline_items[idx].level < current_level {
-                                // Found parent
-                                parent_text = Some(self.outline_items[idx].text.clone());
-                                break;
-                            }
-                        }
-
-                        if let Some(parent) = parent_text {
-                            // Collapse the parent
-                            self.collapsed_headings.insert(parent.clone());
-
-                            // Rebuild and move selection to parent
-                            self.rebuild_outline_items();
-
-                            // Select the parent by text
-                            if !self.select_by_text(&parent) {
-                                // Fallback: select first item if parent not found
-                                if !self.outline_items.is_empty() {
-                                    self.outline_state.select(Some(0));
-                                    self.outline_scroll_state =
-                                        ScrollbarState::new(self.outline_items.len()).position(0);
-                                }
-                            }
+                        let parent = (0..i)
+                            .rev()
+                            .find(|&idx| self.outline_items[idx].level < current_level);
+
+                        if let Some(parent_idx) = parent {
+                            // Collapse the parent and move selection to it
+                            let parent_text = self.outline_items[parent_idx].text.clone();
+                            self.collapsed_headings.insert(parent_text.clone());
+                            self.apply_collapsed_change(&parent_text, parent_idx);
                         }
                         // No parent found, do nothing
                     }
@@ -2731,9 +3485,18 @@ impl App {
         }
 
         let count = self.collapsed_headings.len();
+        self.refresh_overview();
         self.set_status_message(&format!("Collapsed {} headings", count));
     }
 
+    /// Re-layout the overview after the fold state changed, since it renders
+    /// collapsed sections as heading lines
+    fn refresh_overview(&mut self) {
+        if !self.focus_mode && self.selected_heading_offset().is_none() {
+            self.previous_selection = None;
+        }
+    }
+
     /// Recursively collect all heading texts that have children
     fn collect_collapsible_headings(node: &HeadingNode) -> Vec<String> {
         let mut result = Vec::new();
@@ -2759,6 +3522,7 @@ impl App {
             self.select_by_text(&text);
         }
 
+        self.refresh_overview();
         self.set_status_message(&format!("Expanded {} headings", count));
     }
 
@@ -2791,6 +3555,7 @@ impl App {
             }
         }
 
+        self.refresh_overview();
         self.set_status_message(&format!("Collapsed {} h{} headings", count, level));
     }
 
@@ -2841,6 +3606,7 @@ impl App {
             self.select_by_text(&text);
         }
 
+        self.refresh_overview();
         self.set_status_message(&format!("Expanded {} h{} headings", count, level));
     }
 
@@ -3031,7 +3797,7 @@ impl App {
 
     /// Add a character to command palette search
     pub fn command_palette_input(&mut self, c: char) {
-        if self.command_query.len() < 32 {
+        if self.command_query.len() < 64 {
             self.command_query.push(c);
             self.filter_commands();
         }
@@ -3064,6 +3830,11 @@ impl App {
         }
     }
 
+    /// Heading number typed into the command palette, as in `:42`
+    pub fn palette_heading_number(&self) -> Option<usize> {
+        self.command_query.trim().parse().ok()
+    }
+
     /// Move selection down in command palette
     pub fn command_palette_next(&mut self) {
         if !self.command_filtered.is_empty() {
@@ -3103,6 +3874,14 @@ impl App {
 
     /// Execute selected command and return whether to quit
     pub fn execute_selected_command(&mut self) -> bool {
+        // `:42` goes to the 42nd heading
+        if let Some(number) = self.palette_heading_number() {
+            self.mode = AppMode::Normal;
+            self.command_query.clear();
+            self.jump_to_heading_number(number);
+            return false;
+        }
+
         let should_quit = if let Some(&cmd_idx) = self.command_filtered.get(self.command_selected) {
             let action = PALETTE_COMMANDS[cmd_idx].action;
             let query = self.command_query.clone(); // Capture query for argument parsing
@@ -3148,6 +3927,38 @@ impl App {
                 self.toggle_raw_source();
                 false
             }
+            CommandAction::ToggleFocusMode => {
+                self.toggle_focus_mode();
+                false
+            }
+            CommandAction::CopyContentHtml => {
+                self.copy_content_html();
+                false
+            }
+            CommandAction::CopyHeadingLink => {
+                self.copy_file_anchor(true);
+                false
+            }
+            CommandAction::OpenContainingDir => {
+                self.open_containing_dir();
+                false
+            }
+            CommandAction::Reload => {
+                self.reload_on_demand();
+                false
+            }
+            CommandAction::ToggleAutoReload => {
+                self.toggle_auto_reload();
+                false
+            }
+            CommandAction::ExportVisible => {
+                self.export_visible(command_argument(query));
+                false
+            }
+            CommandAction::Registers => {
+                self.show_or_copy_registers(command_argument(query));
+                false
+            }
             CommandAction::JumpToTop => {
                 self.first();
                 false
@@ -3183,7 +3994,9 @@ impl App {
                 false
             }
             CommandAction::SaveFile => {
-                if let Err(e) = self.save_pending_edits_to_file() {
+                if self.has_unsaved_changes && self.check_disk_changed() {
+                    self.request_overwrite(PendingOverwrite::Edits { quit: false });
+                } else if let Err(e) = self.save_pending_edits_to_file() {
                     self.set_status_message(&format!("✗ Save failed: {}", e));
                 }
                 false
@@ -3229,6 +4042,13 @@ impl App {
         }
     }
 
+    /// Select the `number`th outline heading (1-based), clamped to the outline
+    pub fn jump_to_heading_number(&mut self, number: usize) {
+        if !self.outline_items.is_empty() {
+            self.select_outline_index(number.clamp(1, self.outline_items.len()) - 1);
+        }
+    }
+
     pub fn set_bookmark(&mut self) {
         // Store bookmark as heading text instead of index
         self.bookmark_position = self.selected_heading_text().map(|s| s.to_string());
@@ -3248,6 +4068,202 @@ impl App {
             .map(|item| item.text.as_str())
     }
 
+    /// Get the byte offset of the currently selected heading.
+    ///
+    /// Returns None if no heading is selected or if the selection is the document overview.
+    pub fn selected_heading_offset(&self) -> Option<usize> {
+        self.outline_state
+            .selected()
+            .and_then(|i| self.outline_items.get(i))
+            .and_then(|item| item.offset)
+    }
+
+    /// Task completion of the selected section, shown next to its heading
+    pub fn selected_task_stats(&self) -> Option<TaskStats> {
+        let offset = self.selected_heading_offset()?;
+        let heading = self.document.headings.iter().find(|h| h.offset == offset)?;
+        self.document.task_stats_for_section(heading)
+    }
+
+    /// Task completion shown next to an outline item (with `ui.outline_task_progress`)
+    pub fn outline_task_stats(&self, item: &OutlineItem) -> Option<TaskStats> {
+        item.offset
+            .and_then(|offset| self.outline_task_stats.get(&offset).copied())
+    }
+
+    /// Task completion of every section with tasks, keyed by heading offset
+    fn collect_task_stats(document: &Document, enabled: bool) -> HashMap<usize, TaskStats> {
+        if !enabled {
+            return HashMap::new();
+        }
+        document
+            .headings
+            .iter()
+            .filter_map(|heading| {
+                let stats = document.task_stats_for_section(heading)?;
+                Some((heading.offset, stats))
+            })
+            .collect()
+    }
+
+    /// Get the content of the currently selected section.
+    ///
+    /// Resolves the section by heading offset so repeated heading text selects the
+    /// right section. Falls back to the whole document for the overview entry,
+    /// with sections collapsed in the outline reduced to their heading line.
+    /// While the outline follows the scroll position, this stays the section
+    /// being scrolled.
+    pub fn selected_section_content(&self) -> Rc<str> {
+        if self.focus_mode {
+            return self.focus_mode_content().into();
+        }
+        self.content_anchor()
+            .unwrap_or_else(|| self.selected_heading_offset())
+            .and_then(|offset| self.section_at(offset))
+            .unwrap_or_else(|| {
+                if self.collapsed_headings.is_empty() {
+                    self.document.content.as_str().into()
+                } else {
+                    self.visible_markdown().into()
+                }
+            })
+    }
+
+    /// Content of the section whose heading starts at `offset`, extracted
+    /// once per document version.
+    fn section_at(&self, offset: usize) -> Option<Rc<str>> {
+        if let Some(section) = self.section_cache.borrow().get(&offset) {
+            return Some(Rc::clone(section));
+        }
+        let section: Rc<str> = self
+            .config
+            .behavior
+            .section_at(&self.document, offset)?
+            .into();
+        self.section_cache
+            .borrow_mut()
+            .insert(offset, Rc::clone(&section));
+        Some(section)
+    }
+
+    /// Indices into the document's headings that the outline shows, i.e.
+    /// every heading not nested under a collapsed one.
+    fn visible_heading_indices(&self) -> Vec<usize> {
+        // Level of the collapsed heading whose descendants are being skipped
+        let mut hidden_below: Option<usize> = None;
+        let mut visible = Vec::new();
+        for (i, heading) in self.document.headings.iter().enumerate() {
+            if hidden_below.is_some_and(|level| heading.level > level) {
+                continue;
+            }
+            hidden_below = self
+                .collapsed_headings
+                .contains(&heading.text)
+                .then_some(heading.level);
+            visible.push(i);
+        }
+        visible
+    }
+
+    /// Content shown in focus mode.
+    ///
+    /// Every heading is listed, but only the selected section keeps its body;
+    /// the preamble belongs to the overview entry.
+    fn focus_mode_content(&self) -> String {
+        let content = &self.document.content;
+        let headings = &self.document.headings;
+        let first_heading = headings.first().map_or(content.len(), |h| h.offset);
+
+        // Byte range of the expanded section
+        let selected = self
+            .selected_heading_offset()
+            .and_then(|offset| headings.iter().find(|h| h.offset == offset));
+        let expanded = match selected {
+            Some(heading) => {
+                let end = headings
+                    .iter()
+                    .find(|h| h.offset > heading.offset && h.level <= heading.level)
+                    .map_or(content.len(), |h| h.offset);
+                heading.offset..end
+            }
+            None => 0..first_heading,
+        };
+
+        let mut out = String::new();
+        if selected.is_none() {
+            out.push_str(content[..first_heading].trim());
+            out.push_str("\n\n");
+        }
+        for (i, heading) in headings.iter().enumerate() {
+            if expanded.contains(&heading.offset) {
+                let next = headings.get(i + 1).map_or(content.len(), |h| h.offset);
+                out.push_str(content[heading.offset..next].trim_end());
+            } else {
+                // Rebuilt as an ATX heading so setext headings stay headings
+                out.push_str(&format!("{} {}", "#".repeat(heading.level), heading.text));
+            }
+            out.push_str("\n\n");
+        }
+        out.trim_end().to_string()
+    }
+
+    /// Markdown for what the outline shows: expanded sections in full,
+    /// collapsed headings as just the heading line, and their descendants
+    /// left out.
+    pub fn visible_markdown(&self) -> String {
+        let content = &self.document.content;
+        let headings = &self.document.headings;
+        let first_heading = headings.first().map_or(content.len(), |h| h.offset);
+
+        let mut out = String::new();
+        let preamble = content[..first_heading].trim();
+        if !preamble.is_empty() {
+            out.push_str(preamble);
+            out.push_str("\n\n");
+        }
+        for i in self.visible_heading_indices() {
+            let heading = &headings[i];
+            if self.collapsed_headings.contains(&heading.text) {
+                out.push_str(&format!("{} {}", "#".repeat(heading.level), heading.text));
+            } else {
+                let next = headings.get(i + 1).map_or(content.len(), |h| h.offset);
+                out.push_str(content[heading.offset..next].trim_end());
+            }
+            out.push_str("\n\n");
+        }
+        let mut out = out.trim_end().to_string();
+        out.push('\n');
+        out
+    }
+
+    /// Export [`visible_markdown`](Self::visible_markdown) to the clipboard,
+    /// or to `path` (relative to the current file) when given
+    pub fn export_visible(&mut self, path: Option<&str>) {
+        let markdown = self.visible_markdown();
+        let Some(path) = path else {
+            self.status_message = Some(match self.copy_to_clipboard(&markdown) {
+                Ok(()) => "✓ Visible sections copied to clipboard".to_string(),
+                Err(e) => format!("✗ {}", e),
+            });
+            return;
+        };
+
+        let base = self
+            .current_file_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let target = base.join(path);
+        self.status_message = Some(if target.exists() {
+            format!("✗ {} already exists", target.display())
+        } else {
+            match std::fs::write(&target, markdown) {
+                Ok(()) => format!("✓ Visible sections written to {}", target.display()),
+                Err(e) => format!("✗ Failed to write {}: {}", target.display(), e),
+            }
+        });
+    }
+
     /// Get the source line number (1-indexed) for the currently selected heading.
     ///
     /// Returns None if no heading is selected or if the selection is the document overview.
@@ -3259,23 +4275,28 @@ impl App {
             return Some(1); // Return line 1 for document overview
         }
 
-        // Find the heading in the document by text
-        let heading = self
-            .document
-            .headings
-            .iter()
-            .find(|h| h.text == selected_text)?;
+        Some(source_line(
+            &self.document.content,
+            self.selected_heading_offset()?,
+        ))
+    }
 
-        // Convert byte offset to line number (1-indexed)
-        let offset = heading.offset.min(self.document.content.len());
-        let before = &self.document.content[..offset];
-        let line = before.chars().filter(|&c| c == '\n').count() + 1;
-        Some(line as u32)
+    /// Identify the current selection by heading text and offset.
+    ///
+    /// The offset distinguishes headings that share the same text.
+    fn selection_key(&self) -> Option<(String, Option<usize>)> {
+        if let Some(synced) = &self.synced_outline
+            && self.outline_state.selected() == Some(synced.index)
+        {
+            return synced.anchor_key.clone();
+        }
+        self.selected_heading_text()
+            .map(|text| (text.to_string(), self.selected_heading_offset()))
     }
 
     /// Sync previous_selection to current selection (prevents spurious scroll resets)
     pub fn sync_previous_selection(&mut self) {
-        self.previous_selection = self.selected_heading_text().map(|s| s.to_string());
+        self.previous_selection = self.selection_key();
     }
 
     pub fn toggle_theme_picker(&mut self) {
@@ -3297,6 +4318,8 @@ impl App {
                 ThemeName::Gruvbox => 5,
                 ThemeName::TokyoNight => 6,
                 ThemeName::CatppuccinMocha => 7,
+                ThemeName::HighContrast => 8,
+                ThemeName::Deuteranopia => 9,
             };
             self.show_theme_picker = true;
         }
@@ -3313,6 +4336,8 @@ impl App {
             5 => ThemeName::Gruvbox,
             6 => ThemeName::TokyoNight,
             7 => ThemeName::CatppuccinMocha,
+            8 => ThemeName::HighContrast,
+            9 => ThemeName::Deuteranopia,
             _ => ThemeName::OceanDark,
         }
     }
@@ -3326,7 +4351,7 @@ impl App {
     }
 
     pub fn theme_picker_next(&mut self) {
-        if self.theme_picker_selected < 7 {
+        if self.theme_picker_selected < 9 {
             self.theme_picker_selected += 1;
             // Apply theme preview immediately
             let theme_name = Self::theme_name_from_index(self.theme_picker_selected);
@@ -3348,8 +4373,10 @@ impl App {
         self.theme_picker_original = None; // Clear so toggle doesn't restore
         self.show_theme_picker = false;
 
-        // Save to config (silently ignore errors)
-        let _ = self.config.set_theme(self.current_theme);
+        // The theme stays applied for this session even if saving fails
+        if let Err(e) = self.config.set_theme(self.current_theme) {
+            self.set_status_message(&format!("✗ Theme not saved to config: {}", e));
+        }
     }
 
     /// Get the editor configuration for external file editing
@@ -3358,22 +4385,23 @@ impl App {
     }
 
     pub fn copy_content(&mut self) {
+        // A selected link or image copies its target instead of the section
+        if let Some((target, image)) = self.selected_link_target() {
+            let label = if image { "Image source" } else { "URL" };
+            self.status_message = Some(match self.copy_to_clipboard(&target) {
+                Ok(()) => format!("✓ {} copied: {}", label, target),
+                Err(e) => format!("✗ {}", e),
+            });
+            return;
+        }
+
         // Copy the currently selected section's content
-        if let Some(heading_text) = self.selected_heading_text() {
-            if let Some(section) = self.document.extract_section(heading_text) {
-                // Use persistent clipboard for Linux X11 compatibility
-                if let Some(clipboard) = &mut self.clipboard {
-                    match clipboard.set_text(section) {
-                        Ok(_) => {
-                            self.status_message = Some("✓ Section copied to clipboard".to_string());
-                        }
-                        Err(e) => {
-                            self.status_message = Some(format!("✗ Clipboard error: {}", e));
-                        }
-                    }
-                } else {
-                    self.status_message = Some("✗ Clipboard not available".to_string());
-                }
+        if let Some(offset) = self.selected_heading_offset() {
+            if let Some(section) = self.document.extract_section_at(offset) {
+                self.status_message = Some(match self.copy_to_clipboard(&section) {
+                    Ok(()) => "✓ Section copied to clipboard".to_string(),
+                    Err(e) => format!("✗ {}", e),
+                });
             } else {
                 self.status_message = Some("✗ Could not extract section".to_string());
             }
@@ -3382,49 +4410,102 @@ impl App {
         }
     }
 
+    /// Copy the selected section as HTML, including its heading.
+    ///
+    /// The markdown is stored alongside as plain text; if the clipboard can't
+    /// hold HTML, only the markdown is copied.
+    pub fn copy_content_html(&mut self) {
+        let Some(heading) = self
+            .selected_heading_offset()
+            .and_then(|offset| self.document.headings.iter().find(|h| h.offset == offset))
+        else {
+            self.status_message = Some("✗ No heading selected".to_string());
+            return;
+        };
+        let Some(section) = self.document.extract_section_at(heading.offset) else {
+            self.status_message = Some("✗ Could not extract section".to_string());
+            return;
+        };
+
+        let markdown = format!(
+            "{} {}\n\n{}",
+            "#".repeat(heading.level),
+            heading.text,
+            section
+        );
+        let html = crate::render::markdown_to_html(&markdown);
+
+        let Some(clipboard) = &mut self.clipboard else {
+            self.status_message = Some(format!("✗ {}", clipboard::UNAVAILABLE));
+            return;
+        };
+        self.status_message = Some(match clipboard.set_html(&html, &markdown) {
+            Ok(()) => "✓ Section copied as HTML".to_string(),
+            Err(_) => match clipboard.set_text(&markdown) {
+                Ok(()) => "✓ Section copied as markdown (HTML clipboard not supported)".to_string(),
+                Err(e) => format!("✗ Clipboard error: {}", e),
+            },
+        });
+    }
+
     pub fn copy_anchor(&mut self) {
         // Copy the anchor link for the currently selected heading
         if let Some(heading_text) = self.selected_heading_text() {
-            // Convert heading to anchor format (lowercase, replace spaces with dashes)
+            // Convert heading to a GitHub-style anchor
             let anchor = Self::heading_to_anchor(heading_text);
             let anchor_link = format!("#{}", anchor);
 
-            // Use persistent clipboard for Linux X11 compatibility
-            if let Some(clipboard) = &mut self.clipboard {
-                match clipboard.set_text(anchor_link) {
-                    Ok(_) => {
-                        self.status_message = Some(format!("✓ Anchor link copied: #{}", anchor));
-                    }
-                    Err(e) => {
-                        self.status_message = Some(format!("✗ Clipboard error: {}", e));
-                    }
-                }
-            } else {
-                self.status_message = Some("✗ Clipboard not available".to_string());
-            }
+            self.status_message = Some(match self.copy_to_clipboard(&anchor_link) {
+                Ok(()) => format!("✓ Anchor link copied: #{}", anchor),
+                Err(e) => format!("✗ {}", e),
+            });
         } else {
             self.status_message = Some("✗ No heading selected".to_string());
         }
     }
 
-    /// Convert heading text to anchor format using the parser's slugify for consistency
+    /// Copy the selected heading as `path/to/file.md#anchor`, with the path
+    /// relative to the project root (see [`project_relative_path`]). With
+    /// `as_link`, copy a markdown link `[Heading](path#anchor)` instead.
+    pub fn copy_file_anchor(&mut self, as_link: bool) {
+        let Some(heading_text) = self.selected_heading_text().map(str::to_string) else {
+            self.status_message = Some("✗ No heading selected".to_string());
+            return;
+        };
+        if !self.current_file_path.is_file() {
+            self.status_message = Some("✗ No file to reference (reading from stdin)".to_string());
+            return;
+        }
+
+        let cwd = std::env::current_dir().ok();
+        let path = project_relative_path(&self.current_file_path, cwd.as_deref());
+        let reference = format!("{}#{}", path, Self::heading_to_anchor(&heading_text));
+        let copied = if !as_link {
+            reference
+        } else if reference.contains(' ') {
+            format!("[{}](<{}>)", heading_text, reference)
+        } else {
+            format!("[{}]({})", heading_text, reference)
+        };
+
+        self.status_message = Some(match self.copy_to_clipboard(&copied) {
+            Ok(()) => format!("✓ Copied: {}", copied),
+            Err(e) => format!("✗ {}", e),
+        });
+    }
+
+    /// Convert heading text to a GitHub-compatible anchor
     fn heading_to_anchor(heading: &str) -> String {
-        crate::parser::content::slugify(heading)
+        crate::parser::utils::slugify(heading)
     }
 
     /// Enter link follow mode - extract links from current section and highlight them
     pub fn enter_link_follow_mode(&mut self) {
         // Extract content for current section
-        let content = if let Some(heading_text) = self.selected_heading_text() {
-            self.document
-                .extract_section(heading_text)
-                .unwrap_or_else(|| self.document.content.clone())
-        } else {
-            self.document.content.clone()
-        };
+        let content = self.selected_section_content();
 
         // Extract all links from the content
-        self.links_in_view = extract_links(&content);
+        self.links_in_view = extract_links_and_images(&content);
 
         // Initialize filtered indices to show all links
         self.filtered_link_indices = (0..self.links_in_view.len()).collect();
@@ -3564,14 +4645,8 @@ impl App {
                             self.select_outline_index(i);
 
                             // Now extract links from parent's content
-                            let content = if let Some(heading_text) = self.selected_heading_text() {
-                                self.document
-                                    .extract_section(heading_text)
-                                    .unwrap_or_else(|| self.document.content.clone())
-                            } else {
-                                self.document.content.clone()
-                            };
-                            self.links_in_view = extract_links(&content);
+                            let content = self.selected_section_content();
+                            self.links_in_view = extract_links_and_images(&content);
 
                             // Reset link selection
                             if !self.links_in_view.is_empty() {
@@ -3599,9 +4674,18 @@ impl App {
     // ===== File Picker Methods =====
 
     /// Scan current directory for .md files (non-recursive, alphabetically sorted)
+    ///
+    /// In recent-files mode the picker lists recently opened files instead,
+    /// most recent first.
     pub fn scan_markdown_files(&mut self) {
         use std::fs;
 
+        if self.file_picker_recent {
+            self.files_in_directory = self.config.recent_files();
+            self.update_file_filter();
+            return;
+        }
+
         // Use custom directory if set, otherwise use current working directory
         let dir = self
             .file_picker_dir
@@ -3705,6 +4789,7 @@ impl App {
         // Don't reload if it's already the current file
         if file_path == self.current_file_path {
             self.mode = AppMode::Normal;
+            self.file_picker_recent = false;
             self.file_search_query.clear();
             self.file_search_active = false;
             return Ok(());
@@ -3719,12 +4804,17 @@ impl App {
             content_scroll: self.content_scroll,
             outline_state_selected: self.outline_state.selected(),
         };
-        self.file_history.push(current_state);
+        push_bounded(
+            &mut self.file_history,
+            current_state,
+            self.config.ui.history_depth,
+        );
         self.file_future.clear(); // Clear forward history when navigating to new file
 
         // Load new file
         let content = std::fs::read_to_string(&file_path)
             .map_err(|e| format!("Failed to read file: {}", e))?;
+        let content = crate::input::markdown_for_file(&file_path, content);
         let document = crate::parser::parse_markdown(&content);
         let filename = file_path
             .file_name()
@@ -3734,8 +4824,9 @@ impl App {
 
         self.load_document(document, filename, file_path);
 
-        // Exit picker mode
+        // Exit picker mode (later pickers list the directory again)
         self.mode = AppMode::Normal;
+        self.file_picker_recent = false;
         self.file_search_query.clear();
         self.file_search_active = false;
 
@@ -3781,6 +4872,24 @@ impl App {
             .and_then(|&real_idx| self.links_in_view.get(real_idx))
     }
 
+    /// Target of the link or image selected in link follow or interactive
+    /// mode, and whether it is an image.
+    fn selected_link_target(&self) -> Option<(String, bool)> {
+        use crate::tui::interactive::ElementType;
+
+        match self.mode {
+            AppMode::LinkFollow => self
+                .get_selected_link()
+                .map(|link| (link.target.as_str(), link.image)),
+            AppMode::Interactive => match &self.interactive_state.current_element()?.element_type {
+                ElementType::Link { link, .. } => Some((link.target.as_str(), link.image)),
+                ElementType::Image { src, .. } => Some((src.clone(), true)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     /// Check if frontmatter should be hidden (from config)
     pub fn should_hide_frontmatter(&self) -> bool {
         self.config.content.hide_frontmatter
@@ -3791,11 +4900,64 @@ impl App {
         self.config.content.hide_latex
     }
 
+    /// Columns between tab stops in code and raw source (from config)
+    pub fn tab_width(&self) -> usize {
+        self.config.ui.tab_width
+    }
+
+    /// Whether long table cells wrap instead of being cut off (from config)
+    pub fn table_wrap(&self) -> bool {
+        self.config.ui.table_wrap
+    }
+
+    /// Color mode the theme was built for
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Blank lines between rendered blocks, 0 or 1 (from config)
+    pub fn block_spacing(&self) -> usize {
+        self.config.ui.block_spacing
+    }
+
     /// Check if aggressive LaTeX filtering is enabled (from config)
     pub fn should_latex_aggressive(&self) -> bool {
         self.config.content.latex_aggressive
     }
 
+    /// Replace how followed links are resolved.
+    ///
+    /// Defaults to [`DefaultLinkResolver`]. Custom resolvers can map wikilinks
+    /// to a vault index, rewrite URLs, or reject links.
+    pub fn set_link_resolver(&mut self, resolver: impl LinkResolver + 'static) {
+        self.link_resolver = Box::new(resolver);
+    }
+
+    /// Replace where copies go.
+    ///
+    /// Defaults to the backend named by `ui.clipboard`. Every copy action,
+    /// including HTML section copies, goes through this backend.
+    pub fn set_clipboard_backend(&mut self, backend: impl ClipboardBackend + 'static) {
+        self.clipboard = Some(Box::new(backend));
+    }
+
+    /// Write navigation events to `out` as JSON lines (`--emit-events`).
+    ///
+    /// Events are written from a background thread, so a slow reader never
+    /// blocks the TUI. A `file_loaded` event for the current file is sent
+    /// right away.
+    pub fn set_event_writer(&mut self, out: impl std::io::Write + Send + 'static) {
+        self.event_sink = Some(EventSink::new(out));
+        self.emit(EventKind::FileLoaded);
+    }
+
+    /// Send a navigation event for the current file and section, if enabled
+    fn emit(&self, kind: EventKind) {
+        if let Some(sink) = &self.event_sink {
+            sink.emit(kind, &self.current_file_path, self.selected_heading_text());
+        }
+    }
+
     /// Follow the currently selected link
     pub fn follow_selected_link(&mut self) -> Result<(), String> {
         let link = match self.get_selected_link() {
@@ -3803,119 +4965,170 @@ impl App {
             None => return Err("No link selected".to_string()),
         };
 
-        match link.target {
-            crate::parser::LinkTarget::Anchor(anchor) => {
-                // Jump to heading in current document
-                self.jump_to_anchor(&anchor)?;
-                self.status_message = Some(format!("✓ Jumped to #{}", anchor));
-                self.exit_link_follow_mode();
-                Ok(())
-            }
-            crate::parser::LinkTarget::RelativeFile { path, anchor } => {
-                // Check if the file has a markdown extension
-                let has_md_extension = path
-                    .extension()
-                    .and_then(|ext| ext.to_str())
-                    .map(|ext| {
-                        let ext_lower = ext.to_lowercase();
-                        ext_lower == "md" || ext_lower == "markdown" || ext_lower == "mdown"
-                    })
-                    .unwrap_or(false);
-
-                let current_dir = self
-                    .current_file_path
-                    .parent()
-                    .ok_or("Cannot determine current directory")?;
-
-                let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
-
-                if has_md_extension {
-                    // Explicit markdown extension - load in treemd
-                    self.load_file(&path, anchor.as_deref())?;
-                    // Only exit link follow mode if we're not prompting for file creation
-                    if self.mode != AppMode::ConfirmFileCreate {
-                        self.status_message = Some(format!("✓ Opened {}", filename));
-                        self.exit_link_follow_mode();
-                    }
-                } else {
-                    // No markdown extension - could be:
-                    // 1. A markdown file without extension (common in wikis)
-                    // 2. A non-markdown file to open in editor
-
-                    // First, try with .md extension (wiki-style links)
-                    let md_path = PathBuf::from(format!("{}.md", path.display()));
-                    let absolute_md_path = current_dir.join(&md_path);
-
-                    if absolute_md_path.exists() && !absolute_md_path.is_symlink() {
-                        // Found markdown file with .md extension (exists check passed)
-                        self.load_file(&md_path, anchor.as_deref())?;
-                        self.status_message = Some(format!("✓ Opened {}.md", filename));
-                        self.exit_link_follow_mode();
-                    } else {
-                        // Try the path as-is
-                        let absolute_path = current_dir.join(&path);
-
-                        if absolute_path.exists() && !absolute_path.is_symlink() {
-                            // File exists - open in editor (non-markdown)
-                            self.pending_editor_file = Some(absolute_path);
-                            self.exit_link_follow_mode();
-                        } else {
-                            // File doesn't exist - prompt to create markdown file
-                            let relative_path = if path.extension().is_none() {
-                                PathBuf::from(format!("{}.md", path.display()))
-                            } else {
-                                path.clone()
-                            };
-                            self.load_file(&relative_path, anchor.as_deref())?;
-                            // Only exit link follow mode if we're not prompting for file creation
-                            if self.mode != AppMode::ConfirmFileCreate {
-                                self.status_message =
-                                    Some(format!("✓ Opened {}", relative_path.display()));
-                                self.exit_link_follow_mode();
-                            }
-                        }
-                    }
-                }
-                Ok(())
-            }
-            crate::parser::LinkTarget::WikiLink { target, .. } => {
-                // Try to find and load the wikilinked file
-                self.load_wikilink(&target)?;
-                // Only exit link follow mode if we're not prompting for file creation
-                if self.mode != AppMode::ConfirmFileCreate {
-                    self.status_message = Some(format!("✓ Opened [[{}]]", target));
-                    self.exit_link_follow_mode();
-                }
-                Ok(())
-            }
-            crate::parser::LinkTarget::External(url) => {
-                // Try to open in default browser
-                let open_result = open::that(&url);
-
-                // Also copy to clipboard as backup
-                let mut clipboard_success = false;
-                if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                    clipboard_success = clipboard.set_text(url.clone()).is_ok();
-                }
+        if link.image {
+            self.view_or_copy_image(&link.text, &link.target.as_str());
+            self.exit_link_follow_mode();
+            return Ok(());
+        }
 
-                // Set status message
-                self.status_message = match (open_result, clipboard_success) {
-                    (Ok(_), true) => Some(format!(
-                        "✓ Opened {} in browser (also copied to clipboard)",
-                        url
-                    )),
-                    (Ok(_), false) => Some(format!("✓ Opened {} in browser", url)),
-                    (Err(_), true) => Some(format!(
-                        "⚠ Could not open browser, URL copied to clipboard: {}",
-                        url
-                    )),
-                    (Err(_), false) => Some(format!("✗ Failed to open URL: {}", url)),
-                };
+        self.follow_link(&link)?;
 
-                self.exit_link_follow_mode();
-                Ok(())
+        // Stay in link follow mode while a confirmation prompt is shown
+        if !self.is_confirming_navigation() {
+            self.exit_link_follow_mode();
+        }
+        Ok(())
+    }
+
+    /// File and line to edit for the selected link: the target file at the
+    /// linked heading, or line 1 when there is no anchor or it doesn't resolve.
+    fn selected_link_edit_target(&self) -> Result<(PathBuf, u32), String> {
+        let link = self.get_selected_link().ok_or("No link selected")?;
+        let base = self
+            .current_file_path
+            .parent()
+            .ok_or("Cannot determine current directory")?;
+
+        let (path, anchor) = match self.link_resolver.resolve(link, base) {
+            ResolvedLink::Anchor(anchor) => (self.current_file_path.clone(), Some(anchor)),
+            ResolvedLink::File { path, anchor } => (path, anchor),
+            ResolvedLink::Editor(path) => (path, None),
+            ResolvedLink::External(url) => return Err(format!("{} is not a local file", url)),
+            ResolvedLink::Rejected(reason) => return Err(reason),
+        };
+        if !path.exists() {
+            return Err(format!("{} not found", self.display_path(&path).display()));
+        }
+
+        let line = anchor
+            .and_then(|anchor| {
+                let parsed;
+                let document = if path == self.current_file_path {
+                    &self.document
+                } else {
+                    parsed = crate::parser::parse_file(&path).ok()?;
+                    &parsed
+                };
+                let heading = document.find_anchor(&anchor)?;
+                Some(source_line(&document.content, heading.offset))
+            })
+            .unwrap_or(1);
+        Ok((path, line))
+    }
+
+    /// Resolve a link with the link resolver and navigate to its destination.
+    fn follow_link(&mut self, link: &Link) -> Result<ResolvedLink, String> {
+        let base = self
+            .current_file_path
+            .parent()
+            .ok_or("Cannot determine current directory")?
+            .to_path_buf();
+        let resolved = self.link_resolver.resolve(link, &base);
+        if !matches!(resolved, ResolvedLink::Rejected(_)) {
+            self.emit(EventKind::LinkFollowed {
+                target: &link.target.as_str(),
+            });
+        }
+
+        match &resolved {
+            ResolvedLink::Anchor(anchor) => {
+                // Jump to heading in current document
+                self.jump_to_anchor(anchor)?;
+                self.status_message = Some(format!("✓ Jumped to #{}", anchor));
             }
+            ResolvedLink::File { path, anchor } => {
+                self.load_file(path, anchor.as_deref())?;
+
+                let wikilink = match &link.target {
+                    crate::parser::LinkTarget::WikiLink { target, .. } => Some(target),
+                    _ => None,
+                };
+                if self.mode == AppMode::ConfirmFileCreate {
+                    if let Some(target) = wikilink {
+                        self.pending_file_create_message = Some(format!(
+                            "Wikilink '[[{}]]' not found. Create '{}'?",
+                            target,
+                            self.display_path(path).display()
+                        ));
+                    }
+                } else if !self.is_confirming_navigation() {
+                    let opened = match wikilink {
+                        Some(target) => format!("[[{}]]", target),
+                        None => self.display_path(path).display().to_string(),
+                    };
+                    self.status_message = Some(format!("✓ Opened {}", opened));
+                }
+            }
+            ResolvedLink::Editor(path) => {
+                // Non-markdown file - open in editor
+                self.pending_editor_file = Some(path.clone());
+            }
+            ResolvedLink::External(url) => self.open_external_url(url),
+            ResolvedLink::Rejected(reason) => return Err(reason.clone()),
         }
+
+        Ok(resolved)
+    }
+
+    /// Show the current file's directory in the system file manager
+    pub fn open_containing_dir(&mut self) {
+        let Some(dir) = self.containing_dir() else {
+            self.status_message = Some("✗ No folder to open for this document".to_string());
+            return;
+        };
+        self.status_message = Some(match clipboard::open_dir(&dir) {
+            Ok(()) => format!("✓ Opened {}", dir.display()),
+            Err(e) => format!("✗ Failed to open {}: {}", dir.display(), e),
+        });
+    }
+
+    /// Directory containing the current file, if it exists on disk
+    fn containing_dir(&self) -> Option<PathBuf> {
+        let path = self.current_file_path.canonicalize().ok()?;
+        path.parent().map(Path::to_path_buf)
+    }
+
+    /// Open a URL in the default browser, also copying it to the clipboard as a backup
+    fn open_external_url(&mut self, url: &str) {
+        let open_result = clipboard::open_url(url);
+        // Email links copy just the address
+        let copied = url.strip_prefix("mailto:").unwrap_or(url);
+        let clipboard_success = self.copy_to_clipboard(copied).is_ok();
+        let handler = if url.starts_with("mailto:") {
+            "mail client"
+        } else {
+            "browser"
+        };
+
+        self.status_message = match (open_result, clipboard_success) {
+            (Ok(_), true) => Some(format!(
+                "✓ Opened {} in {} (also copied to clipboard)",
+                url, handler
+            )),
+            (Ok(_), false) => Some(format!("✓ Opened {} in {}", url, handler)),
+            (Err(_), true) => Some(format!(
+                "⚠ Could not open {}, copied to clipboard: {}",
+                handler, copied
+            )),
+            (Err(e), false) => Some(format!("✗ Failed to open URL {}: {}", url, e)),
+        };
+    }
+
+    /// Whether navigation is waiting on a save or create confirmation
+    fn is_confirming_navigation(&self) -> bool {
+        matches!(
+            self.mode,
+            AppMode::ConfirmFileCreate | AppMode::ConfirmSaveBeforeNav
+        )
+    }
+
+    /// Path shown to the user, relative to the current file's directory when possible
+    fn display_path<'a>(&self, path: &'a Path) -> &'a Path {
+        self.current_file_path
+            .parent()
+            .and_then(|dir| path.strip_prefix(dir).ok())
+            .filter(|relative| !relative.as_os_str().is_empty())
+            .unwrap_or(path)
     }
 
     /// Jump to a heading by anchor name or heading text.
@@ -3948,84 +5161,41 @@ impl App {
         Err(format!("Heading '{}' not found", anchor))
     }
 
-    /// Load a file by relative path (checks for unsaved changes first)
-    ///
-    /// Security: Validates path to prevent directory traversal attacks.
-    /// Files must be within the current file's directory or its subdirectories.
-    fn load_file(&mut self, relative_path: &PathBuf, anchor: Option<&str>) -> Result<(), String> {
+    /// Load a file resolved by the link resolver (checks for unsaved changes first)
+    fn load_file(&mut self, path: &Path, anchor: Option<&str>) -> Result<(), String> {
         // Check for unsaved changes before navigating to a different file
         if self.has_unsaved_changes {
             self.pending_navigation = Some(PendingNavigation::LoadFile(
-                relative_path.clone(),
+                path.to_path_buf(),
                 anchor.map(|s| s.to_string()),
             ));
             self.mode = AppMode::ConfirmSaveBeforeNav;
             return Ok(()); // Not an error - we're asking user to confirm
         }
 
-        self.load_file_internal(relative_path, anchor)
+        self.load_file_internal(path, anchor)
     }
 
     /// Internal file loading - skips unsaved changes check
     ///
-    /// Security: Validates path to prevent directory traversal attacks.
-    /// Files must be within the current file's directory or its subdirectories.
-    fn load_file_internal(
-        &mut self,
-        relative_path: &PathBuf,
-        anchor: Option<&str>,
-    ) -> Result<(), String> {
-        // Reject absolute paths
-        if relative_path.is_absolute() {
-            return Err("Absolute paths are not allowed for security reasons".to_string());
-        }
-
-        // Reject paths containing .. components (path traversal)
-        if relative_path
-            .components()
-            .any(|c| matches!(c, std::path::Component::ParentDir))
-        {
-            return Err("Path traversal (..) is not allowed for security reasons".to_string());
-        }
-
-        // Resolve path relative to current file
-        let current_dir = self
-            .current_file_path
-            .parent()
-            .ok_or("Cannot determine current directory")?;
-        let absolute_path = current_dir.join(relative_path);
-
-        // Verify the resolved path is within allowed boundaries
-        // (defense in depth - even though we rejected .., canonicalize to be sure)
-        if let (Ok(canonical_path), Ok(canonical_base)) =
-            (absolute_path.canonicalize(), current_dir.canonicalize())
-        {
-            if !canonical_path.starts_with(&canonical_base) {
-                return Err("Path escapes document directory boundary".to_string());
-            }
-        }
-
-        // Check for symlink (prevent symlink attacks)
-        if absolute_path.is_symlink() {
-            return Err("Symlinks are not allowed for security reasons".to_string());
-        }
-
+    /// Paths come from the link resolver, which performs security validation.
+    fn load_file_internal(&mut self, path: &Path, anchor: Option<&str>) -> Result<(), String> {
         // Check if file exists - if not, prompt to create it
-        if !absolute_path.exists() {
-            self.pending_file_create = Some(absolute_path.clone());
+        if !path.exists() {
+            self.pending_file_create = Some(path.to_path_buf());
             self.pending_file_create_message = Some(format!(
                 "File '{}' does not exist. Create it?",
-                relative_path.display()
+                self.display_path(path).display()
             ));
             self.mode = AppMode::ConfirmFileCreate;
             return Ok(()); // Not an error - we're asking user to confirm
         }
 
         // Parse the new file
-        let new_document = crate::parser::parse_file(&absolute_path)
-            .map_err(|e| format!("Failed to load file: {}", e))?;
+        let new_document =
+            crate::parser::parse_file(path).map_err(|e| format!("Failed to load file: {}", e))?;
 
-        let new_filename = absolute_path
+        let new_filename = path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("unknown")
@@ -4035,7 +5205,7 @@ impl App {
         self.save_to_history();
 
         // Load new document
-        self.load_document(new_document, new_filename, absolute_path);
+        self.load_document(new_document, new_filename, path.to_path_buf());
 
         // Jump to anchor if specified
         if let Some(anchor_name) = anchor {
@@ -4045,103 +5215,6 @@ impl App {
         Ok(())
     }
 
-    /// Find and load a wikilinked file
-    ///
-    /// Supports formats:
-    /// - `[[filename]]` - load file (tries .md, .markdown extensions)
-    /// - `[[filename#anchor]]` - load file and jump to anchor
-    /// - `[[#anchor]]` - jump to anchor in current document
-    /// - `[[path/to/file]]` - load file with path (e.g., `[[diary/notes.md]]`)
-    ///
-    /// Security: Path traversal (..) and absolute paths are blocked.
-    /// The `load_file()` function provides additional security validation.
-    fn load_wikilink(&mut self, target: &str) -> Result<(), String> {
-        // Handle anchor-only wikilinks (e.g., [[#section]])
-        if let Some(anchor) = target.strip_prefix('#') {
-            // Jump to heading in current document
-            self.jump_to_anchor(anchor)?;
-            self.status_message = Some(format!("✓ Jumped to #{}", anchor));
-            return Ok(());
-        }
-
-        // Split target into file and optional anchor (e.g., "file#section" -> ("file", Some("section")))
-        let (file_target, anchor) = if let Some((file, anchor)) = target.split_once('#') {
-            (file, Some(anchor))
-        } else {
-            (target, None)
-        };
-
-        // Security: Reject path traversal attempts
-        if file_target.contains("..") {
-            return Err("WikiLinks cannot contain path traversal (..)".to_string());
-        }
-
-        // Security: Reject absolute paths
-        if file_target.starts_with('/') {
-            return Err("WikiLinks cannot be absolute paths".to_string());
-        }
-
-        // Security: Reject Windows absolute paths (drive letters)
-        #[cfg(windows)]
-        if file_target.len() >= 2 && file_target.chars().nth(1) == Some(':') {
-            return Err("WikiLinks cannot be absolute paths".to_string());
-        }
-
-        // Normalize backslashes to forward slashes for cross-platform compatibility
-        let file_target = file_target.replace('\\', "/");
-
-        // Try to find the file relative to current directory
-        let current_dir = self
-            .current_file_path
-            .parent()
-            .ok_or("Cannot determine current directory")?;
-
-        // Check if target already has a markdown extension
-        let file_target_lower = file_target.to_lowercase();
-        let has_md_extension = file_target_lower.ends_with(".md")
-            || file_target_lower.ends_with(".markdown")
-            || file_target_lower.ends_with(".mdown");
-
-        // Try various extensions (only add extensions if target doesn't already have one)
-        let candidates: Vec<String> = if has_md_extension {
-            // Already has markdown extension - just try as-is
-            vec![file_target.to_string()]
-        } else {
-            // Try with various extensions
-            vec![
-                format!("{}.md", file_target),
-                format!("{}.markdown", file_target),
-                file_target.to_string(),
-            ]
-        };
-
-        for candidate in &candidates {
-            let path = current_dir.join(candidate);
-            // Check for symlinks
-            if path.is_symlink() {
-                continue; // Skip symlinks for security
-            }
-            if path.exists() {
-                return self.load_file(&PathBuf::from(candidate), anchor);
-            }
-        }
-
-        // File not found - prompt to create it (default to .md extension if not already present)
-        let default_filename = if has_md_extension {
-            file_target.to_string()
-        } else {
-            format!("{}.md", file_target)
-        };
-        let new_path = current_dir.join(&default_filename);
-        self.pending_file_create = Some(new_path);
-        self.pending_file_create_message = Some(format!(
-            "Wikilink '[[{}]]' not found. Create '{}'?",
-            target, default_filename
-        ));
-        self.mode = AppMode::ConfirmFileCreate;
-        Ok(()) // Not an error - we're asking user to confirm
-    }
-
     /// Save current state to history before navigating away
     fn save_to_history(&mut self) {
         let state = FileState {
@@ -4152,7 +5225,7 @@ impl App {
             content_scroll: self.content_scroll,
             outline_state_selected: self.outline_state.selected(),
         };
-        self.file_history.push(state);
+        push_bounded(&mut self.file_history, state, self.config.ui.history_depth);
 
         // Clear forward history when navigating to a new file
         self.file_future.clear();
@@ -4160,14 +5233,35 @@ impl App {
 
     /// Load a new document and update all related state
     fn load_document(&mut self, document: Document, filename: String, path: PathBuf) {
+        use crate::parser::content::parse_content;
+        let blocks = parse_content(&document.content, 0);
+        self.load_parsed_document(document, filename, path, &blocks);
+    }
+
+    /// Load a document whose content blocks have already been parsed
+    fn load_parsed_document(
+        &mut self,
+        document: Document,
+        filename: String,
+        path: PathBuf,
+        blocks: &[crate::parser::output::Block],
+    ) {
         // Signal file watcher if path changed
-        if self.current_file_path != path {
+        let path_changed = self.current_file_path != path;
+        if path_changed {
             self.file_path_changed = true;
+            if self.remember_recent_files {
+                self.save_file_position();
+                let _ = self.config.push_recent(&path);
+            }
         }
 
         self.document = document;
+        self.section_cache.get_mut().clear();
         self.filename = filename;
         self.current_file_path = path;
+        self.file_mtime = modified_time(&self.current_file_path);
+        self.disk_changed = false;
 
         // Rebuild tree and outline (with overview entry if applicable)
         self.tree = self.document.build_tree();
@@ -4191,14 +5285,115 @@ impl App {
         self.previous_selection = None;
 
         // Load first image from the new document
-        let content = self.document.content.clone();
-        self.load_first_image(&content);
+        self.load_first_image_from_blocks(blocks);
 
         // Index interactive elements (links, images, etc.) even in normal mode
         // This allows inline images to render without entering interactive mode
-        use crate::parser::content::parse_content;
-        let blocks = parse_content(&content, 0);
-        self.interactive_state.index_elements(&blocks);
+        self.interactive_state
+            .open_details(&self.document.content, blocks);
+        self.interactive_state.index_elements(blocks);
+
+        if path_changed {
+            self.restore_file_position();
+            self.emit(EventKind::FileLoaded);
+        }
+    }
+
+    /// Remember where the current file was left, to pick up there the next
+    /// time it is opened. Only done when recent files are remembered.
+    pub fn save_file_position(&mut self) {
+        if !self.remember_recent_files || !self.current_file_path.is_file() {
+            return;
+        }
+        let Some(position) = self.current_file_position() else {
+            return;
+        };
+        let path = self.current_file_path.clone();
+        let _ = self.config.save_file_position(&path, position);
+    }
+
+    /// Where the current file is being read, or `None` while a background
+    /// load still shows the placeholder document, so quitting mid-load
+    /// keeps the position saved last time.
+    fn current_file_position(&self) -> Option<FilePosition> {
+        if self.is_loading() {
+            return None;
+        }
+        Some(FilePosition {
+            heading: self.selected_heading_text().map(|s| s.to_string()),
+            content_scroll: self.content_scroll,
+        })
+    }
+
+    /// Return to the saved position of the current file, if any.
+    ///
+    /// Keeps the first item selected when the saved heading no longer exists.
+    fn restore_file_position(&mut self) {
+        let Some(position) = self.config.file_position(&self.current_file_path).cloned() else {
+            return;
+        };
+        if let Some(heading) = &position.heading
+            && !self.select_by_text(heading)
+        {
+            return;
+        }
+        // Lay out the restored section first so the scroll isn't reset
+        self.update_content_metrics();
+        self.content_scroll = position
+            .content_scroll
+            .min(self.content_height.saturating_sub(1));
+        self.content_scroll_state = self
+            .content_scroll_state
+            .position(self.content_scroll as usize);
+    }
+
+    // ===== Background Loading =====
+
+    /// Parse `content` on a worker thread, showing a loading screen until ready.
+    ///
+    /// Use this for large documents (see [`BACKGROUND_LOAD_BYTES`]) so the TUI
+    /// stays responsive. The event loop calls [`App::poll_loading`], which
+    /// installs the document under the current filename and path once parsed.
+    ///
+    /// [`BACKGROUND_LOAD_BYTES`]: crate::tui::BACKGROUND_LOAD_BYTES
+    pub fn load_in_background(&mut self, content: String) {
+        self.loader = Some(DocumentLoader::spawn(content));
+    }
+
+    /// Whether a background load is in progress
+    pub fn is_loading(&self) -> bool {
+        self.loader.is_some()
+    }
+
+    /// Spinner frame and progress text while a background load is in progress
+    pub fn loading_status(&self) -> Option<(&'static str, String)> {
+        self.loader.as_ref().map(|loader| loader.status())
+    }
+
+    /// Check on a background load, installing the document once it is ready.
+    ///
+    /// Returns `true` when loading finished (successfully or not).
+    pub fn poll_loading(&mut self) -> bool {
+        let Some(loader) = self.loader.as_mut() else {
+            return false;
+        };
+
+        match loader.poll() {
+            Ok(None) => false,
+            Ok(Some(loaded)) => {
+                self.loader = None;
+                let filename = self.filename.clone();
+                let path = self.current_file_path.clone();
+                self.load_parsed_document(loaded.document, filename, path, &loaded.blocks);
+                self.restore_file_position();
+                true
+            }
+            Err(e) => {
+                self.loader = None;
+                self.status_message = Some(format!("✗ {}", e));
+                true
+            }
+        }
     }
 
     /// Navigate back in file history
@@ -4217,7 +5412,11 @@ impl App {
             content_scroll: self.content_scroll,
             outline_state_selected: self.outline_state.selected(),
         };
-        self.file_future.push(current_state);
+        push_bounded(
+            &mut self.file_future,
+            current_state,
+            self.config.ui.history_depth,
+        );
 
         // Restore previous state
         self.restore_file_state(previous_state);
@@ -4238,7 +5437,11 @@ impl App {
             content_scroll: self.content_scroll,
             outline_state_selected: self.outline_state.selected(),
         };
-        self.file_history.push(current_state);
+        push_bounded(
+            &mut self.file_history,
+            current_state,
+            self.config.ui.history_depth,
+        );
 
         // Restore next state
         self.restore_file_state(next_state);
@@ -4272,6 +5475,7 @@ impl App {
         // Reload the file
         let content = std::fs::read_to_string(&self.current_file_path)
             .map_err(|e| format!("Failed to reload file: {}", e))?;
+        let content = crate::input::markdown_for_file(&self.current_file_path, content);
 
         let document = crate::parser::parse_markdown(&content);
         let filename = self
@@ -4283,6 +5487,21 @@ impl App {
 
         self.load_document(document, filename, self.current_file_path.clone());
 
+        // load_document re-applied the fold state; forget headings the edit removed
+        let remaining: HashSet<&str> = self
+            .document
+            .headings
+            .iter()
+            .map(|h| h.text.as_str())
+            .collect();
+        self.collapsed_headings
+            .retain(|text| remaining.contains(text.as_str()));
+
+        // Keep an active outline search filter applied
+        if !self.search_query.is_empty() {
+            self.filter_outline();
+        }
+
         // Try to restore selection if the heading still exists
         if let Some(heading) = current_selection {
             self.select_by_text(&heading);
@@ -4297,6 +5516,54 @@ impl App {
         Ok(())
     }
 
+    /// Reload the current file, keeping the interactive element selection and
+    /// scroll position where possible
+    pub fn reload_preserving_view(&mut self) -> Result<(), String> {
+        let was_interactive = self.mode == AppMode::Interactive;
+        let saved_scroll = self.content_scroll;
+        let saved_element_idx = self.interactive_state.current_index;
+
+        self.reload_current_file()?;
+
+        // Re-index interactive elements if in interactive mode
+        if was_interactive {
+            self.reindex_interactive_elements();
+            // Restore element selection if still valid
+            if let Some(idx) = saved_element_idx
+                && idx < self.interactive_state.elements.len()
+            {
+                self.interactive_state.current_index = Some(idx);
+            }
+        }
+        // Restore scroll position
+        self.content_scroll = saved_scroll.min(self.content_height.saturating_sub(1));
+        self.content_scroll_state = self
+            .content_scroll_state
+            .position(self.content_scroll as usize);
+        // Sync previous_selection to prevent update_content_metrics() from resetting scroll
+        self.sync_previous_selection();
+        Ok(())
+    }
+
+    /// Reload the current file on request (the `Reload` action)
+    pub fn reload_on_demand(&mut self) {
+        self.status_message = Some(match self.reload_preserving_view() {
+            Ok(()) => "↻ File reloaded".to_string(),
+            Err(e) => format!("✗ Reload failed: {}", e),
+        });
+    }
+
+    /// Toggle reloading when the file changes on disk and save the preference
+    pub fn toggle_auto_reload(&mut self) {
+        self.auto_reload = !self.auto_reload;
+        let _ = self.config.set_auto_reload(self.auto_reload);
+        self.status_message = Some(if self.auto_reload {
+            "✓ Auto-reload on: file changes reload the view".to_string()
+        } else {
+            "✓ Auto-reload off: press R to reload".to_string()
+        });
+    }
+
     /// Enter interactive mode - build element index and enter mode
     pub fn enter_interactive_mode(&mut self) {
         // Exit raw source view if active (interactive elements aren't visible in raw mode)
@@ -4305,20 +5572,18 @@ impl App {
         }
 
         // Get current section content to index
-        let content = if let Some(selected) = self.selected_heading_text() {
-            self.document
-                .extract_section(selected)
-                .unwrap_or_else(|| self.document.content.clone())
-        } else {
-            self.document.content.clone()
-        };
+        let content = self.selected_section_content();
 
         // Parse content into blocks
         use crate::parser::content::parse_content;
         let blocks = parse_content(&content, 0);
 
         // Index interactive elements
+        self.interactive_state.open_details(&content, &blocks);
         self.interactive_state.index_elements(&blocks);
+        self.interactive_state
+            .index_inline_checkboxes(&content, &blocks);
+        self.interactive_state.index_footnotes(&content, &blocks);
 
         // Enter interactive mode at current scroll position (preserve user's view)
         self.interactive_state
@@ -4367,18 +5632,15 @@ impl App {
             std::fs::write(&path, &default_content)
                 .map_err(|e| format!("Failed to create file: {}", e))?;
 
-            // Load the new file
-            let relative_path = path
-                .file_name()
-                .map(PathBuf::from)
-                .unwrap_or_else(|| path.clone());
-
             self.pending_file_create_message = None;
             self.mode = AppMode::Normal;
 
             // Load the newly created file
-            self.load_file(&relative_path, None)?;
-            self.status_message = Some(format!("✓ Created and opened {}", relative_path.display()));
+            self.load_file(&path, None)?;
+            self.status_message = Some(format!(
+                "✓ Created and opened {}",
+                self.display_path(&path).display()
+            ));
             self.exit_link_follow_mode();
         }
         Ok(())
@@ -4425,32 +5687,101 @@ impl App {
                 item_idx,
                 ..
             } => {
+                let pending = PendingOverwrite::Checkbox {
+                    block_idx: *block_idx,
+                    item_idx: *item_idx,
+                    checked: *checked,
+                };
+                if self.check_disk_changed() {
+                    self.request_overwrite(pending);
+                    return Ok(());
+                }
                 // Toggle checkbox and save to file
                 self.toggle_checkbox_and_save(*block_idx, *item_idx, *checked)?;
                 Ok(())
             }
+            ElementType::InlineCheckbox {
+                checked,
+                offset,
+                context,
+                ..
+            } => {
+                let (checked, offset, context) = (*checked, *offset, context.clone());
+                if self.check_disk_changed() {
+                    self.request_overwrite(PendingOverwrite::InlineCheckbox {
+                        offset,
+                        context,
+                        checked,
+                    });
+                    return Ok(());
+                }
+                self.toggle_inline_checkbox_and_save(offset, &context, checked)
+            }
             ElementType::Link { link, .. } => {
                 // Follow link using existing link follow logic
                 self.follow_link_from_interactive(&link.clone())?;
                 Ok(())
             }
-            ElementType::CodeBlock { content, .. } => {
-                // Copy code to clipboard
-                self.copy_to_clipboard(content)?;
-                self.status_message = Some("✓ Code copied to clipboard".to_string());
+            ElementType::CodeBlock { .. } => {
+                // Collapse to a summary line, or expand back (y copies)
+                self.interactive_state.toggle_code_block(element.id);
+
+                // Re-index elements since the block's height changed
+                self.reindex_interactive_elements();
+
+                self.status_message = Some(
+                    if self.interactive_state.is_code_collapsed(element.id) {
+                        "✓ Code block collapsed"
+                    } else {
+                        "✓ Code block expanded"
+                    }
+                    .to_string(),
+                );
+                Ok(())
+            }
+            ElementType::DataFold {
+                line, block_idx, ..
+            } => {
+                let code_block = crate::tui::interactive::ElementId {
+                    block_idx: *block_idx,
+                    sub_idx: None,
+                };
+                self.interactive_state.toggle_data_fold(code_block, *line);
+
+                // Re-index elements since the block's height changed
+                self.reindex_interactive_elements();
+
+                let folded = self
+                    .interactive_state
+                    .data_folds(code_block)
+                    .is_some_and(|folds| folds.is_folded(*line));
+                self.status_message =
+                    Some(if folded { "✓ Folded" } else { "✓ Unfolded" }.to_string());
                 Ok(())
             }
-            ElementType::Image { src, alt, .. } => {
-                if self.images_enabled {
-                    // Open image modal to view the image fullscreen
-                    self.open_image_modal(src);
-                    self.status_message = Some(format!("📸 Viewing: {} (Esc:Close)", alt));
+            ElementType::Footnote {
+                label, definition, ..
+            } => {
+                let target = if *definition {
+                    "reference"
                 } else {
-                    self.status_message =
-                        Some("Images disabled (use --images or config to enable)".to_string());
+                    "definition"
+                };
+                if self.interactive_state.select_footnote_counterpart() {
+                    self.scroll_to_interactive_element(self.content_viewport_height);
+                    self.status_message = Some(format!("✓ Footnote [^{}] {}", label, target));
+                } else {
+                    self.status_message = Some(format!(
+                        "⚠ Footnote [^{}] has no {} in this section",
+                        label, target
+                    ));
                 }
                 Ok(())
             }
+            ElementType::Image { src, alt, .. } => {
+                self.view_or_copy_image(alt, src);
+                Ok(())
+            }
             ElementType::Table { rows, cols, .. } => {
                 // Enter table navigation mode
                 self.interactive_state.enter_table_mode()?;
@@ -4463,17 +5794,15 @@ impl App {
 
     /// Re-index interactive elements after state changes
     pub fn reindex_interactive_elements(&mut self) {
-        let content = if let Some(selected) = self.selected_heading_text() {
-            self.document
-                .extract_section(selected)
-                .unwrap_or_else(|| self.document.content.clone())
-        } else {
-            self.document.content.clone()
-        };
+        let content = self.selected_section_content();
 
         use crate::parser::content::parse_content;
         let blocks = parse_content(&content, 0);
+        self.interactive_state.open_details(&content, &blocks);
         self.interactive_state.index_elements(&blocks);
+        self.interactive_state
+            .index_inline_checkboxes(&content, &blocks);
+        self.interactive_state.index_footnotes(&content, &blocks);
     }
 
     /// Toggle a checkbox and save changes to the file
@@ -4485,13 +5814,7 @@ impl App {
     ) -> Result<(), String> {
         // Get the checkbox content text to use as identifier
         let checkbox_content = {
-            let content = if let Some(selected) = self.selected_heading_text() {
-                self.document
-                    .extract_section(selected)
-                    .unwrap_or_else(|| self.document.content.clone())
-            } else {
-                self.document.content.clone()
-            };
+            let content = self.selected_section_content();
 
             use crate::parser::content::parse_content;
             let blocks = parse_content(&content, 0);
@@ -4514,6 +5837,48 @@ impl App {
         let new_content =
             self.toggle_checkbox_by_content(&file_content, &checkbox_content, checked)?;
 
+        self.save_toggled_checkbox(&new_content, &checkbox_content, checked)
+    }
+
+    /// Toggle a `[x]`/`[ ]` in a paragraph or table and save the file.
+    ///
+    /// `offset` is the token's position in the section content and `context`
+    /// its trimmed source line; the file is searched for that line, since the
+    /// section may not start at the same place in the file.
+    fn toggle_inline_checkbox_and_save(
+        &mut self,
+        offset: usize,
+        context: &str,
+        checked: bool,
+    ) -> Result<(), String> {
+        use crate::parser::content::inline_checkboxes;
+
+        // Which checkbox on its line this is
+        let content = self.selected_section_content();
+        let line_start = content[..offset.min(content.len())]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let nth = inline_checkboxes(&content)
+            .iter()
+            .filter(|c| (line_start..offset).contains(&c.range.start))
+            .count();
+
+        let file_content = std::fs::read_to_string(&self.current_file_path)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        let new_content = toggle_inline_checkbox_on_line(&file_content, context, nth, checked)?;
+
+        self.save_toggled_checkbox(&new_content, context, checked)
+    }
+
+    /// Write a file with a toggled checkbox, then reload it in place.
+    fn save_toggled_checkbox(
+        &mut self,
+        new_content: &str,
+        item: &str,
+        checked: bool,
+    ) -> Result<(), String> {
+        self.check_source_is_markdown()?;
+
         // Atomic write: write to temp file, then rename (prevents data corruption)
         use std::io::Write;
         let parent_dir = self
@@ -4563,12 +5928,17 @@ impl App {
         // IMPORTANT: Sync previous_selection to prevent update_content_metrics() from resetting scroll
         // After reload, load_document() sets previous_selection = None, but current selection is restored.
         // Without this sync, update_content_metrics() thinks selection changed and resets scroll to 0.
-        self.previous_selection = self.selected_heading_text().map(|s| s.to_string());
+        self.previous_selection = self.selection_key();
 
         // Suppress file watcher for this save - we already reloaded internally
         // Without this, file watcher detects our save and triggers a second reload
         self.suppress_file_watch = true;
 
+        self.emit(EventKind::CheckboxToggled {
+            item,
+            checked: !checked,
+        });
+
         let new_state = if checked { "unchecked" } else { "checked" };
         self.status_message = Some(format!("✓ Checkbox {} and saved", new_state));
 
@@ -4639,114 +6009,34 @@ impl App {
     }
 
     /// Follow a link from interactive mode
-    fn follow_link_from_interactive(&mut self, link: &crate::parser::Link) -> Result<(), String> {
-        use crate::parser::LinkTarget;
-
-        match &link.target {
-            LinkTarget::Anchor(anchor) => {
-                // Jump to heading in current document
-                self.jump_to_anchor(anchor)?;
-                self.exit_interactive_mode();
-                self.status_message = Some(format!("✓ Jumped to #{}", anchor));
-                Ok(())
-            }
-            LinkTarget::RelativeFile { path, anchor } => {
-                // Check if the file has a markdown extension
-                let has_md_extension = path
-                    .extension()
-                    .and_then(|ext| ext.to_str())
-                    .map(|ext| {
-                        let ext_lower = ext.to_lowercase();
-                        ext_lower == "md" || ext_lower == "markdown" || ext_lower == "mdown"
-                    })
-                    .unwrap_or(false);
-
-                let current_dir = self
-                    .current_file_path
-                    .parent()
-                    .ok_or("Cannot determine current directory")?;
-
-                if has_md_extension {
-                    // Explicit markdown extension - load in treemd
-                    self.load_file(path, anchor.as_deref())?;
-                    // Only exit interactive mode if we're not prompting for file creation
-                    if self.mode != AppMode::ConfirmFileCreate {
-                        self.exit_interactive_mode();
-                    }
-                } else {
-                    // No markdown extension - could be:
-                    // 1. A markdown file without extension (common in wikis)
-                    // 2. A non-markdown file to open in editor
-
-                    // First, try with .md extension (wiki-style links)
-                    let md_path = PathBuf::from(format!("{}.md", path.display()));
-                    let absolute_md_path = current_dir.join(&md_path);
-
-                    if absolute_md_path.exists() && !absolute_md_path.is_symlink() {
-                        // Found markdown file with .md extension (exists check passed)
-                        self.load_file(&md_path, anchor.as_deref())?;
-                        self.exit_interactive_mode();
-                    } else {
-                        // Try the path as-is
-                        let absolute_path = current_dir.join(path);
-
-                        if absolute_path.exists() && !absolute_path.is_symlink() {
-                            // File exists - open in editor (non-markdown)
-                            self.pending_editor_file = Some(absolute_path);
-                            self.exit_interactive_mode();
-                        } else {
-                            // File doesn't exist - prompt to create markdown file
-                            let relative_path = if path.extension().is_none() {
-                                PathBuf::from(format!("{}.md", path.display()))
-                            } else {
-                                path.clone()
-                            };
-                            self.load_file(&relative_path, anchor.as_deref())?;
-                            // Only exit interactive mode if we're not prompting for file creation
-                            if self.mode != AppMode::ConfirmFileCreate {
-                                self.exit_interactive_mode();
-                            }
-                        }
-                    }
-                }
-                Ok(())
-            }
-            LinkTarget::WikiLink { target, .. } => {
-                // Try to find and load the wikilinked file
-                self.load_wikilink(target)?;
-                // Only exit interactive mode if we're not prompting for file creation
-                if self.mode != AppMode::ConfirmFileCreate {
-                    self.exit_interactive_mode();
-                }
-                Ok(())
-            }
-            LinkTarget::External(url) => {
-                // Security: Validate URL scheme (only http/https allowed)
-                if !url.starts_with("http://") && !url.starts_with("https://") {
-                    return Err(
-                        "Unsafe URL scheme. Only http:// and https:// URLs are allowed."
-                            .to_string(),
-                    );
-                }
+    fn follow_link_from_interactive(&mut self, link: &Link) -> Result<(), String> {
+        let resolved = self.follow_link(link)?;
 
-                // Use the `open` crate for safe URL opening (no shell injection)
-                open::that(url).map_err(|e| format!("Failed to open URL: {}", e))?;
-
-                self.status_message = Some(format!("✓ Opened {}", url));
-                Ok(())
-            }
+        // Stay in interactive mode after opening a URL or while a prompt is shown
+        if !matches!(resolved, ResolvedLink::External(_)) && !self.is_confirming_navigation() {
+            // Exiting clears the status message; keep the result visible
+            let status = self.status_message.take();
+            self.exit_interactive_mode();
+            self.status_message = status;
         }
+        Ok(())
     }
 
     /// Copy text to clipboard
+    ///
+    /// Uses the persistent clipboard backend for Linux X11 compatibility.
     fn copy_to_clipboard(&mut self, text: &str) -> Result<(), String> {
+        if let Some(register) = self.yank_register.take() {
+            self.store_register(register, text);
+            return Ok(());
+        }
         if let Some(clipboard) = &mut self.clipboard {
             clipboard
-                .set_text(text.to_string())
+                .set_text(text)
                 .map_err(|e| format!("Clipboard error: {}", e))?;
             Ok(())
         } else {
-            Err("Clipboard not available".to_string())
+            Err(clipboard::UNAVAILABLE.to_string())
         }
     }
 
@@ -4757,13 +6047,7 @@ impl App {
                 &element.element_type
             {
                 // Parse current section to get table data
-                let content = if let Some(selected) = self.selected_heading_text() {
-                    self.document
-                        .extract_section(selected)
-                        .unwrap_or_else(|| self.document.content.clone())
-                } else {
-                    self.document.content.clone()
-                };
+                let content = self.selected_section_content();
 
                 use crate::parser::content::parse_content;
                 let blocks = parse_content(&content, 0);
@@ -4833,7 +6117,52 @@ impl App {
         }
     }
 
+    /// Copy entire table as CSV for pasting into spreadsheets
+    pub fn copy_table_csv(&mut self) -> Result<(), String> {
+        let (headers, rows) = self
+            .get_current_table_data()
+            .ok_or_else(|| "No table data available".to_string())?;
+        self.copy_to_clipboard(&Self::table_to_csv(&headers, &rows))?;
+        self.status_message = Some(format!("✓ Table copied as CSV ({} rows)", rows.len()));
+        Ok(())
+    }
+
+    /// Serialize a table as RFC 4180 CSV: CRLF line endings, with fields
+    /// containing commas, quotes or line breaks quoted and quotes doubled.
+    fn table_to_csv(headers: &[String], rows: &[Vec<String>]) -> String {
+        fn field(cell: &str) -> String {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        }
+
+        // Short rows are padded so every record has the same field count
+        let width = rows.iter().map(Vec::len).fold(headers.len(), usize::max);
+        std::iter::once(headers)
+            .chain(rows.iter().map(Vec::as_slice))
+            .map(|cells| {
+                (0..width)
+                    .map(|i| field(cells.get(i).map(String::as_str).unwrap_or("")))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .map(|record| record + "\r\n")
+            .collect()
+    }
+
     /// Enter cell edit mode for the currently selected table cell
+    /// Delete the last grapheme cluster of the cell being edited, so emoji
+    /// sequences and combining accents are removed whole.
+    pub fn cell_edit_backspace(&mut self) {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        if let Some((idx, _)) = self.cell_edit_value.grapheme_indices(true).next_back() {
+            self.cell_edit_value.truncate(idx);
+        }
+    }
+
     pub fn enter_cell_edit_mode(&mut self) -> Result<(), String> {
         if let Some((headers, rows)) = self.get_current_table_data() {
             if let Some((row, col)) = self.interactive_state.get_table_position() {
@@ -4904,6 +6233,7 @@ impl App {
 
         // Update the in-memory document content
         self.document.content = new_content;
+        self.section_cache.get_mut().clear();
 
         // Re-parse headings if needed (table edits don't affect heading structure)
         // The document tree stays the same, only content changed
@@ -4923,13 +6253,7 @@ impl App {
         use crate::parser::output::Block;
 
         // Get the current section content to find the right table
-        let section_content = if let Some(heading_text) = self.selected_heading_text() {
-            self.document
-                .extract_section(heading_text)
-                .unwrap_or_else(|| self.document.content.clone())
-        } else {
-            self.document.content.clone()
-        };
+        let section_content = self.selected_section_content();
 
         // Parse to find the table block
         let blocks = parse_content(&section_content, 0);
@@ -4946,7 +6270,7 @@ impl App {
                     .count();
 
                 // Find where this section starts in the full file and count tables before it
-                let section_start = self.document.content.find(&section_content).unwrap_or(0);
+                let section_start = self.document.content.find(&*section_content).unwrap_or(0);
                 let content_before_section = &self.document.content[..section_start];
 
                 // Count tables (groups of | lines) before section
@@ -4971,6 +6295,19 @@ impl App {
         Err("Could not locate table".to_string())
     }
 
+    /// Refuse to write over org-mode and AsciiDoc files. They are shown
+    /// converted to markdown, so saving the document would replace the
+    /// original syntax.
+    fn check_source_is_markdown(&self) -> Result<(), String> {
+        use crate::input::InputFormat;
+        let source = std::fs::read_to_string(&self.current_file_path).unwrap_or_default();
+        match InputFormat::detect(Some(&self.current_file_path), &source) {
+            InputFormat::Markdown => Ok(()),
+            InputFormat::Org => Err("read-only: converted from org-mode".to_string()),
+            InputFormat::AsciiDoc => Err("read-only: converted from AsciiDoc".to_string()),
+        }
+    }
+
     /// Write all pending edits to the file
     pub fn save_pending_edits_to_file(&mut self) -> Result<(), String> {
         use std::io::Write;
@@ -4979,6 +6316,7 @@ impl App {
             self.status_message = Some("No changes to save".to_string());
             return Ok(());
         }
+        self.check_source_is_markdown()?;
 
         // Atomic write: write to temp file, then rename (prevents data corruption)
         let parent_dir = self
@@ -4989,8 +6327,18 @@ impl App {
         let mut temp_file = tempfile::NamedTempFile::new_in(parent_dir)
             .map_err(|e| format!("Failed to create temp file: {}", e))?;
 
+        // The parser normalizes line endings; restore CRLF if the file used it
+        let uses_crlf = std::fs::read(&self.current_file_path)
+            .map(|bytes| bytes.windows(2).any(|w| w == b"\r\n"))
+            .unwrap_or(false);
+        let output = if uses_crlf {
+            self.document.content.replace('\n', "\r\n")
+        } else {
+            self.document.content.clone()
+        };
+
         temp_file
-            .write_all(self.document.content.as_bytes())
+            .write_all(output.as_bytes())
             .map_err(|e| format!("Failed to write temp file: {}", e))?;
 
         temp_file
@@ -5004,6 +6352,8 @@ impl App {
         temp_file
             .persist(&self.current_file_path)
             .map_err(|e| format!("Failed to save file: {}", e))?;
+        self.file_mtime = modified_time(&self.current_file_path);
+        self.disk_changed = false;
 
         // Clear the pending edits buffer
         let edit_count = self.pending_edits.len();
@@ -5032,6 +6382,7 @@ impl App {
             )?;
 
             self.document.content = new_content;
+            self.section_cache.get_mut().clear();
             self.has_unsaved_changes = !self.pending_edits.is_empty();
 
             if self.pending_edits.is_empty() {
@@ -5167,3 +6518,1383 @@ impl App {
         Ok(base_dir.join(src))
     }
 }
+
+/// Modification time of the file at `path`, if it can be read
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// 1-based line of byte `offset` in `content`
+fn source_line(content: &str, offset: usize) -> u32 {
+    let before = &content[..offset.min(content.len())];
+    before.matches('\n').count() as u32 + 1
+}
+
+/// `content` with the `nth` inline checkbox on the first line reading
+/// `line` (trimmed) toggled from `checked`.
+fn toggle_inline_checkbox_on_line(
+    content: &str,
+    line: &str,
+    nth: usize,
+    checked: bool,
+) -> Result<String, String> {
+    use crate::parser::content::inline_checkboxes;
+
+    let checkboxes = inline_checkboxes(content);
+    let mut line_start = 0;
+    for source_line in content.split_inclusive('\n') {
+        let line_end = line_start + source_line.len();
+        if source_line.trim() == line {
+            let checkbox = checkboxes
+                .iter()
+                .filter(|c| (line_start..line_end).contains(&c.range.start))
+                .nth(nth)
+                .filter(|c| c.checked == checked);
+            if let Some(checkbox) = checkbox {
+                let mark = if checked { "[ ]" } else { "[x]" };
+                let mut toggled = content.to_string();
+                toggled.replace_range(checkbox.range.clone(), mark);
+                return Ok(toggled);
+            }
+        }
+        line_start = line_end;
+    }
+    Err(format!("Checkbox not found in file: '{}'", line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{LinkTarget, extract_links, parse_markdown};
+
+    fn test_app(content: &str) -> App {
+        App::new(
+            parse_markdown(content),
+            "test.md".to_string(),
+            PathBuf::from("test.md"),
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        )
+    }
+
+    fn search(app: &mut App, query: &str) {
+        app.update_content_metrics();
+        app.doc_search_query = query.to_string();
+        app.update_doc_search_matches();
+    }
+
+    #[test]
+    fn test_search_in_crlf_document_scrolls_to_match_line() {
+        let lf = "# Title\n\nFirst paragraph\nstill first\n\n- item one\n- item two\n\nThe needle is here\n";
+        let crlf = lf.replace('\n', "\r\n");
+
+        for content in [lf.to_string(), crlf] {
+            let mut app = test_app(&content);
+            app.content_viewport_height = 4;
+            search(&mut app, "needle");
+
+            assert_eq!(app.doc_search_matches.len(), 1);
+            let m = &app.doc_search_matches[0];
+            // Section lines: "First paragraph", "still first", "", "- item one",
+            // "- item two", "", "The needle is here"
+            assert_eq!(m.line, 6);
+            assert_eq!(m.col_start, 4);
+            // Centered in a 4-line viewport
+            assert_eq!(app.content_scroll, 4);
+        }
+    }
+
+    #[test]
+    fn test_search_reports_wrap_only_at_boundary() {
+        let mut app = test_app("# Title\n\nneedle one\n\nneedle two\n\nneedle three\n");
+        search(&mut app, "needle");
+        assert_eq!(app.doc_search_matches.len(), 3);
+        app.status_message = None;
+
+        // Forward: 0 -> 1 -> 2 stays quiet, 2 -> 0 wraps
+        app.doc_search_current_idx = Some(0);
+        app.next_doc_match();
+        app.next_doc_match();
+        assert_eq!(app.doc_search_current_idx, Some(2));
+        assert_eq!(app.status_message, None);
+        app.next_doc_match();
+        assert_eq!(app.doc_search_current_idx, Some(0));
+        assert_eq!(app.status_message.as_deref(), Some("Search wrapped to top"));
+
+        // Backward: 0 -> 2 wraps, 2 -> 1 stays quiet
+        app.status_message = None;
+        app.prev_doc_match();
+        assert_eq!(app.doc_search_current_idx, Some(2));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Search wrapped to bottom")
+        );
+        app.status_message = None;
+        app.prev_doc_match();
+        assert_eq!(app.doc_search_current_idx, Some(1));
+        assert_eq!(app.status_message, None);
+    }
+
+    #[test]
+    fn test_search_stops_collecting_at_max_matches() {
+        let content = format!(
+            "# Title\n\n{}",
+            "needle needle needle needle\n".repeat(10_000)
+        );
+        let mut app = test_app(&content);
+        search(&mut app, "needle");
+        assert_eq!(app.doc_search_matches.len(), 5000);
+        assert!(app.doc_search_truncated);
+        assert!(
+            app.doc_search_status()
+                .contains("(1/5000) (first 5000 matches)")
+        );
+
+        // Navigation wraps within the collected matches
+        app.doc_search_current_idx = Some(4999);
+        app.next_doc_match();
+        assert_eq!(app.doc_search_current_idx, Some(0));
+
+        search(&mut app, "title");
+        assert!(!app.doc_search_truncated);
+    }
+
+    #[test]
+    fn test_search_can_skip_code_blocks() {
+        let md = "# Title\n\nneedle in text\n\n```rust\nlet needle = 1;\n```\n\nlast needle\n";
+        let mut app = test_app(md);
+
+        search(&mut app, "needle");
+        assert_eq!(app.doc_search_matches.len(), 3);
+
+        app.toggle_doc_search_in_code();
+        assert!(!app.doc_search_in_code);
+        let lines: Vec<_> = app.doc_search_matches.iter().map(|m| m.line).collect();
+        assert_eq!(lines, vec![0, 6]);
+
+        app.toggle_doc_search_in_code();
+        assert_eq!(app.doc_search_matches.len(), 3);
+    }
+
+    #[test]
+    fn test_outline_search_can_match_heading_paths() {
+        let md = "# Usage\n\n## Install\n\n### Linux\n\n# API\n\n## Usage notes\n\n### Errors\n";
+        let mut app = test_app(md);
+        let texts = |app: &App| -> Vec<String> {
+            app.outline_items.iter().map(|i| i.text.clone()).collect()
+        };
+
+        app.search_query = "usage".to_string();
+        app.filter_outline();
+        assert_eq!(texts(&app), ["Usage", "Usage notes"]);
+
+        // Headings under a match are kept, siblings of a match are not
+        app.toggle_outline_search_path();
+        assert!(app.outline_search_path);
+        assert_eq!(
+            texts(&app),
+            ["Usage", "Install", "Linux", "Usage notes", "Errors"]
+        );
+
+        app.toggle_outline_search_path();
+        assert_eq!(texts(&app), ["Usage", "Usage notes"]);
+    }
+
+    #[test]
+    fn test_search_in_code_defaults_from_config() {
+        let mut config = Config::default();
+        config.ui.search_in_code = false;
+        let app = App::new(
+            parse_markdown("# Title\n"),
+            "test.md".to_string(),
+            PathBuf::from("test.md"),
+            config,
+            ColorMode::Rgb,
+            false,
+        );
+        assert!(!app.doc_search_in_code);
+    }
+
+    #[test]
+    fn test_link_follow_selects_wikilink_alias() {
+        let md = "# Title\n\nSee [[glossary]] and [[notes/setup|Setup Guide]].\n";
+        let mut app = test_app(md);
+        app.enter_link_follow_mode();
+
+        app.selected_link_idx = Some(1);
+        let link = app.get_selected_link().unwrap();
+        assert_eq!(link.text, "Setup Guide");
+        assert!(
+            matches!(&link.target, LinkTarget::WikiLink { target, .. } if target == "notes/setup")
+        );
+    }
+
+    #[test]
+    fn test_link_follow_opens_autolinked_email() {
+        let mut app = test_app("# Title\n\nWrite to me@example.com.\n");
+        app.select_by_text("Title");
+        app.enter_link_follow_mode();
+
+        app.selected_link_idx = Some(0);
+        let link = app.get_selected_link().unwrap();
+        assert_eq!(link.target.as_str(), "mailto:me@example.com");
+        // Opened like any other external link rather than rejected
+        assert_eq!(
+            app.link_resolver.resolve(link, Path::new(".")),
+            ResolvedLink::External("mailto:me@example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_headingless_documents_show_only_overview() {
+        for content in ["", "   \n\t\n  ", "Just body text\nwith [a link](#x)\n"] {
+            let mut app = test_app(content);
+
+            assert_eq!(app.outline_items.len(), 1, "content: {:?}", content);
+            assert_eq!(app.outline_items[0].text, DOCUMENT_OVERVIEW);
+            assert_eq!(app.selected_heading_text(), Some(DOCUMENT_OVERVIEW));
+            assert_eq!(&*app.selected_section_content(), content);
+
+            // Navigation and outline actions must not panic
+            app.update_content_metrics();
+            app.next();
+            app.previous();
+            app.last();
+            app.first();
+            app.jump_to_parent();
+            app.toggle_expand();
+            app.collapse_all();
+            app.expand_all();
+            app.scroll_page_down();
+            app.scroll_page_up();
+            app.toggle_todo_filter();
+            app.toggle_todo_filter();
+            app.enter_link_follow_mode();
+            app.next_link();
+            app.previous_link();
+            app.exit_link_follow_mode();
+            app.enter_interactive_mode();
+            app.exit_interactive_mode();
+            search(&mut app, "text");
+
+            assert_eq!(app.selected_heading_text(), Some(DOCUMENT_OVERVIEW));
+        }
+    }
+
+    #[test]
+    fn test_search_match_selects_link_on_its_line() {
+        let md = "# Title\n\n[alpha](#a)[beta](#b)\n[gamma](https://example.com/very/long/url)\n";
+        let mut app = test_app(md);
+
+        search(&mut app, "beta");
+        assert_eq!(app.doc_search_selected_link_idx, Some(1));
+
+        search(&mut app, "gamma");
+        assert_eq!(app.doc_search_selected_link_idx, Some(2));
+
+        search(&mut app, "example");
+        assert_eq!(app.doc_search_selected_link_idx, None);
+    }
+
+    fn outline_texts(app: &App) -> Vec<&str> {
+        app.outline_items.iter().map(|i| i.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_reload_keeps_collapsed_sections() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "# A\n## A1\n# B\n## B1\n").unwrap();
+
+        let mut app = test_app("");
+        app.current_file_path = file.path().to_path_buf();
+        app.reload_current_file().unwrap();
+
+        app.focus = Focus::Outline;
+        app.select_by_text("A");
+        app.toggle_expand();
+        assert_eq!(outline_texts(&app), vec!["A", "B", "B1"]);
+
+        // External edit adds a sibling section
+        std::fs::write(file.path(), "# A\n## A1\n# New\n## N1\n# B\n## B1\n").unwrap();
+        app.reload_current_file().unwrap();
+
+        assert_eq!(outline_texts(&app), vec!["A", "New", "N1", "B", "B1"]);
+        assert!(!app.outline_items[0].expanded);
+        assert_eq!(app.selected_heading_text(), Some("A"));
+
+        // Removing the collapsed heading prunes it from the fold state
+        std::fs::write(file.path(), "# B\n## B1\n").unwrap();
+        app.reload_current_file().unwrap();
+        assert!(app.collapsed_headings.is_empty());
+    }
+
+    #[test]
+    fn test_section_cache_cleared_on_reload() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "# A\nold text\n# B\n").unwrap();
+
+        let mut app = test_app("");
+        app.current_file_path = file.path().to_path_buf();
+        app.reload_current_file().unwrap();
+        app.select_by_text("A");
+        assert_eq!(&*app.selected_section_content(), "old text");
+        assert_eq!(app.section_cache.borrow().len(), 1);
+
+        // Same heading offset, different body
+        std::fs::write(file.path(), "# A\nnew text\n# B\n").unwrap();
+        app.reload_current_file().unwrap();
+        app.select_by_text("A");
+        assert_eq!(&*app.selected_section_content(), "new text");
+    }
+
+    #[test]
+    fn test_section_content_follows_include_subsections() {
+        let md = "# Guide\nIntro\n## Setup\nSteps\n# Notes\n";
+        let mut app = test_app(md);
+        app.select_by_text("Guide");
+        assert_eq!(&*app.selected_section_content(), "Intro\n## Setup\nSteps");
+
+        let mut app = test_app(md);
+        app.config.behavior.include_subsections = false;
+        app.select_by_text("Guide");
+        assert_eq!(&*app.selected_section_content(), "Intro");
+        app.select_by_text("Setup");
+        assert_eq!(&*app.selected_section_content(), "Steps");
+    }
+
+    #[test]
+    fn test_startup_picker_lists_recent_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let older = dir.path().join("older.md");
+        let newer = dir.path().join("newer.md");
+        std::fs::write(&older, "# Older\n").unwrap();
+        std::fs::write(&newer, "# Newer\n").unwrap();
+
+        let mut config = Config::default();
+        config.history.recent_files = vec![newer.clone(), older.clone()];
+        let mut app = App::new(
+            parse_markdown("# Select a file\n"),
+            "stdin".to_string(),
+            PathBuf::from("<stdin>"),
+            config,
+            ColorMode::Rgb,
+            false,
+        );
+        app.file_picker_recent = true;
+        app.enter_file_picker();
+
+        assert_eq!(app.files_in_directory, vec![newer, older.clone()]);
+        app.next_file();
+        app.select_file_from_picker().unwrap();
+
+        assert_eq!(app.current_file_path, older);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(!app.file_picker_recent);
+    }
+
+    #[test]
+    fn test_background_load_installs_document() {
+        let content = "# Big\n\n".to_string() + &"## Part\n\nText\n\n".repeat(1000);
+        let mut app = test_app("");
+        app.load_in_background(content.clone());
+        assert!(app.is_loading());
+        assert!(app.loading_status().is_some());
+
+        let deadline = Instant::now() + Duration::from_secs(60);
+        while !app.poll_loading() {
+            assert!(Instant::now() < deadline, "background load did not finish");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(!app.is_loading());
+        assert_eq!(app.document, crate::parse_markdown(&content));
+        assert_eq!(app.filename, "test.md");
+        assert_eq!(app.outline_items.len(), 1001);
+        assert_eq!(app.selected_heading_text(), Some("Big"));
+    }
+
+    #[test]
+    fn test_converted_files_are_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.org");
+        let org = "#+TITLE: Notes\n* Tasks\n| a | b |\n|---+---|\n| 1 | 2 |\n\
+                   #+BEGIN_SRC sh\nls\n#+END_SRC\n";
+        std::fs::write(&path, org).unwrap();
+
+        let mut app = test_app("");
+        app.current_file_path = path.clone();
+        app.reload_current_file().unwrap();
+        assert!(app.document.content.contains("# Tasks"));
+
+        // An edit to the converted markdown, as a table cell edit leaves it
+        app.document.content = app.document.content.replace("| 1 |", "| 9 |");
+        app.has_unsaved_changes = true;
+        let error = app.save_pending_edits_to_file().unwrap_err();
+        assert!(
+            error.contains("read-only: converted from org-mode"),
+            "{error}"
+        );
+        assert!(app.has_unsaved_changes);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), org);
+
+        // Markdown files still save
+        let md = dir.path().join("notes.md");
+        std::fs::write(&md, "# Tasks\n").unwrap();
+        app.current_file_path = md.clone();
+        app.reload_current_file().unwrap();
+        app.document.content.push_str("more\n");
+        app.has_unsaved_changes = true;
+        app.save_pending_edits_to_file().unwrap();
+        assert_eq!(std::fs::read_to_string(&md).unwrap(), "# Tasks\nmore\n");
+    }
+
+    #[test]
+    fn test_quitting_mid_load_keeps_saved_position() {
+        use crate::config::FilePosition;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("big.md");
+        let content = "# Big\n\n".to_string() + &"## Part\n\nText\n\n".repeat(1000);
+        std::fs::write(&file, &content).unwrap();
+        let file = file.canonicalize().unwrap();
+        let saved = FilePosition {
+            heading: Some("Part".to_string()),
+            content_scroll: 3,
+        };
+
+        let mut config = Config::default();
+        config.history.recent_files = vec![file.clone()];
+        config.history.positions.insert(file.clone(), saved.clone());
+        let mut app = App::new(
+            Document::new(String::new(), Vec::new()),
+            "big.md".to_string(),
+            file.clone(),
+            config,
+            ColorMode::Rgb,
+            false,
+        );
+        app.load_in_background(content);
+
+        // The placeholder's position is never recorded
+        assert!(app.is_loading());
+        assert_eq!(app.current_file_position(), None);
+        assert_eq!(app.config.file_position(&file), Some(&saved));
+
+        let deadline = Instant::now() + Duration::from_secs(60);
+        while !app.poll_loading() {
+            assert!(Instant::now() < deadline, "background load did not finish");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        // Restored once loaded (the short section clamps the scroll)
+        assert_eq!(app.selected_heading_text(), Some("Part"));
+    }
+
+    #[test]
+    fn test_outline_matches_tree_iteration_order() {
+        let app = test_app("# A\n## A1\n### A1a\n## A2\n# B\n### B-deep\n");
+        let from_iter: Vec<&str> = app
+            .document
+            .iter_tree()
+            .map(|(_, heading)| heading.text.as_str())
+            .collect();
+        assert_eq!(outline_texts(&app), from_iter);
+    }
+
+    #[test]
+    fn test_incremental_toggle_matches_full_rebuild() {
+        let mut app = test_app(
+            "Intro\n# A\n## Notes\n### N1\n## A2\n### A2a\n# B\n## Notes\n### N2\n## B2\n",
+        );
+        app.focus = Focus::Outline;
+        let snapshot = |items: &[OutlineItem]| -> Vec<(String, usize, bool)> {
+            items
+                .iter()
+                .map(|item| (item.text.clone(), item.level, item.expanded))
+                .collect()
+        };
+        let assert_consistent = |app: &mut App| {
+            let incremental = snapshot(&app.outline_items);
+            app.rebuild_outline_items();
+            assert_eq!(incremental, snapshot(&app.outline_items));
+        };
+
+        // Collapsing the second "Notes" folds both, keeping the selection on it
+        let second_notes = app.outline_items.iter().rposition(|i| i.text == "Notes");
+        app.select_outline_index(second_notes.unwrap());
+        app.toggle_expand();
+        assert_eq!(app.selected_heading_offset(), app.outline_items[6].offset);
+        assert_eq!(app.outline_items.len(), 8);
+        assert_consistent(&mut app);
+
+        // Collapsing from a leaf folds its parent and selects it
+        app.select_by_text("A2a");
+        app.collapse();
+        assert_eq!(app.selected_heading_text(), Some("A2"));
+        assert_consistent(&mut app);
+
+        // Folding and unfolding "A" restores its children with their own state
+        app.select_by_text("A");
+        app.toggle_expand();
+        assert_eq!(
+            outline_texts(&app),
+            vec![DOCUMENT_OVERVIEW, "A", "B", "Notes", "B2"]
+        );
+        app.expand();
+        assert_eq!(app.selected_heading_text(), Some("A"));
+        assert_consistent(&mut app);
+        assert_eq!(
+            outline_texts(&app),
+            vec![DOCUMENT_OVERVIEW, "A", "Notes", "A2", "B", "Notes", "B2"]
+        );
+
+        // The overview entry never folds away the document
+        app.select_outline_index(0);
+        app.toggle_expand();
+        assert_eq!(app.outline_items.len(), 7);
+        assert_consistent(&mut app);
+    }
+
+    #[test]
+    fn test_focus_mode_expands_only_selected_section() {
+        let mut app = test_app("Intro\n\n# A\n\nalpha\n\n## A1\n\nnested\n\nB\n=\n\nbeta\n");
+        app.toggle_focus_mode();
+        app.update_content_metrics();
+        assert_eq!(app.selected_heading_text(), Some(DOCUMENT_OVERVIEW));
+        assert_eq!(
+            &*app.selected_section_content(),
+            "Intro\n\n# A\n\n## A1\n\n# B"
+        );
+        assert_eq!(app.content_scroll, 0);
+
+        app.select_by_text("A");
+        app.update_content_metrics();
+        assert_eq!(
+            &*app.selected_section_content(),
+            "# A\n\nalpha\n\n## A1\n\nnested\n\n# B"
+        );
+        assert_eq!(app.content_scroll, 0);
+
+        // Selecting another heading moves the view to it
+        app.select_by_text("B");
+        app.update_content_metrics();
+        assert_eq!(
+            &*app.selected_section_content(),
+            "# A\n\n## A1\n\nB\n=\n\nbeta"
+        );
+        assert_eq!(app.content_scroll, 4);
+
+        app.toggle_focus_mode();
+        app.select_by_text("A1");
+        assert_eq!(&*app.selected_section_content(), "nested");
+    }
+
+    #[test]
+    fn test_containing_dir_of_current_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.md");
+        std::fs::write(&file, "# Notes\n").unwrap();
+
+        let mut app = test_app("# Notes\n");
+        app.current_file_path = file;
+        assert_eq!(
+            app.containing_dir(),
+            Some(dir.path().canonicalize().unwrap())
+        );
+
+        // Documents read from stdin have no folder
+        app.current_file_path = PathBuf::from("<stdin>");
+        assert_eq!(app.containing_dir(), None);
+        app.open_containing_dir();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✗ No folder to open for this document")
+        );
+    }
+
+    #[test]
+    fn test_reload_on_demand_reads_file_from_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.md");
+        std::fs::write(&file, "# Notes\n").unwrap();
+
+        let mut app = test_app("# Notes\n");
+        app.current_file_path = file.clone();
+        assert!(app.auto_reload);
+
+        std::fs::write(&file, "# Notes\n\n## Added\n").unwrap();
+        app.reload_on_demand();
+        assert_eq!(app.document.headings.len(), 2);
+        assert_eq!(app.status_message.as_deref(), Some("↻ File reloaded"));
+
+        std::fs::remove_file(&file).unwrap();
+        app.reload_on_demand();
+        assert!(app.status_message.unwrap().starts_with("✗ Reload failed"));
+    }
+
+    #[test]
+    fn test_export_visible_respects_fold_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_app(
+            "Intro\n\n# A\n\na text\n\n## B\n\nb text\n\n### B1\n\nb1 text\n\n## C\n\nc text\n",
+        );
+        app.current_file_path = dir.path().join("doc.md");
+        app.collapsed_headings.insert("B".to_string());
+
+        let expected = "Intro\n\n# A\n\na text\n\n## B\n\n## C\n\nc text\n";
+        assert_eq!(app.visible_markdown(), expected);
+
+        app.open_command_palette();
+        for c in "export out.md".chars() {
+            app.command_palette_input(c);
+        }
+        app.execute_selected_command();
+        let written = dir.path().join("out.md");
+        assert_eq!(std::fs::read_to_string(&written).unwrap(), expected);
+
+        // Existing files are left alone
+        app.export_visible(Some("out.md"));
+        assert!(app.status_message.unwrap().ends_with("already exists"));
+    }
+
+    #[test]
+    fn test_file_history_is_capped_at_history_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.ui.history_depth = 3;
+        let mut app = test_app("# Start\n");
+        app.config = config;
+
+        for i in 0..6 {
+            let path = dir.path().join(format!("{}.md", i));
+            std::fs::write(&path, format!("# File {}\n", i)).unwrap();
+            app.load_file(&path, None).unwrap();
+            assert!(app.file_history.len() <= 3);
+        }
+        // The oldest files were dropped; the most recent ones remain
+        let kept: Vec<_> = app
+            .file_history
+            .iter()
+            .map(|s| s.filename.as_str())
+            .collect();
+        assert_eq!(kept, ["2.md", "3.md", "4.md"]);
+
+        while app.go_back().is_ok() {
+            assert!(app.file_future.len() <= 3);
+        }
+        assert_eq!(app.filename, "2.md");
+        assert_eq!(app.file_future.len(), 3);
+    }
+
+    #[test]
+    fn test_theme_picker_previews_and_cancel_restores() {
+        let mut app = test_app("# A\n");
+        let original = app.current_theme;
+        let original_bg = app.theme.background;
+
+        app.execute_action(Action::ToggleThemePicker);
+        app.execute_action(Action::ThemePickerNext);
+        let previewed = app.current_theme;
+        assert_ne!(previewed, original);
+        assert_eq!(app.theme.name, Theme::from_name(previewed).name);
+        assert_ne!(app.theme.background, original_bg);
+
+        // Esc puts the theme that was active before the picker back
+        app.execute_action(Action::ToggleThemePicker);
+        assert!(!app.show_theme_picker);
+        assert_eq!(app.current_theme, original);
+        assert_eq!(app.theme.background, original_bg);
+    }
+
+    #[test]
+    fn test_count_prefix_jumps_to_heading_number() {
+        let content: String = (1..=50).map(|i| format!("# H{}\n\ntext\n\n", i)).collect();
+        let mut app = test_app(&content);
+
+        for c in "42".chars() {
+            app.accumulate_count_digit(c);
+        }
+        app.execute_action(Action::Last);
+        assert_eq!(app.selected_heading_text(), Some("H42"));
+        assert!(!app.has_count());
+
+        // Enter works too, and numbers past the end clamp to the last heading
+        for c in "420".chars() {
+            app.accumulate_count_digit(c);
+        }
+        app.execute_action(Action::ToggleExpand);
+        assert_eq!(app.selected_heading_text(), Some("H50"));
+
+        // Without a count, G still goes to the end and Enter still toggles
+        app.first();
+        app.execute_action(Action::Last);
+        assert_eq!(app.selected_heading_text(), Some("H50"));
+
+        app.open_command_palette();
+        app.command_palette_input('7');
+        app.execute_selected_command();
+        assert_eq!(app.selected_heading_text(), Some("H7"));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_pending_chord_expires_after_timeout() {
+        let mut config = Config::default();
+        config.ui.chord_timeout_ms = 50;
+        config.keybindings = toml::from_str("[Normal]\n\"Ctrl+g Ctrl+g\" = \"Last\"").unwrap();
+        let mut app = App::new(
+            parse_markdown("# A\n\n## B\n"),
+            "test.md".to_string(),
+            PathBuf::from("test.md"),
+            config,
+            ColorMode::Rgb,
+            false,
+        );
+        let ctrl_g =
+            |app: &mut App| app.get_action_for_key(KeyCode::Char('g'), KeyModifiers::CONTROL);
+
+        assert_eq!(ctrl_g(&mut app), None);
+        app.expire_pending_chord();
+        assert!(app.keybindings.is_sequence_ongoing(KeybindingMode::Normal));
+        assert_eq!(ctrl_g(&mut app), Some(Action::Last));
+
+        assert_eq!(ctrl_g(&mut app), None);
+        std::thread::sleep(Duration::from_millis(60));
+        app.expire_pending_chord();
+        assert!(!app.keybindings.is_sequence_ongoing(KeybindingMode::Normal));
+    }
+
+    #[test]
+    fn test_custom_link_resolver_controls_navigation() {
+        struct VaultIndex(PathBuf);
+
+        impl LinkResolver for VaultIndex {
+            fn resolve(&self, link: &Link, _base: &Path) -> ResolvedLink {
+                match &link.target {
+                    LinkTarget::WikiLink { target, .. } => ResolvedLink::File {
+                        path: self.0.join(format!("{}.md", target.to_lowercase())),
+                        anchor: None,
+                    },
+                    _ => ResolvedLink::Rejected("Only wikilinks are supported".to_string()),
+                }
+            }
+        }
+
+        let vault = tempfile::tempdir().unwrap();
+        std::fs::write(vault.path().join("setup.md"), "# Setup Guide\n").unwrap();
+
+        let mut app = test_app("# Intro\n\nSee [[Setup]] and [docs](docs.md).\n");
+        app.set_link_resolver(VaultIndex(vault.path().to_path_buf()));
+
+        let links = extract_links(&app.document.content);
+        app.follow_link(&links[0]).unwrap();
+        assert_eq!(app.filename, "setup.md");
+        assert_eq!(app.document.headings[0].text, "Setup Guide");
+        assert_eq!(app.status_message.as_deref(), Some("✓ Opened [[Setup]]"));
+
+        assert_eq!(
+            app.follow_link(&links[1]),
+            Err("Only wikilinks are supported".to_string())
+        );
+    }
+
+    #[test]
+    fn test_table_to_csv_quotes_special_cells() {
+        let headers = vec!["Name".to_string(), "Notes".to_string()];
+        let rows = vec![
+            vec!["a, b".to_string(), "say \"hi\"".to_string()],
+            vec!["x | y".to_string(), "line\nbreak".to_string()],
+            vec!["short".to_string()],
+        ];
+
+        assert_eq!(
+            App::table_to_csv(&headers, &rows),
+            "Name,Notes\r\n\"a, b\",\"say \"\"hi\"\"\"\r\nx | y,\"line\nbreak\"\r\nshort,\r\n"
+        );
+    }
+
+    #[test]
+    fn test_cell_edit_backspace_removes_whole_graphemes() {
+        let mut app = test_app("# Title\n");
+        app.mode = AppMode::CellEdit;
+
+        app.cell_edit_value = "ab\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}".to_string();
+        app.handle_search_backspace();
+        assert_eq!(app.cell_edit_value, "ab");
+
+        app.cell_edit_value = "cafe\u{0301}".to_string();
+        app.handle_search_backspace();
+        assert_eq!(app.cell_edit_value, "caf");
+
+        app.cell_edit_value.clear();
+        app.handle_search_backspace();
+        assert_eq!(app.cell_edit_value, "");
+    }
+
+    #[test]
+    fn test_copies_go_through_custom_clipboard_backend() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct Recorder(Rc<RefCell<Vec<String>>>);
+
+        impl ClipboardBackend for Recorder {
+            fn set_text(&mut self, text: &str) -> Result<(), String> {
+                self.0.borrow_mut().push(text.to_string());
+                Ok(())
+            }
+        }
+
+        let copies = Rc::new(RefCell::new(Vec::new()));
+        let mut app = test_app("# Getting Started\n\nBody\n");
+        app.set_clipboard_backend(Recorder(copies.clone()));
+
+        app.copy_anchor();
+        app.copy_content_html();
+        assert_eq!(copies.borrow()[0], "#getting-started");
+        // HTML is unsupported by the backend, so the markdown is copied instead
+        assert!(copies.borrow()[1].starts_with("# Getting Started"));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✓ Section copied as markdown (HTML clipboard not supported)")
+        );
+    }
+
+    #[test]
+    fn test_copies_into_registers() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct Recorder(Rc<RefCell<Vec<String>>>);
+
+        impl ClipboardBackend for Recorder {
+            fn set_text(&mut self, text: &str) -> Result<(), String> {
+                self.0.borrow_mut().push(text.to_string());
+                Ok(())
+            }
+        }
+
+        let copies = Rc::new(RefCell::new(Vec::new()));
+        let mut app = test_app("# Install\nRun it\n# Usage\nUse it\n");
+        app.set_clipboard_backend(Recorder(copies.clone()));
+        let press = |app: &mut App, c: char| {
+            if !app.handle_register_key(KeyCode::Char(c)) {
+                let action = app.get_action_for_key(KeyCode::Char(c), KeyModifiers::NONE);
+                app.execute_action(action.unwrap());
+            }
+        };
+
+        app.select_by_text("Install");
+        for c in ['"', 'a', 'y'] {
+            press(&mut app, c);
+        }
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✓ Copied to register \"a (1 line)")
+        );
+
+        // Uppercase appends; other actions drop the register
+        app.select_by_text("Usage");
+        for c in ['"', 'A', 'y', '"', 'b', 'j', 'y'] {
+            press(&mut app, c);
+        }
+        assert_eq!(app.registers.get(&'a').unwrap(), "Run it\n\nUse it");
+        assert!(!app.registers.contains_key(&'b'));
+        assert_eq!(*copies.borrow(), vec!["Use it"]);
+
+        press(&mut app, '"');
+        press(&mut app, '1');
+        assert_eq!(app.status_message.as_deref(), Some("✗ Invalid register: 1"));
+
+        app.show_or_copy_registers(None);
+        assert_eq!(app.status_message.as_deref(), Some("Registers: \"a Run it"));
+        app.show_or_copy_registers(Some("b"));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✗ Register \"b is empty")
+        );
+        app.show_or_copy_registers(Some("a"));
+        assert_eq!(copies.borrow().last().unwrap(), "Run it\n\nUse it");
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✓ Copied register \"a to clipboard")
+        );
+    }
+
+    #[test]
+    fn test_link_follow_includes_images() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct Recorder(Rc<RefCell<Vec<String>>>);
+
+        impl ClipboardBackend for Recorder {
+            fn set_text(&mut self, text: &str) -> Result<(), String> {
+                self.0.borrow_mut().push(text.to_string());
+                Ok(())
+            }
+        }
+
+        let copies = Rc::new(RefCell::new(Vec::new()));
+        let mut app =
+            test_app("# Gallery\n\n![Diagram](img/arch.png) and [Home](https://example.com)\n");
+        app.set_clipboard_backend(Recorder(copies.clone()));
+        app.images_enabled = false;
+
+        app.enter_link_follow_mode();
+        let links: Vec<_> = app.links_in_view.iter().map(|l| l.image).collect();
+        assert_eq!(links, vec![true, false]);
+
+        // y copies the selected image's source, then the next link's URL
+        app.execute_action(Action::CopyContent);
+        app.execute_action(Action::NextLink);
+        app.execute_action(Action::CopyContent);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✓ URL copied: https://example.com")
+        );
+
+        // Following an image that can't be viewed copies its source
+        app.execute_action(Action::PreviousLink);
+        app.execute_action(Action::FollowLink);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✓ Image source copied (images disabled): img/arch.png")
+        );
+        assert_eq!(
+            *copies.borrow(),
+            vec!["img/arch.png", "https://example.com", "img/arch.png"]
+        );
+
+        // Outside link follow mode y still copies the section
+        app.execute_action(Action::CopyContent);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✓ Section copied to clipboard")
+        );
+    }
+
+    #[test]
+    fn test_copy_file_anchor_is_relative_to_project_root() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct Recorder(Rc<RefCell<Vec<String>>>);
+
+        impl ClipboardBackend for Recorder {
+            fn set_text(&mut self, text: &str) -> Result<(), String> {
+                self.0.borrow_mut().push(text.to_string());
+                Ok(())
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs guide");
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::create_dir_all(&docs).unwrap();
+        let file = docs.join("setup.md");
+        std::fs::write(&file, "# Getting Started\n").unwrap();
+
+        // The project root wins over the working directory
+        assert_eq!(
+            project_relative_path(&file, Some(&docs)),
+            "docs guide/setup.md"
+        );
+        let outside = Path::new("/elsewhere/notes.md");
+        assert_eq!(
+            project_relative_path(outside, Some(Path::new("/elsewhere"))),
+            "notes.md"
+        );
+        assert_eq!(project_relative_path(outside, None), "/elsewhere/notes.md");
+
+        let copies = Rc::new(RefCell::new(Vec::new()));
+        let mut app = test_app("# Getting Started\n");
+        app.set_clipboard_backend(Recorder(copies.clone()));
+        app.copy_file_anchor(false);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✗ No file to reference (reading from stdin)")
+        );
+
+        app.current_file_path = file;
+        app.execute_action(Action::CopyFileAnchor);
+        app.copy_file_anchor(true);
+        assert_eq!(
+            *copies.borrow(),
+            [
+                "docs guide/setup.md#getting-started",
+                "[Getting Started](<docs guide/setup.md#getting-started>)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_event_writer_emits_json_lines() {
+        use std::sync::mpsc;
+
+        struct ChannelWriter(mpsc::Sender<String>);
+
+        impl std::io::Write for ChannelWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                let _ = self.0.send(String::from_utf8_lossy(buf).into_owned());
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mut app = test_app("# One\n\n# Two\n");
+        app.set_event_writer(ChannelWriter(tx));
+        app.update_content_metrics();
+        app.next();
+        app.update_content_metrics();
+        // No selection change, no event
+        app.update_content_metrics();
+
+        let events: Vec<serde_json::Value> = (0..3)
+            .map(|_| {
+                let line = rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+                serde_json::from_str(&line).unwrap()
+            })
+            .collect();
+        let kinds: Vec<_> = events
+            .iter()
+            .map(|e| e["event"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            kinds,
+            ["file_loaded", "section_selected", "section_selected"]
+        );
+        assert_eq!(events[2]["file"], "test.md");
+        assert_eq!(events[2]["heading"], "Two");
+        assert!(events[2]["timestamp"].as_u64().unwrap() > 0);
+        assert!(
+            rx.recv_timeout(std::time::Duration::from_millis(100))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_task_progress_for_selection_and_outline() {
+        let md = "# Sprint\n- [x] plan\n- [ ] build\n## Tests\n- [x] unit\n# Notes\ntext\n";
+        let mut app = test_app(md);
+        assert_eq!(
+            app.selected_task_stats(),
+            Some(TaskStats { done: 2, total: 3 })
+        );
+        // The outline indicator is opt-in
+        assert_eq!(app.outline_task_stats(&app.outline_items[0]), None);
+
+        app.last();
+        assert_eq!(app.selected_task_stats(), None);
+
+        let mut config = Config::default();
+        config.ui.outline_task_progress = true;
+        let app = App::new(
+            parse_markdown(md),
+            "test.md".to_string(),
+            PathBuf::from("test.md"),
+            config,
+            ColorMode::Rgb,
+            false,
+        );
+        let stats: Vec<_> = app
+            .outline_items
+            .iter()
+            .map(|item| app.outline_task_stats(item))
+            .collect();
+        assert_eq!(
+            stats,
+            [
+                Some(TaskStats { done: 2, total: 3 }),
+                Some(TaskStats { done: 1, total: 1 }),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_activating_code_block_toggles_collapse() {
+        let mut app = test_app("# Code\n\n```rust\nfn main() {}\n```\n");
+        app.enter_interactive_mode();
+        let id = app.interactive_state.current_element().unwrap().id;
+
+        app.activate_interactive_element().unwrap();
+        assert!(app.interactive_state.is_code_collapsed(id));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✓ Code block collapsed")
+        );
+
+        app.activate_interactive_element().unwrap();
+        assert!(!app.interactive_state.is_code_collapsed(id));
+    }
+
+    #[test]
+    fn test_activating_data_fold_toggles_it() {
+        use crate::tui::interactive::ElementType;
+
+        let mut app = test_app("# Data\n\n```yaml\nserver:\n  port: 80\nname: x\n```\n");
+        app.interactive_state.set_fold_data_blocks(true);
+        app.enter_interactive_mode();
+        app.execute_action(Action::InteractiveNext);
+        let fold = app.interactive_state.current_element().unwrap().clone();
+        assert!(matches!(
+            fold.element_type,
+            ElementType::DataFold {
+                line: 0,
+                folded: false,
+                ..
+            }
+        ));
+
+        app.execute_action(Action::InteractiveActivate);
+        assert_eq!(app.status_message.as_deref(), Some("✓ Folded"));
+        let code = app.interactive_state.elements()[0].clone();
+        assert_eq!(code.line_range().len(), 4);
+        assert!(matches!(
+            app.interactive_state
+                .current_element()
+                .unwrap()
+                .element_type,
+            ElementType::DataFold { folded: true, .. }
+        ));
+
+        app.execute_action(Action::InteractiveActivate);
+        assert_eq!(app.status_message.as_deref(), Some("✓ Unfolded"));
+    }
+
+    #[test]
+    fn test_confirm_before_overwriting_external_changes() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "# Todo\n\n- [ ] task\n").unwrap();
+
+        let mut app = test_app("");
+        app.current_file_path = file.path().to_path_buf();
+        app.reload_current_file().unwrap();
+        app.select_by_text("Todo");
+        app.enter_interactive_mode();
+        assert!(!app.check_disk_changed());
+
+        // Another program edits the file
+        std::fs::write(file.path(), "# Todo\n\n- [ ] task\n\nexternal\n").unwrap();
+        let later = SystemTime::now() + Duration::from_secs(10);
+        std::fs::File::options()
+            .write(true)
+            .open(file.path())
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        // Toggling asks first; cancelling leaves the file alone
+        app.activate_interactive_element().unwrap();
+        assert_eq!(app.mode, AppMode::ConfirmOverwrite);
+        assert!(app.disk_changed);
+        app.handle_cancel_action();
+        assert_eq!(app.mode, AppMode::Interactive);
+        assert!(
+            std::fs::read_to_string(file.path())
+                .unwrap()
+                .contains("- [ ] task")
+        );
+
+        // Confirming writes, keeping the external edit
+        app.activate_interactive_element().unwrap();
+        assert!(app.handle_confirm_action().is_none());
+        assert_eq!(app.mode, AppMode::Interactive);
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            "# Todo\n\n- [x] task\n\nexternal\n"
+        );
+        assert!(!app.disk_changed);
+    }
+
+    #[test]
+    fn test_overview_hides_collapsed_sections() {
+        let mut app = test_app("Intro\n\n# A\n\na text\n\n## B\n\nb text\n\n### B1\n\nb1 text\n");
+        assert_eq!(app.selected_heading_text(), Some(DOCUMENT_OVERVIEW));
+        app.update_content_metrics();
+        assert!(app.selected_section_content().contains("b1 text"));
+
+        app.collapse_level(2);
+        assert_eq!(
+            &*app.selected_section_content(),
+            "Intro\n\n# A\n\na text\n\n## B\n"
+        );
+        // The overview is re-laid out for the new content
+        assert_eq!(app.previous_selection, None);
+
+        app.expand_all();
+        assert_eq!(&*app.selected_section_content(), app.document.content);
+    }
+
+    #[test]
+    fn test_new_restores_saved_file_position() {
+        use crate::config::FilePosition;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("guide.md");
+        let body = "line\n\n".repeat(30);
+        let content = format!("# Intro\n\nHi\n\n# Usage\n\n{body}");
+        std::fs::write(&file, &content).unwrap();
+        let file = file.canonicalize().unwrap();
+
+        let open = |heading: &str| {
+            let mut config = Config::default();
+            config.history.recent_files = vec![file.clone()];
+            config.history.positions.insert(
+                file.clone(),
+                FilePosition {
+                    heading: Some(heading.to_string()),
+                    content_scroll: 7,
+                },
+            );
+            App::new(
+                parse_markdown(&content),
+                "guide.md".to_string(),
+                file.clone(),
+                config,
+                ColorMode::Rgb,
+                false,
+            )
+        };
+
+        let mut app = open("Usage");
+        assert_eq!(app.selected_heading_text(), Some("Usage"));
+        app.update_content_metrics();
+        assert_eq!(app.content_scroll, 7);
+
+        // A heading that no longer exists leaves the first item selected
+        let app = open("Removed");
+        assert_eq!(app.selected_heading_text(), Some("Intro"));
+        assert_eq!(app.content_scroll, 0);
+    }
+
+    #[test]
+    fn test_inline_checkboxes_are_interactive_and_toggle_in_place() {
+        use crate::tui::interactive::ElementType;
+
+        let md =
+            "# Tasks\n\nShip it [x] and test [ ]\n\n| Task | Done |\n|---|---|\n| docs | [ ] |\n";
+        let mut app = test_app(md);
+        app.next();
+        app.enter_interactive_mode();
+
+        let checkboxes: Vec<(bool, &str)> = app
+            .interactive_state
+            .elements
+            .iter()
+            .filter_map(|e| match &e.element_type {
+                ElementType::InlineCheckbox {
+                    checked, context, ..
+                } => Some((*checked, context.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            checkboxes,
+            [
+                (true, "Ship it [x] and test [ ]"),
+                (false, "Ship it [x] and test [ ]"),
+                (false, "| docs | [ ] |"),
+            ]
+        );
+
+        let file = "Ship it [x] and test [ ]\r\n| docs | [ ] |\r\n";
+        assert_eq!(
+            toggle_inline_checkbox_on_line(file, "Ship it [x] and test [ ]", 1, false).unwrap(),
+            "Ship it [x] and test [x]\r\n| docs | [ ] |\r\n"
+        );
+        assert_eq!(
+            toggle_inline_checkbox_on_line(file, "| docs | [ ] |", 0, false).unwrap(),
+            "Ship it [x] and test [ ]\r\n| docs | [x] |\r\n"
+        );
+        assert!(toggle_inline_checkbox_on_line(file, "| docs | [ ] |", 0, true).is_err());
+    }
+
+    #[test]
+    fn test_footnotes_jump_between_reference_and_definition() {
+        use crate::tui::interactive::ElementType;
+
+        let md = "# Notes\n\nClaim[^1] and guess[^x].\n\nMore text\n\nAgain[^1].\n\n[^1]: The first source.\n";
+        let mut app = test_app(md);
+        app.next();
+        app.update_content_metrics();
+        app.enter_interactive_mode();
+
+        let footnotes: Vec<(&str, bool)> = app
+            .interactive_state
+            .elements
+            .iter()
+            .filter_map(|e| match &e.element_type {
+                ElementType::Footnote {
+                    label, definition, ..
+                } => Some((label.as_str(), *definition)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            footnotes,
+            [("1", false), ("x", false), ("1", false), ("1", true)]
+        );
+        let hint = |app: &App| app.interactive_state.get_status_hint();
+
+        // Reference to definition, and back to the first reference
+        app.execute_action(Action::InteractiveActivate);
+        assert_eq!(hint(&app), "Footnote def: [^1]");
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✓ Footnote [^1] definition")
+        );
+        app.execute_action(Action::InteractiveActivate);
+        assert_eq!(app.interactive_state.current_index, Some(0));
+
+        // An undefined reference stays put with a warning
+        app.interactive_state.next();
+        app.execute_action(Action::InteractiveActivate);
+        assert_eq!(hint(&app), "Footnote: [^x]");
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("⚠ Footnote [^x] has no definition in this section")
+        );
+    }
+
+    #[test]
+    fn test_edit_link_target_opens_editor_at_heading() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("guide.md"),
+            "# Guide\n\nintro\n\n## Setup\n\nsteps\n",
+        )
+        .unwrap();
+        let md = "# Index\n\n[setup](guide.md#setup) [guide](guide.md#missing) [web](https://example.com)\n\n## Local\n";
+        let mut app = test_app(md);
+        app.current_file_path = dir.path().join("index.md");
+        app.enter_link_follow_mode();
+
+        let edit = |app: &mut App| match app.execute_action(Action::EditLinkTarget) {
+            ActionResult::RunEditor(path, line) => Some((path, line)),
+            _ => None,
+        };
+        assert_eq!(edit(&mut app), Some((dir.path().join("guide.md"), Some(5))));
+        assert_eq!(app.mode, AppMode::Normal);
+
+        // Anchors that don't resolve open the file at the top
+        app.enter_link_follow_mode();
+        app.next_link();
+        assert_eq!(edit(&mut app), Some((dir.path().join("guide.md"), Some(1))));
+
+        app.enter_link_follow_mode();
+        app.next_link();
+        app.next_link();
+        assert_eq!(edit(&mut app), None);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("✗ Error: https://example.com is not a local file")
+        );
+    }
+}
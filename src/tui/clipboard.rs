@@ -0,0 +1,246 @@
+//! Clipboard, browser and file manager access, available with the
+//! `interactivity` feature.
+//!
+//! Builds without the feature keep the same API; every operation fails with
+//! a message explaining that it is disabled in this build. The OSC 52
+//! clipboard only talks to the terminal and works in every build.
+
+use std::io::Write;
+
+/// Message shown when no clipboard can be used
+#[cfg(feature = "interactivity")]
+pub const UNAVAILABLE: &str = "Clipboard not available";
+#[cfg(not(feature = "interactivity"))]
+pub const UNAVAILABLE: &str = "Clipboard disabled in this build";
+
+/// Destination for copied text.
+///
+/// [`App`](crate::App) copies through the backend chosen by `ui.clipboard`
+/// (`"system"` or `"osc52"`); embedders can install their own with
+/// [`App::set_clipboard_backend`](crate::App::set_clipboard_backend).
+pub trait ClipboardBackend {
+    /// Replace the clipboard contents with `text`.
+    fn set_text(&mut self, text: &str) -> Result<(), String>;
+
+    /// Replace the clipboard contents with `html`, with `alt_text` for
+    /// applications that only paste plain text.
+    ///
+    /// Backends without HTML support keep this default, which fails so
+    /// callers fall back to [`set_text`](Self::set_text).
+    fn set_html(&mut self, _html: &str, _alt_text: &str) -> Result<(), String> {
+        Err("HTML clipboard not supported".to_string())
+    }
+}
+
+/// Create the backend named by the `ui.clipboard` setting.
+///
+/// `"osc52"` selects [`Osc52Clipboard`]; anything else uses the system
+/// clipboard, or `None` when it can't be reached.
+pub fn backend(name: &str) -> Option<Box<dyn ClipboardBackend>> {
+    if name.eq_ignore_ascii_case("osc52") {
+        return Some(Box::new(Osc52Clipboard::new()));
+    }
+    SystemClipboard::new()
+        .ok()
+        .map(|clipboard| Box::new(clipboard) as Box<dyn ClipboardBackend>)
+}
+
+/// System clipboard handle.
+///
+/// On Linux the handle must stay alive to serve paste requests, so keep it
+/// around instead of creating one per copy.
+pub struct SystemClipboard {
+    #[cfg(feature = "interactivity")]
+    inner: arboard::Clipboard,
+}
+
+impl SystemClipboard {
+    /// Connect to the system clipboard.
+    #[cfg(feature = "interactivity")]
+    pub fn new() -> Result<Self, String> {
+        arboard::Clipboard::new()
+            .map(|inner| Self { inner })
+            .map_err(|e| e.to_string())
+    }
+
+    /// Connect to the system clipboard.
+    #[cfg(not(feature = "interactivity"))]
+    pub fn new() -> Result<Self, String> {
+        Err(UNAVAILABLE.to_string())
+    }
+}
+
+impl ClipboardBackend for SystemClipboard {
+    #[cfg(feature = "interactivity")]
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        self.inner.set_text(text).map_err(|e| e.to_string())
+    }
+
+    #[cfg(not(feature = "interactivity"))]
+    fn set_text(&mut self, _text: &str) -> Result<(), String> {
+        Err(UNAVAILABLE.to_string())
+    }
+
+    #[cfg(feature = "interactivity")]
+    fn set_html(&mut self, html: &str, alt_text: &str) -> Result<(), String> {
+        self.inner
+            .set_html(html, Some(alt_text))
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Clipboard set by the terminal through an OSC 52 escape sequence.
+///
+/// Needs no local clipboard, so it works over SSH and in headless sessions
+/// as long as the terminal supports OSC 52. Inside tmux (`$TMUX` set), the
+/// sequence is wrapped in tmux's DCS passthrough so it reaches the outer
+/// terminal even with tmux's default `set-clipboard external`; tmux 3.3 and
+/// later also need `set -g allow-passthrough on`.
+pub struct Osc52Clipboard {
+    out: Box<dyn Write>,
+    /// Wrap sequences for tmux passthrough
+    tmux: bool,
+}
+
+impl Osc52Clipboard {
+    /// Send sequences to standard output, where the TUI draws.
+    pub fn new() -> Self {
+        Self::with_writer(std::io::stdout()).tmux(std::env::var_os("TMUX").is_some())
+    }
+
+    /// Send sequences to `out` instead of standard output.
+    pub fn with_writer(out: impl Write + 'static) -> Self {
+        Self {
+            out: Box::new(out),
+            tmux: false,
+        }
+    }
+
+    /// Wrap sequences in tmux's passthrough, as [`new`](Self::new) does
+    /// when `$TMUX` is set.
+    pub fn tmux(mut self, tmux: bool) -> Self {
+        self.tmux = tmux;
+        self
+    }
+}
+
+impl Default for Osc52Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClipboardBackend for Osc52Clipboard {
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        let sequence = format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()));
+        let sequence = if self.tmux {
+            // Escapes inside the passthrough are doubled
+            format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+        } else {
+            sequence
+        };
+        self.out
+            .write_all(sequence.as_bytes())
+            .and_then(|()| self.out.flush())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Standard base64 with padding, as OSC 52 expects.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Open a URL in the default browser.
+#[cfg(feature = "interactivity")]
+pub fn open_url(url: &str) -> Result<(), String> {
+    // Use the `open` crate for safe URL opening (no shell injection)
+    open::that(url).map_err(|e| e.to_string())
+}
+
+/// Open a URL in the default browser.
+#[cfg(not(feature = "interactivity"))]
+pub fn open_url(_url: &str) -> Result<(), String> {
+    Err("Opening links is disabled in this build".to_string())
+}
+
+/// Show a directory in the system file manager.
+#[cfg(feature = "interactivity")]
+pub fn open_dir(dir: &std::path::Path) -> Result<(), String> {
+    open::that(dir).map_err(|e| e.to_string())
+}
+
+/// Show a directory in the system file manager.
+#[cfg(not(feature = "interactivity"))]
+pub fn open_dir(_dir: &std::path::Path) -> Result<(), String> {
+    Err("Opening folders is disabled in this build".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Writer whose output stays readable after it is handed to a backend
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_base64_padding() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode("héllo".as_bytes()), "aMOpbGxv");
+    }
+
+    #[test]
+    fn test_osc52_writes_escape_sequence() {
+        let buffer = SharedBuffer::default();
+        let mut clipboard = Osc52Clipboard::with_writer(buffer.clone());
+
+        clipboard.set_text("#anchor").unwrap();
+        assert_eq!(&*buffer.0.borrow(), b"\x1b]52;c;I2FuY2hvcg==\x07");
+        assert!(clipboard.set_html("<p>x</p>", "x").is_err());
+    }
+
+    #[test]
+    fn test_osc52_wraps_sequence_for_tmux() {
+        let buffer = SharedBuffer::default();
+        let mut clipboard = Osc52Clipboard::with_writer(buffer.clone()).tmux(true);
+
+        clipboard.set_text("#anchor").unwrap();
+        assert_eq!(
+            &*buffer.0.borrow(),
+            b"\x1bPtmux;\x1b\x1b]52;c;I2FuY2hvcg==\x07\x1b\\"
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Structural folding for JSON and YAML code blocks.
+//!
+//! With `ui.fold_data_blocks` on, interactive mode finds the nested objects
+//! and arrays in `json` and `yaml` code blocks and lets each be folded to a
+//! single line ending in `{…}` or `[…]`. Folding only hides source lines;
+//! the lines that stay visible keep their syntax highlighting.
+
+use std::collections::BTreeSet;
+
+/// A nested object or array spanning several lines of a code block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldRegion {
+    /// Line that opens the region, shown when folded
+    pub start: usize,
+    /// Last line of the region (inclusive)
+    pub end: usize,
+    /// Text appended to the start line when folded, e.g. `…},` or ` […]`
+    pub placeholder: String,
+}
+
+/// The fold regions of one code block and which of them are folded.
+#[derive(Debug, Clone)]
+pub struct DataFolds {
+    /// Code the regions were found in, to notice when the block changes
+    content: String,
+    regions: Vec<FoldRegion>,
+    /// Start lines of the folded regions
+    folded: BTreeSet<usize>,
+}
+
+impl DataFolds {
+    /// Fold regions for a code block in `language`, or `None` when the
+    /// language isn't JSON or YAML, the data is malformed or nothing nests.
+    pub fn new(language: Option<&str>, content: &str) -> Option<Self> {
+        let regions = match language?.to_ascii_lowercase().as_str() {
+            "json" => json_regions(content)?,
+            "yaml" | "yml" => yaml_regions(content)?,
+            _ => return None,
+        };
+        (!regions.is_empty()).then(|| Self {
+            content: content.to_string(),
+            regions,
+            folded: BTreeSet::new(),
+        })
+    }
+
+    /// Whether these folds were found in `content`.
+    pub fn matches(&self, content: &str) -> bool {
+        self.content == content
+    }
+
+    /// The region starting at `line`, if any.
+    pub fn region_at(&self, line: usize) -> Option<&FoldRegion> {
+        self.regions.iter().find(|region| region.start == line)
+    }
+
+    /// Whether the region starting at `line` is folded.
+    pub fn is_folded(&self, line: usize) -> bool {
+        self.folded.contains(&line)
+    }
+
+    /// Fold or unfold the region starting at `line`.
+    pub fn toggle(&mut self, line: usize) {
+        if self.region_at(line).is_some() && !self.folded.remove(&line) {
+            self.folded.insert(line);
+        }
+    }
+
+    /// The source lines left visible, in order, each with the placeholder
+    /// to append when a folded region starts there.
+    ///
+    /// ```
+    /// use treemd::tui::data_fold::DataFolds;
+    ///
+    /// let json = "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": 3\n}";
+    /// let mut folds = DataFolds::new(Some("json"), json).unwrap();
+    /// folds.toggle(1);
+    /// let lines: Vec<_> = folds.visible_lines().collect();
+    /// assert_eq!(lines, vec![(0, None), (1, Some("…],")), (5, None), (6, None)]);
+    /// ```
+    pub fn visible_lines(&self) -> impl Iterator<Item = (usize, Option<&str>)> + '_ {
+        let mut hidden_until = None;
+        (0..self.content.lines().count()).filter_map(move |line| {
+            if hidden_until.is_some_and(|end| line <= end) {
+                return None;
+            }
+            let folded = self
+                .region_at(line)
+                .filter(|region| self.folded.contains(&region.start));
+            hidden_until = folded.map(|region| region.end).or(hidden_until);
+            Some((line, folded.map(|region| region.placeholder.as_str())))
+        })
+    }
+}
+
+/// Regions between brackets opened and closed on different lines, for
+/// valid JSON only.
+fn json_regions(content: &str) -> Option<Vec<FoldRegion>> {
+    serde_json::from_str::<serde_json::Value>(content).ok()?;
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut regions: Vec<FoldRegion> = Vec::new();
+    // Line of each open bracket
+    let mut open: Vec<usize> = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for (line_idx, line) in lines.iter().enumerate() {
+        for c in line.chars() {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => in_string = true,
+                '{' | '[' => open.push(line_idx),
+                '}' | ']' => {
+                    let start = open.pop()?;
+                    if line_idx == start {
+                        continue;
+                    }
+                    let placeholder = format!("…{}", line.trim());
+                    // The outermost bracket opened on a line owns its fold
+                    match regions.iter_mut().find(|region| region.start == start) {
+                        Some(region) => {
+                            region.end = line_idx;
+                            region.placeholder = placeholder;
+                        }
+                        None => regions.push(FoldRegion {
+                            start,
+                            end: line_idx,
+                            placeholder,
+                        }),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    // Outer regions close last; keep them in line order
+    regions.sort_by_key(|region| region.start);
+    Some(regions)
+}
+
+/// Regions of more-indented lines under a line, for YAML indented with
+/// spaces. Block sequences written at their key's indentation belong to
+/// the key.
+fn yaml_regions(content: &str) -> Option<Vec<FoldRegion>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let indent = |line: &str| line.len() - line.trim_start_matches(' ').len();
+    let is_content = |line: &str| {
+        let trimmed = line.trim();
+        !trimmed.is_empty() && !trimmed.starts_with('#')
+    };
+    if lines
+        .iter()
+        .any(|line| line.trim_start_matches(' ').starts_with('\t'))
+    {
+        return None;
+    }
+
+    let mut regions = Vec::new();
+    for (start, line) in lines.iter().enumerate() {
+        if !is_content(line) {
+            continue;
+        }
+        let depth = indent(line);
+        let trimmed = line.trim();
+        let opens_key = trimmed.ends_with(':') && !trimmed.starts_with("- ");
+
+        let mut end = start;
+        let mut array = None;
+        for (idx, next) in lines.iter().enumerate().skip(start + 1) {
+            if !is_content(next) {
+                continue;
+            }
+            let next_trimmed = next.trim_start();
+            let sequence_item = next_trimmed.starts_with("- ") || next_trimmed == "-";
+            let child =
+                indent(next) > depth || (opens_key && indent(next) == depth && sequence_item);
+            if !child {
+                break;
+            }
+            array.get_or_insert(sequence_item);
+            end = idx;
+        }
+        if end > start {
+            let placeholder = match (trimmed.ends_with(':'), array) {
+                (true, Some(true)) => " […]",
+                (true, _) => " {…}",
+                _ => " …",
+            };
+            regions.push(FoldRegion {
+                start,
+                end,
+                placeholder: placeholder.to_string(),
+            });
+        }
+    }
+    Some(regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_regions_and_folding() {
+        let json = "{\n  \"name\": \"a{b\",\n  \"tags\": [\"x\", \"y\"],\n  \"deps\": {\n    \"serde\": [\n      1\n    ]\n  }\n}";
+        let mut folds = DataFolds::new(Some("json"), json).unwrap();
+        let starts: Vec<_> = folds.regions.iter().map(|r| (r.start, r.end)).collect();
+        assert_eq!(starts, vec![(0, 8), (3, 7), (4, 6)]);
+
+        folds.toggle(3);
+        let visible: Vec<_> = folds.visible_lines().collect();
+        assert_eq!(
+            visible,
+            vec![(0, None), (1, None), (2, None), (3, Some("…}")), (8, None)]
+        );
+
+        // Folding the whole document leaves one line
+        folds.toggle(0);
+        assert_eq!(folds.visible_lines().count(), 1);
+        folds.toggle(2); // not a region
+        assert!(!folds.is_folded(2));
+    }
+
+    #[test]
+    fn test_malformed_or_flat_data_is_not_foldable() {
+        assert!(DataFolds::new(Some("json"), "{\n  \"a\": 1,\n").is_none());
+        assert!(DataFolds::new(Some("json"), "[1, 2]").is_none());
+        assert!(DataFolds::new(Some("yaml"), "a:\n\tb: 1\n").is_none());
+        assert!(DataFolds::new(Some("rust"), "fn main() {\n}\n").is_none());
+        assert!(DataFolds::new(None, "{\n}\n").is_none());
+    }
+
+    #[test]
+    fn test_yaml_regions() {
+        let yaml = "server:\n  host: localhost\n  ports:\n  - 80\n  - 443\n# comment\nitems:\n  - name: a\n    size: 1\nlast: true";
+        let folds = DataFolds::new(Some("yml"), yaml).unwrap();
+        let regions: Vec<_> = folds
+            .regions
+            .iter()
+            .map(|r| (r.start, r.end, r.placeholder.as_str()))
+            .collect();
+        assert_eq!(
+            regions,
+            vec![(0, 4, " {…}"), (2, 4, " […]"), (6, 8, " […]"), (7, 8, " …"),]
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Navigation events for scripting (`--emit-events`).
+//!
+//! Each event is one JSON object per line, so another process can follow
+//! what the reader is doing, e.g. to sync a preview pane. Lines are queued
+//! on a channel and written by a worker thread, so a slow reader never
+//! stalls the TUI.
+
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What happened, serialized as the `event` field plus its own details.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum EventKind<'a> {
+    /// The outline selection moved to another section
+    SectionSelected,
+    /// A link was followed; `target` is the link as written
+    LinkFollowed { target: &'a str },
+    /// A different file was opened
+    FileLoaded,
+    /// A task list checkbox was toggled and saved
+    CheckboxToggled { item: &'a str, checked: bool },
+}
+
+/// A single event line.
+#[derive(Debug, Serialize)]
+struct Event<'a> {
+    #[serde(flatten)]
+    kind: EventKind<'a>,
+    file: &'a Path,
+    heading: Option<&'a str>,
+    /// Milliseconds since the Unix epoch
+    timestamp: u64,
+}
+
+/// Queue of event lines drained by a writer thread.
+pub(crate) struct EventSink {
+    tx: Sender<String>,
+}
+
+impl EventSink {
+    /// Start a writer thread that sends each event line to `out`.
+    ///
+    /// The thread ends when the sink is dropped or `out` fails.
+    pub fn new(mut out: impl Write + Send + 'static) -> Self {
+        let (tx, rx) = mpsc::channel::<String>();
+        thread::spawn(move || {
+            for line in rx {
+                if out
+                    .write_all(line.as_bytes())
+                    .and_then(|()| out.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+        Self { tx }
+    }
+
+    /// Queue an event without waiting for it to be written.
+    pub fn emit(&self, kind: EventKind, file: &Path, heading: Option<&str>) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        let event = Event {
+            kind,
+            file,
+            heading,
+            timestamp,
+        };
+        if let Ok(mut line) = serde_json::to_string(&event) {
+            line.push('\n');
+            // A closed channel means the writer gave up; events are best effort
+            let _ = self.tx.send(line);
+        }
+    }
+}
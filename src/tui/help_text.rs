@@ -113,7 +113,13 @@ pub const HELP_LINES: &[HelpLine] = &[
     keybinding("/", "Search document content (Esc: clear, n/N: navigate)"),
     keybinding("s", "Filter outline headings (Esc: clear, Enter: keep)"),
     keybinding("n/N", "Next/previous search match"),
+    keybinding("Alt+C", "Toggle search inside code blocks"),
+    keybinding("Alt+P", "Keep headings under outline search matches"),
     keybinding("r", "Toggle raw source view"),
+    keybinding(
+        "z",
+        "Toggle focus mode (only the selected section expanded)",
+    ),
     keybinding("?", "Toggle this help"),
     keybinding("q/Esc", "Quit"),
     blank(),
@@ -124,6 +130,7 @@ pub const HELP_LINES: &[HelpLine] = &[
     keybinding("S", "Save outline width to config (with confirmation)"),
     keybinding(":", "Open command palette (fuzzy search commands)"),
     keybinding("[N]j/k", "Move N items (vim count prefix, e.g., 5j)"),
+    keybinding("[N]G, :N", "Jump to heading N (e.g., 42G or :42 Enter)"),
     keybinding("m", "Set bookmark (shows ⚑ indicator)"),
     keybinding("'", "Jump to bookmarked position"),
     blank(),
@@ -133,6 +140,7 @@ pub const HELP_LINES: &[HelpLine] = &[
     keybinding("Tab", "Cycle through links (in link mode)"),
     keybinding("1-9", "Jump to link by number (in link mode)"),
     keybinding("Enter", "Follow selected link (in link mode)"),
+    keybinding("e", "Edit link target at its heading (in link mode)"),
     keybinding("p", "Jump to parent's links (stay in link mode)"),
     keybinding("b/Bksp", "Go back to previous file"),
     keybinding("F", "Go forward in navigation history"),
@@ -141,20 +149,36 @@ pub const HELP_LINES: &[HelpLine] = &[
     section("Interactive Mode"),
     keybinding("i", "Enter interactive mode (navigate elements)"),
     keybinding("Tab/j/k", "Next element | Shift+Tab/k to go back"),
+    keybinding("[ ]", "Previous/next element of the same type"),
     keybinding("u/d", "Page up/down (PgUp/PgDn also work)"),
     keybinding("Enter", "Activate element (toggle/follow/edit)"),
     keybinding("Space", "Toggle checkboxes/details blocks"),
+    keybinding(
+        "Enter",
+        "Fold/unfold JSON/YAML objects (ui.fold_data_blocks)",
+    ),
     keybinding("y", "Copy element (code/cell/link)"),
     keybinding("hjkl", "Navigate table cells (in table mode)"),
+    keybinding("PgUp/PgDn", "Page through large tables (in table mode)"),
     keybinding("Enter", "Edit table cell (in table mode)"),
+    keybinding("c", "Copy table as CSV (in table mode)"),
     keybinding("Esc", "Exit interactive mode"),
     blank(),
     // Themes & Clipboard
     section("Themes & Clipboard"),
     keybinding("t", "Cycle color theme"),
     keybinding("y", "Copy current section content (works in all modes)"),
+    keybinding("Alt+y", "Copy current section as HTML (rich text)"),
     keybinding("Y", "Copy anchor link (works in all modes)"),
+    keybinding(
+        "Alt+Y",
+        "Copy path/to/file.md#anchor (:link for a markdown link)",
+    ),
+    keybinding("\"a y", "Copy into register a (\"A y appends, :reg lists)"),
     keybinding("e", "Edit file in default editor ($VISUAL or $EDITOR)"),
+    keybinding("O", "Open the file's folder in the file manager"),
+    keybinding("R", "Reload file from disk"),
+    keybinding("Alt+r", "Toggle auto-reload on file changes"),
     blank(),
     // Note
     note("On Linux, install a clipboard manager (clipit, parcellite, xclip) for best results"),
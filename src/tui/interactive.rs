@@ -3,14 +3,23 @@
 //! Provides modal navigation through all interactive elements in markdown:
 //! - Details blocks (expand/collapse)
 //! - Links (follow/copy)
-//! - Checkboxes (toggle/save)
-//! - Code blocks (copy)
+//! - Checkboxes (toggle/save), in lists or inline in paragraphs and tables
+//! - Code blocks (collapse/copy), and the nested objects and arrays of JSON
+//!   and YAML code blocks when `ui.fold_data_blocks` is on (fold/unfold)
+//! - Footnote references and definitions (jump between them)
 //! - Tables (navigate cells)
 //! - Images (view info)
+//!
+//! Embedders can index blocks from [`parse_content`](crate::parser::content::parse_content)
+//! with [`InteractiveState::index_elements`] and read the result through
+//! [`InteractiveState::elements`], for custom navigation or to verify indexing.
 
 use crate::parser::output::{Block, InlineElement};
 use crate::parser::{Link, LinkTarget};
+use crate::tui::data_fold::DataFolds;
 use std::collections::HashMap;
+use std::mem;
+use std::ops::Range;
 
 // Sub-index encoding constants for nested elements within list items
 // Format: item_idx * ITEM_MULTIPLIER + nested_idx * NESTED_MULTIPLIER + TYPE_OFFSET
@@ -28,6 +37,10 @@ pub const CODE_BLOCK_OFFSET: usize = 5000;
 pub const TABLE_OFFSET: usize = 6000;
 /// Offset for images nested in list items
 pub const IMAGE_OFFSET: usize = 7000;
+/// Offset for `[x]`/`[ ]` checkboxes inside a paragraph or table
+pub const INLINE_CHECKBOX_OFFSET: usize = 8000;
+/// Offset for footnote references and definitions inside a paragraph
+pub const FOOTNOTE_OFFSET: usize = 9000;
 
 // Sub-index encoding constants for nested elements within details blocks
 /// Base offset for elements nested inside details blocks
@@ -35,6 +48,22 @@ pub const DETAILS_NESTED_BASE: usize = 100000;
 /// Multiplier for nested block index within details
 pub const DETAILS_NESTED_MULTIPLIER: usize = 100;
 
+/// Data rows shown at once when navigating a large table
+pub const TABLE_PAGE_ROWS: usize = 20;
+
+/// Data rows to render in table mode, or `None` when the whole table fits.
+///
+/// Large tables are shown a page of [`TABLE_PAGE_ROWS`] at a time; the page is
+/// the one holding `selected_row` (0 is the header, 1 the first data row).
+pub fn table_window(selected_row: usize, data_rows: usize) -> Option<Range<usize>> {
+    if data_rows <= TABLE_PAGE_ROWS {
+        return None;
+    }
+    let page = selected_row.saturating_sub(1) / TABLE_PAGE_ROWS;
+    let start = (page * TABLE_PAGE_ROWS).min(data_rows.saturating_sub(1));
+    Some(start..(start + TABLE_PAGE_ROWS).min(data_rows))
+}
+
 /// Interactive navigation state
 #[derive(Debug, Clone)]
 pub struct InteractiveState {
@@ -46,9 +75,35 @@ pub struct InteractiveState {
     pub element_states: HashMap<ElementId, ElementState>,
     /// Current detail navigation mode (for tables/lists)
     pub detail_mode: Option<DetailMode>,
+    /// First rendered line of each block, from the last indexing
+    block_lines: Vec<usize>,
+    /// `ui.block_spacing`, for counting the blank lines between blocks
+    block_spacing: usize,
+    /// `ui.fold_data_blocks`: offer folds in JSON and YAML code blocks
+    fold_data_blocks: bool,
+    /// Fold regions and state of JSON/YAML code blocks, by code block id
+    data_folds: HashMap<ElementId, DataFolds>,
 }
 
 /// Unique identifier for an element
+///
+/// `block_idx` is the index of the top-level block in the parsed content.
+/// `sub_idx` locates the element inside that block:
+///
+/// | Element | `sub_idx` |
+/// |---------|-----------|
+/// | Code block, table, image or details block | `None` |
+/// | Foldable line in a JSON or YAML code block | source line in the block |
+/// | Link or image in a paragraph | inline index |
+/// | Checkbox list item | item index |
+/// | Inline checkbox in a paragraph or table | `INLINE_CHECKBOX_OFFSET + n`, the nth in the block |
+/// | Footnote reference or definition in a paragraph | `FOOTNOTE_OFFSET + n`, the nth in the block |
+/// | Link in a list item | `item * LINK_ITEM_MULTIPLIER + LINK_OFFSET + inline` |
+/// | Block nested in a list item | `item * ITEM_MULTIPLIER + nested * NESTED_MULTIPLIER + kind` |
+/// | Block nested in an expanded details block | `DETAILS_NESTED_BASE + nested * DETAILS_NESTED_MULTIPLIER + kind` |
+/// | Link in a paragraph nested in details | `DETAILS_NESTED_BASE + nested * DETAILS_NESTED_MULTIPLIER + LINK_OFFSET + inline` |
+///
+/// `kind` is [`CODE_BLOCK_OFFSET`], [`TABLE_OFFSET`] or [`IMAGE_OFFSET`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ElementId {
     /// Block index in parsed content
@@ -72,6 +127,24 @@ pub struct InteractiveElement {
     pub line_range: (usize, usize),
 }
 
+impl InteractiveElement {
+    /// Identifier of the element within the parsed blocks.
+    pub fn id(&self) -> ElementId {
+        self.id
+    }
+
+    /// What kind of element this is, with its content.
+    pub fn element_type(&self) -> &ElementType {
+        &self.element_type
+    }
+
+    /// Rendered lines the element covers (end exclusive), counted the way
+    /// the content pane lays out blocks.
+    pub fn line_range(&self) -> std::ops::Range<usize> {
+        self.line_range.0..self.line_range.1
+    }
+}
+
 /// Types of interactive elements
 #[derive(Debug, Clone)]
 pub enum ElementType {
@@ -91,11 +164,28 @@ pub enum ElementType {
         block_idx: usize,
         item_idx: usize,
     },
+    /// A `[x]`/`[ ]` written in a paragraph or table cell
+    InlineCheckbox {
+        checked: bool,
+        /// Byte offset of the token in the indexed markdown
+        offset: usize,
+        /// The source line holding the checkbox, trimmed
+        context: String,
+        block_idx: usize,
+    },
     CodeBlock {
         language: Option<String>,
         content: String,
         block_idx: usize,
     },
+    /// A `[^label]` footnote reference, or the one starting its definition
+    Footnote {
+        label: String,
+        definition: bool,
+        /// Byte offset of the token in the indexed markdown
+        offset: usize,
+        block_idx: usize,
+    },
     Table {
         rows: usize,
         cols: usize,
@@ -106,6 +196,15 @@ pub enum ElementType {
         src: String,
         block_idx: usize,
     },
+    /// A line opening a nested object or array in a JSON or YAML code block
+    DataFold {
+        /// Source line within the code block
+        line: usize,
+        /// The line's text, trimmed
+        summary: String,
+        folded: bool,
+        block_idx: usize,
+    },
 }
 
 /// Per-element state
@@ -114,6 +213,9 @@ pub enum ElementState {
     Details {
         expanded: bool,
     },
+    Code {
+        collapsed: bool,
+    },
     Table {
         selected_row: usize,
         selected_col: usize,
@@ -137,6 +239,58 @@ impl InteractiveState {
             current_index: None,
             element_states: HashMap::new(),
             detail_mode: None,
+            block_lines: Vec::new(),
+            block_spacing: 1,
+            fold_data_blocks: false,
+            data_folds: HashMap::new(),
+        }
+    }
+
+    /// Count lines the way the content pane lays out blocks with
+    /// `ui.block_spacing` set to `spacing` (see [`blank_line_after`]).
+    pub fn set_block_spacing(&mut self, spacing: usize) {
+        self.block_spacing = spacing;
+    }
+
+    /// Offer folding of nested objects and arrays in JSON and YAML code
+    /// blocks (`ui.fold_data_blocks`).
+    pub fn set_fold_data_blocks(&mut self, enabled: bool) {
+        self.fold_data_blocks = enabled;
+        if !enabled {
+            self.data_folds.clear();
+        }
+    }
+
+    /// Fold state of the code block `id`, when it holds foldable data.
+    pub fn data_folds(&self, id: ElementId) -> Option<&DataFolds> {
+        self.data_folds.get(&id)
+    }
+
+    /// Fold or unfold the region starting at `line` of the code block `id`.
+    pub fn toggle_data_fold(&mut self, id: ElementId, line: usize) {
+        if let Some(folds) = self.data_folds.get_mut(&id) {
+            folds.toggle(line);
+        }
+    }
+
+    /// Start `<details open>` blocks expanded.
+    ///
+    /// Call before [`index_elements`](Self::index_elements) with the
+    /// markdown `blocks` were parsed from, so line ranges count the open
+    /// content. Blocks the user has already toggled keep their state.
+    pub fn open_details(&mut self, markdown: &str, blocks: &[Block]) {
+        use crate::parser::content::details_open;
+
+        for (block_idx, open) in details_open(markdown, blocks).into_iter().enumerate() {
+            if open {
+                let id = ElementId {
+                    block_idx,
+                    sub_idx: None,
+                };
+                self.element_states
+                    .entry(id)
+                    .or_insert(ElementState::Details { expanded: true });
+            }
         }
     }
 
@@ -146,10 +300,12 @@ impl InteractiveState {
     /// so they are detected during Block parsing along with regular links.
     pub fn index_elements(&mut self, blocks: &[Block]) {
         self.elements.clear();
+        self.block_lines.clear();
         let mut current_line = 0;
 
         for (block_idx, block) in blocks.iter().enumerate() {
             let start_line = current_line;
+            self.block_lines.push(start_line);
 
             match block {
                 Block::Details {
@@ -234,7 +390,7 @@ impl InteractiveState {
                                         sub_idx: Some(nested_base + CODE_BLOCK_OFFSET),
                                     };
 
-                                    let code_lines = 2 + content.lines().count();
+                                    let code_lines = self.code_block_lines(nested_id, content);
 
                                     self.elements.push(InteractiveElement {
                                         id: nested_id,
@@ -280,35 +436,7 @@ impl InteractiveState {
                                                 ),
                                             };
 
-                                            let target = if let Some(wikilink_target) =
-                                                url.strip_prefix("wikilink:")
-                                            {
-                                                LinkTarget::WikiLink {
-                                                    target: wikilink_target.to_string(),
-                                                    alias: if text != wikilink_target {
-                                                        Some(text.clone())
-                                                    } else {
-                                                        None
-                                                    },
-                                                }
-                                            } else if let Some(anchor) = url.strip_prefix('#') {
-                                                LinkTarget::Anchor(anchor.to_string())
-                                            } else if url.starts_with("http://")
-                                                || url.starts_with("https://")
-                                            {
-                                                LinkTarget::External(url.clone())
-                                            } else if let Some((path, anchor)) = url.split_once('#')
-                                            {
-                                                LinkTarget::RelativeFile {
-                                                    path: path.into(),
-                                                    anchor: Some(anchor.to_string()),
-                                                }
-                                            } else {
-                                                LinkTarget::RelativeFile {
-                                                    path: url.into(),
-                                                    anchor: None,
-                                                }
-                                            };
+                                            let target = LinkTarget::from_url(text, url);
 
                                             self.elements.push(InteractiveElement {
                                                 id: nested_id,
@@ -343,33 +471,7 @@ impl InteractiveState {
                             };
 
                             // Parse link target
-                            let target = if let Some(wikilink_target) =
-                                url.strip_prefix("wikilink:")
-                            {
-                                // Wikilink parsed from [[target]] or [[target|alias]] syntax
-                                LinkTarget::WikiLink {
-                                    target: wikilink_target.to_string(),
-                                    alias: if text != wikilink_target {
-                                        Some(text.clone())
-                                    } else {
-                                        None
-                                    },
-                                }
-                            } else if let Some(anchor) = url.strip_prefix('#') {
-                                LinkTarget::Anchor(anchor.to_string())
-                            } else if url.starts_with("http://") || url.starts_with("https://") {
-                                LinkTarget::External(url.clone())
-                            } else if let Some((path, anchor)) = url.split_once('#') {
-                                LinkTarget::RelativeFile {
-                                    path: path.into(),
-                                    anchor: Some(anchor.to_string()),
-                                }
-                            } else {
-                                LinkTarget::RelativeFile {
-                                    path: url.into(),
-                                    anchor: None,
-                                }
-                            };
+                            let target = LinkTarget::from_url(text, url);
 
                             self.elements.push(InteractiveElement {
                                 id,
@@ -444,34 +546,7 @@ impl InteractiveState {
                                 let link_line = item_start_line + offset;
 
                                 // Parse link target
-                                let target = if let Some(wikilink_target) =
-                                    url.strip_prefix("wikilink:")
-                                {
-                                    // Wikilink parsed from [[target]] or [[target|alias]] syntax
-                                    LinkTarget::WikiLink {
-                                        target: wikilink_target.to_string(),
-                                        alias: if text != wikilink_target {
-                                            Some(text.clone())
-                                        } else {
-                                            None
-                                        },
-                                    }
-                                } else if let Some(anchor) = url.strip_prefix('#') {
-                                    LinkTarget::Anchor(anchor.to_string())
-                                } else if url.starts_with("http://") || url.starts_with("https://")
-                                {
-                                    LinkTarget::External(url.clone())
-                                } else if let Some((path, anchor)) = url.split_once('#') {
-                                    LinkTarget::RelativeFile {
-                                        path: path.into(),
-                                        anchor: Some(anchor.to_string()),
-                                    }
-                                } else {
-                                    LinkTarget::RelativeFile {
-                                        path: url.into(),
-                                        anchor: None,
-                                    }
-                                };
+                                let target = LinkTarget::from_url(text, url);
 
                                 self.elements.push(InteractiveElement {
                                     id,
@@ -502,7 +577,7 @@ impl InteractiveState {
                                         sub_idx: Some(nested_base + CODE_BLOCK_OFFSET),
                                     };
 
-                                    let lines = 2 + content.lines().count();
+                                    let lines = self.code_block_lines(id, content);
 
                                     self.elements.push(InteractiveElement {
                                         id,
@@ -577,7 +652,15 @@ impl InteractiveState {
                         sub_idx: None,
                     };
 
-                    let lines = 2 + content.lines().count(); // +2 for fences
+                    if self.fold_data_blocks
+                        && !self.data_folds.get(&id).is_some_and(|f| f.matches(content))
+                    {
+                        match DataFolds::new(language.as_deref(), content) {
+                            Some(folds) => self.data_folds.insert(id, folds),
+                            None => self.data_folds.remove(&id),
+                        };
+                    }
+                    let lines = self.code_block_lines(id, content);
 
                     self.elements.push(InteractiveElement {
                         id,
@@ -589,6 +672,36 @@ impl InteractiveState {
                         line_range: (current_line, current_line + lines),
                     });
 
+                    // Foldable lines, below the opening fence
+                    let folds = self
+                        .data_folds
+                        .get(&id)
+                        .filter(|_| !self.is_code_collapsed(id));
+                    if let Some(folds) = folds {
+                        let source: Vec<&str> = content.lines().collect();
+                        let mut fold_elements = Vec::new();
+                        for (row, (line, _)) in folds.visible_lines().enumerate() {
+                            if folds.region_at(line).is_none() {
+                                continue;
+                            }
+                            let row = current_line + 1 + row;
+                            fold_elements.push(InteractiveElement {
+                                id: ElementId {
+                                    block_idx,
+                                    sub_idx: Some(line),
+                                },
+                                element_type: ElementType::DataFold {
+                                    line,
+                                    summary: source.get(line).unwrap_or(&"").trim().to_string(),
+                                    folded: folds.is_folded(line),
+                                    block_idx,
+                                },
+                                line_range: (row, row + 1),
+                            });
+                        }
+                        self.elements.extend(fold_elements);
+                    }
+
                     current_line += lines;
                 }
                 Block::Table { headers, rows, .. } => {
@@ -644,13 +757,188 @@ impl InteractiveState {
             }
 
             // Account for blank line added after each block in render_markdown_enhanced
-            current_line += 1;
+            if blank_line_after(blocks, block_idx, self.block_spacing) {
+                current_line += 1;
+            }
         }
 
         // Sort elements by line position for proper navigation order
         self.elements.sort_by_key(|e| e.line_range.0);
+        self.clamp_selection();
+    }
+
+    /// Add the checkboxes written inline in paragraphs and table cells.
+    ///
+    /// Call after [`index_elements`](Self::index_elements) with the
+    /// markdown `blocks` were parsed from. Checkboxes are matched to blocks by
+    /// position, and skipped when the block doesn't show the same token.
+    pub fn index_inline_checkboxes(&mut self, markdown: &str, blocks: &[Block]) {
+        use crate::parser::content::inline_checkboxes;
+
+        // The blocks inline_checkboxes counts: paragraphs (a lone image is
+        // one too) and tables
+        let containers: Vec<usize> = blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| {
+                matches!(
+                    block,
+                    Block::Paragraph { .. } | Block::Table { .. } | Block::Image { .. }
+                )
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let mut per_block: HashMap<usize, usize> = HashMap::new();
+        for checkbox in inline_checkboxes(markdown) {
+            let Some(&block_idx) = containers.get(checkbox.block) else {
+                continue;
+            };
+            let token = &markdown[checkbox.range.clone()];
+            let start_line = self.block_lines.get(block_idx).copied().unwrap_or(0);
+            let line = match &blocks[block_idx] {
+                Block::Paragraph { inline, .. } if !checkbox.in_table => {
+                    let text: String = inline
+                        .iter()
+                        .filter_map(|e| match e {
+                            InlineElement::Text { value } => Some(value.as_str()),
+                            _ => None,
+                        })
+                        .collect();
+                    if !text.contains(token) {
+                        continue;
+                    }
+                    start_line
+                }
+                Block::Table { headers, rows, .. } if checkbox.in_table => {
+                    // Source line 1 is the delimiter row, drawn as the separator
+                    // under the header; the top border comes first
+                    let cells = match checkbox.line {
+                        0 => Some(headers),
+                        1 => None,
+                        row => rows.get(row - 2),
+                    };
+                    if !cells.is_some_and(|cells| cells.iter().any(|c| c.contains(token))) {
+                        continue;
+                    }
+                    start_line + checkbox.line + 1
+                }
+                _ => continue,
+            };
 
-        // Reset selection if elements changed
+            let nth = per_block.entry(block_idx).or_default();
+            let line_start = markdown[..checkbox.range.start]
+                .rfind('\n')
+                .map_or(0, |i| i + 1);
+            let line_end = markdown[checkbox.range.start..]
+                .find('\n')
+                .map_or(markdown.len(), |i| checkbox.range.start + i);
+            self.elements.push(InteractiveElement {
+                id: ElementId::new(block_idx, Some(INLINE_CHECKBOX_OFFSET + *nth)),
+                element_type: ElementType::InlineCheckbox {
+                    checked: checkbox.checked,
+                    offset: checkbox.range.start,
+                    context: markdown[line_start..line_end].trim().to_string(),
+                    block_idx,
+                },
+                line_range: (line, line + 1),
+            });
+            *nth += 1;
+        }
+
+        self.elements.sort_by_key(|e| e.line_range.0);
+        self.clamp_selection();
+    }
+
+    /// Add the footnote references and definitions written in paragraphs.
+    ///
+    /// Call after [`index_elements`](Self::index_elements) with the
+    /// markdown `blocks` were parsed from. Each token is matched to the next
+    /// paragraph showing it, and skipped when there is none.
+    pub fn index_footnotes(&mut self, markdown: &str, blocks: &[Block]) {
+        use crate::parser::content::footnotes;
+
+        let texts: Vec<Option<String>> = blocks
+            .iter()
+            .map(|block| match block {
+                Block::Paragraph { inline, .. } => Some(
+                    inline
+                        .iter()
+                        .filter_map(|e| match e {
+                            InlineElement::Text { value } => Some(value.as_str()),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect();
+
+        // Paragraph being searched, and how far into its text
+        let (mut block_idx, mut searched) = (0, 0);
+        let mut per_block: HashMap<usize, usize> = HashMap::new();
+        for mark in footnotes(markdown) {
+            let token = &markdown[mark.range.clone()];
+            let (mut idx, mut from) = (block_idx, searched);
+            let found = loop {
+                let Some(text) = texts.get(idx) else {
+                    break None;
+                };
+                if let Some(pos) = text.as_deref().and_then(|t| t[from..].find(token)) {
+                    break Some((idx, from + pos + token.len()));
+                }
+                idx += 1;
+                from = 0;
+            };
+            let Some((idx, end)) = found else {
+                continue;
+            };
+            (block_idx, searched) = (idx, end);
+
+            let line = self.block_lines.get(idx).copied().unwrap_or(0);
+            let nth = per_block.entry(idx).or_default();
+            self.elements.push(InteractiveElement {
+                id: ElementId::new(idx, Some(FOOTNOTE_OFFSET + *nth)),
+                element_type: ElementType::Footnote {
+                    label: mark.label,
+                    definition: mark.definition,
+                    offset: mark.range.start,
+                    block_idx: idx,
+                },
+                line_range: (line, line + 1),
+            });
+            *nth += 1;
+        }
+
+        self.elements.sort_by_key(|e| e.line_range.0);
+        self.clamp_selection();
+    }
+
+    /// Select the other end of the selected footnote: a reference's
+    /// definition, or a definition's first reference.
+    ///
+    /// Returns `false`, keeping the selection, when the indexed content has
+    /// no such element or the selection is not a footnote.
+    pub fn select_footnote_counterpart(&mut self) -> bool {
+        let Some(ElementType::Footnote {
+            label, definition, ..
+        }) = self.current_element().map(|e| &e.element_type)
+        else {
+            return false;
+        };
+        let (label, definition) = (label.to_lowercase(), *definition);
+        let target = self.elements.iter().position(|e| {
+            matches!(&e.element_type, ElementType::Footnote { label: l, definition: d, .. }
+                if *d != definition && l.to_lowercase() == label)
+        });
+        if target.is_some() {
+            self.current_index = target;
+        }
+        target.is_some()
+    }
+
+    /// Keep the selection on an existing element after re-indexing.
+    fn clamp_selection(&mut self) {
         if self.current_index.is_some() {
             if self.elements.is_empty() {
                 self.current_index = None;
@@ -662,6 +950,11 @@ impl InteractiveState {
         }
     }
 
+    /// All indexed elements, in rendered line order
+    pub fn elements(&self) -> &[InteractiveElement] {
+        &self.elements
+    }
+
     /// Get the currently selected element
     pub fn current_element(&self) -> Option<&InteractiveElement> {
         self.current_index.and_then(|idx| self.elements.get(idx))
@@ -674,27 +967,61 @@ impl InteractiveState {
 
     /// Move to next element
     pub fn next(&mut self) {
-        if self.elements.is_empty() {
-            return;
-        }
-
-        self.current_index = Some(match self.current_index {
-            Some(idx) if idx >= self.elements.len() - 1 => 0, // Wrap to first
-            Some(idx) => idx + 1,
-            None => 0,
-        });
+        self.next_matching(|_| true);
     }
 
     /// Move to previous element
     pub fn previous(&mut self) {
-        if self.elements.is_empty() {
-            return;
+        self.previous_matching(|_| true);
+    }
+
+    /// Move to the next element of the same kind as the current one
+    /// (e.g. the next code block), wrapping around.
+    pub fn next_of_type(&mut self) {
+        match self.current_kind() {
+            Some(kind) => self.next_matching(|e| mem::discriminant(&e.element_type) == kind),
+            None => self.next(),
         }
+    }
 
-        self.current_index = Some(match self.current_index {
-            Some(0) | None => self.elements.len() - 1, // Wrap to last
-            Some(idx) => idx - 1,
-        });
+    /// Move to the previous element of the same kind as the current one.
+    pub fn previous_of_type(&mut self) {
+        match self.current_kind() {
+            Some(kind) => self.previous_matching(|e| mem::discriminant(&e.element_type) == kind),
+            None => self.previous(),
+        }
+    }
+
+    fn current_kind(&self) -> Option<mem::Discriminant<ElementType>> {
+        self.current_element()
+            .map(|e| mem::discriminant(&e.element_type))
+    }
+
+    /// Move forward to the next element accepted by `pred`, wrapping to the
+    /// first. The selection stays put when nothing else matches.
+    fn next_matching(&mut self, pred: impl Fn(&InteractiveElement) -> bool) {
+        let len = self.elements.len();
+        // Start just before the first element when nothing is selected
+        let start = self.current_index.unwrap_or(len.saturating_sub(1));
+        if let Some(idx) = (1..=len)
+            .map(|step| (start + step) % len)
+            .find(|&idx| pred(&self.elements[idx]))
+        {
+            self.current_index = Some(idx);
+        }
+    }
+
+    /// Move back to the previous element accepted by `pred`, wrapping to
+    /// the last.
+    fn previous_matching(&mut self, pred: impl Fn(&InteractiveElement) -> bool) {
+        let len = self.elements.len();
+        let start = self.current_index.unwrap_or(0);
+        if let Some(idx) = (1..=len)
+            .map(|step| (start + len - step) % len)
+            .find(|&idx| pred(&self.elements[idx]))
+        {
+            self.current_index = Some(idx);
+        }
     }
 
     /// Check if details block is expanded
@@ -712,6 +1039,38 @@ impl InteractiveState {
         }
     }
 
+    /// Check if a code block is collapsed to its summary line
+    pub fn is_code_collapsed(&self, id: ElementId) -> bool {
+        matches!(
+            self.element_states.get(&id),
+            Some(ElementState::Code { collapsed: true })
+        )
+    }
+
+    /// Toggle a code block between its summary line and the full code
+    pub fn toggle_code_block(&mut self, id: ElementId) {
+        if let ElementState::Code { collapsed } = self
+            .element_states
+            .entry(id)
+            .or_insert(ElementState::Code { collapsed: false })
+        {
+            *collapsed = !*collapsed;
+        }
+    }
+
+    /// Rendered lines of a code block: the fences and code, or one summary line
+    fn code_block_lines(&self, id: ElementId, content: &str) -> usize {
+        if self.is_code_collapsed(id) {
+            1
+        } else {
+            let code = match self.data_folds.get(&id) {
+                Some(folds) => folds.visible_lines().count(),
+                None => content.lines().count(),
+            };
+            2 + code // +2 for fences
+        }
+    }
+
     /// Get status bar text for current element
     pub fn status_text(&self) -> String {
         if let Some(element) = self.current_element() {
@@ -738,7 +1097,7 @@ impl InteractiveState {
                         position
                     )
                 }
-                ElementType::Checkbox { .. } => {
+                ElementType::Checkbox { .. } | ElementType::InlineCheckbox { .. } => {
                     format!(
                         "[INTERACTIVE] Checkbox({}) | Space:Toggle Tab:Next Esc:Exit",
                         position
@@ -746,7 +1105,7 @@ impl InteractiveState {
                 }
                 ElementType::CodeBlock { .. } => {
                     format!(
-                        "[INTERACTIVE] Code({}) | y:Copy Tab:Next Esc:Exit",
+                        "[INTERACTIVE] Code({}) | Enter:Collapse y:Copy Tab:Next Esc:Exit",
                         position
                     )
                 }
@@ -756,6 +1115,19 @@ impl InteractiveState {
                         position
                     )
                 }
+                ElementType::Footnote { .. } => {
+                    format!(
+                        "[INTERACTIVE] Footnote({}) | Enter:Jump Tab:Next Esc:Exit",
+                        position
+                    )
+                }
+                ElementType::DataFold { folded, .. } => {
+                    format!(
+                        "[INTERACTIVE] Fold({}) | Enter:{} Tab:Next Esc:Exit",
+                        position,
+                        if *folded { "Unfold" } else { "Fold" }
+                    )
+                }
                 ElementType::Image { .. } => {
                     format!(
                         "[INTERACTIVE] Image({}) | i:Info y:Copy Tab:Next Esc:Exit",
@@ -856,6 +1228,17 @@ impl InteractiveState {
                     };
                     format!("{} {}", mark, text)
                 }
+                ElementType::InlineCheckbox {
+                    checked, context, ..
+                } => {
+                    let mark = if *checked { "☑" } else { "☐" };
+                    let text = if context.chars().count() > 15 {
+                        format!("{}...", context.chars().take(12).collect::<String>())
+                    } else {
+                        context.clone()
+                    };
+                    format!("{} {}", mark, text)
+                }
                 ElementType::CodeBlock { language, .. } => {
                     let lang = language.as_deref().unwrap_or("code");
                     format!("Code: {}", lang)
@@ -863,6 +1246,23 @@ impl InteractiveState {
                 ElementType::Table { rows, cols, .. } => {
                     format!("Table: {}×{}", rows, cols)
                 }
+                ElementType::Footnote {
+                    label, definition, ..
+                } => {
+                    let kind = if *definition {
+                        "Footnote def"
+                    } else {
+                        "Footnote"
+                    };
+                    format!("{}: [^{}]", kind, label)
+                }
+                ElementType::DataFold {
+                    summary, folded, ..
+                } => {
+                    let text: String = summary.chars().take(20).collect();
+                    let state = if *folded { "folded" } else { "unfolded" };
+                    format!("Fold ({}): {}", state, text)
+                }
                 ElementType::Image { alt, .. } => {
                     let text = if alt.len() > 20 {
                         format!("{}...", &alt[..17])
@@ -966,7 +1366,7 @@ impl InteractiveState {
                 }) = self.element_states.get(&id)
                 {
                     return format!(
-                        "[TABLE] Cell({},{}) | hjkl:Move y:Copy Y:CopyRow r:CopyTable Esc:Exit",
+                        "[TABLE] Cell({},{}) | hjkl:Move y:Copy Y:CopyRow r:CopyTable c:CSV Esc:Exit",
                         selected_row + 1,
                         selected_col + 1
                     );
@@ -1043,6 +1443,35 @@ impl InteractiveState {
         }
     }
 
+    /// Move to the first row of the next page
+    pub fn table_page_down(&mut self, rows: usize) {
+        if let Some(ElementState::Table { selected_row, .. }) = self.table_state_mut() {
+            let page = selected_row.saturating_sub(1) / TABLE_PAGE_ROWS;
+            // Stay on the last row once there is no further page
+            *selected_row = ((page + 1) * TABLE_PAGE_ROWS + 1).min(rows);
+        }
+    }
+
+    /// Move to the first row of the previous page, or the header from the first page
+    pub fn table_page_up(&mut self) {
+        if let Some(ElementState::Table { selected_row, .. }) = self.table_state_mut() {
+            let page = selected_row.saturating_sub(1) / TABLE_PAGE_ROWS;
+            *selected_row = match page {
+                0 => 0,
+                page => (page - 1) * TABLE_PAGE_ROWS + 1,
+            };
+        }
+    }
+
+    /// Navigation state of the table being navigated
+    fn table_state_mut(&mut self) -> Option<&mut ElementState> {
+        let Some(DetailMode::Table { element_idx }) = &self.detail_mode else {
+            return None;
+        };
+        let id = self.elements.get(*element_idx)?.id;
+        self.element_states.get_mut(&id)
+    }
+
     /// Get the currently selected table cell content
     pub fn get_table_cell(&self, headers: &[String], rows: &[Vec<String>]) -> Option<String> {
         if let Some(DetailMode::Table { element_idx }) = &self.detail_mode {
@@ -1114,6 +1543,19 @@ impl Default for InteractiveState {
     }
 }
 
+/// Whether the content pane draws a blank line after `blocks[idx]`.
+///
+/// With `spacing` 0, consecutive lists and a heading followed directly by a
+/// paragraph are drawn without one; every other block keeps its blank line.
+pub fn blank_line_after(blocks: &[Block], idx: usize, spacing: usize) -> bool {
+    spacing > 0
+        || !matches!(
+            (&blocks[idx], blocks.get(idx + 1)),
+            (Block::List { .. }, Some(Block::List { .. }))
+                | (Block::Heading { .. }, Some(Block::Paragraph { .. }))
+        )
+}
+
 /// Count lines for nested blocks
 fn count_block_lines(blocks: &[Block]) -> usize {
     blocks.iter().map(count_single_block_lines).sum()
@@ -1186,6 +1628,40 @@ mod interactive_tests {
         assert_eq!(table_count, 1, "Should find 1 table");
     }
 
+    #[test]
+    fn test_public_accessors_expose_ids_and_lines() {
+        let blocks = parse_content(
+            "See [a](#a).\n\n- [ ] task [b](#b)\n\n  ```sh\n  x\n  ```\n\n| H |\n|---|\n| 1 |\n",
+            0,
+        );
+        let mut state = InteractiveState::new();
+        state.index_elements(&blocks);
+
+        let summary: Vec<_> = state
+            .elements()
+            .iter()
+            .map(|e| (e.id().block_idx, e.id().sub_idx, e.line_range()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, Some(1), 0..1),
+                (1, Some(0), 2..3),
+                (1, Some(LINK_OFFSET + 1), 2..3),
+                (1, Some(CODE_BLOCK_OFFSET), 3..6),
+                (2, None, 7..11),
+            ]
+        );
+        assert!(matches!(
+            state.elements()[4].element_type(),
+            ElementType::Table {
+                rows: 1,
+                cols: 1,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_mixed_interactive_elements() {
         let markdown = r#"# Document
@@ -1245,4 +1721,77 @@ fn main() {}
             link_count
         );
     }
+
+    #[test]
+    fn test_large_tables_page_by_selected_row() {
+        assert_eq!(table_window(0, TABLE_PAGE_ROWS), None);
+        assert_eq!(table_window(0, 50), Some(0..20));
+        assert_eq!(table_window(20, 50), Some(0..20));
+        assert_eq!(table_window(21, 50), Some(20..40));
+        assert_eq!(table_window(50, 50), Some(40..50));
+
+        let rows: String = (0..50).map(|i| format!("| {} |\n", i)).collect();
+        let markdown = format!("| n |\n|---|\n{}", rows);
+        let blocks = parse_content(&markdown, 0);
+        let mut state = InteractiveState::new();
+        state.index_elements(&blocks);
+        state.current_index = Some(0);
+        state.enter_table_mode().unwrap();
+
+        state.table_page_down(50);
+        assert_eq!(state.get_table_position(), Some((21, 0)));
+        state.table_page_down(50);
+        state.table_page_down(50);
+        assert_eq!(state.get_table_position(), Some((50, 0)));
+        state.table_page_up();
+        assert_eq!(state.get_table_position(), Some((21, 0)));
+        state.table_page_up();
+        state.table_page_up();
+        assert_eq!(state.get_table_position(), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_collapsed_code_block_takes_one_line() {
+        let markdown = "```sh\none\ntwo\nthree\n```\n\n[link](#x)\n";
+        let blocks = parse_content(markdown, 0);
+        let mut state = InteractiveState::new();
+        state.index_elements(&blocks);
+        let code = state.elements[0].id;
+        assert_eq!(state.elements[0].line_range(), 0..5);
+        assert_eq!(state.elements[1].line_range().start, 6);
+
+        state.toggle_code_block(code);
+        state.index_elements(&blocks);
+        assert!(state.is_code_collapsed(code));
+        assert_eq!(state.elements[0].line_range(), 0..1);
+        assert_eq!(state.elements[1].line_range().start, 2);
+    }
+
+    #[test]
+    fn test_next_of_type_skips_other_elements() {
+        let markdown = "```sh\none\n```\n\n[a](#a)\n\n```sh\ntwo\n```\n\n[b](#b)\n";
+        let mut state = InteractiveState::new();
+        state.index_elements(&parse_content(markdown, 0));
+        let is_code = |s: &InteractiveState| {
+            matches!(
+                s.current_element().map(|e| &e.element_type),
+                Some(ElementType::CodeBlock { .. })
+            )
+        };
+
+        state.next();
+        assert!(is_code(&state));
+        state.next_of_type();
+        assert_eq!(state.current_index, Some(2));
+        // Wraps around to the first code block
+        state.next_of_type();
+        assert_eq!(state.current_index, Some(0));
+        state.previous_of_type();
+        assert_eq!(state.current_index, Some(2));
+
+        state.next();
+        assert!(!is_code(&state));
+        state.previous_of_type();
+        assert_eq!(state.current_index, Some(1));
+    }
 }
@@ -0,0 +1,353 @@
+//! Link resolution for following links in the TUI.
+//!
+//! [`App`](crate::App) asks a [`LinkResolver`] where a followed link leads and
+//! then performs the navigation. [`DefaultLinkResolver`] implements treemd's
+//! built-in behavior; embedders can install their own resolver with
+//! [`App::set_link_resolver`](crate::App::set_link_resolver), e.g. to map
+//! wikilinks to a vault index or rewrite external URLs.
+
+use crate::parser::{Link, LinkTarget};
+use std::path::{Component, Path, PathBuf};
+
+/// Where a followed link leads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedLink {
+    /// Jump to a heading (anchor or heading text) in the current document
+    Anchor(String),
+    /// Open a markdown file in treemd, optionally jumping to an anchor.
+    /// Missing files prompt the user to create them.
+    File {
+        path: PathBuf,
+        anchor: Option<String>,
+    },
+    /// Open a non-markdown file in the external editor
+    Editor(PathBuf),
+    /// Open a URL in the browser
+    External(String),
+    /// The link cannot be followed; the message is shown to the user
+    Rejected(String),
+}
+
+/// Decides where links lead when they are followed.
+pub trait LinkResolver {
+    /// Resolve `link` found in a document located in the directory `base`.
+    fn resolve(&self, link: &Link, base: &Path) -> ResolvedLink;
+}
+
+/// Built-in link resolution.
+///
+/// - Relative files resolve against `base`, trying `.md` for extensionless links
+/// - Wikilinks try `.md`, `.markdown` and the bare name
+/// - External links must use `http://`, `https://` or `mailto:` (bare email
+///   addresses are autolinked as `mailto:`)
+///
+/// Absolute paths, `..` traversal, symlinks and paths escaping `base` are
+/// rejected.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultLinkResolver;
+
+impl LinkResolver for DefaultLinkResolver {
+    fn resolve(&self, link: &Link, base: &Path) -> ResolvedLink {
+        match &link.target {
+            LinkTarget::Anchor(anchor) => ResolvedLink::Anchor(anchor.clone()),
+            LinkTarget::RelativeFile { path, anchor } => {
+                resolve_relative_file(path, anchor.clone(), base)
+            }
+            LinkTarget::WikiLink { target, .. } => resolve_wikilink(target, base),
+            LinkTarget::External(url) => {
+                // Security: Validate URL scheme (only http/https/mailto allowed)
+                if url.starts_with("http://") || url.starts_with("https://") || is_mailto(url) {
+                    ResolvedLink::External(url.clone())
+                } else {
+                    ResolvedLink::Rejected(
+                        "Unsafe URL scheme. Only http://, https:// and mailto: URLs are allowed."
+                            .to_string(),
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// A `mailto:` URL with an address and nothing a mail client could
+/// misread as extra arguments
+fn is_mailto(url: &str) -> bool {
+    url.strip_prefix("mailto:").is_some_and(|address| {
+        address.contains('@')
+            && !address.starts_with('-')
+            && !address.chars().any(|c| c.is_whitespace() || c.is_control())
+    })
+}
+
+fn has_markdown_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| {
+            let ext_lower = ext.to_lowercase();
+            ext_lower == "md" || ext_lower == "markdown" || ext_lower == "mdown"
+        })
+        .unwrap_or(false)
+}
+
+fn resolve_relative_file(path: &Path, anchor: Option<String>, base: &Path) -> ResolvedLink {
+    if has_markdown_extension(path) {
+        // Explicit markdown extension - load in treemd
+        return markdown_file(path, anchor, base);
+    }
+
+    // No markdown extension - could be:
+    // 1. A markdown file without extension (common in wikis)
+    // 2. A non-markdown file to open in editor
+
+    // First, try with .md extension (wiki-style links)
+    let md_path = PathBuf::from(format!("{}.md", path.display()));
+    let absolute_md_path = base.join(&md_path);
+    if absolute_md_path.exists() && !absolute_md_path.is_symlink() {
+        return markdown_file(&md_path, anchor, base);
+    }
+
+    // Try the path as-is - an existing file opens in the editor
+    let absolute_path = base.join(path);
+    if absolute_path.exists() && !absolute_path.is_symlink() {
+        return ResolvedLink::Editor(absolute_path);
+    }
+
+    // File doesn't exist - offer to create a markdown file
+    let relative_path = if path.extension().is_none() {
+        md_path
+    } else {
+        path.to_path_buf()
+    };
+    markdown_file(&relative_path, anchor, base)
+}
+
+/// Resolve a wikilink target.
+///
+/// Supports formats:
+/// - `[[filename]]` - load file (tries .md, .markdown extensions)
+/// - `[[filename#anchor]]` - load file and jump to anchor
+/// - `[[#anchor]]` - jump to anchor in current document
+/// - `[[path/to/file]]` - load file with path (e.g., `[[diary/notes.md]]`)
+fn resolve_wikilink(target: &str, base: &Path) -> ResolvedLink {
+    // Handle anchor-only wikilinks (e.g., [[#section]])
+    if let Some(anchor) = target.strip_prefix('#') {
+        return ResolvedLink::Anchor(anchor.to_string());
+    }
+
+    // Split target into file and optional anchor (e.g., "file#section" -> ("file", Some("section")))
+    let (file_target, anchor) = match target.split_once('#') {
+        Some((file, anchor)) => (file, Some(anchor.to_string())),
+        None => (target, None),
+    };
+
+    // Security: Reject path traversal attempts
+    if file_target.contains("..") {
+        return ResolvedLink::Rejected("WikiLinks cannot contain path traversal (..)".to_string());
+    }
+
+    // Security: Reject absolute paths
+    if file_target.starts_with('/') {
+        return ResolvedLink::Rejected("WikiLinks cannot be absolute paths".to_string());
+    }
+
+    // Security: Reject Windows absolute paths (drive letters)
+    #[cfg(windows)]
+    if file_target.len() >= 2 && file_target.chars().nth(1) == Some(':') {
+        return ResolvedLink::Rejected("WikiLinks cannot be absolute paths".to_string());
+    }
+
+    // Normalize backslashes to forward slashes for cross-platform compatibility
+    let file_target = file_target.replace('\\', "/");
+
+    // Check if target already has a markdown extension
+    let has_md_extension = has_markdown_extension(Path::new(&file_target));
+
+    // Try various extensions (only add extensions if target doesn't already have one)
+    let candidates: Vec<String> = if has_md_extension {
+        vec![file_target.clone()]
+    } else {
+        vec![
+            format!("{}.md", file_target),
+            format!("{}.markdown", file_target),
+            file_target.clone(),
+        ]
+    };
+
+    for candidate in &candidates {
+        let path = base.join(candidate);
+        // Skip symlinks for security
+        if !path.is_symlink() && path.exists() {
+            return markdown_file(Path::new(candidate), anchor, base);
+        }
+    }
+
+    // File not found - offer to create it (default to .md extension if not already present)
+    let default_filename = if has_md_extension {
+        file_target
+    } else {
+        format!("{}.md", file_target)
+    };
+    markdown_file(Path::new(&default_filename), anchor, base)
+}
+
+fn markdown_file(relative_path: &Path, anchor: Option<String>, base: &Path) -> ResolvedLink {
+    match validate_relative_path(relative_path, base) {
+        Ok(path) => ResolvedLink::File { path, anchor },
+        Err(e) => ResolvedLink::Rejected(e),
+    }
+}
+
+/// Resolve `relative_path` against `base`, rejecting paths that could escape it.
+///
+/// Security: Files must be within `base` or its subdirectories.
+fn validate_relative_path(relative_path: &Path, base: &Path) -> Result<PathBuf, String> {
+    // Reject absolute paths
+    if relative_path.is_absolute() {
+        return Err("Absolute paths are not allowed for security reasons".to_string());
+    }
+
+    // Reject paths containing .. components (path traversal)
+    if relative_path
+        .components()
+        .any(|c| matches!(c, Component::ParentDir))
+    {
+        return Err("Path traversal (..) is not allowed for security reasons".to_string());
+    }
+
+    let absolute_path = base.join(relative_path);
+
+    // Verify the resolved path is within allowed boundaries
+    // (defense in depth - even though we rejected .., canonicalize to be sure)
+    if let (Ok(canonical_path), Ok(canonical_base)) =
+        (absolute_path.canonicalize(), base.canonicalize())
+    {
+        if !canonical_path.starts_with(&canonical_base) {
+            return Err("Path escapes document directory boundary".to_string());
+        }
+    }
+
+    // Check for symlink (prevent symlink attacks)
+    if absolute_path.is_symlink() {
+        return Err("Symlinks are not allowed for security reasons".to_string());
+    }
+
+    Ok(absolute_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(target: LinkTarget) -> Link {
+        Link::new("text".to_string(), target, 0)
+    }
+
+    fn resolve(target: LinkTarget, base: &Path) -> ResolvedLink {
+        DefaultLinkResolver.resolve(&link(target), base)
+    }
+
+    #[test]
+    fn test_default_resolver_files_and_wikilinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        std::fs::write(base.join("notes.md"), "# Notes\n").unwrap();
+        std::fs::write(base.join("data.csv"), "a,b\n").unwrap();
+
+        let relative = |path: &str| LinkTarget::RelativeFile {
+            path: PathBuf::from(path),
+            anchor: Some("intro".to_string()),
+        };
+        let wikilink = |target: &str| LinkTarget::WikiLink {
+            target: target.to_string(),
+            alias: None,
+        };
+
+        assert_eq!(
+            resolve(relative("notes"), base),
+            ResolvedLink::File {
+                path: base.join("notes.md"),
+                anchor: Some("intro".to_string())
+            }
+        );
+        assert_eq!(
+            resolve(relative("data.csv"), base),
+            ResolvedLink::Editor(base.join("data.csv"))
+        );
+        assert_eq!(
+            resolve(wikilink("notes#Setup"), base),
+            ResolvedLink::File {
+                path: base.join("notes.md"),
+                anchor: Some("Setup".to_string())
+            }
+        );
+        assert_eq!(
+            resolve(wikilink("#Setup"), base),
+            ResolvedLink::Anchor("Setup".to_string())
+        );
+        // Missing files resolve to their would-be path so the user can create them
+        assert_eq!(
+            resolve(wikilink("new page"), base),
+            ResolvedLink::File {
+                path: base.join("new page.md"),
+                anchor: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_default_resolver_security_checks() {
+        let base = Path::new("/docs");
+        let rejected = |resolved: ResolvedLink| matches!(resolved, ResolvedLink::Rejected(_));
+
+        assert!(rejected(resolve(
+            LinkTarget::RelativeFile {
+                path: PathBuf::from("../secret.md"),
+                anchor: None
+            },
+            base
+        )));
+        assert!(rejected(resolve(
+            LinkTarget::WikiLink {
+                target: "../secret".to_string(),
+                alias: None
+            },
+            base
+        )));
+        assert!(rejected(resolve(
+            LinkTarget::WikiLink {
+                target: "/etc/passwd".to_string(),
+                alias: None
+            },
+            base
+        )));
+        assert!(rejected(resolve(
+            LinkTarget::External("javascript:alert(1)".to_string()),
+            base
+        )));
+        for bad in [
+            "mailto:",
+            "mailto:nobody",
+            "mailto:-x@y.z",
+            "mailto:a@b.c\n-x",
+        ] {
+            assert!(rejected(resolve(
+                LinkTarget::External(bad.to_string()),
+                base
+            )));
+        }
+        assert_eq!(
+            resolve(
+                LinkTarget::External("mailto:me@example.com".to_string()),
+                base
+            ),
+            ResolvedLink::External("mailto:me@example.com".to_string())
+        );
+        assert_eq!(
+            resolve(
+                LinkTarget::External("https://example.com".to_string()),
+                base
+            ),
+            ResolvedLink::External("https://example.com".to_string())
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Background loading for large documents.
+//!
+//! Parsing and indexing a multi-megabyte file takes long enough to freeze the
+//! TUI. [`DocumentLoader`] does that work on a worker thread and reports
+//! progress over a channel that the event loop polls between key events.
+
+use crate::parser::content::parse_content;
+use crate::parser::output::Block;
+use crate::parser::{Document, parse_markdown_with_progress};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Instant;
+
+/// Documents at least this large are loaded in the background.
+pub const BACKGROUND_LOAD_BYTES: usize = 1024 * 1024;
+
+/// Spinner frames shown while loading.
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// A parsed document together with its content blocks.
+pub struct LoadedDocument {
+    pub document: Document,
+    pub blocks: Vec<Block>,
+}
+
+/// Messages sent from the worker thread.
+enum LoadEvent {
+    /// Headings found so far
+    Progress(usize),
+    /// Headings are parsed; content blocks are being indexed
+    Indexing(usize),
+    Finished(Box<LoadedDocument>),
+}
+
+/// Current stage of a background load.
+#[derive(Debug, Clone, Copy)]
+enum LoadStage {
+    Parsing { headings: usize },
+    Indexing { headings: usize },
+}
+
+/// Handle to a document being parsed on a worker thread.
+pub struct DocumentLoader {
+    receiver: Receiver<LoadEvent>,
+    stage: LoadStage,
+    started: Instant,
+}
+
+impl DocumentLoader {
+    /// Start parsing `content` on a worker thread.
+    pub fn spawn(content: String) -> Self {
+        let (tx, rx) = mpsc::channel();
+
+        // Send errors mean the loader was dropped (e.g. the user quit); the
+        // thread just finishes its work and exits.
+        thread::spawn(move || {
+            let document = parse_markdown_with_progress(&content, |headings| {
+                let _ = tx.send(LoadEvent::Progress(headings));
+            });
+            let _ = tx.send(LoadEvent::Indexing(document.headings.len()));
+            let blocks = parse_content(&document.content, 0);
+            let _ = tx.send(LoadEvent::Finished(Box::new(LoadedDocument {
+                document,
+                blocks,
+            })));
+        });
+
+        Self {
+            receiver: rx,
+            stage: LoadStage::Parsing { headings: 0 },
+            started: Instant::now(),
+        }
+    }
+
+    /// Drain pending progress messages, returning the document once loaded.
+    ///
+    /// Returns `Err` if the worker thread died without finishing.
+    pub fn poll(&mut self) -> Result<Option<LoadedDocument>, String> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(LoadEvent::Progress(headings)) => self.stage = LoadStage::Parsing { headings },
+                Ok(LoadEvent::Indexing(headings)) => self.stage = LoadStage::Indexing { headings },
+                Ok(LoadEvent::Finished(loaded)) => return Ok(Some(*loaded)),
+                Err(TryRecvError::Empty) => return Ok(None),
+                Err(TryRecvError::Disconnected) => {
+                    return Err("Document loading stopped unexpectedly".to_string());
+                }
+            }
+        }
+    }
+
+    /// Spinner frame and progress text for the loading screen.
+    pub fn status(&self) -> (&'static str, String) {
+        let elapsed = self.started.elapsed();
+        let frame = SPINNER_FRAMES[(elapsed.as_millis() / 80) as usize % SPINNER_FRAMES.len()];
+        let text = match self.stage {
+            LoadStage::Parsing { headings } => {
+                format!("Parsing ({} headings so far)", headings)
+            }
+            LoadStage::Indexing { headings } => {
+                format!("Indexing content ({} headings)", headings)
+            }
+        };
+        (frame, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_loader_reports_progress_and_finishes() {
+        let section = "## Section\n\nSome text with a [link](other.md).\n\n";
+        let content = format!("# Big\n\n{}", section.repeat(20_000));
+
+        let mut loader = DocumentLoader::spawn(content.clone());
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let loaded = loop {
+            if let Some(loaded) = loader.poll().unwrap() {
+                break loaded;
+            }
+            assert!(Instant::now() < deadline, "loader did not finish");
+            thread::sleep(Duration::from_millis(5));
+        };
+
+        assert_eq!(loaded.document, crate::parse_markdown(&content));
+        assert_eq!(loader.status().1, "Indexing content (20001 headings)");
+        assert!(!loaded.blocks.is_empty());
+    }
+}
@@ -1,19 +1,31 @@
 mod app;
+mod clipboard;
+pub mod data_fold;
+mod events;
 mod help_text;
 mod image_cache;
-mod interactive;
+pub mod interactive;
 mod kitty_animation;
+pub mod link_resolver;
+mod loader;
 mod syntax;
 pub mod terminal_compat;
 pub mod theme;
 pub mod tty; // Public module for TTY handling
 mod ui;
-mod watcher;
+pub mod watcher;
 
 pub use app::{ActionResult, App};
-pub use interactive::InteractiveState;
+pub use clipboard::{ClipboardBackend, Osc52Clipboard, SystemClipboard};
+pub use interactive::{ElementId, ElementType, InteractiveElement, InteractiveState};
+pub use link_resolver::{DefaultLinkResolver, LinkResolver, ResolvedLink};
+pub use loader::BACKGROUND_LOAD_BYTES;
+pub use syntax::{DEFAULT_TAB_WIDTH, Highlighter, PlainHighlighter, SyntaxHighlighter};
 pub use terminal_compat::{ColorMode, TerminalCapabilities};
 pub use theme::ThemeName;
+pub use watcher::FileWatcher;
+
+pub(crate) use ui::render_markdown_enhanced;
 
 use crate::keybindings::Action;
 use color_eyre::Result;
@@ -77,7 +89,14 @@ fn run_editor(
 /// Returns `Ok(())` on successful exit, or an error if something goes wrong.
 pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
     let mut app = app;
+    let result = run_loop(terminal, &mut app);
+    // Remember where the file was left, for the next time it is opened
+    app.save_file_position();
+    result
+}
 
+/// Event loop behind [`run`]; returns when the user quits
+fn run_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
     // Handle startup file picker if needed
     if app.startup_needs_file_picker {
         app.enter_file_picker();
@@ -107,12 +126,27 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
             let _ = stdout().execute(BeginSynchronizedUpdate);
         }
 
-        terminal.draw(|frame| ui::render(frame, &mut app))?;
+        terminal.draw(|frame| ui::render(frame, app))?;
 
         if use_sync {
             let _ = stdout().execute(EndSynchronizedUpdate);
         }
 
+        // While a large document loads in the background, only watch for
+        // progress and quit keys
+        if app.is_loading() {
+            app.poll_loading();
+            if tty::poll_event(Duration::from_millis(80))?
+                && let Event::Key(key) = tty::read_event()?
+                && key.kind == KeyEventKind::Press
+                && (key.code == KeyCode::Esc
+                    || app.get_action_for_key(key.code, key.modifiers) == Some(Action::Quit))
+            {
+                return Ok(());
+            }
+            continue;
+        }
+
         // Update file watcher if the current file changed (e.g., via navigation)
         if app.file_path_changed {
             app.file_path_changed = false;
@@ -146,6 +180,8 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
             .time_until_next_frame()
             .unwrap_or(Duration::from_millis(100));
         if !tty::poll_event(poll_timeout)? {
+            app.expire_pending_chord();
+
             // No keyboard event - check for file changes (unless suppressed after internal save)
             if app.suppress_file_watch {
                 // Clear suppression and drain any pending file events
@@ -153,37 +189,18 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
                 if let Some(ref mut watcher) = file_watcher {
                     watcher.check_for_changes(); // Drain events, ignore result
                 }
-            } else if let Some(ref mut watcher) = file_watcher {
-                if watcher.check_for_changes() {
-                    // Save state before reload
-                    let was_interactive = app.mode == app::AppMode::Interactive;
-                    let saved_scroll = app.content_scroll;
-                    let saved_element_idx = app.interactive_state.current_index;
-
+            } else if let Some(ref mut watcher) = file_watcher
+                && watcher.check_for_changes()
+            {
+                if app.auto_reload && !app.has_unsaved_changes {
                     // File changed externally - reload with state preservation
-                    if let Err(e) = app.reload_current_file() {
-                        app.status_message = Some(format!("✗ Reload failed: {}", e));
-                    } else {
-                        // Re-index interactive elements if in interactive mode
-                        if was_interactive {
-                            app.reindex_interactive_elements();
-                            // Restore element selection if still valid
-                            if let Some(idx) = saved_element_idx {
-                                if idx < app.interactive_state.elements.len() {
-                                    app.interactive_state.current_index = Some(idx);
-                                }
-                            }
-                        }
-                        // Restore scroll position
-                        app.content_scroll = saved_scroll.min(app.content_height.saturating_sub(1));
-                        app.content_scroll_state = app
-                            .content_scroll_state
-                            .position(app.content_scroll as usize);
-                        // Sync previous_selection to prevent update_content_metrics() from resetting scroll
-                        app.sync_previous_selection();
-
-                        app.status_message = Some("↻ File reloaded (external change)".to_string());
-                    }
+                    app.status_message = Some(match app.reload_preserving_view() {
+                        Ok(()) => "↻ File reloaded (external change)".to_string(),
+                        Err(e) => format!("✗ Reload failed: {}", e),
+                    });
+                } else {
+                    // Keep the view (and any unsaved edits); flag it in the title
+                    app.check_disk_changed();
                 }
             }
             continue;
@@ -214,9 +231,9 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
                 }
 
                 // Handle text input modes separately - these need raw character input
-                let handled = handle_text_input(&mut app, key.code, key.modifiers);
+                let handled = handle_text_input(app, key.code, key.modifiers);
 
-                if !handled {
+                if !handled && !app.handle_register_key(key.code) {
                     // Handle vim-style count prefix (digits before motion commands)
                     // Only in modes where count makes sense (Normal, Interactive)
                     // Skip in LinkFollow mode where 1-9 jump to links
@@ -258,6 +275,11 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
                                                 if let Err(e) = app.reload_current_file() {
                                                     app.status_message =
                                                         Some(format!("✗ Failed to reload: {}", e));
+                                                } else if path != app.current_file_path {
+                                                    app.status_message = Some(format!(
+                                                        "✓ Finished editing {}",
+                                                        path.display()
+                                                    ));
                                                 } else {
                                                     app.status_message = Some(
                                                         "✓ File reloaded after editing".to_string(),
@@ -302,6 +324,10 @@ fn handle_text_input(
                 app.filter_outline();
                 return true;
             }
+            KeyCode::Char('p') if modifiers.contains(crossterm::event::KeyModifiers::ALT) => {
+                app.toggle_outline_search_path();
+                return true;
+            }
             KeyCode::Char(c) => {
                 app.search_input(c);
                 return true;
@@ -318,6 +344,10 @@ fn handle_text_input(
                 app.update_doc_search_matches();
                 return true;
             }
+            KeyCode::Char('c') if modifiers.contains(crossterm::event::KeyModifiers::ALT) => {
+                app.toggle_doc_search_in_code();
+                return true;
+            }
             KeyCode::Char(c) => {
                 app.doc_search_input(c);
                 return true;
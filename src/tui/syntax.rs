@@ -1,13 +1,78 @@
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
+use std::borrow::Cow;
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Theme, ThemeSet};
+use syntect::html::{
+    ClassStyle, ClassedHTMLGenerator, IncludeBackground, styled_line_to_highlighted_html,
+};
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
+use unicode_width::UnicodeWidthChar;
 
+/// Columns between tab stops unless `ui.tab_width` says otherwise
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// Replace tabs with spaces up to the next tab stop every `width` columns.
+///
+/// Terminals draw tabs inconsistently and leave artifacts while scrolling,
+/// so code and raw source are expanded before they are displayed.
+pub fn expand_tabs(text: &str, width: usize) -> Cow<'_, str> {
+    if !text.contains('\t') {
+        return Cow::Borrowed(text);
+    }
+    let width = width.max(1);
+    let mut expanded = String::with_capacity(text.len());
+    let mut column = 0;
+    for c in text.chars() {
+        match c {
+            '\t' => {
+                let spaces = width - column % width;
+                expanded.extend(std::iter::repeat_n(' ', spaces));
+                column += spaces;
+            }
+            '\n' => {
+                expanded.push(c);
+                column = 0;
+            }
+            _ => {
+                expanded.push(c);
+                column += c.width().unwrap_or(0);
+            }
+        }
+    }
+    Cow::Owned(expanded)
+}
+
+/// Turns code into styled lines for code block rendering.
+///
+/// The renderer only depends on this trait, so alternative backends (or
+/// [`PlainHighlighter`] for no highlighting) can replace [`SyntaxHighlighter`].
+pub trait Highlighter {
+    /// Highlight `code` written in `language` (a code fence info token such
+    /// as `rust`), returning one line per source line.
+    fn highlight_code(&self, code: &str, language: &str) -> Vec<Line<'static>>;
+
+    /// The lines [`highlight_code`](Self::highlight_code) returns, without
+    /// styling. Used for code outside the visible part of the content pane.
+    fn plain_code(&self, code: &str) -> Vec<Line<'static>> {
+        plain_lines(code, DEFAULT_TAB_WIDTH)
+    }
+}
+
+/// One unstyled line per source line, tabs expanded to `tab_width`
+fn plain_lines(code: &str, tab_width: usize) -> Vec<Line<'static>> {
+    expand_tabs(code, tab_width)
+        .lines()
+        .map(|line| Line::from(line.to_string()))
+        .collect()
+}
+
+/// Syntect-based highlighter using the `base16-ocean.dark` theme.
 pub struct SyntaxHighlighter {
     syntax_set: SyntaxSet,
     theme: Theme,
+    tab_width: usize,
 }
 
 impl SyntaxHighlighter {
@@ -16,20 +81,76 @@ impl SyntaxHighlighter {
         let theme_set = ThemeSet::load_defaults();
         let theme = theme_set.themes["base16-ocean.dark"].clone();
 
-        Self { syntax_set, theme }
+        Self {
+            syntax_set,
+            theme,
+            tab_width: DEFAULT_TAB_WIDTH,
+        }
     }
 
-    pub fn highlight_code(&self, code: &str, language: &str) -> Vec<Line<'static>> {
-        // Replace tabs with spaces to avoid terminal rendering artifacts during scrolling
-        // Tabs can cause inconsistent display widths across different terminals
-        let code = code.replace('\t', "    ");
+    /// Expand tabs in highlighted code to stops every `width` columns.
+    pub fn with_tab_width(mut self, width: usize) -> Self {
+        self.tab_width = width;
+        self
+    }
 
-        let syntax = self
-            .syntax_set
+    /// Highlight code as HTML with the theme's colors in inline `style`
+    /// attributes. Text is HTML-escaped; the background is left to the page.
+    pub fn highlight_html_styled(&self, code: &str, language: &str) -> String {
+        let mut highlighter = HighlightLines::new(self.syntax_for(language), &self.theme);
+        let mut html = String::new();
+        for line in LinesWithEndings::from(code) {
+            let ranges = highlighter
+                .highlight_line(line, &self.syntax_set)
+                .unwrap_or_default();
+            match styled_line_to_highlighted_html(&ranges, IncludeBackground::No) {
+                Ok(line_html) => html.push_str(&line_html),
+                Err(_) => html.push_str(&crate::render::escape_html(line)),
+            }
+        }
+        html
+    }
+
+    /// Highlight code as HTML spans carrying the syntax's scope names as
+    /// classes (`<span class="keyword control rust">`), for styling with CSS.
+    pub fn highlight_html_classed(&self, code: &str, language: &str) -> String {
+        let mut generator = ClassedHTMLGenerator::new_with_class_style(
+            self.syntax_for(language),
+            &self.syntax_set,
+            ClassStyle::Spaced,
+        );
+        for line in LinesWithEndings::from(code) {
+            if generator
+                .parse_html_for_line_which_includes_newline(line)
+                .is_err()
+            {
+                return crate::render::escape_html(code);
+            }
+        }
+        generator.finalize()
+    }
+
+    fn syntax_for(&self, language: &str) -> &syntect::parsing::SyntaxReference {
+        self.syntax_set
             .find_syntax_by_token(language)
-            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text())
+    }
 
-        let mut highlighter = HighlightLines::new(syntax, &self.theme);
+    pub fn detect_language(info_string: &str) -> String {
+        // Extract language from info string (e.g., "rust" from "```rust")
+        info_string
+            .split_whitespace()
+            .next()
+            .unwrap_or("text")
+            .to_lowercase()
+    }
+}
+
+impl Highlighter for SyntaxHighlighter {
+    fn highlight_code(&self, code: &str, language: &str) -> Vec<Line<'static>> {
+        let code = expand_tabs(code, self.tab_width);
+
+        let mut highlighter = HighlightLines::new(self.syntax_for(language), &self.theme);
         let mut lines = Vec::new();
 
         for line in LinesWithEndings::from(&code) {
@@ -73,13 +194,8 @@ impl SyntaxHighlighter {
         lines
     }
 
-    pub fn detect_language(info_string: &str) -> String {
-        // Extract language from info string (e.g., "rust" from "```rust")
-        info_string
-            .split_whitespace()
-            .next()
-            .unwrap_or("text")
-            .to_lowercase()
+    fn plain_code(&self, code: &str) -> Vec<Line<'static>> {
+        plain_lines(code, self.tab_width)
     }
 }
 
@@ -88,3 +204,65 @@ impl Default for SyntaxHighlighter {
         Self::new()
     }
 }
+
+/// Highlighter that leaves code uncolored, with tabs at the default width.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlainHighlighter;
+
+impl Highlighter for PlainHighlighter {
+    fn highlight_code(&self, code: &str, _language: &str) -> Vec<Line<'static>> {
+        // Same tab handling as SyntaxHighlighter so layouts match
+        self.plain_code(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_highlighters_keep_code_lines() {
+        let code = "fn main() {\n\tprintln!(\"hi\");\n}\n";
+        let plain = PlainHighlighter.highlight_code(code, "rust");
+        let syntect = SyntaxHighlighter::new().highlight_code(code, "rust");
+
+        assert_eq!(
+            text(&plain),
+            vec!["fn main() {", "    println!(\"hi\");", "}"]
+        );
+        assert!(plain.iter().all(|line| line.style == Style::default()
+            && line.spans.iter().all(|s| s.style == Style::default())));
+        // Syntect keeps each line's trailing newline inside its spans
+        assert_eq!(
+            text(&syntect)
+                .iter()
+                .map(|l| l.trim_end().to_string())
+                .collect::<Vec<_>>(),
+            text(&plain)
+        );
+        assert_eq!(
+            text(&SyntaxHighlighter::new().plain_code(code)),
+            text(&plain)
+        );
+    }
+
+    #[test]
+    fn test_expand_tabs_to_tab_stops() {
+        assert_eq!(expand_tabs("\tx", 2), "  x");
+        assert_eq!(expand_tabs("ab\tc\n\td", 4), "ab  c\n    d");
+        assert!(matches!(expand_tabs("no tabs", 4), Cow::Borrowed(_)));
+
+        let code = "def f():\n\treturn 1\n";
+        let lines = SyntaxHighlighter::new()
+            .with_tab_width(2)
+            .highlight_code(code, "python");
+        assert_eq!(text(&lines)[1].trim_end(), "  return 1");
+    }
+}
@@ -4,6 +4,7 @@ use supports_color::{Stream, on};
 pub enum ColorMode {
     Rgb,        // True color (16M colors)
     Indexed256, // 256-color palette
+    None,       // No color: terminal default foreground and background
 }
 
 #[derive(Debug)]
@@ -30,7 +31,9 @@ impl TerminalCapabilities {
         let macos_version = Self::detect_macos_version();
 
         // Determine if we should warn and which color mode to use
-        let (should_warn, recommended_color_mode) = if is_terminal_app {
+        let (should_warn, recommended_color_mode) = if Self::detect_no_color() {
+            (false, ColorMode::None)
+        } else if is_terminal_app {
             match macos_version {
                 Some(version) if version >= 26 => {
                     // macOS 26+ (Tahoe and later) - Terminal.app works well
@@ -79,6 +82,15 @@ impl TerminalCapabilities {
         None
     }
 
+    /// Whether colors are unwanted or unsupported: `NO_COLOR` is set, or
+    /// stdout is a terminal that reports no color support (e.g. `TERM=dumb`).
+    fn detect_no_color() -> bool {
+        use std::io::IsTerminal;
+
+        std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+            || (std::io::stdout().is_terminal() && on(Stream::Stdout).is_none())
+    }
+
     /// Detect truecolor (24-bit RGB) support using multiple methods.
     ///
     /// Per termstandard/colors recommendations, we check in this order:
@@ -175,16 +187,17 @@ mod tests {
         let caps = TerminalCapabilities::detect();
 
         // The detection should return a valid color mode regardless of environment
-        assert!(
-            caps.recommended_color_mode == ColorMode::Rgb
-                || caps.recommended_color_mode == ColorMode::Indexed256
-        );
+        assert!(matches!(
+            caps.recommended_color_mode,
+            ColorMode::Rgb | ColorMode::Indexed256 | ColorMode::None
+        ));
     }
 
     #[test]
     fn test_color_mode_enum() {
         // Verify ColorMode variants are distinct
         assert_ne!(ColorMode::Rgb, ColorMode::Indexed256);
+        assert_ne!(ColorMode::Indexed256, ColorMode::None);
 
         // Verify Copy trait works
         let mode = ColorMode::Rgb;
@@ -1,5 +1,6 @@
 use crate::tui::terminal_compat::ColorMode;
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::BorderType;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ThemeName {
@@ -11,6 +12,8 @@ pub enum ThemeName {
     Gruvbox,
     TokyoNight,
     CatppuccinMocha,
+    HighContrast,
+    Deuteranopia,
 }
 
 #[derive(Debug, Clone)]
@@ -55,6 +58,37 @@ pub struct Theme {
     pub help_key_fg: Color,
     pub help_desc_fg: Color,
     pub footer_bg: Color,
+    /// Task checkbox markers, for list items and inline `[x]`/`[ ]`
+    pub checkbox_glyphs: CheckboxGlyphs,
+    /// Line style of pane and popup borders
+    pub border_type: BorderType,
+}
+
+/// Glyphs drawn in place of task checkboxes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckboxGlyphs {
+    pub checked: String,
+    pub unchecked: String,
+}
+
+impl CheckboxGlyphs {
+    /// The glyph for a checkbox in the given state.
+    pub fn glyph(&self, checked: bool) -> &str {
+        if checked {
+            &self.checked
+        } else {
+            &self.unchecked
+        }
+    }
+}
+
+impl Default for CheckboxGlyphs {
+    fn default() -> Self {
+        Self {
+            checked: "☑".to_string(),
+            unchecked: "☐".to_string(),
+        }
+    }
 }
 
 impl Theme {
@@ -68,6 +102,8 @@ impl Theme {
             ThemeName::Gruvbox => Self::gruvbox(),
             ThemeName::TokyoNight => Self::tokyo_night(),
             ThemeName::CatppuccinMocha => Self::catppuccin_mocha(),
+            ThemeName::HighContrast => Self::high_contrast(),
+            ThemeName::Deuteranopia => Self::deuteranopia(),
         }
     }
 
@@ -81,6 +117,8 @@ impl Theme {
             ThemeName::Gruvbox => Self::gruvbox_256(),
             ThemeName::TokyoNight => Self::tokyo_night_256(),
             ThemeName::CatppuccinMocha => Self::catppuccin_mocha_256(),
+            ThemeName::HighContrast => Self::high_contrast_256(),
+            ThemeName::Deuteranopia => Self::deuteranopia_256(),
         }
     }
 
@@ -126,6 +164,8 @@ impl Theme {
             help_key_fg: Color::Rgb(100, 200, 255),
             help_desc_fg: Color::Rgb(150, 155, 165),
             footer_bg: Color::Rgb(35, 40, 50),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -170,6 +210,8 @@ impl Theme {
             help_key_fg: Color::Rgb(136, 192, 208),
             help_desc_fg: Color::Rgb(147, 155, 170),
             footer_bg: Color::Rgb(46, 52, 64),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -214,6 +256,8 @@ impl Theme {
             help_key_fg: Color::Rgb(139, 233, 253),
             help_desc_fg: Color::Rgb(98, 114, 164),
             footer_bg: Color::Rgb(40, 42, 54),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -258,6 +302,8 @@ impl Theme {
             help_key_fg: Color::Rgb(38, 139, 210),
             help_desc_fg: Color::Rgb(88, 110, 117),
             footer_bg: Color::Rgb(0, 43, 54),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -302,6 +348,8 @@ impl Theme {
             help_key_fg: Color::Rgb(102, 217, 239),
             help_desc_fg: Color::Rgb(117, 113, 94),
             footer_bg: Color::Rgb(39, 40, 34),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -346,6 +394,8 @@ impl Theme {
             help_key_fg: Color::Rgb(131, 165, 152),
             help_desc_fg: Color::Rgb(146, 131, 116),
             footer_bg: Color::Rgb(40, 40, 40),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -390,6 +440,8 @@ impl Theme {
             help_key_fg: Color::Rgb(122, 162, 247),
             help_desc_fg: Color::Rgb(86, 95, 137),
             footer_bg: Color::Rgb(26, 27, 38),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -434,6 +486,100 @@ impl Theme {
             help_key_fg: Color::Rgb(137, 180, 250),
             help_desc_fg: Color::Rgb(108, 112, 134),
             footer_bg: Color::Rgb(30, 30, 46),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
+        }
+    }
+
+    /// High Contrast - Bright text on near-black with thick borders
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "High Contrast",
+            background: Color::Rgb(10, 10, 10),
+            foreground: Color::Rgb(255, 255, 255),
+            heading_1: Color::Rgb(255, 255, 0),   // Yellow
+            heading_2: Color::Rgb(0, 255, 255),   // Cyan
+            heading_3: Color::Rgb(255, 175, 255), // Light magenta
+            heading_4: Color::Rgb(175, 255, 175), // Light green
+            heading_5: Color::Rgb(215, 215, 215),
+            border_focused: Color::Rgb(255, 255, 0),
+            border_unfocused: Color::Rgb(170, 170, 170),
+            selection_bg: Color::Rgb(255, 255, 0),
+            selection_fg: Color::Rgb(0, 0, 0),
+            status_bar_bg: Color::Rgb(40, 40, 40),
+            status_bar_fg: Color::Rgb(255, 255, 255),
+            inline_code_fg: Color::Rgb(255, 175, 0),
+            inline_code_bg: Color::Rgb(30, 30, 30),
+            bold_fg: Color::Rgb(255, 255, 255),
+            italic_fg: Color::Rgb(175, 215, 255),
+            list_bullet: Color::Rgb(0, 255, 255),
+            blockquote_border: Color::Rgb(200, 200, 200),
+            blockquote_fg: Color::Rgb(220, 220, 220),
+            code_fence: Color::Rgb(175, 215, 255),
+            title_bar_fg: Color::Rgb(255, 255, 0),
+            scrollbar_fg: Color::Rgb(200, 200, 200),
+            selection_indicator_fg: Color::Rgb(0, 0, 0),
+            selection_indicator_bg: Color::Rgb(255, 255, 0),
+            link_fg: Color::Rgb(0, 215, 255),
+            link_selected_bg: Color::Rgb(0, 255, 255),
+            link_selected_fg: Color::Rgb(0, 0, 0),
+            table_border: Color::Rgb(200, 200, 200),
+            search_match_bg: Color::Rgb(255, 95, 255),
+            search_match_fg: Color::Rgb(0, 0, 0),
+            search_current_bg: Color::Rgb(255, 255, 0),
+            search_current_fg: Color::Rgb(0, 0, 0),
+            help_key_bg: Color::Rgb(255, 255, 255),
+            help_key_fg: Color::Rgb(0, 0, 0),
+            help_desc_fg: Color::Rgb(220, 220, 220),
+            footer_bg: Color::Rgb(10, 10, 10),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Thick,
+        }
+    }
+
+    /// Deuteranopia - Blue/orange/yellow accents that avoid red/green pairs
+    pub fn deuteranopia() -> Self {
+        Self {
+            name: "Deuteranopia",
+            background: Color::Rgb(24, 26, 32),
+            foreground: Color::Rgb(225, 225, 230),
+            heading_1: Color::Rgb(100, 143, 255), // Blue
+            heading_2: Color::Rgb(230, 159, 0),   // Orange
+            heading_3: Color::Rgb(240, 228, 66),  // Yellow
+            heading_4: Color::Rgb(204, 121, 167), // Reddish purple
+            heading_5: Color::Rgb(190, 190, 190),
+            border_focused: Color::Rgb(230, 159, 0),
+            border_unfocused: Color::Rgb(90, 90, 100),
+            selection_bg: Color::Rgb(230, 159, 0),
+            selection_fg: Color::Rgb(0, 0, 0),
+            status_bar_bg: Color::Rgb(40, 44, 52),
+            status_bar_fg: Color::Rgb(225, 225, 230),
+            inline_code_fg: Color::Rgb(240, 228, 66),
+            inline_code_bg: Color::Rgb(40, 44, 52),
+            bold_fg: Color::Rgb(255, 255, 255),
+            italic_fg: Color::Rgb(204, 121, 167),
+            list_bullet: Color::Rgb(86, 180, 233), // Sky blue
+            blockquote_border: Color::Rgb(120, 120, 130),
+            blockquote_fg: Color::Rgb(175, 175, 185),
+            code_fence: Color::Rgb(86, 180, 233),
+            title_bar_fg: Color::Rgb(86, 180, 233),
+            scrollbar_fg: Color::Rgb(120, 120, 130),
+            selection_indicator_fg: Color::Rgb(0, 0, 0),
+            selection_indicator_bg: Color::Rgb(230, 159, 0),
+            link_fg: Color::Rgb(86, 180, 233),
+            link_selected_bg: Color::Rgb(86, 180, 233),
+            link_selected_fg: Color::Rgb(0, 0, 0),
+            table_border: Color::Rgb(120, 120, 130),
+            search_match_bg: Color::Rgb(204, 121, 167),
+            search_match_fg: Color::Rgb(0, 0, 0),
+            search_current_bg: Color::Rgb(240, 228, 66),
+            search_current_fg: Color::Rgb(0, 0, 0),
+            help_key_bg: Color::Rgb(40, 44, 52),
+            help_key_fg: Color::Rgb(230, 159, 0),
+            help_desc_fg: Color::Rgb(175, 175, 185),
+            footer_bg: Color::Rgb(24, 26, 32),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -480,6 +626,8 @@ impl Theme {
             help_key_fg: Color::Indexed(117),
             help_desc_fg: Color::Indexed(246),
             footer_bg: Color::Indexed(236),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -524,6 +672,8 @@ impl Theme {
             help_key_fg: Color::Indexed(109),
             help_desc_fg: Color::Indexed(240),
             footer_bg: Color::Indexed(236),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -568,6 +718,8 @@ impl Theme {
             help_key_fg: Color::Indexed(117),
             help_desc_fg: Color::Indexed(61),
             footer_bg: Color::Indexed(236),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -612,6 +764,8 @@ impl Theme {
             help_key_fg: Color::Indexed(33),
             help_desc_fg: Color::Indexed(240),
             footer_bg: Color::Indexed(234),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -656,6 +810,8 @@ impl Theme {
             help_key_fg: Color::Indexed(81),
             help_desc_fg: Color::Indexed(241),
             footer_bg: Color::Indexed(235),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -701,6 +857,8 @@ impl Theme {
             help_key_fg: Color::Indexed(108),
             help_desc_fg: Color::Indexed(243),
             footer_bg: Color::Indexed(235),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -746,6 +904,8 @@ impl Theme {
             help_key_fg: Color::Indexed(110),
             help_desc_fg: Color::Indexed(243),
             footer_bg: Color::Indexed(234),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -791,6 +951,100 @@ impl Theme {
             help_key_fg: Color::Indexed(117),
             help_desc_fg: Color::Indexed(242),
             footer_bg: Color::Indexed(235),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
+        }
+    }
+
+    /// High Contrast - 256-color optimized variant
+    pub fn high_contrast_256() -> Self {
+        Self {
+            name: "High Contrast",
+            background: Color::Indexed(16),
+            foreground: Color::Indexed(231),
+            heading_1: Color::Indexed(226), // Yellow
+            heading_2: Color::Indexed(51),  // Cyan
+            heading_3: Color::Indexed(219), // Light magenta
+            heading_4: Color::Indexed(157), // Light green
+            heading_5: Color::Indexed(252),
+            border_focused: Color::Indexed(226),
+            border_unfocused: Color::Indexed(248),
+            selection_bg: Color::Indexed(226),
+            selection_fg: Color::Indexed(16),
+            status_bar_bg: Color::Indexed(236),
+            status_bar_fg: Color::Indexed(231),
+            inline_code_fg: Color::Indexed(214),
+            inline_code_bg: Color::Indexed(234),
+            bold_fg: Color::Indexed(231),
+            italic_fg: Color::Indexed(153),
+            list_bullet: Color::Indexed(51),
+            blockquote_border: Color::Indexed(250),
+            blockquote_fg: Color::Indexed(253),
+            code_fence: Color::Indexed(153),
+            title_bar_fg: Color::Indexed(226),
+            scrollbar_fg: Color::Indexed(250),
+            selection_indicator_fg: Color::Indexed(16),
+            selection_indicator_bg: Color::Indexed(226),
+            link_fg: Color::Indexed(45),
+            link_selected_bg: Color::Indexed(51),
+            link_selected_fg: Color::Indexed(16),
+            table_border: Color::Indexed(250),
+            search_match_bg: Color::Indexed(207),
+            search_match_fg: Color::Indexed(16),
+            search_current_bg: Color::Indexed(226),
+            search_current_fg: Color::Indexed(16),
+            help_key_bg: Color::Indexed(231),
+            help_key_fg: Color::Indexed(16),
+            help_desc_fg: Color::Indexed(253),
+            footer_bg: Color::Indexed(16),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Thick,
+        }
+    }
+
+    /// Deuteranopia - 256-color optimized variant
+    pub fn deuteranopia_256() -> Self {
+        Self {
+            name: "Deuteranopia",
+            background: Color::Indexed(234),
+            foreground: Color::Indexed(253),
+            heading_1: Color::Indexed(69),  // Blue
+            heading_2: Color::Indexed(214), // Orange
+            heading_3: Color::Indexed(227), // Yellow
+            heading_4: Color::Indexed(175), // Reddish purple
+            heading_5: Color::Indexed(250),
+            border_focused: Color::Indexed(214),
+            border_unfocused: Color::Indexed(240),
+            selection_bg: Color::Indexed(214),
+            selection_fg: Color::Indexed(16),
+            status_bar_bg: Color::Indexed(236),
+            status_bar_fg: Color::Indexed(253),
+            inline_code_fg: Color::Indexed(227),
+            inline_code_bg: Color::Indexed(236),
+            bold_fg: Color::Indexed(231),
+            italic_fg: Color::Indexed(175),
+            list_bullet: Color::Indexed(74), // Sky blue
+            blockquote_border: Color::Indexed(243),
+            blockquote_fg: Color::Indexed(248),
+            code_fence: Color::Indexed(74),
+            title_bar_fg: Color::Indexed(74),
+            scrollbar_fg: Color::Indexed(243),
+            selection_indicator_fg: Color::Indexed(16),
+            selection_indicator_bg: Color::Indexed(214),
+            link_fg: Color::Indexed(74),
+            link_selected_bg: Color::Indexed(74),
+            link_selected_fg: Color::Indexed(16),
+            table_border: Color::Indexed(243),
+            search_match_bg: Color::Indexed(175),
+            search_match_fg: Color::Indexed(16),
+            search_current_bg: Color::Indexed(227),
+            search_current_fg: Color::Indexed(16),
+            help_key_bg: Color::Indexed(236),
+            help_key_fg: Color::Indexed(214),
+            help_desc_fg: Color::Indexed(248),
+            footer_bg: Color::Indexed(234),
+            checkbox_glyphs: CheckboxGlyphs::default(),
+            border_type: BorderType::Plain,
         }
     }
 
@@ -817,6 +1071,17 @@ impl Theme {
             .bg(self.selection_bg)
             .fg(self.selection_fg)
             .add_modifier(Modifier::BOLD)
+            .patch(self.reverse_if_colorless(self.selection_bg))
+    }
+
+    /// Reverse video where a background color would mark something out,
+    /// for themes without colors ([`ColorMode::None`])
+    fn reverse_if_colorless(&self, bg: Color) -> Style {
+        if bg == Color::Reset {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        }
     }
 
     pub fn status_bar_style(&self) -> Style {
@@ -873,6 +1138,7 @@ impl Theme {
         Style::default()
             .bg(self.search_match_bg)
             .fg(self.search_match_fg)
+            .patch(self.reverse_if_colorless(self.search_match_bg))
     }
 
     /// Style for the current/focused search match
@@ -881,6 +1147,7 @@ impl Theme {
             .bg(self.search_current_bg)
             .fg(self.search_current_fg)
             .add_modifier(Modifier::BOLD)
+            .patch(self.reverse_if_colorless(self.search_current_bg))
     }
 
     pub fn modal_text(&self) -> Color {
@@ -930,7 +1197,9 @@ impl Theme {
         // Helper macro to apply color override if present
         macro_rules! apply_color {
             ($field:ident) => {
-                if let Some(ref color_value) = custom.$field {
+                if let Some(ref color_value) = custom.$field
+                    && mode != ColorMode::None
+                {
                     if let Some(color) = color_value.to_color() {
                         // Quantize custom RGB colors if in 256-color mode
                         self.$field = if matches!(mode, ColorMode::Indexed256) {
@@ -983,6 +1252,13 @@ impl Theme {
         apply_color!(help_desc_fg);
         apply_color!(footer_bg);
 
+        if let Some(ref glyph) = custom.checkbox_checked {
+            self.checkbox_glyphs.checked = glyph.clone();
+        }
+        if let Some(ref glyph) = custom.checkbox_unchecked {
+            self.checkbox_glyphs.unchecked = glyph.clone();
+        }
+
         self
     }
 
@@ -994,58 +1270,64 @@ impl Theme {
                 // Use optimized 256-color variants for built-in themes
                 Theme::from_name_256(theme_name)
             }
+            // Terminal default colors everywhere
+            ColorMode::None => self.map_colors(|_| Color::Reset),
         }
     }
 
     /// Apply color mode to custom theme (convert RGB to 256-color if needed)
     /// This is used for custom themes that don't have optimized variants
-    pub fn with_color_mode_custom(mut self, mode: ColorMode) -> Self {
+    pub fn with_color_mode_custom(self, mode: ColorMode) -> Self {
         match mode {
             ColorMode::Rgb => self,
-            ColorMode::Indexed256 => {
-                self.background = rgb_to_256(self.background);
-                self.foreground = rgb_to_256(self.foreground);
-                self.heading_1 = rgb_to_256(self.heading_1);
-                self.heading_2 = rgb_to_256(self.heading_2);
-                self.heading_3 = rgb_to_256(self.heading_3);
-                self.heading_4 = rgb_to_256(self.heading_4);
-                self.heading_5 = rgb_to_256(self.heading_5);
-                self.border_focused = rgb_to_256(self.border_focused);
-                self.border_unfocused = rgb_to_256(self.border_unfocused);
-                self.selection_bg = rgb_to_256(self.selection_bg);
-                self.selection_fg = rgb_to_256(self.selection_fg);
-                self.status_bar_bg = rgb_to_256(self.status_bar_bg);
-                self.status_bar_fg = rgb_to_256(self.status_bar_fg);
-                self.inline_code_fg = rgb_to_256(self.inline_code_fg);
-                self.inline_code_bg = rgb_to_256(self.inline_code_bg);
-                self.bold_fg = rgb_to_256(self.bold_fg);
-                self.italic_fg = rgb_to_256(self.italic_fg);
-                self.list_bullet = rgb_to_256(self.list_bullet);
-                self.blockquote_border = rgb_to_256(self.blockquote_border);
-                self.blockquote_fg = rgb_to_256(self.blockquote_fg);
-                self.code_fence = rgb_to_256(self.code_fence);
-                self.title_bar_fg = rgb_to_256(self.title_bar_fg);
-                self.scrollbar_fg = rgb_to_256(self.scrollbar_fg);
-                self.selection_indicator_fg = rgb_to_256(self.selection_indicator_fg);
-                self.selection_indicator_bg = rgb_to_256(self.selection_indicator_bg);
-                self.link_fg = rgb_to_256(self.link_fg);
-                self.link_selected_bg = rgb_to_256(self.link_selected_bg);
-                self.link_selected_fg = rgb_to_256(self.link_selected_fg);
-                self.table_border = rgb_to_256(self.table_border);
-                // Search highlighting
-                self.search_match_bg = rgb_to_256(self.search_match_bg);
-                self.search_match_fg = rgb_to_256(self.search_match_fg);
-                self.search_current_bg = rgb_to_256(self.search_current_bg);
-                self.search_current_fg = rgb_to_256(self.search_current_fg);
-                // Footer keybinding hints
-                self.help_key_bg = rgb_to_256(self.help_key_bg);
-                self.help_key_fg = rgb_to_256(self.help_key_fg);
-                self.help_desc_fg = rgb_to_256(self.help_desc_fg);
-                self.footer_bg = rgb_to_256(self.footer_bg);
-                self
-            }
+            ColorMode::Indexed256 => self.map_colors(rgb_to_256),
+            ColorMode::None => self.map_colors(|_| Color::Reset),
         }
     }
+
+    /// Replace every theme color with `f(color)`
+    fn map_colors(mut self, f: impl Fn(Color) -> Color) -> Self {
+        self.background = f(self.background);
+        self.foreground = f(self.foreground);
+        self.heading_1 = f(self.heading_1);
+        self.heading_2 = f(self.heading_2);
+        self.heading_3 = f(self.heading_3);
+        self.heading_4 = f(self.heading_4);
+        self.heading_5 = f(self.heading_5);
+        self.border_focused = f(self.border_focused);
+        self.border_unfocused = f(self.border_unfocused);
+        self.selection_bg = f(self.selection_bg);
+        self.selection_fg = f(self.selection_fg);
+        self.status_bar_bg = f(self.status_bar_bg);
+        self.status_bar_fg = f(self.status_bar_fg);
+        self.inline_code_fg = f(self.inline_code_fg);
+        self.inline_code_bg = f(self.inline_code_bg);
+        self.bold_fg = f(self.bold_fg);
+        self.italic_fg = f(self.italic_fg);
+        self.list_bullet = f(self.list_bullet);
+        self.blockquote_border = f(self.blockquote_border);
+        self.blockquote_fg = f(self.blockquote_fg);
+        self.code_fence = f(self.code_fence);
+        self.title_bar_fg = f(self.title_bar_fg);
+        self.scrollbar_fg = f(self.scrollbar_fg);
+        self.selection_indicator_fg = f(self.selection_indicator_fg);
+        self.selection_indicator_bg = f(self.selection_indicator_bg);
+        self.link_fg = f(self.link_fg);
+        self.link_selected_bg = f(self.link_selected_bg);
+        self.link_selected_fg = f(self.link_selected_fg);
+        self.table_border = f(self.table_border);
+        // Search highlighting
+        self.search_match_bg = f(self.search_match_bg);
+        self.search_match_fg = f(self.search_match_fg);
+        self.search_current_bg = f(self.search_current_bg);
+        self.search_current_fg = f(self.search_current_fg);
+        // Footer keybinding hints
+        self.help_key_bg = f(self.help_key_bg);
+        self.help_key_fg = f(self.help_key_fg);
+        self.help_desc_fg = f(self.help_desc_fg);
+        self.footer_bg = f(self.footer_bg);
+        self
+    }
 }
 
 /// Convert RGB color to nearest 256-color palette entry
@@ -1076,3 +1358,140 @@ fn rgb_to_256(color: Color) -> Color {
         other => other,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Approximate RGB value of a color as an xterm would show it
+    fn rgb(color: Color) -> (u8, u8, u8) {
+        const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        match color {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Black => (0, 0, 0),
+            Color::White => (255, 255, 255),
+            Color::Indexed(n @ 16..=231) => {
+                let n = n - 16;
+                (
+                    CUBE[(n / 36) as usize],
+                    CUBE[(n / 6 % 6) as usize],
+                    CUBE[(n % 6) as usize],
+                )
+            }
+            Color::Indexed(n @ 232..) => {
+                let level = 8 + 10 * (n - 232);
+                (level, level, level)
+            }
+            other => panic!("no RGB value for {:?}", other),
+        }
+    }
+
+    /// WCAG 2 contrast ratio between two colors
+    fn contrast(a: Color, b: Color) -> f64 {
+        let luminance = |c: Color| {
+            let (r, g, b) = rgb(c);
+            let channel = |v: u8| {
+                let v = v as f64 / 255.0;
+                if v <= 0.03928 {
+                    v / 12.92
+                } else {
+                    ((v + 0.055) / 1.055).powf(2.4)
+                }
+            };
+            0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+        };
+        let (la, lb) = (luminance(a), luminance(b));
+        (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+    }
+
+    fn assert_readable(theme: &Theme, min: f64) {
+        let pairs = [
+            ("text", theme.foreground, theme.background),
+            ("selection", theme.selection_fg, theme.selection_bg),
+            ("link", theme.link_fg, theme.background),
+            (
+                "selected link",
+                theme.link_selected_fg,
+                theme.link_selected_bg,
+            ),
+            ("search match", theme.search_match_fg, theme.search_match_bg),
+        ];
+        let headings =
+            (1..=5).map(|level| ("heading", theme.heading_color(level), theme.background));
+        for (what, fg, bg) in pairs.into_iter().chain(headings) {
+            let ratio = contrast(fg, bg);
+            assert!(
+                ratio >= min,
+                "{} {} contrast is {:.2}, expected at least {}",
+                theme.name,
+                what,
+                ratio,
+                min
+            );
+        }
+    }
+
+    #[test]
+    fn test_accessible_themes_meet_contrast_ratios() {
+        // WCAG AAA for High Contrast, AA for Deuteranopia, in both color modes
+        for mode in [ColorMode::Rgb, ColorMode::Indexed256] {
+            for (name, min) in [
+                (ThemeName::HighContrast, 7.0),
+                (ThemeName::Deuteranopia, 4.5),
+            ] {
+                assert_readable(&Theme::from_name(name).with_color_mode(mode, name), min);
+            }
+        }
+        assert_eq!(
+            Theme::from_name(ThemeName::HighContrast).border_type,
+            BorderType::Thick
+        );
+    }
+
+    #[test]
+    fn test_no_color_mode_styles_are_plain() {
+        let custom = crate::config::CustomThemeConfig {
+            heading_1: Some(crate::config::ColorValue::Named("red".to_string())),
+            ..Default::default()
+        };
+        let theme = Theme::from_name(ThemeName::Dracula)
+            .with_color_mode(ColorMode::None, ThemeName::Dracula)
+            .with_custom_colors(&custom, ColorMode::None);
+
+        let styles = [
+            theme.border_style(true),
+            theme.status_bar_style(),
+            theme.inline_code_style(),
+            theme.bold_style(),
+            theme.text_style(),
+            theme.content_style(),
+            theme.code_fence_style(),
+            theme.selection_style(),
+            theme.search_match_style(),
+            theme.search_current_style(),
+            theme.help_key_style(),
+            theme.footer_style(),
+        ];
+        for style in styles {
+            assert!(matches!(style.fg, None | Some(Color::Reset)), "{style:?}");
+            assert!(matches!(style.bg, None | Some(Color::Reset)), "{style:?}");
+        }
+        assert_eq!(theme.heading_color(1), Color::Reset);
+        assert_eq!(
+            theme.with_color_mode_custom(ColorMode::None).link_fg,
+            Color::Reset
+        );
+
+        // Selections stay visible without a background color
+        let selection = Theme::ocean_dark()
+            .with_color_mode(ColorMode::None, ThemeName::OceanDark)
+            .selection_style();
+        assert!(selection.add_modifier.contains(Modifier::REVERSED));
+        assert!(
+            !Theme::ocean_dark()
+                .selection_style()
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+    }
+}
@@ -5,14 +5,18 @@ mod util;
 
 use layout::{DynamicLayout, Section};
 
+use crate::keybindings::{Action, KeybindingMode};
 use crate::tui::app::{App, AppMode, Focus};
+use crate::tui::terminal_compat::ColorMode;
 use crate::tui::theme::Theme;
 use popups::{
     render_cell_edit_overlay, render_command_palette, render_file_create_confirm,
-    render_file_picker, render_help_popup, render_link_picker, render_save_before_nav_confirm,
-    render_save_before_quit_confirm, render_save_width_confirm, render_theme_picker,
+    render_file_picker, render_help_popup, render_link_picker, render_overwrite_confirm,
+    render_save_before_nav_confirm, render_save_before_quit_confirm, render_save_width_confirm,
+    render_theme_picker,
 };
 use ratatui::Frame;
+use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
@@ -20,10 +24,26 @@ use ratatui::widgets::{
     Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
     ScrollbarState, Wrap,
 };
-use table::render_table;
-use util::{detect_checkbox_in_text, filter_content};
+use table::{TableLayout, render_table};
+use util::filter_content;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
+    render_app(frame, app);
+
+    // Syntax highlighting and other hard-coded colors bypass the theme
+    if app.color_mode() == ColorMode::None {
+        strip_colors(frame.buffer_mut());
+    }
+}
+
+/// Reset every cell to the terminal's default colors, keeping modifiers
+fn strip_colors(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        cell.set_fg(Color::Reset).set_bg(Color::Reset);
+    }
+}
+
+fn render_app(frame: &mut Frame, app: &mut App) {
     // Update content metrics before rendering to ensure content height and scroll are correct
     app.update_content_metrics();
 
@@ -32,6 +52,12 @@ pub fn render(frame: &mut Frame, app: &mut App) {
 
     let area = frame.area();
 
+    // Large documents are parsed in the background; show progress until ready
+    if let Some((spinner, progress)) = app.loading_status() {
+        render_loading_screen(frame, app, area, spinner, &progress);
+        return;
+    }
+
     // Create dynamic main layout
     // Show search bar if: outline search is active OR in document search mode (typing or viewing results)
     let show_search_bar = app.show_search || app.mode == AppMode::DocSearch;
@@ -143,6 +169,11 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         render_save_before_nav_confirm(frame, app.pending_edits.len(), &app.theme);
     }
 
+    // Render overwrite confirmation when the file changed on disk
+    if matches!(app.mode, AppMode::ConfirmOverwrite) {
+        render_overwrite_confirm(frame, &app.filename, &app.theme);
+    }
+
     // Render command palette
     if matches!(app.mode, AppMode::CommandPalette) {
         render_command_palette(frame, app, &app.theme);
@@ -151,7 +182,10 @@ pub fn render(frame: &mut Frame, app: &mut App) {
 
 fn render_title_bar(frame: &mut Frame, app: &App, area: Rect) {
     let heading_count = app.document.headings.len();
-    let title_text = format!("treemd - {} - {} headings", app.filename, heading_count);
+    let mut title_text = format!("treemd - {} - {} headings", app.filename, heading_count);
+    if app.disk_changed {
+        title_text.push_str(" [disk-changed]");
+    }
 
     let title = Paragraph::new(title_text)
         .style(
@@ -163,6 +197,43 @@ fn render_title_bar(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(title, area);
 }
 
+fn render_loading_screen(frame: &mut Frame, app: &App, area: Rect, spinner: &str, progress: &str) {
+    let layout = DynamicLayout::vertical(area)
+        .section(Section::Title, Constraint::Length(2))
+        .section(Section::Content, Constraint::Min(0))
+        .build();
+
+    let title = Paragraph::new(format!("treemd - {} - loading", app.filename))
+        .style(
+            Style::default()
+                .fg(app.theme.title_bar_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::BOTTOM));
+    frame.render_widget(title, layout.require(Section::Title));
+
+    let content_area = layout.require(Section::Content);
+    let [message_area] = Layout::vertical([Constraint::Length(3)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(content_area);
+    let message = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", spinner),
+                Style::default().fg(app.theme.selection_indicator_fg),
+            ),
+            Span::styled(progress, Style::default().fg(app.theme.foreground)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "q: quit",
+            Style::default().fg(app.theme.help_desc_fg),
+        )),
+    ])
+    .alignment(ratatui::layout::Alignment::Center);
+    frame.render_widget(message, message_area);
+}
+
 fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
     // Unified search bar rendering for both outline and document search
     let is_doc_search = app.mode == AppMode::DocSearch;
@@ -172,7 +243,11 @@ fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
         let info = if !app.doc_search_matches.is_empty() {
             let current = app.doc_search_current_idx.unwrap_or(0) + 1;
             let total = app.doc_search_matches.len();
-            format!(" [{}/{}]", current, total)
+            if app.doc_search_truncated {
+                format!(" [{}/{}] (first {} matches)", current, total, total)
+            } else {
+                format!(" [{}/{}]", current, total)
+            }
         } else if !app.doc_search_query.is_empty() {
             " [no matches]".to_string()
         } else {
@@ -217,11 +292,25 @@ fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
         Span::raw(match_info),
     ];
 
+    // Show when code blocks are excluded from content search
+    if is_doc_search && !app.doc_search_in_code {
+        line_spans.push(Span::styled(
+            " [code:off]",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    // Show when outline search keeps headings under a match
+    if !is_doc_search && app.outline_search_path {
+        line_spans.push(Span::styled(" [path]", Style::default().fg(Color::Yellow)));
+    }
+
     // Add hint text - consistent for both modes
-    let hint = if is_active {
-        "  (Esc, Ctrl+U)"
-    } else {
-        "  (Esc, Tab, /: edit)"
+    let hint = match (is_active, is_doc_search) {
+        (true, true) => "  (Esc, Ctrl+U, Alt+C: code)",
+        (true, false) => "  (Esc, Ctrl+U, Alt+P: path)",
+        (false, true) => "  (Esc, Tab, /: edit, Alt+C: code)",
+        (false, false) => "  (Esc, Tab, /: edit)",
     };
     line_spans.push(Span::styled(
         hint.to_string(),
@@ -232,6 +321,7 @@ fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(app.theme.border_type)
                 .border_style(Style::default().fg(accent_color))
                 .title(title)
                 .style(Style::default().bg(Color::Rgb(30, 30, 50))),
@@ -241,9 +331,11 @@ fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+const OUTLINE_HIGHLIGHT_SYMBOL: &str = "► ";
+
 fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
     use crate::tui::app::DOCUMENT_OVERVIEW;
-    use util::build_highlighted_line;
+    use util::{build_highlighted_line, truncate_with_ellipsis};
 
     let theme = &app.theme;
     let search_query = if app.show_search && !app.search_query.is_empty() {
@@ -252,6 +344,11 @@ fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
         None
     };
 
+    // Width left for each item inside the borders and highlight symbol
+    let inner_width = area
+        .width
+        .saturating_sub(2 + OUTLINE_HIGHLIGHT_SYMBOL.width() as u16);
+
     let items: Vec<ListItem> = app
         .outline_items
         .iter()
@@ -287,14 +384,33 @@ fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
                 )
             };
 
+            // Task completion after the heading, when enabled
+            let progress = app
+                .outline_task_stats(item)
+                .map(|stats| format!(" {}", util::task_progress(stats)));
+            let progress_width = progress.as_deref().map_or(0, UnicodeWidthStr::width);
+
+            // Truncate long headings to the pane; selection and search still
+            // use the full item text
+            let text_width = (inner_width as usize)
+                .saturating_sub(prefix_text.width())
+                .saturating_sub(progress_width);
+            let display_text = truncate_with_ellipsis(&item.text, text_width);
+
             // Build line with search highlighting using shared utility
-            let line = build_highlighted_line(
+            let mut line = build_highlighted_line(
                 vec![Span::styled(prefix_text, base_style)],
-                &item.text,
+                &display_text,
                 search_query,
                 base_style,
                 theme.search_match_style(),
             );
+            if let Some(progress) = progress {
+                line.spans.push(Span::styled(
+                    progress,
+                    Style::default().fg(theme.list_bullet),
+                ));
+            }
 
             ListItem::new(line)
         })
@@ -306,12 +422,13 @@ fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(theme.border_type)
                 .border_style(block_style)
                 .title(" Outline "),
         )
         .style(theme.content_style())
         .highlight_style(theme.selection_style())
-        .highlight_symbol("► ");
+        .highlight_symbol(OUTLINE_HIGHLIGHT_SYMBOL);
 
     frame.render_stateful_widget(list, area, &mut app.outline_state);
 
@@ -338,29 +455,36 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
     let theme = app.theme.clone();
     let block_style = theme.border_style(app.focus == Focus::Content);
 
+    let raw_indicator = match (app.show_raw_source, app.focus_mode) {
+        (true, true) => "[RAW] [FOCUS] ",
+        (true, false) => "[RAW] ",
+        (false, true) => "[FOCUS] ",
+        (false, false) => "",
+    };
+
     // Get content for selected section and determine title
     let (content_text, title) = if let Some(heading_text) = app.selected_heading_text() {
-        let content = app
-            .document
-            .extract_section(heading_text)
-            .unwrap_or_else(|| app.document.content.clone());
+        let content = app.selected_section_content();
 
         // Build title with various indicators
-        let raw_indicator = if app.show_raw_source { "[RAW] " } else { "" };
+        let progress = app
+            .selected_task_stats()
+            .map(|stats| format!(" {}", util::task_progress(stats)))
+            .unwrap_or_default();
         let title = if app.mode == AppMode::LinkFollow && !app.links_in_view.is_empty() {
             format!(
-                " {}{} [Links: {}] ",
+                " {}{}{} [Links: {}] ",
                 raw_indicator,
                 heading_text,
+                progress,
                 app.links_in_view.len()
             )
         } else {
-            format!(" {}{} ", raw_indicator, heading_text)
+            format!(" {}{}{} ", raw_indicator, heading_text, progress)
         };
 
         (content, title)
     } else {
-        let raw_indicator = if app.show_raw_source { "[RAW] " } else { "" };
         let title = if app.mode == AppMode::LinkFollow && !app.links_in_view.is_empty() {
             format!(
                 " {}Content [Links: {}] ",
@@ -370,7 +494,7 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
         } else {
             format!(" {}Content ", raw_indicator)
         };
-        (app.document.content.clone(), title)
+        (app.document.content.as_str().into(), title)
     };
 
     // Apply content filtering (frontmatter, LaTeX) based on config
@@ -383,13 +507,26 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
             app.should_latex_aggressive(),
         )
     } else {
-        content_text
+        content_text.to_string()
     };
 
+    // Calculate available width for tables (content area minus borders and padding)
+    let content_width = area.width.saturating_sub(2); // 2 for left/right borders
+
     // Check if we should render raw source or enhanced markdown
-    let mut rendered_text = if app.show_raw_source {
+    let rendered = if app.show_raw_source {
         // Raw source view - show unprocessed markdown
-        render_raw_markdown(&content_text, &theme)
+        let text = render_raw_markdown(&content_text, &theme, app.tab_width());
+        let line_rows = text
+            .lines
+            .iter()
+            .map(|line| line_rows(line, Some(content_width)))
+            .collect();
+        RenderedContent {
+            text,
+            heading_lines: Vec::new(),
+            line_rows,
+        }
     } else {
         // Enhanced markdown rendering with syntax highlighting
         // Pre-extract what we need before passing app as mutable to avoid borrow conflicts
@@ -401,18 +538,23 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
         // Clone interactive state to avoid keeping a borrow when passing app as mutable
         let interactive_state = app.interactive_state.clone();
 
-        // Calculate available width for tables (content area minus borders and padding)
-        let content_width = area.width.saturating_sub(2); // 2 for left/right borders
-
-        render_markdown_enhanced(
+        render_markdown_for_pane(
             &content_text,
-            &app.highlighter,
+            app.highlighter.as_ref(),
             &theme,
             selected_element_id,
             Some(&interactive_state), // Pass cloned copy to release borrow
             Some(content_width),
+            BlockLayout {
+                table_wrap: app.table_wrap(),
+                block_spacing: app.block_spacing(),
+                viewport: Some(highlight_rows(app.content_scroll, area.height)),
+                parse_cache: Some(&app.content_parse),
+            },
         )
     };
+    app.content_heading_lines = rendered.heading_lines;
+    let mut rendered_text = rendered.text;
 
     // Apply search highlighting only for document/content search mode
     // Outline search (s) only filters headings, it doesn't highlight content
@@ -422,6 +564,7 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
             &app.doc_search_query,
             app.doc_search_current_idx,
             app.doc_search_matches.len(),
+            app.doc_search_in_code,
             &theme,
         );
     }
@@ -435,16 +578,27 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
             ScrollbarState::new(rendered_line_count as usize).position(app.content_scroll as usize);
     }
 
+    // Only the lines on screen go to the paragraph, so wrapping and drawing
+    // cost the same however long the document is
+    let (visible, skip_rows) = visible_lines(
+        &rendered.line_rows,
+        app.content_scroll as usize,
+        area.height as usize,
+    );
+    rendered_text.lines.truncate(visible.end);
+    rendered_text.lines.drain(..visible.start);
+
     let paragraph = Paragraph::new(rendered_text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(theme.border_type)
                 .border_style(block_style)
                 .title(title),
         )
         .style(theme.content_style())
         .wrap(Wrap { trim: false })
-        .scroll((app.content_scroll, 0));
+        .scroll((skip_rows as u16, 0));
 
     frame.render_widget(paragraph, area);
 
@@ -572,6 +726,7 @@ fn render_inline_images(frame: &mut Frame, app: &mut App, area: Rect) {
 
                             let border = Block::default()
                                 .borders(Borders::ALL)
+                                .border_type(theme.border_type)
                                 .border_style(border_style)
                                 .title(" ▶ Selected ")
                                 .title_alignment(ratatui::layout::Alignment::Left);
@@ -835,6 +990,7 @@ fn render_image_modal(frame: &mut Frame, app: &mut App, area: Rect) {
         // Render modal border (but NOT over image area during animation)
         let modal_border = ratatui::widgets::Block::default()
             .borders(Borders::ALL)
+            .border_type(app.theme.border_type)
             .border_style(
                 Style::default()
                     .fg(theme_heading_1)
@@ -964,7 +1120,8 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    let status_text = if app.mode == AppMode::Interactive {
+    // Interactive and link modes have a status of their own
+    let mode_status = if app.mode == AppMode::Interactive {
         // Interactive mode status with position info
         let total = app.interactive_state.elements.len();
         let current = app
@@ -981,46 +1138,25 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         // Get element-specific hint (shows current element info)
         let element_hint = app.interactive_state.get_status_hint();
 
-        format!(
+        Some(format!(
             " [INTERACTIVE] {}/{} ({}%) • {}",
             current, total, percentage, element_hint
-        )
+        ))
     } else if app.mode == AppMode::LinkFollow {
-        // Link follow mode status
-        let link_count = app.links_in_view.len();
+        // Link follow mode status (selection indexes the filtered list)
+        let link_count = app.filtered_link_indices.len();
         let selected = app.selected_link_idx.map(|i| i + 1).unwrap_or(0);
 
         let link_info = if link_count > 0 {
-            // Show current link details
-            let current_link = app
-                .selected_link_idx
-                .and_then(|idx| app.links_in_view.get(idx));
-
-            if let Some(link) = current_link {
-                use crate::parser::LinkTarget;
-                let target_str = match &link.target {
-                    LinkTarget::Anchor(a) => format!("#{}", a),
-                    LinkTarget::RelativeFile { path, anchor } => {
-                        if let Some(a) = anchor {
-                            format!("{}#{}", path.display(), a)
-                        } else {
-                            path.display().to_string()
-                        }
-                    }
-                    LinkTarget::WikiLink { target, .. } => format!("[[{}]]", target),
-                    LinkTarget::External(url) => {
-                        // Truncate long URLs
-                        if url.len() > 40 {
-                            format!("{}...", &url[..37])
-                        } else {
-                            url.clone()
-                        }
-                    }
-                };
-
+            // Show current link details; wikilinks show their alias as the text
+            if let Some(link) = app.get_selected_link() {
                 format!(
-                    "Link {}/{}: \"{}\" → {}",
-                    selected, link_count, link.text, target_str
+                    "{} {}/{}: \"{}\" → {}",
+                    if link.image { "Image" } else { "Link" },
+                    selected,
+                    link_count,
+                    link.text,
+                    util::format_link_destination(link, 40)
                 )
             } else {
                 format!("Link {}/{}", selected, link_count)
@@ -1029,66 +1165,34 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
             "No links in current section".to_string()
         };
 
-        format!(" [LINKS] {} ", link_info)
+        Some(format!(" [LINKS] {} ", link_info))
     } else {
-        // Normal mode status - show position based on focus
-        let (focus_indicator, position_info) = match app.focus {
-            Focus::Outline => {
-                let selected_idx = app.outline_state.selected().unwrap_or(0);
-                let total = app.outline_items.len();
-                let percentage = if total > 0 {
-                    (selected_idx + 1) * 100 / total
-                } else {
-                    0
-                };
-                (
-                    "Outline",
-                    format!("{}/{} ({}%)", selected_idx + 1, total, percentage),
-                )
-            }
-            Focus::Content => {
-                // Show content scroll position
-                let scroll_pos = app.content_scroll as usize;
-                let content_height = app.content_height as usize;
-                let percentage = if content_height > 0 {
-                    ((scroll_pos + 1) * 100 / content_height).min(100)
-                } else {
-                    0
-                };
-                (
-                    "Content",
-                    format!("Line {} ({}%)", scroll_pos + 1, percentage),
-                )
-            }
-        };
-
-        let outline_status = if app.show_outline {
-            format!("Outline:{}%", app.outline_width)
-        } else {
-            "Outline:Hidden".to_string()
-        };
-
-        let bookmark_indicator = if app.bookmark_position.is_some() {
-            " ⚑"
-        } else {
-            ""
-        };
+        None
+    };
 
-        let history_indicator = if !app.file_history.is_empty() {
-            format!(" ← {} ", app.file_history.len())
+    let mut status_text = if let Some(status_text) = mode_status {
+        let theme_name = format!(" • Theme:{}", app.theme.name);
+        let reload_indicator = if app.auto_reload {
+            " [auto]"
         } else {
-            "".to_string()
+            " [manual]"
         };
-
+        let raw_indicator = if app.show_raw_source { " [RAW]" } else { "" };
         format!(
-            " [{}] {}{}{} • {}",
-            focus_indicator, position_info, bookmark_indicator, history_indicator, outline_status
+            "{}{}{}{}",
+            status_text, theme_name, reload_indicator, raw_indicator
         )
+    } else {
+        // Everywhere else the status follows `ui.status_format`
+        util::format_status(&app.status_format, |token| status_token(app, token))
     };
-
-    let theme_name = format!(" • Theme:{}", app.theme.name);
-    let raw_indicator = if app.show_raw_source { " [RAW]" } else { "" };
-    let status_text = format!("{}{}{}", status_text, theme_name, raw_indicator);
+    // Count typed so far, e.g. `42` before `G` or `j`
+    if let Some(count) = app.count_prefix {
+        status_text.push_str(&format!(" • {}", count));
+        if app.mode == AppMode::Normal {
+            status_text.push_str(&format!(" (G/Enter: heading {})", count));
+        }
+    }
 
     let status_style = if app.mode == AppMode::Interactive {
         Style::default()
@@ -1109,107 +1213,199 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(status, area);
 }
 
+/// Value of a `ui.status_format` token, or `None` for unknown tokens.
+fn status_token(app: &App, token: &str) -> Option<String> {
+    // Position of the focused pane: outline item or content line
+    let (index, total) = match app.focus {
+        Focus::Outline => (
+            app.outline_state.selected().unwrap_or(0) + 1,
+            app.outline_items.len(),
+        ),
+        Focus::Content => (app.content_scroll as usize + 1, app.content_height as usize),
+    };
+    let percent = (index * 100).checked_div(total).unwrap_or(0).min(100);
+    let words = || app.selected_section_content().split_whitespace().count();
+
+    let value = match token {
+        "focus" => match app.focus {
+            Focus::Outline => "Outline".to_string(),
+            Focus::Content => "Content".to_string(),
+        },
+        "position" => match app.focus {
+            Focus::Outline => format!("{}/{} ({}%)", index, total, percent),
+            Focus::Content => format!("Line {} ({}%)", index, percent),
+        },
+        "index" => index.to_string(),
+        "total" => total.to_string(),
+        "percent" => percent.to_string(),
+        "bookmark" => if app.bookmark_position.is_some() {
+            "⚑"
+        } else {
+            ""
+        }
+        .to_string(),
+        "history" if app.file_history.is_empty() => String::new(),
+        "history" => format!("← {}", app.file_history.len()),
+        "outline" if app.show_outline => format!("Outline:{}%", app.outline_width),
+        "outline" => "Outline:Hidden".to_string(),
+        "theme" => app.theme.name.to_string(),
+        "reload" => if app.auto_reload {
+            "[auto]"
+        } else {
+            "[manual]"
+        }
+        .to_string(),
+        "raw" => if app.show_raw_source { "[RAW]" } else { "" }.to_string(),
+        "file" => app.filename.clone(),
+        "heading" => app.selected_heading_text().unwrap_or_default().to_string(),
+        "words" => words().to_string(),
+        "reading_time" => format!("{} min", words().div_ceil(WORDS_PER_MINUTE)),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Reading speed behind the `{reading_time}` status token
+const WORDS_PER_MINUTE: usize = 200;
+
 /// Render the footer with context-aware keybinding hints
 fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     use crate::tui::app::AppMode;
 
     let theme = &app.theme;
 
-    // Define keybindings based on current mode
-    let keys: Vec<(&str, &str)> = match app.mode {
+    // Hints show the keys actually bound in the current mode
+    use crate::keybindings::Action::*;
+    let mode = app.current_keybinding_mode();
+    let hint = |actions: &[Action], desc: &'static str| (key_hint(app, mode, actions), desc);
+
+    let keys: Vec<(String, &str)> = match app.mode {
         AppMode::Interactive => {
             // Check if we're in table mode
             if app.interactive_state.is_in_table_mode() {
                 vec![
-                    ("j/k", "Row"),
-                    ("h/l", "Col"),
-                    ("e", "Edit"),
-                    ("y", "Copy"),
-                    ("Esc", "Exit Table"),
+                    hint(&[InteractiveNext, InteractivePrevious], "Row"),
+                    hint(&[InteractiveLeft, InteractiveRight], "Col"),
+                    hint(&[InteractiveActivate], "Edit"),
+                    hint(&[CopyContent], "Copy"),
+                    hint(&[CopyTableCsv], "CSV"),
+                    hint(&[ExitMode], "Exit Table"),
                 ]
             } else {
                 // Get current element type for context-specific hints
                 use crate::tui::interactive::ElementType;
+                let navigate = hint(&[InteractiveNext, InteractivePrevious], "Navigate");
+                let exit = hint(&[ExitInteractiveMode], "Exit");
                 let element_hint = match app.interactive_state.current_element() {
                     Some(elem) => match &elem.element_type {
-                        ElementType::Checkbox { .. } => {
-                            vec![("j/k", "Navigate"), ("Space", "Toggle"), ("Esc", "Exit")]
+                        ElementType::Checkbox { .. } | ElementType::InlineCheckbox { .. } => {
+                            vec![navigate, hint(&[InteractiveActivate], "Toggle"), exit]
                         }
                         ElementType::Table { .. } => {
                             vec![
-                                ("j/k", "Navigate"),
-                                ("Enter", "Enter Table"),
-                                ("y", "Copy"),
-                                ("Esc", "Exit"),
+                                navigate,
+                                hint(&[InteractiveActivate], "Enter Table"),
+                                hint(&[CopyContent], "Copy"),
+                                exit,
                             ]
                         }
                         ElementType::Link { .. } => {
                             vec![
-                                ("j/k", "Navigate"),
-                                ("Enter", "Follow"),
-                                ("y", "Copy URL"),
-                                ("Esc", "Exit"),
+                                navigate,
+                                hint(&[InteractiveActivate], "Follow"),
+                                hint(&[CopyContent], "Copy URL"),
+                                exit,
                             ]
                         }
                         ElementType::Details { .. } => {
-                            vec![("j/k", "Navigate"), ("Enter", "Expand"), ("Esc", "Exit")]
+                            vec![navigate, hint(&[InteractiveActivate], "Expand"), exit]
                         }
                         ElementType::CodeBlock { .. } => {
-                            vec![("j/k", "Navigate"), ("y", "Copy"), ("Esc", "Exit")]
+                            vec![
+                                navigate,
+                                hint(&[InteractiveActivate], "Collapse"),
+                                hint(&[CopyContent], "Copy"),
+                                exit,
+                            ]
                         }
                         ElementType::Image { .. } => {
-                            vec![("j/k", "Navigate"), ("Enter", "Open"), ("Esc", "Exit")]
+                            vec![
+                                navigate,
+                                hint(&[InteractiveActivate], "Open"),
+                                hint(&[CopyContent], "Copy Source"),
+                                exit,
+                            ]
+                        }
+                        ElementType::Footnote { .. } => {
+                            vec![navigate, hint(&[InteractiveActivate], "Jump"), exit]
+                        }
+                        ElementType::DataFold { folded, .. } => {
+                            let label = if *folded { "Unfold" } else { "Fold" };
+                            vec![navigate, hint(&[InteractiveActivate], label), exit]
                         }
                     },
-                    None => vec![("j/k", "Navigate"), ("Enter", "Action"), ("Esc", "Exit")],
+                    None => vec![navigate, hint(&[InteractiveActivate], "Action"), exit],
                 };
                 element_hint
             }
         }
         AppMode::LinkFollow => {
+            let jump = format!(
+                "{}-{}",
+                key_hint(app, mode, &[JumpToLink1]),
+                key_hint(app, mode, &[JumpToLink9])
+            );
             vec![
-                ("Tab", "Next Link"),
-                ("1-9", "Jump"),
-                ("Enter", "Follow"),
-                ("y", "Copy URL"),
-                ("Esc", "Exit"),
+                hint(&[NextLink], "Next Link"),
+                (jump, "Jump"),
+                hint(&[FollowLink], "Follow"),
+                hint(&[EditLinkTarget], "Edit"),
+                hint(&[CopyContent], "Copy URL"),
+                hint(&[ExitMode], "Exit"),
             ]
         }
         AppMode::DocSearch => {
             vec![
-                ("n/N", "Next/Prev"),
-                ("Tab", "Outline Search"),
-                ("Enter", "Accept"),
-                ("Esc", "Cancel"),
+                hint(&[NextMatch, PrevMatch], "Next/Prev"),
+                hint(&[ToggleSearchMode], "Outline Search"),
+                hint(&[ConfirmAction], "Accept"),
+                hint(&[ExitMode], "Cancel"),
             ]
         }
         AppMode::CellEdit => {
-            vec![("Enter", "Save"), ("Esc", "Cancel")]
+            vec![
+                hint(&[ConfirmAction], "Save"),
+                hint(&[CancelAction], "Cancel"),
+            ]
         }
         AppMode::CommandPalette => {
-            vec![("j/k", "Navigate"), ("Enter", "Select"), ("Esc", "Cancel")]
+            vec![
+                hint(&[CommandPaletteNext, CommandPalettePrev], "Navigate"),
+                hint(&[ConfirmAction], "Select"),
+                hint(&[ExitMode], "Cancel"),
+            ]
         }
         _ => {
             // Normal mode - show based on focus
             match app.focus {
                 Focus::Outline => {
                     vec![
-                        ("j/k", "Navigate"),
-                        ("Enter", "Select"),
-                        ("/", "Search"),
-                        ("i", "Interactive"),
-                        ("f", "Links"),
-                        ("?", "Help"),
+                        hint(&[Next, Previous], "Navigate"),
+                        hint(&[ToggleExpand], "Select"),
+                        hint(&[EnterDocSearch], "Search"),
+                        hint(&[EnterInteractiveMode], "Interactive"),
+                        hint(&[EnterLinkFollowMode], "Links"),
+                        hint(&[ToggleHelp], "Help"),
                     ]
                 }
                 Focus::Content => {
                     vec![
-                        ("j/k", "Scroll"),
-                        ("/", "Search"),
-                        ("i", "Interactive"),
-                        ("f", "Links"),
-                        ("y", "Copy"),
-                        ("?", "Help"),
+                        hint(&[Next, Previous], "Scroll"),
+                        hint(&[EnterDocSearch], "Search"),
+                        hint(&[EnterInteractiveMode], "Interactive"),
+                        hint(&[EnterLinkFollowMode], "Links"),
+                        hint(&[CopyContent], "Copy"),
+                        hint(&[ToggleHelp], "Help"),
                     ]
                 }
             }
@@ -1233,13 +1429,42 @@ fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(footer, area);
 }
 
-use crate::parser::content::parse_content;
+/// Keys bound to `actions` in `mode`, joined with `/`.
+///
+/// Uses the first key bound to each action, or the action's name if unbound.
+fn key_hint(app: &App, mode: KeybindingMode, actions: &[Action]) -> String {
+    actions
+        .iter()
+        .map(|action| {
+            app.keybindings
+                .keys_for_action(mode, *action)
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| action.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+use crate::parser::LinkTarget;
+use crate::parser::content::{
+    ItemLine, ListLayout, ListMarker, ParsedContent, SourceLayout, inline_checkboxes,
+    parse_with_layout,
+};
 use crate::parser::output::{Block as ContentBlock, InlineElement};
 use crate::parser::utils::parse_inline_html;
-use crate::tui::syntax::SyntaxHighlighter;
+use crate::tui::interactive::blank_line_after;
+use crate::tui::syntax::Highlighter;
+use crate::tui::syntax::{DEFAULT_TAB_WIDTH, expand_tabs};
+use crate::tui::theme::CheckboxGlyphs;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+use unicode_width::UnicodeWidthStr;
 
 /// Render raw markdown source with line numbers
-fn render_raw_markdown(content: &str, theme: &Theme) -> Text<'static> {
+fn render_raw_markdown(content: &str, theme: &Theme, tab_width: usize) -> Text<'static> {
     let lines: Vec<Line<'static>> = content
         .lines()
         .enumerate()
@@ -1249,8 +1474,7 @@ fn render_raw_markdown(content: &str, theme: &Theme) -> Text<'static> {
                 format!("{:4} │ ", idx + 1),
                 Style::default().fg(theme.border_unfocused),
             );
-            // Replace tabs with spaces to avoid terminal rendering artifacts
-            let line_content = line.replace('\t', "    ");
+            let line_content = expand_tabs(line, tab_width).into_owned();
             // Raw content with plain text styling
             let content_span = Span::styled(line_content, Style::default().fg(theme.foreground));
             Line::from(vec![line_num, content_span])
@@ -1260,20 +1484,312 @@ fn render_raw_markdown(content: &str, theme: &Theme) -> Text<'static> {
     Text::from(lines)
 }
 
-fn render_markdown_enhanced(
+pub(crate) fn render_markdown_enhanced(
     content: &str,
-    highlighter: &SyntaxHighlighter,
+    highlighter: &dyn Highlighter,
     theme: &Theme,
     selected_element_id: Option<crate::tui::interactive::ElementId>,
     interactive_state: Option<&crate::tui::interactive::InteractiveState>,
     available_width: Option<u16>,
 ) -> Text<'static> {
+    render_markdown_for_pane(
+        content,
+        highlighter,
+        theme,
+        selected_element_id,
+        interactive_state,
+        available_width,
+        BlockLayout::default(),
+    )
+    .text
+}
+
+/// How blocks are laid out in the content pane: `[ui]` settings and what is
+/// on screen.
+#[derive(Debug, Clone)]
+struct BlockLayout<'a> {
+    /// Wrap long table cells instead of cutting them off
+    table_wrap: bool,
+    /// Blank lines between blocks (see [`blank_line_after`])
+    block_spacing: usize,
+    /// Screen rows worth highlighting; code blocks entirely outside them are
+    /// laid out unstyled. `None` highlights everything.
+    viewport: Option<Range<usize>>,
+    /// Where to keep the parsed content between frames
+    parse_cache: Option<&'a ParseCache>,
+}
+
+impl Default for BlockLayout<'_> {
+    fn default() -> Self {
+        Self {
+            table_wrap: true,
+            block_spacing: 1,
+            viewport: None,
+            parse_cache: None,
+        }
+    }
+}
+
+/// Rows of a content pane `height` rows tall worth highlighting at `scroll`:
+/// the visible ones, and a few either side so scrolling a line doesn't show
+/// unstyled code before the next frame.
+fn highlight_rows(scroll: u16, height: u16) -> Range<usize> {
+    let (scroll, height) = (scroll as usize, height as usize);
+    let margin = height / 4;
+    scroll.saturating_sub(margin)..scroll + height + margin
+}
+
+/// Rows `text` takes when wrapped to `width` columns, counted the way the
+/// content pane's [`Paragraph`] wraps it: by words, not characters.
+fn wrapped_rows<'a>(text: impl Into<Text<'a>>, width: Option<u16>) -> usize {
+    let text = text.into();
+    match width {
+        Some(width) if width > 0 => Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .line_count(width),
+        _ => text.height(),
+    }
+}
+
+/// Rows a rendered `line` takes when wrapped to `width` columns.
+fn line_rows(line: &Line, width: Option<u16>) -> usize {
+    let spans: Vec<Span> = line
+        .spans
+        .iter()
+        .map(|span| Span::raw(span.content.as_ref()))
+        .collect();
+    wrapped_rows(Line::from(spans), width)
+}
+
+/// Rows a code block's `code` takes when wrapped to `width` columns,
+/// counted from the source so nothing is built just to measure it.
+///
+/// Ignores folds, so it can err high.
+fn code_rows(code: &str, width: Option<u16>) -> usize {
+    wrapped_rows(expand_tabs(code, DEFAULT_TAB_WIDTH).as_ref(), width)
+}
+
+/// Lines to hand the content pane for the `height` rows from `scroll`, given
+/// the rows each line wraps to, and how many rows of the first to skip.
+fn visible_lines(line_rows: &[usize], scroll: usize, height: usize) -> (Range<usize>, usize) {
+    // Rows above the first visible line
+    let mut above = 0;
+    let start = line_rows
+        .iter()
+        .position(|&rows| {
+            if above + rows > scroll {
+                return true;
+            }
+            above += rows;
+            false
+        })
+        .unwrap_or(line_rows.len());
+
+    let mut end = start;
+    let mut bottom = above;
+    while end < line_rows.len() && bottom < scroll + height {
+        bottom += line_rows[end];
+        end += 1;
+    }
+    (start..end, scroll.saturating_sub(above))
+}
+
+/// Code lines with folded regions hidden: each folded line ends in its
+/// placeholder, and the fold at `selected` source line gets a marker.
+fn fold_code_lines(
+    code: Vec<Line<'static>>,
+    folds: &crate::tui::data_fold::DataFolds,
+    selected: Option<usize>,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut code: Vec<Option<Line>> = code.into_iter().map(Some).collect();
+    folds
+        .visible_lines()
+        .filter_map(|(line, placeholder)| {
+            let mut rendered = code.get_mut(line)?.take()?;
+            if let Some(placeholder) = placeholder {
+                // Highlighted lines keep their newline; the placeholder goes before it
+                if let Some(last) = rendered.spans.last_mut()
+                    && let Some(text) = last.content.strip_suffix('\n')
+                {
+                    last.content = text.to_string().into();
+                }
+                rendered.spans.push(Span::styled(
+                    placeholder.to_string(),
+                    theme.code_fence_style(),
+                ));
+            }
+            if selected == Some(line) {
+                rendered.spans.insert(
+                    0,
+                    Span::styled(
+                        "▸ ",
+                        Style::default()
+                            .fg(theme.selection_indicator_fg)
+                            .bg(theme.selection_indicator_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                );
+            }
+            Some(rendered)
+        })
+        .collect()
+}
+
+/// One-line summary shown for a collapsed code block: ``▶ ```rust (42 lines)``
+fn code_summary_line(language: Option<&str>, content: &str, theme: &Theme) -> Line<'static> {
+    let count = content.lines().count();
+    Line::from(vec![
+        Span::styled("▶ ", Style::default().fg(theme.list_bullet)),
+        Span::styled(
+            format!(
+                "```{} ({} line{})",
+                language.unwrap_or(""),
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            theme.code_fence_style(),
+        ),
+    ])
+}
+
+/// Summary line for `block` when it is a code block collapsed in interactive mode
+fn collapsed_code_summary(
+    block: &ContentBlock,
+    id: crate::tui::interactive::ElementId,
+    interactive_state: Option<&crate::tui::interactive::InteractiveState>,
+    theme: &Theme,
+) -> Option<Vec<Line<'static>>> {
+    let ContentBlock::Code {
+        language, content, ..
+    } = block
+    else {
+        return None;
+    };
+    interactive_state
+        .is_some_and(|state| state.is_code_collapsed(id))
+        .then(|| vec![code_summary_line(language.as_deref(), content, theme)])
+}
+
+/// `content` with inline checkboxes replaced by the theme's glyphs; the one
+/// at byte offset `selected` gets a selection marker.
+fn draw_inline_checkboxes<'a>(
+    content: &'a str,
+    theme: &Theme,
+    selected: Option<usize>,
+) -> Cow<'a, str> {
+    let checkboxes = inline_checkboxes(content);
+    if checkboxes.is_empty() {
+        return Cow::Borrowed(content);
+    }
+
+    let mut drawn = String::with_capacity(content.len());
+    let mut copied = 0;
+    for checkbox in checkboxes {
+        drawn.push_str(&content[copied..checkbox.range.start]);
+        if selected == Some(checkbox.range.start) {
+            drawn.push_str("▸ ");
+        }
+        drawn.push_str(theme.checkbox_glyphs.glyph(checkbox.checked));
+        copied = checkbox.range.end;
+    }
+    drawn.push_str(&content[copied..]);
+    Cow::Owned(drawn)
+}
+
+/// Put the selection marker before the footnote token at `selected`.
+fn mark_selected_footnote(content: &str, selected: Option<usize>) -> Cow<'_, str> {
+    let at_token = |&offset: &usize| content.get(offset..).is_some_and(|c| c.starts_with("[^"));
+    match selected.filter(at_token) {
+        Some(offset) => Cow::Owned(format!("{}▸ {}", &content[..offset], &content[offset..])),
+        None => Cow::Borrowed(content),
+    }
+}
+
+/// The content pane's last parse, so redrawing an unchanged section doesn't
+/// parse it again.
+#[derive(Debug, Default)]
+pub(crate) struct ParseCache(RefCell<Option<(String, Rc<ParsedContent>)>>);
+
+impl ParseCache {
+    /// `content` parsed by [`parse_with_layout`], reusing the last parse if
+    /// it was of the same content.
+    fn parse(&self, content: &str) -> Rc<ParsedContent> {
+        if let Some((parsed_from, parsed)) = &*self.0.borrow()
+            && parsed_from == content
+        {
+            return Rc::clone(parsed);
+        }
+        let parsed = Rc::new(parse_with_layout(content, 0));
+        *self.0.borrow_mut() = Some((content.to_string(), Rc::clone(&parsed)));
+        parsed
+    }
+}
+
+/// Content pane text, with what the pane needs to know about its lines.
+struct RenderedContent {
+    text: Text<'static>,
+    /// Line index of each heading block, in document order
+    heading_lines: Vec<u16>,
+    /// Rows each line wraps to, measured when laying out for a viewport
+    line_rows: Vec<usize>,
+}
+
+/// Like [`render_markdown_enhanced`], also returning where the headings are
+/// and, with a viewport, how many rows each line takes.
+fn render_markdown_for_pane(
+    content: &str,
+    highlighter: &dyn Highlighter,
+    theme: &Theme,
+    selected_element_id: Option<crate::tui::interactive::ElementId>,
+    interactive_state: Option<&crate::tui::interactive::InteractiveState>,
+    available_width: Option<u16>,
+    layout: BlockLayout<'_>,
+) -> RenderedContent {
+    let table_wrap = layout.table_wrap;
     let mut lines = Vec::new();
+    let mut heading_lines = Vec::new();
+
+    // Inline `[x]`/`[ ]` become glyphs before parsing, so tables size
+    // their columns for them. Only one element is selected, so marking a
+    // footnote first leaves no checkbox offset to shift
+    use crate::tui::interactive::ElementType;
+    let selected = interactive_state
+        .and_then(|state| state.current_element())
+        .filter(|elem| Some(elem.id) == selected_element_id);
+    let selected_checkbox = selected.and_then(|elem| match elem.element_type {
+        ElementType::InlineCheckbox { offset, .. } => Some(offset),
+        _ => None,
+    });
+    let selected_footnote = selected.and_then(|elem| match elem.element_type {
+        ElementType::Footnote { offset, .. } => Some(offset),
+        _ => None,
+    });
+    let content = &mark_selected_footnote(content, selected_footnote);
+    let content = &draw_inline_checkboxes(content, theme, selected_checkbox);
 
     // Parse content into structured blocks
-    let blocks = parse_content(content, 0);
+    let parsed = match layout.parse_cache {
+        Some(cache) => cache.parse(content),
+        None => Rc::new(parse_with_layout(content, 0)),
+    };
+    let ParsedContent { blocks, layouts } = &*parsed;
+
+    // Screen rows above the current block, counted as lines are added
+    let (mut rows, mut line_rows) = (0, Vec::new());
+    let mut measure = |lines: &[Line], line_rows: &mut Vec<usize>| {
+        if layout.viewport.is_some() {
+            for line in &lines[line_rows.len()..] {
+                line_rows.push(self::line_rows(line, available_width));
+                rows += line_rows.last().unwrap();
+            }
+        }
+        rows
+    };
 
     for (block_idx, block) in blocks.iter().enumerate() {
+        let rows = measure(&lines, &mut line_rows);
+
         // Check if any element in this block is selected (block-level or inline)
         let is_block_selected = selected_element_id
             .map(|id| id.block_idx == block_idx)
@@ -1307,6 +1823,8 @@ fn render_markdown_enhanced(
                     span.style = heading_style;
                 }
 
+                heading_lines.push(lines.len() as u16);
+
                 // Add selection indicator if selected (with background for visibility)
                 if is_block_selected {
                     formatted.insert(
@@ -1358,14 +1876,49 @@ fn render_markdown_enhanced(
                     }
                 }
             }
+            ContentBlock::Code {
+                language, content, ..
+            } if interactive_state.is_some_and(|state| {
+                state.is_code_collapsed(crate::tui::interactive::ElementId {
+                    block_idx,
+                    sub_idx: None,
+                })
+            }) =>
+            {
+                // Collapsed in interactive mode: one summary line
+                let mut summary = code_summary_line(language.as_deref(), content, theme);
+                if is_block_selected {
+                    summary.spans.insert(
+                        0,
+                        Span::styled(
+                            "→ ",
+                            Style::default()
+                                .fg(theme.selection_indicator_fg)
+                                .bg(theme.selection_indicator_bg)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    );
+                }
+                lines.push(summary);
+            }
             ContentBlock::Code {
                 language, content, ..
             } => {
                 // Opening fence
                 let lang_str = language.as_deref().unwrap_or("");
 
+                let code_id = crate::tui::interactive::ElementId {
+                    block_idx,
+                    sub_idx: None,
+                };
+                let folds = interactive_state.and_then(|state| state.data_folds(code_id));
+                // Source line of the selected fold, when one is selected
+                let selected_fold = selected_element_id
+                    .filter(|id| id.block_idx == block_idx && folds.is_some())
+                    .and_then(|id| id.sub_idx);
+
                 let mut fence_spans = vec![];
-                if is_block_selected {
+                if is_block_selected && selected_fold.is_none() {
                     fence_spans.push(Span::styled(
                         "→ ",
                         Style::default()
@@ -1381,9 +1934,21 @@ fn render_markdown_enhanced(
 
                 lines.push(Line::from(fence_spans));
 
-                // Highlighted code
-                let highlighted = highlighter.highlight_code(content, lang_str);
-                lines.extend(highlighted);
+                // Highlighted code, or plain when scrolled out of view: the
+                // same lines either way, so heights and positions don't change
+                let fold = |code: Vec<Line<'static>>| match folds {
+                    Some(folds) => fold_code_lines(code, folds, selected_fold, theme),
+                    None => code,
+                };
+                let visible = layout.viewport.as_ref().is_none_or(|viewport| {
+                    let code_rows = code_rows(content, available_width);
+                    rows < viewport.end && rows + code_rows + 2 > viewport.start
+                });
+                if visible {
+                    lines.extend(fold(highlighter.highlight_code(content, lang_str)));
+                } else {
+                    lines.extend(fold(highlighter.plain_code(content)));
+                }
 
                 // Closing fence
                 lines.push(Line::from(vec![Span::styled(
@@ -1441,135 +2006,28 @@ fn render_markdown_enhanced(
                         selected_line_offset
                     };
 
-                    // Check if content has nested items (contains newlines with indentation)
-                    let has_nested = item.content.contains('\n');
-
-                    if has_nested {
-                        // Render multi-line item with nested items
-                        let content_lines = item.content.lines();
-                        for (line_idx, line) in content_lines.enumerate() {
-                            // Check if this specific line should have the pointer
-                            let show_pointer = pointer_line == Some(line_idx);
-
-                            if line_idx == 0 {
-                                // First line: use regular list marker
-                                let mut spans = vec![];
-
-                                // Pointer replaces leading spaces, not prepended
-                                if show_pointer {
-                                    spans.push(Span::styled(
-                                        "→ ",
-                                        Style::default()
-                                            .fg(theme.selection_indicator_fg)
-                                            .bg(theme.selection_indicator_bg)
-                                            .add_modifier(Modifier::BOLD),
-                                    ));
-                                } else {
-                                    spans.push(Span::raw("  "));
-                                }
-
-                                let prefix = if let Some(checked) = item.checked {
-                                    let checkbox = if checked { "☑" } else { "☐" };
-                                    format!("{} ", checkbox)
-                                } else if *ordered {
-                                    format!("{}. ", idx + 1)
-                                } else {
-                                    "• ".to_string()
-                                };
-                                let formatted = format_inline_markdown(line, theme);
-                                spans.push(Span::styled(
-                                    prefix,
-                                    Style::default().fg(theme.list_bullet),
-                                ));
-                                spans.extend(formatted);
-                                lines.push(Line::from(spans));
-                            } else {
-                                // Nested items: detect indentation and add bullet/checkbox
-                                let trimmed = line.trim_start();
-                                let indent_count = line.len() - trimmed.len();
-                                if indent_count > 0 {
-                                    // Check if this is a task list item by looking for checkbox in text
-                                    let (is_task, checked, text_after_marker) =
-                                        detect_checkbox_in_text(trimmed);
-
-                                    let mut spans = vec![];
-
-                                    // Calculate indent: reserve 2 chars for pointer at appropriate depth
-                                    // Base indent (2) + nested indent, with pointer replacing last 2 chars
-                                    let total_indent = indent_count + 2;
-                                    if show_pointer {
-                                        // Indent up to pointer position, then pointer
-                                        let pre_pointer_indent =
-                                            " ".repeat(total_indent.saturating_sub(2));
-                                        spans.push(Span::raw(pre_pointer_indent));
-                                        spans.push(Span::styled(
-                                            "→ ",
-                                            Style::default()
-                                                .fg(theme.selection_indicator_fg)
-                                                .bg(theme.selection_indicator_bg)
-                                                .add_modifier(Modifier::BOLD),
-                                        ));
-                                    } else {
-                                        spans.push(Span::raw(" ".repeat(total_indent)));
-                                    }
-
-                                    let marker = if is_task {
-                                        // Task list item with checkbox
-                                        if checked { "☑ " } else { "☐ " }
-                                    } else {
-                                        // Regular bullet
-                                        "• "
-                                    };
-
-                                    let formatted =
-                                        format_inline_markdown(text_after_marker, theme);
-                                    spans.push(Span::styled(
-                                        marker,
-                                        Style::default().fg(theme.list_bullet),
-                                    ));
-                                    spans.extend(formatted);
-                                    lines.push(Line::from(spans));
-                                } else {
-                                    // Empty line or continuation
-                                    lines.push(Line::from(line.to_string()));
-                                }
-                            }
-                        }
-                    } else {
-                        // Simple single-line item (or item with nested blocks)
-                        let formatted = if !item.inline.is_empty() {
+                    let item_lines = layouts[block_idx].list().map_or_else(
+                        || ListLayout::new(*ordered).lines(idx, item),
+                        |layout| layout.lines(idx, item),
+                    );
+                    let layout = layout_list_item(&item_lines, &theme.checkbox_glyphs);
+                    let single_line = layout.len() == 1;
+                    for (line_idx, list_line) in layout.iter().enumerate() {
+                        // Single-line items keep their parsed inline elements so
+                        // links inside them can be highlighted
+                        let text_spans = if single_line && !item.inline.is_empty() {
                             render_inline_elements(&item.inline, theme, selected_link_inline_idx)
                         } else {
-                            format_inline_markdown(&item.content, theme)
-                        };
-
-                        let mut spans = vec![];
-
-                        // Pointer replaces leading spaces, not prepended
-                        if pointer_line.is_some() {
-                            spans.push(Span::styled(
-                                "→ ",
-                                Style::default()
-                                    .fg(theme.selection_indicator_fg)
-                                    .bg(theme.selection_indicator_bg)
-                                    .add_modifier(Modifier::BOLD),
-                            ));
-                        } else {
-                            spans.push(Span::raw("  "));
-                        }
-
-                        let prefix = if let Some(checked) = item.checked {
-                            let checkbox = if checked { "☑" } else { "☐" };
-                            format!("{} ", checkbox)
-                        } else if *ordered {
-                            format!("{}. ", idx + 1)
-                        } else {
-                            "• ".to_string()
+                            format_inline_markdown(list_line.text, theme)
                         };
-
-                        spans.push(Span::styled(prefix, Style::default().fg(theme.list_bullet)));
-                        spans.extend(formatted);
-                        lines.push(Line::from(spans));
+                        let show_pointer = pointer_line == Some(line_idx);
+                        lines.push(build_list_line(
+                            list_line,
+                            LIST_GUTTER,
+                            show_pointer,
+                            text_spans,
+                            theme,
+                        ));
                     }
 
                     // Render nested blocks within this list item (e.g., code blocks)
@@ -1597,10 +2055,30 @@ fn render_markdown_enhanced(
                             })
                             .unwrap_or(false);
 
-                        // Reduce width by indent (5 spaces)
-                        let nested_width = available_width.map(|w| w.saturating_sub(5));
+                        // Nested blocks line up with the item's text
+                        let nested_indent = LIST_GUTTER + layout[0].text_column();
+                        let nested_width =
+                            available_width.map(|w| w.saturating_sub(nested_indent as u16));
+                        let code_id = crate::tui::interactive::ElementId {
+                            block_idx,
+                            sub_idx: Some(
+                                idx * ITEM_MULTIPLIER
+                                    + nested_idx * NESTED_MULTIPLIER
+                                    + CODE_BLOCK_OFFSET,
+                            ),
+                        };
                         let nested_lines =
-                            render_block_to_lines(nested_block, highlighter, theme, nested_width);
+                            collapsed_code_summary(nested_block, code_id, interactive_state, theme)
+                                .unwrap_or_else(|| {
+                                    render_block_to_lines(
+                                        nested_block,
+                                        &SourceLayout::None,
+                                        highlighter,
+                                        theme,
+                                        nested_width,
+                                        table_wrap,
+                                    )
+                                });
                         for (line_idx, nested_line) in nested_lines.into_iter().enumerate() {
                             let mut indented_spans = vec![];
 
@@ -1613,9 +2091,10 @@ fn render_markdown_enhanced(
                                         .bg(theme.selection_indicator_bg)
                                         .add_modifier(Modifier::BOLD),
                                 ));
-                                indented_spans.push(Span::raw("   ")); // 3 spaces (5 - 2 for arrow)
+                                indented_spans
+                                    .push(Span::raw(" ".repeat(nested_indent.saturating_sub(2))));
                             } else {
-                                indented_spans.push(Span::raw("     ")); // 5 spaces indent
+                                indented_spans.push(Span::raw(" ".repeat(nested_indent)));
                             }
 
                             indented_spans.extend(nested_line.spans);
@@ -1630,11 +2109,17 @@ fn render_markdown_enhanced(
             } => {
                 // If we have nested blocks, render them recursively
                 if !nested.is_empty() {
-                    for nested_block in nested {
+                    for (nested_idx, nested_block) in nested.iter().enumerate() {
                         // Reduce width by blockquote prefix (2 chars)
                         let nested_width = available_width.map(|w| w.saturating_sub(2));
-                        let nested_lines =
-                            render_block_to_lines(nested_block, highlighter, theme, nested_width);
+                        let nested_lines = render_block_to_lines(
+                            nested_block,
+                            layouts[block_idx].nested(nested_idx),
+                            highlighter,
+                            theme,
+                            nested_width,
+                            table_wrap,
+                        );
                         for nested_line in nested_lines {
                             let mut spans = vec![Span::styled(
                                 "│ ",
@@ -1691,16 +2176,23 @@ fn render_markdown_enhanced(
                     (false, None)
                 };
 
-                // Use available_width for smart table collapsing
+                // Use available_width for smart table collapsing; large tables
+                // show one page of rows around the selected cell
+                let visible_rows = selected_cell
+                    .and_then(|(row, _)| crate::tui::interactive::table_window(row, rows.len()));
                 let table_lines = render_table(
                     headers,
                     alignments,
                     rows,
                     theme,
-                    is_block_selected,
-                    in_table_mode,
-                    selected_cell,
-                    available_width,
+                    TableLayout {
+                        is_selected: is_block_selected,
+                        in_table_mode,
+                        selected_cell,
+                        available_width,
+                        visible_rows,
+                        wrap: table_wrap,
+                    },
                 );
                 lines.extend(table_lines);
             }
@@ -1842,15 +2334,22 @@ fn render_markdown_enhanced(
 
                             // Reduce available width by indent (2 spaces)
                             let nested_width = available_width.map(|w| w.saturating_sub(2));
+                            let visible_rows = selected_cell.and_then(|(row, _)| {
+                                crate::tui::interactive::table_window(row, nested_rows.len())
+                            });
                             let table_lines = render_table(
                                 nested_headers,
                                 nested_alignments,
                                 nested_rows,
                                 theme,
-                                is_this_table_selected,
-                                in_table_mode,
-                                selected_cell,
-                                nested_width,
+                                TableLayout {
+                                    is_selected: is_this_table_selected,
+                                    in_table_mode,
+                                    selected_cell,
+                                    available_width: nested_width,
+                                    visible_rows,
+                                    wrap: table_wrap,
+                                },
                             );
 
                             for nested_line in table_lines {
@@ -1862,12 +2361,26 @@ fn render_markdown_enhanced(
                             // Other block types use the standard renderer
                             // Reduce width by indent (2 spaces)
                             let block_width = available_width.map(|w| w.saturating_sub(2));
-                            let nested_lines = render_block_to_lines(
+                            let code_element = crate::tui::interactive::ElementId {
+                                block_idx,
+                                sub_idx: Some(code_id),
+                            };
+                            let nested_lines = collapsed_code_summary(
                                 nested_block,
-                                highlighter,
+                                code_element,
+                                interactive_state,
                                 theme,
-                                block_width,
-                            );
+                            )
+                            .unwrap_or_else(|| {
+                                render_block_to_lines(
+                                    nested_block,
+                                    layouts[block_idx].nested(nested_idx),
+                                    highlighter,
+                                    theme,
+                                    block_width,
+                                    table_wrap,
+                                )
+                            });
                             for (line_idx, nested_line) in nested_lines.into_iter().enumerate() {
                                 let mut spans = vec![];
 
@@ -1900,10 +2413,17 @@ fn render_markdown_enhanced(
         }
 
         // Add blank line after most blocks for spacing
-        lines.push(Line::from(""));
+        if blank_line_after(blocks, block_idx, layout.block_spacing) {
+            lines.push(Line::from(""));
+        }
     }
 
-    Text::from(lines)
+    measure(&lines, &mut line_rows);
+    RenderedContent {
+        text: Text::from(lines),
+        heading_lines,
+        line_rows,
+    }
 }
 
 /// Apply search highlighting to rendered text while preserving original span styles.
@@ -1913,6 +2433,7 @@ fn apply_search_highlighting(
     query: &str,
     current_match_idx: Option<usize>,
     total_matches: usize,
+    search_in_code: bool,
     theme: &Theme,
 ) -> Text<'static> {
     if query.is_empty() {
@@ -1922,8 +2443,22 @@ fn apply_search_highlighting(
     let query_lower = query.to_lowercase();
     let mut new_lines = Vec::new();
     let mut match_counter = 0usize;
+    let mut in_code_block = false;
 
     for line in text.lines.into_iter() {
+        // Skip code blocks (fences included) when code search is off, so the
+        // highlighted matches stay in step with the filtered match list
+        if !search_in_code {
+            let is_fence = is_code_fence_line(&line);
+            if is_fence || in_code_block {
+                if is_fence {
+                    in_code_block = !in_code_block;
+                }
+                new_lines.push(line);
+                continue;
+            }
+        }
+
         // Build span index: (byte_start, byte_end, span_index)
         let mut span_ranges: Vec<(usize, usize, usize)> = Vec::new();
         let mut byte_pos = 0;
@@ -2040,6 +2575,14 @@ fn apply_search_highlighting(
     Text::from(new_lines)
 }
 
+/// Check whether a rendered line is a code fence, ignoring indentation,
+/// selection pointers and blockquote borders.
+fn is_code_fence_line(line: &Line) -> bool {
+    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    let text = text.trim_start_matches(|c: char| c.is_whitespace() || c == '→' || c == '│');
+    text.starts_with("```") || text.starts_with("~~~")
+}
+
 /// Safely slice a string at byte boundaries, returning None if boundaries are invalid
 fn safe_slice(s: &str, start: usize, end: usize) -> Option<&str> {
     if start > end || end > s.len() {
@@ -2051,11 +2594,114 @@ fn safe_slice(s: &str, start: usize, end: usize) -> Option<&str> {
     Some(&s[start..end])
 }
 
+/// Columns reserved left of top-level list markers for the selection pointer
+const LIST_GUTTER: usize = 2;
+
+/// One display line of a list item, positioned by its nesting depth.
+struct ListLine<'a> {
+    /// Column where the marker starts, relative to the list's left edge
+    indent: usize,
+    /// Bullet, number or checkbox (empty for continuation lines)
+    marker: String,
+    text: &'a str,
+}
+
+impl ListLine<'_> {
+    /// Column where the line's text starts
+    fn text_column(&self) -> usize {
+        self.indent + self.marker.width()
+    }
+}
+
+/// Lay out a list item and its nested items as display lines.
+///
+/// Depths and markers come from the list's [`ListLayout`], and each marker
+/// is placed under the text of its parent so bullets, numbers and checkboxes
+/// line up at every level. Continuation lines after a line break line up
+/// with the text of the item they belong to.
+fn layout_list_item<'a>(lines: &[ItemLine<'a>], glyphs: &CheckboxGlyphs) -> Vec<ListLine<'a>> {
+    let mut layout: Vec<ListLine> = Vec::new();
+    // Text column of the most recent item at each depth
+    let mut text_columns: Vec<usize> = Vec::new();
+
+    for line in lines {
+        let marker = match line.marker {
+            ListMarker::Bullet => "• ".to_string(),
+            ListMarker::Number(number) => format!("{}. ", number),
+            ListMarker::Task(checked) => format!("{} ", glyphs.glyph(checked)),
+            ListMarker::Continuation => String::new(),
+        };
+        let indent = if marker.is_empty() {
+            let depth = line.depth.min(text_columns.len().saturating_sub(1));
+            text_columns.get(depth).copied().unwrap_or(0)
+        } else {
+            // Sit under the closest shallower item's text
+            let parent = line.depth.min(text_columns.len()).saturating_sub(1);
+            match line.depth {
+                0 => 0,
+                _ => text_columns.get(parent).copied().unwrap_or(0),
+            }
+        };
+        let list_line = ListLine {
+            indent,
+            marker,
+            text: line.text,
+        };
+
+        if !list_line.marker.is_empty() {
+            text_columns.truncate(line.depth);
+            text_columns.push(list_line.text_column());
+        }
+        layout.push(list_line);
+    }
+
+    layout
+}
+
+/// Build a rendered list line, with an optional selection pointer before the marker.
+fn build_list_line(
+    list_line: &ListLine,
+    gutter: usize,
+    show_pointer: bool,
+    text_spans: Vec<Span<'static>>,
+    theme: &Theme,
+) -> Line<'static> {
+    let indent = gutter + list_line.indent;
+    let mut spans = vec![];
+
+    // Pointer replaces the two columns before the marker
+    if show_pointer {
+        spans.push(Span::raw(" ".repeat(indent.saturating_sub(2))));
+        spans.push(Span::styled(
+            "→ ",
+            Style::default()
+                .fg(theme.selection_indicator_fg)
+                .bg(theme.selection_indicator_bg)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else {
+        spans.push(Span::raw(" ".repeat(indent)));
+    }
+
+    if !list_line.marker.is_empty() {
+        spans.push(Span::styled(
+            list_line.marker.clone(),
+            Style::default().fg(theme.list_bullet),
+        ));
+    }
+    spans.extend(text_spans);
+    Line::from(spans)
+}
+
+/// Lines of `block` outside interactive mode, its lists laid out as `layout`
+/// says.
 fn render_block_to_lines(
     block: &ContentBlock,
-    highlighter: &SyntaxHighlighter,
+    layout: &SourceLayout,
+    highlighter: &dyn Highlighter,
     theme: &Theme,
     available_width: Option<u16>,
+    table_wrap: bool,
 ) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
 
@@ -2130,11 +2776,17 @@ fn render_block_to_lines(
             lines.push(Line::from(summary_spans));
 
             // Render nested content (indented)
-            for nested_block in nested {
+            for (nested_idx, nested_block) in nested.iter().enumerate() {
                 // Reduce width by indent (2 spaces)
                 let nested_width = available_width.map(|w| w.saturating_sub(2));
-                let nested_lines =
-                    render_block_to_lines(nested_block, highlighter, theme, nested_width);
+                let nested_lines = render_block_to_lines(
+                    nested_block,
+                    layout.nested(nested_idx),
+                    highlighter,
+                    theme,
+                    nested_width,
+                    table_wrap,
+                );
                 for nested_line in nested_lines {
                     let mut spans = vec![Span::raw("  ")];
                     spans.extend(nested_line.spans);
@@ -2153,41 +2805,45 @@ fn render_block_to_lines(
                 alignments,
                 rows,
                 theme,
-                false,
-                false,
-                None,
-                available_width,
+                TableLayout {
+                    available_width,
+                    wrap: table_wrap,
+                    ..Default::default()
+                },
             );
             lines.extend(table_lines);
         }
         ContentBlock::List { ordered, items } => {
+            let fallback = ListLayout::new(*ordered);
+            let list_layout = layout.list().unwrap_or(&fallback);
             for (i, item) in items.iter().enumerate() {
-                let marker = if *ordered {
-                    format!("{}. ", i + 1)
-                } else {
-                    "• ".to_string()
-                };
-
-                // Render item content
-                let item_spans = if !item.inline.is_empty() {
-                    render_inline_elements(&item.inline, theme, None)
-                } else {
-                    format_inline_markdown(&item.content, theme)
-                };
-
-                let mut line_spans =
-                    vec![Span::styled(marker, Style::default().fg(theme.list_bullet))];
-                line_spans.extend(item_spans);
-                lines.push(Line::from(line_spans));
+                let item_lines = list_layout.lines(i, item);
+                let layout = layout_list_item(&item_lines, &theme.checkbox_glyphs);
+                let single_line = layout.len() == 1;
+                for list_line in &layout {
+                    let text_spans = if single_line && !item.inline.is_empty() {
+                        render_inline_elements(&item.inline, theme, None)
+                    } else {
+                        format_inline_markdown(list_line.text, theme)
+                    };
+                    lines.push(build_list_line(list_line, 0, false, text_spans, theme));
+                }
 
-                // Render nested blocks (indented)
+                // Render nested blocks (indented to the item's text)
+                let nested_indent = layout[0].text_column();
                 for nested in &item.blocks {
-                    // Reduce width by indent (2 spaces)
-                    let nested_width = available_width.map(|w| w.saturating_sub(2));
-                    let nested_lines =
-                        render_block_to_lines(nested, highlighter, theme, nested_width);
+                    let nested_width =
+                        available_width.map(|w| w.saturating_sub(nested_indent as u16));
+                    let nested_lines = render_block_to_lines(
+                        nested,
+                        &SourceLayout::None,
+                        highlighter,
+                        theme,
+                        nested_width,
+                        table_wrap,
+                    );
                     for nested_line in nested_lines {
-                        let mut spans = vec![Span::raw("  ")];
+                        let mut spans = vec![Span::raw(" ".repeat(nested_indent))];
                         spans.extend(nested_line.spans);
                         lines.push(Line::from(spans));
                     }
@@ -2205,10 +2861,17 @@ fn render_block_to_lines(
             lines.push(Line::from(quote_spans));
 
             // Render nested blocks
-            for nested in blocks {
+            for (nested_idx, nested) in blocks.iter().enumerate() {
                 // Reduce width by blockquote prefix (2 chars)
                 let nested_width = available_width.map(|w| w.saturating_sub(2));
-                let nested_lines = render_block_to_lines(nested, highlighter, theme, nested_width);
+                let nested_lines = render_block_to_lines(
+                    nested,
+                    layout.nested(nested_idx),
+                    highlighter,
+                    theme,
+                    nested_width,
+                    table_wrap,
+                );
                 for nested_line in nested_lines {
                     let mut spans = vec![Span::styled(
                         "│ ",
@@ -2265,7 +2928,7 @@ fn render_inline_elements(
             InlineElement::Code { value } => {
                 spans.push(Span::styled(value.clone(), theme.inline_code_style()));
             }
-            InlineElement::Link { text, .. } => {
+            InlineElement::Link { text, url, .. } => {
                 if is_selected {
                     // Add selection indicator before selected link (with background for visibility)
                     spans.push(Span::styled(
@@ -2289,6 +2952,9 @@ fn render_inline_elements(
                         .add_modifier(Modifier::UNDERLINED)
                 };
                 spans.push(Span::styled(text.clone(), style));
+                if let Some(marker) = link_marker(&LinkTarget::from_url(text, url)) {
+                    spans.push(Span::styled(marker, Style::default().fg(theme.link_fg)));
+                }
             }
             InlineElement::Strikethrough { value } => {
                 spans.push(Span::styled(
@@ -2312,114 +2978,977 @@ fn render_inline_elements(
     spans
 }
 
+/// Glyph drawn after a link to show where it leads: `None` for anchors
+/// within the document, `↪` for other files, `⇢` for wikilinks and `↗` for
+/// external URLs.
+fn link_marker(target: &LinkTarget) -> Option<&'static str> {
+    match target {
+        LinkTarget::Anchor(_) => None,
+        LinkTarget::RelativeFile { .. } => Some("↪"),
+        LinkTarget::WikiLink { .. } => Some("⇢"),
+        LinkTarget::External(_) => Some("↗"),
+    }
+}
+
+/// Render raw inline markdown (used when no structured inline data is available).
+///
+/// Supports links (bare URLs and emails too), code spans, `*`/`_` emphasis
+/// and `**`/`__` strong emphasis (nestable), and backslash escapes for
+/// literal punctuation.
 fn format_inline_markdown<'a>(text: &str, theme: &Theme) -> Vec<Span<'a>> {
+    let chars: Vec<char> = text.chars().collect();
     let mut spans = Vec::new();
+    push_inline_markdown_spans(&chars, theme.text_style(), theme, &mut spans);
+
+    if spans.is_empty() {
+        spans.push(Span::styled(text.to_string(), theme.text_style()));
+    }
+
+    spans
+}
+
+/// Recursively convert `chars` into styled spans, layering emphasis on top of `base`.
+fn push_inline_markdown_spans<'a>(
+    chars: &[char],
+    base: Style,
+    theme: &Theme,
+    spans: &mut Vec<Span<'a>>,
+) {
     let mut current = String::new();
-    let chars: Vec<char> = text.chars().collect();
+    let flush = |current: &mut String, spans: &mut Vec<Span<'a>>| {
+        if !current.is_empty() {
+            spans.push(Span::styled(std::mem::take(current), base));
+        }
+    };
     let mut i = 0;
 
     while i < chars.len() {
-        // Check for markdown link [text](url)
-        if chars[i] == '[' {
-            // Look for the closing ] and opening (
-            let mut j = i + 1;
-            let mut link_text = String::new();
-            while j < chars.len() && chars[j] != ']' {
-                link_text.push(chars[j]);
-                j += 1;
-            }
-            // Check if followed by (url)
-            if j + 1 < chars.len() && chars[j] == ']' && chars[j + 1] == '(' {
-                let mut k = j + 2;
-                let mut url = String::new();
-                while k < chars.len() && chars[k] != ')' {
-                    url.push(chars[k]);
-                    k += 1;
-                }
-                if k < chars.len() && chars[k] == ')' {
-                    // Valid link found
-                    if !current.is_empty() {
-                        spans.push(Span::raw(current.clone()));
-                        current.clear();
-                    }
-                    // Render link text with link styling
-                    spans.push(Span::styled(
-                        link_text,
-                        Style::default()
-                            .fg(theme.link_fg)
-                            .add_modifier(Modifier::UNDERLINED),
-                    ));
-                    i = k + 1; // Move past the closing )
-                    continue;
-                }
-            }
-            // Not a valid link, treat [ as regular character
-            current.push(chars[i]);
-            i += 1;
+        let c = chars[i];
+
+        // Backslash escape: \* renders a literal *
+        if c == '\\' && i + 1 < chars.len() && chars[i + 1].is_ascii_punctuation() {
+            current.push(chars[i + 1]);
+            i += 2;
+            continue;
         }
-        // Check for inline code `code`
-        else if chars[i] == '`' {
-            if !current.is_empty() {
-                spans.push(Span::raw(current.clone()));
-                current.clear();
-            }
-            i += 1;
-            let mut code = String::new();
-            while i < chars.len() && chars[i] != '`' {
-                code.push(chars[i]);
-                i += 1;
-            }
-            if i < chars.len() {
-                i += 1; // Skip closing `
-            }
+
+        // Markdown link [text](url)
+        if c == '['
+            && let Some((link_text, next)) = parse_inline_link(chars, i)
+        {
+            flush(&mut current, spans);
+            spans.push(Span::styled(
+                link_text,
+                base.patch(
+                    Style::default()
+                        .fg(theme.link_fg)
+                        .add_modifier(Modifier::UNDERLINED),
+                ),
+            ));
+            i = next;
+            continue;
+        }
+
+        // Bare URL or email address
+        if c.is_alphanumeric()
+            && (i == 0 || !chars[i - 1].is_alphanumeric())
+            && let Some(len) = autolink_len(chars, i)
+        {
+            flush(&mut current, spans);
+            spans.push(Span::styled(
+                chars[i..i + len].iter().collect::<String>(),
+                base.patch(
+                    Style::default()
+                        .fg(theme.link_fg)
+                        .add_modifier(Modifier::UNDERLINED),
+                ),
+            ));
+            i += len;
+            continue;
+        }
+
+        // Inline code `code` (contents are literal)
+        if c == '`' {
+            flush(&mut current, spans);
+            let close = find_char(chars, i + 1, '`').unwrap_or(chars.len());
+            let code: String = chars[i + 1..close].iter().collect();
             spans.push(Span::styled(code, theme.inline_code_style()));
+            i = (close + 1).min(chars.len());
+            continue;
         }
-        // Check for bold **text**
-        else if i + 1 < chars.len() && chars[i] == '*' && chars[i + 1] == '*' {
-            if !current.is_empty() {
-                spans.push(Span::raw(current.clone()));
-                current.clear();
-            }
-            i += 2;
-            let mut bold_text = String::new();
-            while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '*') {
-                bold_text.push(chars[i]);
-                i += 1;
-            }
-            if i + 1 < chars.len() {
-                i += 2; // Skip closing **
-            }
-            spans.push(Span::styled(bold_text, theme.bold_style()));
+
+        // Strong emphasis **text** / __text__
+        if (c == '*' || c == '_')
+            && chars.get(i + 1) == Some(&c)
+            && can_open_emphasis(chars, i, 2)
+            && let Some(close) = find_emphasis_close(chars, i + 2, c, 2)
+        {
+            flush(&mut current, spans);
+            let style = base.patch(theme.bold_style());
+            push_inline_markdown_spans(&chars[i + 2..close], style, theme, spans);
+            i = close + 2;
+            continue;
         }
-        // Check for italic *text*
-        else if chars[i] == '*' {
-            if !current.is_empty() {
-                spans.push(Span::raw(current.clone()));
-                current.clear();
-            }
-            i += 1;
-            let mut italic_text = String::new();
-            while i < chars.len() && chars[i] != '*' {
-                italic_text.push(chars[i]);
-                i += 1;
+
+        // Emphasis *text* / _text_
+        if (c == '*' || c == '_')
+            && can_open_emphasis(chars, i, 1)
+            && let Some(close) = find_emphasis_close(chars, i + 1, c, 1)
+        {
+            flush(&mut current, spans);
+            let style = base.patch(theme.italic_style());
+            push_inline_markdown_spans(&chars[i + 1..close], style, theme, spans);
+            i = close + 1;
+            continue;
+        }
+
+        current.push(c);
+        i += 1;
+    }
+
+    flush(&mut current, spans);
+}
+
+/// Parse `[text](url)` starting at `start`, returning the link text and the index after `)`.
+fn parse_inline_link(chars: &[char], start: usize) -> Option<(String, usize)> {
+    let close_bracket = find_char(chars, start + 1, ']')?;
+    if chars.get(close_bracket + 1) != Some(&'(') {
+        return None;
+    }
+    let close_paren = find_char(chars, close_bracket + 2, ')')?;
+    let text: String = chars[start + 1..close_bracket].iter().collect();
+    Some((text, close_paren + 1))
+}
+
+/// Length in chars of the bare URL or email address starting at `start`.
+fn autolink_len(chars: &[char], start: usize) -> Option<usize> {
+    let word: String = chars[start..]
+        .iter()
+        .take_while(|c| !c.is_whitespace())
+        .collect();
+    let (range, _) = crate::parser::links::find_autolinks(&word)
+        .into_iter()
+        .next()?;
+    (range.start == 0).then(|| word[..range.end].chars().count())
+}
+
+/// Find the next unescaped occurrence of `target` at or after `from`.
+fn find_char(chars: &[char], from: usize, target: char) -> Option<usize> {
+    let mut j = from;
+    while j < chars.len() {
+        if chars[j] == '\\' && target != '`' {
+            j += 2;
+            continue;
+        }
+        if chars[j] == target {
+            return Some(j);
+        }
+        j += 1;
+    }
+    None
+}
+
+/// An emphasis run can open if it is followed by non-whitespace and, for `_`,
+/// is not inside a word (so `snake_case_name` stays literal).
+fn can_open_emphasis(chars: &[char], pos: usize, len: usize) -> bool {
+    let Some(next) = chars.get(pos + len) else {
+        return false;
+    };
+    if next.is_whitespace() {
+        return false;
+    }
+    !(chars[pos] == '_' && pos > 0 && chars[pos - 1].is_alphanumeric())
+}
+
+/// Find the closing delimiter run of `len` copies of `marker`, skipping escapes
+/// and code spans. Returns the index of the first closing marker.
+fn find_emphasis_close(chars: &[char], from: usize, marker: char, len: usize) -> Option<usize> {
+    let mut j = from;
+    while j < chars.len() {
+        match chars[j] {
+            '\\' => {
+                j += 2;
+                continue;
             }
-            if i < chars.len() {
-                i += 1; // Skip closing *
+            '`' => {
+                j = find_char(chars, j + 1, '`').map_or(chars.len(), |end| end + 1);
+                continue;
             }
-            spans.push(Span::styled(italic_text, theme.italic_style()));
-        } else {
-            current.push(chars[i]);
-            i += 1;
+            _ => {}
+        }
+
+        if chars[j] != marker {
+            j += 1;
+            continue;
+        }
+
+        // Measure the full run of markers at this position
+        let run_end = chars[j..]
+            .iter()
+            .position(|&ch| ch != marker)
+            .map_or(chars.len(), |p| j + p);
+        let run = run_end - j;
+
+        let preceded_by_space = chars[j - 1].is_whitespace();
+        let followed_by_word = chars.get(run_end).is_some_and(|ch| ch.is_alphanumeric());
+        let closes = j > from && !preceded_by_space && !(marker == '_' && followed_by_word);
+
+        if closes && run >= len && (len == 2 || run != 2) {
+            // For strong emphasis inside a longer run (***), close on the last pair
+            // so the inner text keeps its own emphasis marker.
+            return Some(run_end - len);
         }
+
+        j = run_end;
     }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::content::parse_content;
 
-    if !current.is_empty() {
-        spans.push(Span::styled(current, theme.text_style()));
+    fn render(text: &str) -> Vec<(String, Style)> {
+        format_inline_markdown(text, &Theme::ocean_dark())
+            .into_iter()
+            .map(|span| (span.content.into_owned(), span.style))
+            .collect()
     }
 
-    if spans.is_empty() {
-        spans.push(Span::styled(text.to_string(), theme.text_style()));
+    fn plain(text: &str) -> String {
+        render(text)
+            .into_iter()
+            .map(|(content, _)| content)
+            .collect()
     }
 
-    spans
+    fn has(style: Style, modifier: Modifier) -> bool {
+        style.add_modifier.contains(modifier)
+    }
+
+    #[test]
+    fn test_escaped_markers_are_literal() {
+        let spans = render(r"\*not italic\*");
+        assert_eq!(plain(r"\*not italic\*"), "*not italic*");
+        assert!(
+            spans
+                .iter()
+                .all(|(_, style)| !has(*style, Modifier::ITALIC))
+        );
+
+        assert_eq!(plain(r"\_\_not bold\_\_"), "__not bold__");
+        assert_eq!(plain(r"a \` b"), "a ` b");
+    }
+
+    #[test]
+    fn test_underscore_emphasis() {
+        let spans = render("_italic_ and __bold__");
+        assert_eq!(spans[0].0, "italic");
+        assert!(has(spans[0].1, Modifier::ITALIC));
+        assert_eq!(spans[2].0, "bold");
+        assert!(has(spans[2].1, Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_intraword_underscores_stay_literal() {
+        let spans = render("call snake_case_name here");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].0, "call snake_case_name here");
+    }
+
+    #[test]
+    fn test_nested_emphasis() {
+        let spans = render("**bold with *nested* italic**");
+        assert_eq!(
+            plain("**bold with *nested* italic**"),
+            "bold with nested italic"
+        );
+        let nested = spans.iter().find(|(text, _)| text == "nested").unwrap();
+        assert!(has(nested.1, Modifier::BOLD));
+        assert!(has(nested.1, Modifier::ITALIC));
+        let outer = spans.iter().find(|(text, _)| text == "bold with ").unwrap();
+        assert!(has(outer.1, Modifier::BOLD));
+        assert!(!has(outer.1, Modifier::ITALIC));
+    }
+
+    #[test]
+    fn test_mixed_star_and_underscore() {
+        let spans = render("*italic with __bold__ inside*");
+        let bold = spans.iter().find(|(text, _)| text == "bold").unwrap();
+        assert!(has(bold.1, Modifier::BOLD));
+        assert!(has(bold.1, Modifier::ITALIC));
+
+        let spans = render("***both***");
+        assert_eq!(spans.len(), 1);
+        assert!(has(spans[0].1, Modifier::BOLD));
+        assert!(has(spans[0].1, Modifier::ITALIC));
+    }
+
+    #[test]
+    fn test_unmatched_and_spaced_markers_are_literal() {
+        assert_eq!(plain("2 * 3 * 4"), "2 * 3 * 4");
+        assert_eq!(plain("*unclosed"), "*unclosed");
+        assert_eq!(plain("**unclosed"), "**unclosed");
+    }
+
+    fn highlighted_count(text: Text<'static>, search_in_code: bool) -> usize {
+        let theme = Theme::ocean_dark();
+        let highlighted =
+            apply_search_highlighting(text, "needle", None, 0, search_in_code, &theme);
+        highlighted
+            .lines
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .filter(|span| span.style == theme.search_match_style())
+            .count()
+    }
+
+    #[test]
+    fn test_search_highlighting_skips_code_blocks() {
+        let text = Text::from(vec![
+            Line::from("needle here"),
+            Line::from("```rust"),
+            Line::from("let needle = 1;"),
+            Line::from("```"),
+            Line::from(vec![Span::raw("     "), Span::raw("```")]),
+            Line::from("  nested needle"),
+            Line::from("     ```"),
+            Line::from("last needle"),
+        ]);
+
+        assert_eq!(highlighted_count(text.clone(), true), 4);
+        assert_eq!(highlighted_count(text, false), 2);
+    }
+
+    #[test]
+    fn test_code_and_links() {
+        let spans = render("use `a*b*c` or [the *docs*](https://x.y)");
+        assert_eq!(spans[1].0, "a*b*c");
+        assert_eq!(spans[3].0, "the *docs*");
+        assert!(has(spans[3].1, Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_outline_truncates_long_headings() {
+        use crate::config::Config;
+        use crate::tui::terminal_compat::ColorMode;
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let heading = "A very long heading that cannot fit in the outline pane";
+        let mut app = App::new(
+            crate::parser::parse_markdown(&format!("# {}\n\nBody\n", heading)),
+            "test.md".to_string(),
+            std::path::PathBuf::from("test.md"),
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 5)).unwrap();
+        terminal
+            .draw(|frame| render_outline(frame, &mut app, frame.area()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let row: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, 1)].symbol())
+            .collect();
+        assert!(row.contains("# A very long"), "row: {row:?}");
+        assert!(row.contains("heading t…"), "row: {row:?}");
+        assert_eq!(app.outline_items[0].text, heading);
+    }
+
+    #[test]
+    fn test_sync_outline_follows_content_scroll() {
+        use crate::config::Config;
+        use crate::tui::app::Focus;
+        use crate::tui::terminal_compat::ColorMode;
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut config = Config::default();
+        config.ui.sync_outline = true;
+        let mut app = App::new(
+            crate::parser::parse_markdown("# A\n\nintro\n\n## B\n\nb text\n\n## C\n\nc text\n"),
+            "test.md".to_string(),
+            std::path::PathBuf::from("test.md"),
+            config,
+            ColorMode::Rgb,
+            false,
+        );
+        let mut terminal = Terminal::new(TestBackend::new(40, 6)).unwrap();
+        let mut draw = |app: &mut App| {
+            app.update_content_metrics();
+            terminal
+                .draw(|frame| render_content(frame, app, frame.area()))
+                .unwrap();
+        };
+        draw(&mut app);
+        // Section A renders: intro, blank, B, blank, b text, blank, C, ...
+        assert_eq!(app.content_heading_lines, vec![2, 6]);
+
+        app.focus = Focus::Content;
+        app.next();
+        assert_eq!(app.selected_heading_text(), Some("A"));
+        app.next();
+        assert_eq!(app.selected_heading_text(), Some("B"));
+        draw(&mut app);
+        // Content still shows section A at the same position
+        assert_eq!(app.content_scroll, 2);
+        assert!(app.selected_section_content().starts_with("intro"));
+
+        for _ in 0..4 {
+            app.next();
+        }
+        assert_eq!(app.selected_heading_text(), Some("C"));
+        app.first();
+        draw(&mut app);
+        assert_eq!(app.selected_heading_text(), Some("A"));
+        assert_eq!(app.content_scroll, 0);
+
+        // Selecting in the outline shows that heading's section again
+        app.next();
+        app.next();
+        app.focus = Focus::Outline;
+        app.next();
+        draw(&mut app);
+        assert_eq!(app.selected_heading_text(), Some("C"));
+        assert_eq!(&*app.selected_section_content(), "c text");
+        assert_eq!(app.content_scroll, 0);
+    }
+
+    fn render_lines(markdown: &str) -> Vec<String> {
+        render_markdown_enhanced(
+            markdown,
+            &crate::tui::SyntaxHighlighter::new(),
+            &Theme::ocean_dark(),
+            None,
+            None,
+            None,
+        )
+        .lines
+        .iter()
+        .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+        .collect()
+    }
+
+    #[test]
+    fn test_snapshot_three_level_bullets() {
+        let lines = render_lines("- one\n  - two\n    - three\n- four\n");
+        assert_eq!(
+            lines,
+            vec!["  • one", "    • two", "      • three", "  • four", ""]
+        );
+    }
+
+    #[test]
+    fn test_raw_view_uses_tab_width() {
+        let text = render_raw_markdown("all:\n\tcc -o app\n", &Theme::ocean_dark(), 2);
+        let lines: Vec<String> = text
+            .lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(lines, ["   1 │ all:", "   2 │   cc -o app"]);
+    }
+
+    #[test]
+    fn test_inline_checkboxes_render_as_glyphs() {
+        let lines = render_lines("Done [x], next [ ], literal `[x]`.\n");
+        assert_eq!(lines[0], "Done ☑, next ☐, literal [x].");
+
+        let lines = render_lines("| Task | Done |\n|---|---|\n| ship | [x] |\n");
+        assert!(
+            lines.iter().any(|l| l.contains("ship") && l.contains('☑')),
+            "{lines:?}"
+        );
+        assert!(!lines.iter().any(|l| l.contains("[x]")));
+    }
+
+    #[test]
+    fn test_snapshot_ordered_in_unordered() {
+        let lines = render_lines("- fruit\n  1. apple\n  2. pear\n- veg\n");
+        assert_eq!(
+            lines,
+            vec!["  • fruit", "    1. apple", "    2. pear", "  • veg", ""]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_unordered_in_ordered() {
+        let lines = render_lines("1. first\n   - a\n     - b\n2. second\n");
+        assert_eq!(
+            lines,
+            vec!["  1. first", "     • a", "       • b", "  2. second", ""]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_tasks_nested_under_bullets() {
+        let lines = render_lines("- project\n  - [ ] todo\n  - [x] done\n    - note\n");
+        assert_eq!(
+            lines,
+            vec![
+                "  • project",
+                "    ☐ todo",
+                "    ☑ done",
+                "      • note",
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_footer_hints_follow_custom_keybindings() {
+        use crate::config::Config;
+        use crate::tui::terminal_compat::ColorMode;
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let config = Config {
+            keybindings: toml::from_str(
+                r#"
+            [Normal]
+            "I" = "EnterInteractiveMode"
+            "i" = "None"
+            "f" = "None"
+            "#,
+            )
+            .unwrap(),
+            ..Config::default()
+        };
+        let app = App::new(
+            crate::parser::parse_markdown("# A\n\ntext\n"),
+            "test.md".to_string(),
+            std::path::PathBuf::from("test.md"),
+            config,
+            ColorMode::Rgb,
+            false,
+        );
+        let mut terminal = Terminal::new(TestBackend::new(120, 1)).unwrap();
+        terminal
+            .draw(|frame| render_footer(frame, &app, frame.area()))
+            .unwrap();
+        let footer: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(footer.contains(" j/k Navigate "), "{footer}");
+        assert!(footer.contains(" I Interactive "), "{footer}");
+        assert!(footer.contains(" EnterLinkFollowMode Links "), "{footer}");
+    }
+
+    #[test]
+    fn test_status_bar_follows_status_format() {
+        use crate::config::Config;
+        use crate::tui::terminal_compat::ColorMode;
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut app = App::new(
+            crate::parser::parse_markdown("# A\n\nsome text\n"),
+            "test.md".to_string(),
+            std::path::PathBuf::from("test.md"),
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+        let status = |app: &App| {
+            let mut terminal = Terminal::new(TestBackend::new(80, 1)).unwrap();
+            terminal
+                .draw(|frame| render_status_bar(frame, app, frame.area()))
+                .unwrap();
+            let line: String = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            line.trim_end().to_string()
+        };
+
+        assert_eq!(
+            status(&app),
+            format!(
+                " [Outline] 1/1 (100%) • Outline:{}% • Theme:{} [auto]",
+                app.outline_width, app.theme.name
+            )
+        );
+
+        app.status_format = "{heading}: {words} words, {reading_time} {unknown}|".to_string();
+        assert_eq!(status(&app), "A: 2 words, 1 min |");
+    }
+
+    #[test]
+    fn test_collapsed_code_block_renders_summary_line() {
+        use crate::tui::interactive::{ElementId, InteractiveState};
+
+        let markdown = "```rust\nfn a() {}\nfn b() {}\n```\n\nAfter\n";
+        let mut state = InteractiveState::new();
+        state.index_elements(&parse_content(markdown, 0));
+        state.toggle_code_block(ElementId {
+            block_idx: 0,
+            sub_idx: None,
+        });
+
+        let render = |state: &InteractiveState| -> Vec<String> {
+            render_markdown_enhanced(
+                markdown,
+                &crate::tui::PlainHighlighter,
+                &Theme::ocean_dark(),
+                None,
+                Some(state),
+                None,
+            )
+            .lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+        };
+
+        assert_eq!(render(&state), ["▶ ```rust (2 lines)", "", "After", ""]);
+        state.toggle_code_block(ElementId {
+            block_idx: 0,
+            sub_idx: None,
+        });
+        assert_eq!(
+            render(&state)[..4],
+            ["```rust", "fn a() {}", "fn b() {}", "```"]
+        );
+    }
+
+    #[test]
+    fn test_block_spacing_zero_tightens_lists_and_headings() {
+        use crate::tui::interactive::InteractiveState;
+
+        let markdown = "## Setup\nRun it.\n\n- a\n- b\n\n* c\n\n[docs](#docs)\n";
+        let render = |block_spacing: usize| -> Vec<String> {
+            let layout = BlockLayout {
+                block_spacing,
+                ..BlockLayout::default()
+            };
+            let text = render_markdown_for_pane(
+                markdown,
+                &crate::tui::PlainHighlighter,
+                &Theme::ocean_dark(),
+                None,
+                None,
+                None,
+                layout,
+            )
+            .text;
+            text.lines
+                .iter()
+                .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+
+        let spaced = render(1);
+        let tight = render(0);
+        assert_eq!(spaced.len(), 11);
+        assert_eq!(tight[..5], ["Setup", "Run it.", "", "  • a", "  • b"]);
+        assert_eq!(tight[5], "  • c");
+
+        // Interactive line ranges count the same blank lines
+        let mut state = InteractiveState::new();
+        state.set_block_spacing(0);
+        state.index_elements(&parse_content(markdown, 0));
+        let link_line = state.elements()[0].line_range().start;
+        assert_eq!(tight[link_line], "docs");
+    }
+
+    #[test]
+    fn test_links_are_marked_by_target() {
+        let markdown = "[Setup](#setup), [API](docs/api.md), [[Notes]], [site](https://example.com) and me@example.com\n";
+        let text = render_markdown_enhanced(
+            markdown,
+            &crate::tui::PlainHighlighter,
+            &Theme::ocean_dark(),
+            None,
+            None,
+            None,
+        );
+        let line: String = text.lines[0]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(line, "Setup, API↪, Notes⇢, site↗ and me@example.com↗");
+    }
+
+    #[test]
+    fn test_details_open_starts_expanded() {
+        use crate::tui::interactive::InteractiveState;
+
+        let markdown = "<details open>\n<summary>Shown</summary>\n\nOpen body\n</details>\n\n\
+                        <details>\n<summary>Hidden</summary>\n\nClosed body\n</details>\n";
+        let blocks = parse_content(markdown, 0);
+        let mut state = InteractiveState::new();
+        state.open_details(markdown, &blocks);
+        state.index_elements(&blocks);
+
+        let text: Vec<String> = render_markdown_enhanced(
+            markdown,
+            &crate::tui::PlainHighlighter,
+            &Theme::ocean_dark(),
+            None,
+            Some(&state),
+            None,
+        )
+        .lines
+        .iter()
+        .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+        .collect();
+        assert!(text.iter().any(|line| line.contains("Open body")));
+        assert!(!text.iter().any(|line| line.contains("Closed body")));
+
+        // The open block's line range covers its content
+        let open = state.elements()[0].line_range();
+        let closed_line = state.elements()[1].line_range().start;
+        assert!(open.end > open.start + 1);
+        assert!(text[closed_line].contains("Hidden"));
+    }
+
+    #[test]
+    fn test_json_code_blocks_fold_in_interactive_mode() {
+        use crate::tui::interactive::{ElementType, InteractiveState};
+
+        let markdown = "Config:\n\n```json\n{\n  \"deps\": {\n    \"serde\": 1\n  },\n  \"name\": \"x\"\n}\n```\n\n[After](#after)\n";
+        let blocks = parse_content(markdown, 0);
+        let mut state = InteractiveState::new();
+        state.set_fold_data_blocks(true);
+        state.index_elements(&blocks);
+
+        let folds: Vec<usize> = state
+            .elements()
+            .iter()
+            .filter_map(|e| match e.element_type() {
+                ElementType::DataFold { line, .. } => Some(*line),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(folds, vec![0, 1]);
+
+        let code = state.elements()[0].id();
+        state.toggle_data_fold(code, 1);
+        state.index_elements(&blocks);
+        let fold = state
+            .elements()
+            .iter()
+            .find(|e| {
+                matches!(
+                    e.element_type(),
+                    ElementType::DataFold {
+                        line: 1,
+                        folded: true,
+                        ..
+                    }
+                )
+            })
+            .unwrap();
+
+        let text: Vec<String> = render_markdown_enhanced(
+            markdown,
+            &crate::tui::PlainHighlighter,
+            &Theme::ocean_dark(),
+            Some(fold.id()),
+            Some(&state),
+            None,
+        )
+        .lines
+        .iter()
+        .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+        .collect();
+        assert_eq!(text[fold.line_range().start], "▸   \"deps\": {…},");
+        assert!(!text.iter().any(|line| line.contains("serde")));
+        assert!(text.iter().any(|line| line == "```json"));
+
+        // Line ranges after the folded block still match the rendering
+        let link = state.elements().last().unwrap();
+        assert!(matches!(link.element_type(), ElementType::Link { .. }));
+        assert!(text[link.line_range().start].contains("After"));
+
+        // Without the option, or for malformed data, nothing folds
+        let mut plain = InteractiveState::new();
+        plain.index_elements(&blocks);
+        assert!(plain.data_folds(code).is_none());
+        let broken = parse_content("```json\n{\n  \"a\": [\n}\n```\n", 0);
+        state.index_elements(&broken);
+        assert_eq!(state.elements().len(), 1);
+    }
+
+    #[test]
+    fn test_no_color_mode_renders_without_colors() {
+        use crate::config::Config;
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let markdown =
+            "# Title\n\nSome `code` and [a link](#title)\n\n```rust\nfn main() {}\n```\n";
+        let mut app = App::new(
+            crate::parser::parse_markdown(markdown),
+            "test.md".to_string(),
+            std::path::PathBuf::from("test.md"),
+            Config::default(),
+            ColorMode::None,
+            false,
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal
+            .draw(|frame| super::render(frame, &mut app))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        assert!(
+            buffer
+                .content
+                .iter()
+                .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+        );
+        let text: String = buffer.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("fn main"));
+    }
+
+    #[test]
+    fn test_code_outside_viewport_is_laid_out_plain() {
+        let paragraphs = "Filler.\n\n".repeat(100);
+        let markdown =
+            format!("```rust\nfn top() {{}}\n```\n\n{paragraphs}```rust\nfn bottom() {{}}\n```\n");
+        let highlighter = crate::tui::SyntaxHighlighter::new();
+        let render = |viewport: Option<Range<usize>>| {
+            let layout = BlockLayout {
+                viewport,
+                ..BlockLayout::default()
+            };
+            render_markdown_for_pane(
+                &markdown,
+                &highlighter,
+                &Theme::ocean_dark(),
+                None,
+                None,
+                Some(80),
+                layout,
+            )
+            .text
+        };
+        let text = |rendered: &Text| -> Vec<String> {
+            rendered
+                .lines
+                .iter()
+                .map(|line| {
+                    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+                    text.trim_end().to_string()
+                })
+                .collect()
+        };
+        let styled = |line: &Line| line.spans.iter().any(|s| s.style.fg.is_some());
+
+        let full = render(None);
+        let partial = render(Some(highlight_rows(0, 10)));
+        assert_eq!(text(&partial), text(&full));
+
+        let bottom = text(&partial)
+            .iter()
+            .position(|line| line == "fn bottom() {}")
+            .unwrap();
+        assert!(styled(&partial.lines[1]));
+        assert!(!styled(&partial.lines[bottom]));
+        assert!(styled(&full.lines[bottom]));
+    }
+
+    /// Paragraphs whose words wrap one per row, so counting characters
+    /// would put everything below them too high.
+    fn wrapped_paragraphs_then_code() -> String {
+        let paragraph = "aaaaaaaaaaa bbbbbbbbbbb ccccccccccc ddddddddddd\n\n";
+        format!("{}```rust\nfn below() {{}}\n```\n", paragraph.repeat(40))
+    }
+
+    #[test]
+    fn test_code_below_wrapped_paragraphs_is_highlighted_on_screen() {
+        let markdown = wrapped_paragraphs_then_code();
+        let highlighter = crate::tui::SyntaxHighlighter::new();
+        let render = |viewport: Option<Range<usize>>| {
+            let layout = BlockLayout {
+                viewport,
+                ..BlockLayout::default()
+            };
+            render_markdown_for_pane(
+                &markdown,
+                &highlighter,
+                &Theme::ocean_dark(),
+                None,
+                None,
+                Some(20),
+                layout,
+            )
+        };
+        let measured = render(Some(0..1));
+        let code = measured
+            .text
+            .lines
+            .iter()
+            .position(|line| line.spans.iter().any(|s| s.content.contains("below")))
+            .unwrap();
+        let row: usize = measured.line_rows[..code].iter().sum();
+        let by_chars: usize = measured.text.lines[..code]
+            .iter()
+            .map(|line| line.width().div_ceil(20).max(1))
+            .sum();
+        assert!(row > by_chars + 30);
+
+        let on_screen = render(Some(highlight_rows(row as u16, 4))).text;
+        let styled = |line: &Line| line.spans.iter().any(|s| s.style.fg.is_some());
+        assert!(styled(&on_screen.lines[code]));
+    }
+
+    #[test]
+    fn test_visible_lines_draw_like_the_full_text() {
+        let text = render_markdown_for_pane(
+            &wrapped_paragraphs_then_code(),
+            &crate::tui::PlainHighlighter,
+            &Theme::ocean_dark(),
+            None,
+            None,
+            Some(20),
+            BlockLayout {
+                viewport: Some(0..1),
+                ..BlockLayout::default()
+            },
+        );
+        let area = Rect::new(0, 0, 20, 6);
+        let draw = |text: Text<'static>, scroll: usize| {
+            let mut buffer = Buffer::empty(area);
+            ratatui::widgets::Widget::render(
+                Paragraph::new(text)
+                    .wrap(Wrap { trim: false })
+                    .scroll((scroll as u16, 0)),
+                area,
+                &mut buffer,
+            );
+            buffer
+        };
+
+        let total: usize = text.line_rows.iter().sum();
+        for scroll in [0, 1, 3, 57, 118, total - 6] {
+            let (visible, skip) = visible_lines(&text.line_rows, scroll, 6);
+            let slice = Text::from(text.text.lines[visible].to_vec());
+            assert_eq!(
+                draw(slice, skip),
+                draw(text.text.clone(), scroll),
+                "scroll {scroll}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_code_rows_counts_wrapped_source_lines() {
+        assert_eq!(code_rows("a\n\nbc", None), 3);
+        assert_eq!(code_rows(&"x".repeat(25), Some(10)), 3);
+        assert_eq!(code_rows("\tab", Some(4)), 2);
+        // Wrapped at word boundaries, like the paragraph draws it
+        assert_eq!(code_rows("aaaa bbbb cccc", Some(8)), 3);
+    }
 }
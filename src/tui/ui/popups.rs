@@ -14,7 +14,7 @@ use ratatui::widgets::{
     Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
 };
 
-use super::util::popup_area;
+use super::util::{format_link_destination, popup_area, truncate_start_with_ellipsis};
 
 /// Render the help popup with keyboard shortcuts
 pub fn render_help_popup(frame: &mut Frame, app: &App, area: Rect) {
@@ -32,6 +32,7 @@ pub fn render_help_popup(frame: &mut Frame, app: &App, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(theme.border_type)
                 .border_style(Style::default().fg(theme.modal_border()))
                 .title(" Help ")
                 .style(Style::default().bg(theme.modal_bg())),
@@ -61,8 +62,6 @@ pub fn render_help_popup(frame: &mut Frame, app: &App, area: Rect) {
 
 /// Render the link picker popup
 pub fn render_link_picker(frame: &mut Frame, app: &App, area: Rect) {
-    use crate::parser::LinkTarget;
-
     let theme = &app.theme;
 
     // Create centered popup area (smaller than full screen)
@@ -133,27 +132,14 @@ pub fn render_link_picker(frame: &mut Frame, app: &App, area: Rect) {
 
         // Format link number (show original index for jump commands)
         let number = format!("[{}] ", real_idx + 1);
-        let link_text = &link.text;
+        let link_text = if link.image {
+            format!("🖼 {}", link.text)
+        } else {
+            link.text.clone()
+        };
 
         // Format target
-        let target_str = match &link.target {
-            LinkTarget::Anchor(a) => format!("#{}", a),
-            LinkTarget::RelativeFile { path, anchor } => {
-                if let Some(a) = anchor {
-                    format!("{}#{}", path.display(), a)
-                } else {
-                    path.display().to_string()
-                }
-            }
-            LinkTarget::WikiLink { target, .. } => format!("[[{}]]", target),
-            LinkTarget::External(url) => {
-                if url.len() > 50 {
-                    format!("{}...", &url[..47])
-                } else {
-                    url.clone()
-                }
-            }
-        };
+        let target_str = format_link_destination(link, 50);
 
         // Different styles for selected vs unselected
         if is_selected {
@@ -171,7 +157,7 @@ pub fn render_link_picker(frame: &mut Frame, app: &App, area: Rect) {
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
-                    link_text.clone(),
+                    link_text,
                     Style::default()
                         .fg(theme.selection_indicator_fg)
                         .bg(theme.modal_selected_fg())
@@ -188,7 +174,7 @@ pub fn render_link_picker(frame: &mut Frame, app: &App, area: Rect) {
             lines.push(Line::from(vec![
                 Span::styled("  ", Style::default()),
                 Span::styled(number, Style::default().fg(theme.modal_description())),
-                Span::styled(link_text.clone(), Style::default().fg(theme.modal_text())),
+                Span::styled(link_text, Style::default().fg(theme.modal_text())),
                 Span::styled(
                     format!(" → {}", target_str),
                     Style::default().fg(theme.modal_description()),
@@ -257,6 +243,7 @@ pub fn render_link_picker(frame: &mut Frame, app: &App, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(theme.border_type)
                 .border_style(Style::default().fg(theme.modal_border()))
                 .title(" Link Navigator ")
                 .style(Style::default().bg(theme.modal_bg())),
@@ -326,11 +313,21 @@ pub fn render_theme_picker(frame: &mut Frame, app: &App, area: Rect) {
             "Catppuccin Mocha",
             "Soothing pastel theme for night coding",
         ),
+        (
+            ThemeName::HighContrast,
+            "High Contrast",
+            "Bright text on black with thick borders",
+        ),
+        (
+            ThemeName::Deuteranopia,
+            "Deuteranopia",
+            "Blue and orange accents, no red/green pairs",
+        ),
     ];
 
     // Create centered popup area
-    // Min 35 cols for theme names, min 12 rows for all themes + header
-    let popup_area = popup_area(area, 60, 50, 35, 12);
+    // Min 35 cols for theme names, min 16 rows for all themes + header
+    let popup_area = popup_area(area, 60, 50, 35, 16);
 
     // Clear background
     frame.render_widget(Clear, popup_area);
@@ -385,6 +382,7 @@ pub fn render_theme_picker(frame: &mut Frame, app: &App, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(theme.border_type)
                 .border_style(Style::default().fg(theme.modal_border()))
                 .title(" Theme Selector ")
                 .style(Style::default().bg(theme.modal_bg())),
@@ -419,10 +417,14 @@ pub fn render_cell_edit_overlay(frame: &mut Frame, app: &App, area: Rect) {
     // Clear background
     frame.render_widget(Clear, edit_area);
 
-    // Create edit display
+    // Create edit display, keeping the end of long values (where the cursor
+    // is) inside the borders
+    let label = format!("Edit Cell [{},{}]: ", app.cell_edit_row, app.cell_edit_col);
+    let room = (width as usize).saturating_sub(2 + label.len() + 1);
     let edit_text = format!(
-        "Edit Cell [{},{}]: {}_",
-        app.cell_edit_row, app.cell_edit_col, app.cell_edit_value
+        "{}{}_",
+        label,
+        truncate_start_with_ellipsis(&app.cell_edit_value, room)
     );
 
     let paragraph = Paragraph::new(vec![
@@ -448,6 +450,7 @@ pub fn render_cell_edit_overlay(frame: &mut Frame, app: &App, area: Rect) {
     .block(
         Block::default()
             .borders(Borders::ALL)
+            .border_type(theme.border_type)
             .border_style(Style::default().fg(theme.modal_border()))
             .style(Style::default().bg(theme.modal_bg())),
     );
@@ -494,6 +497,7 @@ pub fn render_file_create_confirm(frame: &mut Frame, message: &str, theme: &Them
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(theme.border_type)
                 .title(" Confirm ")
                 .title_style(Style::default().fg(theme.modal_title()))
                 .border_style(Style::default().fg(theme.modal_border()))
@@ -539,6 +543,7 @@ pub fn render_save_width_confirm(frame: &mut Frame, width: u16, theme: &Theme) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(theme.border_type)
                 .title(" Confirm ")
                 .title_style(Style::default().fg(theme.modal_title()))
                 .border_style(Style::default().fg(theme.modal_border()))
@@ -604,6 +609,7 @@ pub fn render_save_before_quit_confirm(frame: &mut Frame, edit_count: usize, the
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(theme.border_type)
                 .title(" Confirm Quit ")
                 .title_style(Style::default().fg(theme.modal_title()))
                 .border_style(Style::default().fg(theme.modal_border()))
@@ -676,6 +682,7 @@ pub fn render_save_before_nav_confirm(frame: &mut Frame, edit_count: usize, them
         .block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_type(theme.border_type)
                 .title(" Confirm Navigation ")
                 .title_style(Style::default().fg(theme.modal_title()))
                 .border_style(Style::default().fg(theme.modal_border()))
@@ -685,6 +692,61 @@ pub fn render_save_before_nav_confirm(frame: &mut Frame, edit_count: usize, them
     frame.render_widget(paragraph, area);
 }
 
+/// Render the confirmation for writing over a file changed on disk
+pub fn render_overwrite_confirm(frame: &mut Frame, filename: &str, theme: &Theme) {
+    // Min 34 cols for text, min 9 rows for all options
+    let area = popup_area(frame.area(), 58, 26, 34, 9);
+
+    frame.render_widget(Clear, area);
+
+    let text = vec![
+        Line::from(vec![Span::styled(
+            "File Changed on Disk",
+            Style::default()
+                .fg(theme.modal_title())
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!("{} was modified by another program.", filename),
+            Style::default().fg(theme.modal_text()),
+        )]),
+        Line::from(vec![Span::styled(
+            "Saving may overwrite those changes.",
+            Style::default().fg(theme.modal_text()),
+        )]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[y/Enter]", Style::default().fg(theme.modal_key_fg())),
+            Span::styled(
+                " Save anyway",
+                Style::default().fg(theme.modal_description()),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("[Esc]", Style::default().fg(theme.modal_key_fg())),
+            Span::styled(
+                " Cancel (R reloads the file)",
+                Style::default().fg(theme.modal_description()),
+            ),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(theme.border_type)
+                .title(" Confirm Overwrite ")
+                .title_style(Style::default().fg(theme.modal_title()))
+                .border_style(Style::default().fg(theme.modal_border()))
+                .style(Style::default().bg(theme.modal_bg())),
+        );
+
+    frame.render_widget(paragraph, area);
+}
+
 /// Render the command palette with fuzzy search
 pub fn render_command_palette(frame: &mut Frame, app: &App, theme: &Theme) {
     use crate::tui::app::PALETTE_COMMANDS;
@@ -716,7 +778,17 @@ pub fn render_command_palette(frame: &mut Frame, app: &App, theme: &Theme) {
     ];
 
     // Show filtered commands
-    if app.command_filtered.is_empty() {
+    if let Some(number) = app.palette_heading_number() {
+        let total = app.outline_items.len();
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  Go to heading {} of {}",
+                number.clamp(1, total.max(1)),
+                total
+            ),
+            Style::default().fg(theme.modal_text()),
+        )]));
+    } else if app.command_filtered.is_empty() {
         lines.push(Line::from(vec![Span::styled(
             "  No matching commands",
             Style::default()
@@ -780,6 +852,7 @@ pub fn render_command_palette(frame: &mut Frame, app: &App, theme: &Theme) {
     let paragraph = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
+            .border_type(theme.border_type)
             .border_style(Style::default().fg(theme.modal_border()))
             .style(Style::default().bg(theme.modal_bg())),
     );
@@ -809,6 +882,11 @@ pub fn render_file_picker(frame: &mut Frame, app: &App, area: Rect) {
                 "cancel"
             }
         )
+    } else if app.file_picker_recent {
+        format!(
+            "Recent Files ({}) - /: search, j/k: navigate, Enter: open",
+            app.files_in_directory.len()
+        )
     } else {
         format!(
             "Markdown Files in Current Directory ({} found) - /: search, j/k: navigate, Enter: open",
@@ -861,6 +939,15 @@ pub fn render_file_picker(frame: &mut Frame, app: &App, area: Rect) {
 
         let current_marker = if is_current { " [current]" } else { "" };
         let number = format!("[{}] ", real_idx + 1);
+        // Recent files come from many directories, so show where each one lives
+        let location = if app.file_picker_recent {
+            file_path
+                .parent()
+                .map(|dir| format!("  {}", dir.display()))
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
 
         if is_selected {
             lines.push(Line::from(vec![
@@ -884,6 +971,7 @@ pub fn render_file_picker(frame: &mut Frame, app: &App, area: Rect) {
                         .fg(theme.modal_description())
                         .add_modifier(Modifier::ITALIC),
                 ),
+                Span::styled(location, Style::default().fg(theme.modal_description())),
             ]));
         } else {
             let text_style = if is_current {
@@ -904,6 +992,7 @@ pub fn render_file_picker(frame: &mut Frame, app: &App, area: Rect) {
                         .fg(theme.modal_description())
                         .add_modifier(Modifier::ITALIC),
                 ),
+                Span::styled(location, Style::default().fg(theme.modal_description())),
             ]));
         }
 
@@ -959,6 +1048,7 @@ pub fn render_file_picker(frame: &mut Frame, app: &App, area: Rect) {
     let paragraph = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
+            .border_type(theme.border_type)
             .border_style(Style::default().fg(theme.modal_border()))
             .title(" File Picker ")
             .style(Style::default().bg(theme.modal_bg())),
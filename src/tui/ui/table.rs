@@ -7,7 +7,8 @@ use crate::parser::output::Alignment;
 use crate::tui::theme::Theme;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use unicode_width::UnicodeWidthStr;
+use std::ops::Range;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use super::util::align_text;
 
@@ -69,36 +70,19 @@ fn calculate_column_widths(headers: &[String], rows: &[Vec<String>]) -> Vec<usiz
     col_widths
 }
 
-/// Render a complete table with headers, alignments, and rows
+/// Column widths (padding included) for single-line rows
 ///
-/// # Arguments
-/// * `headers` - Column headers
-/// * `alignments` - Column alignments
-/// * `rows` - Data rows
-/// * `theme` - Color theme
-/// * `is_selected` - Whether the table element is selected
-/// * `in_table_mode` - Whether we're in table cell navigation mode
-/// * `selected_cell` - Currently selected cell (row, col) if in table mode
-/// * `available_width` - Optional maximum width to constrain table to
-pub fn render_table(
+/// Columns shrink to fit `available_width`, first by dropping padding and
+/// then proportionally; cells that still don't fit are cut off by
+/// [`align_text`]. `has_prefix` accounts for the selection arrow column.
+fn truncated_column_widths(
     headers: &[String],
-    alignments: &[Alignment],
     rows: &[Vec<String>],
-    theme: &Theme,
-    is_selected: bool,
-    in_table_mode: bool,
-    selected_cell: Option<(usize, usize)>,
     available_width: Option<u16>,
-) -> Vec<Line<'static>> {
-    let mut lines = Vec::new();
-
-    if headers.is_empty() {
-        return lines;
-    }
-
-    let col_count = headers.len();
-
+    has_prefix: bool,
+) -> Vec<usize> {
     // Calculate column widths using content-weighted approach
+    let col_count = headers.len();
     let mut col_widths = calculate_column_widths(headers, rows);
 
     // Start with normal padding (1 space each side = 2 total)
@@ -112,7 +96,7 @@ pub fn render_table(
     // Smart table collapsing: shrink columns proportionally if table is too wide
     if let Some(max_width) = available_width {
         let max_width = max_width as usize;
-        let prefix_width = if in_table_mode || is_selected { 2 } else { 0 };
+        let prefix_width = if has_prefix { 2 } else { 0 };
         let border_width = col_count + 1; // │ between and around columns
 
         // Try shrinking with progressively less padding
@@ -162,6 +146,187 @@ pub fn render_table(
         }
     }
 
+    col_widths
+}
+
+/// Column widths (padding included) for wrapped rows
+///
+/// Every column gets its widest cell when the table fits in `space`.
+/// Otherwise columns narrower than an equal share keep their width and the
+/// wider ones split what is left, so long cells wrap instead of squeezing
+/// short ones.
+fn wrapped_column_widths(
+    headers: &[String],
+    rows: &[Vec<String>],
+    space: Option<usize>,
+) -> Vec<usize> {
+    let natural: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            let widest = std::iter::once(&headers[i])
+                .chain(rows.iter().filter_map(|row| row.get(i)))
+                .map(|cell| cell.width())
+                .max()
+                .unwrap_or(0);
+            widest.max(1) + 2
+        })
+        .collect();
+
+    let Some(space) = space else {
+        return natural;
+    };
+    if natural.iter().sum::<usize>() <= space {
+        return natural;
+    }
+
+    let mut widths = natural.clone();
+    let mut order: Vec<usize> = (0..natural.len()).collect();
+    order.sort_by_key(|&i| natural[i]);
+    let mut remaining = space;
+    for (n, &i) in order.iter().enumerate() {
+        let rest = &order[n..];
+        if natural[i] * rest.len() > remaining {
+            // This column and all wider ones share the remaining space
+            let share = remaining / rest.len();
+            let extra = remaining % rest.len();
+            for (k, &j) in rest.iter().enumerate() {
+                widths[j] = (share + usize::from(k < extra)).max(MIN_COL_WIDTH);
+            }
+            break;
+        }
+        remaining -= natural[i];
+    }
+    widths
+}
+
+/// Split cell text into lines at most `width` columns wide, breaking
+/// between words where possible and inside words longer than the column.
+fn wrap_cell(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    if text.width() <= width {
+        return vec![text.to_string()];
+    }
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let gap = usize::from(!line.is_empty());
+        if line.width() + gap + word.width() <= width {
+            if gap == 1 {
+                line.push(' ');
+            }
+            line.push_str(word);
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        for c in word.chars() {
+            if !line.is_empty() && line.width() + c.width().unwrap_or(0) > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push(c);
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Render a row as one line per wrapped line of its tallest cell
+///
+/// Borders stay aligned across the lines; a selected cell is highlighted on
+/// all of them, while the row arrow is only drawn on the first.
+fn render_wrapped_row(
+    cells: &[String],
+    col_widths: &[usize],
+    alignments: &[Alignment],
+    ctx: &TableRenderContext,
+) -> Vec<Line<'static>> {
+    let wrapped: Vec<Vec<String>> = col_widths
+        .iter()
+        .enumerate()
+        .map(|(i, &width)| {
+            let cell = cells.get(i).map(String::as_str).unwrap_or("");
+            wrap_cell(cell, width.saturating_sub(2))
+        })
+        .collect();
+    let height = wrapped.iter().map(Vec::len).max().unwrap_or(1);
+
+    (0..height)
+        .map(|k| {
+            let parts: Vec<String> = wrapped
+                .iter()
+                .map(|lines| lines.get(k).cloned().unwrap_or_default())
+                .collect();
+            let mut line = render_table_row(&parts, col_widths, alignments, ctx);
+            if k > 0 && ctx.in_table_mode {
+                line.spans[0] = Span::raw("  ");
+            }
+            line
+        })
+        .collect()
+}
+
+/// Selection state and sizing for [`render_table`].
+#[derive(Debug, Clone, Default)]
+pub struct TableLayout {
+    /// Whether the table element is selected
+    pub is_selected: bool,
+    /// Whether we're in table cell navigation mode
+    pub in_table_mode: bool,
+    /// Currently selected cell (row, col) if in table mode
+    pub selected_cell: Option<(usize, usize)>,
+    /// Optional maximum width to constrain the table to
+    pub available_width: Option<u16>,
+    /// Data rows to draw, for paging large tables; column widths still
+    /// account for every row and row numbers stay those of the full table
+    pub visible_rows: Option<Range<usize>>,
+    /// Wrap long cells over several lines to fit `available_width` instead
+    /// of cutting them off
+    pub wrap: bool,
+}
+
+/// Render a complete table with headers, alignments, and rows
+///
+/// # Arguments
+/// * `headers` - Column headers
+/// * `alignments` - Column alignments
+/// * `rows` - Data rows
+/// * `theme` - Color theme
+/// * `layout` - Selection state and sizing (see [`TableLayout`])
+pub fn render_table(
+    headers: &[String],
+    alignments: &[Alignment],
+    rows: &[Vec<String>],
+    theme: &Theme,
+    layout: TableLayout,
+) -> Vec<Line<'static>> {
+    let TableLayout {
+        is_selected,
+        in_table_mode,
+        selected_cell,
+        available_width,
+        visible_rows,
+        wrap,
+    } = layout;
+    let mut lines = Vec::new();
+
+    if headers.is_empty() {
+        return lines;
+    }
+
+    let col_count = headers.len();
+
+    let col_widths = if wrap {
+        let prefix_width = if in_table_mode || is_selected { 2 } else { 0 };
+        let space =
+            available_width.map(|w| (w as usize).saturating_sub(col_count + 1 + prefix_width));
+        wrapped_column_widths(headers, rows, space)
+    } else {
+        truncated_column_widths(headers, rows, available_width, in_table_mode || is_selected)
+    };
+
     // Top border (add selection indicator or spacing)
     let mut top_border_spans = vec![];
 
@@ -193,11 +358,17 @@ pub fn render_table(
     ));
     lines.push(Line::from(top_border_spans));
 
+    let row_lines = |cells: &[String], ctx: &TableRenderContext| {
+        if wrap {
+            render_wrapped_row(cells, &col_widths, alignments, ctx)
+        } else {
+            vec![render_table_row(cells, &col_widths, alignments, ctx)]
+        }
+    };
+
     // Header row (row 0)
-    let header_line = render_table_row(
+    let header_lines = row_lines(
         headers,
-        &col_widths,
-        alignments,
         &TableRenderContext {
             theme,
             row_num: 0,
@@ -207,7 +378,7 @@ pub fn render_table(
             selected_cell,
         },
     );
-    lines.push(header_line);
+    lines.extend(header_lines);
 
     // Header separator
     let mut separator_spans = vec![];
@@ -229,12 +400,16 @@ pub fn render_table(
     lines.push(Line::from(separator_spans));
 
     // Data rows
-    for (row_idx, row) in rows.iter().enumerate() {
+    let visible_rows = visible_rows.unwrap_or(0..rows.len());
+    for (row_idx, row) in rows
+        .iter()
+        .enumerate()
+        .skip(visible_rows.start)
+        .take(visible_rows.len())
+    {
         let data_row = row_idx + 1; // +1 because row 0 is header
-        let row_line = render_table_row(
+        let data_lines = row_lines(
             row,
-            &col_widths,
-            alignments,
             &TableRenderContext {
                 theme,
                 row_num: data_row,
@@ -244,7 +419,7 @@ pub fn render_table(
                 selected_cell,
             },
         );
-        lines.push(row_line);
+        lines.extend(data_lines);
     }
 
     // Bottom border
@@ -266,11 +441,38 @@ pub fn render_table(
     ));
     lines.push(Line::from(bottom_border_spans));
 
+    // Paging indicator when only part of the table is drawn
+    if visible_rows.len() < rows.len() {
+        let prefix = if in_table_mode || is_selected {
+            "  "
+        } else {
+            ""
+        };
+        lines.push(Line::from(vec![
+            Span::raw(prefix),
+            Span::styled(
+                format!(
+                    "… (showing {}–{} of {})",
+                    visible_rows.start + 1,
+                    visible_rows.end,
+                    rows.len()
+                ),
+                Style::default()
+                    .fg(theme.table_border)
+                    .add_modifier(Modifier::ITALIC),
+            ),
+        ]));
+    }
+
     lines
 }
 
 /// Render a single table row with proper alignment and styling
 ///
+/// The row always has one cell per column width: short rows are padded with
+/// empty cells and cells beyond the last column are dropped, so ragged rows
+/// stay aligned with the header.
+///
 /// # Arguments
 /// * `cells` - Cell contents for this row
 /// * `col_widths` - Pre-calculated column widths
@@ -308,8 +510,8 @@ pub fn render_table_row(
         Style::default().fg(ctx.theme.table_border),
     ));
 
-    for (i, cell) in cells.iter().enumerate() {
-        let width = col_widths.get(i).copied().unwrap_or(10);
+    for (i, &width) in col_widths.iter().enumerate() {
+        let cell = cells.get(i).map(String::as_str).unwrap_or("");
         let alignment = alignments.get(i).unwrap_or(&Alignment::Left);
 
         let cell_text = align_text(cell, width, alignment);
@@ -356,7 +558,7 @@ mod tests {
         #[test]
         fn test_empty_headers_returns_empty() {
             let theme = test_theme();
-            let lines = render_table(&[], &[], &[], &theme, false, false, None, None);
+            let lines = render_table(&[], &[], &[], &theme, TableLayout::default());
             assert!(lines.is_empty());
         }
 
@@ -367,16 +569,7 @@ mod tests {
             let alignments = vec![Alignment::Left];
             let rows = vec![vec!["Alice".to_string()], vec!["Bob".to_string()]];
 
-            let lines = render_table(
-                &headers,
-                &alignments,
-                &rows,
-                &theme,
-                false,
-                false,
-                None,
-                None,
-            );
+            let lines = render_table(&headers, &alignments, &rows, &theme, TableLayout::default());
 
             // Should have: top border, header, separator, 2 data rows, bottom border = 6 lines
             assert_eq!(lines.len(), 6);
@@ -392,16 +585,7 @@ mod tests {
                 vec!["Bob".to_string(), "25".to_string(), "LA".to_string()],
             ];
 
-            let lines = render_table(
-                &headers,
-                &alignments,
-                &rows,
-                &theme,
-                false,
-                false,
-                None,
-                None,
-            );
+            let lines = render_table(&headers, &alignments, &rows, &theme, TableLayout::default());
 
             // Should have: top border, header, separator, 2 data rows, bottom border = 6 lines
             assert_eq!(lines.len(), 6);
@@ -414,9 +598,17 @@ mod tests {
             let rows = vec![vec!["Data".to_string()]];
 
             let lines_unselected =
-                render_table(&headers, &[], &rows, &theme, false, false, None, None);
-            let lines_selected =
-                render_table(&headers, &[], &rows, &theme, true, false, None, None);
+                render_table(&headers, &[], &rows, &theme, TableLayout::default());
+            let lines_selected = render_table(
+                &headers,
+                &[],
+                &rows,
+                &theme,
+                TableLayout {
+                    is_selected: true,
+                    ..Default::default()
+                },
+            );
 
             // Selected table should have arrow prefix on first line
             let first_unselected = &lines_unselected[0];
@@ -439,7 +631,18 @@ mod tests {
             let rows = vec![vec!["Row1".to_string()], vec!["Row2".to_string()]];
 
             // Select cell at row 1, col 0
-            let lines = render_table(&headers, &[], &rows, &theme, true, true, Some((1, 0)), None);
+            let lines = render_table(
+                &headers,
+                &[],
+                &rows,
+                &theme,
+                TableLayout {
+                    is_selected: true,
+                    in_table_mode: true,
+                    selected_cell: Some((1, 0)),
+                    ..Default::default()
+                },
+            );
 
             // Row 1 (first data row, which is lines[3] - after top, header, separator)
             // should have the arrow indicator
@@ -459,16 +662,7 @@ mod tests {
             let alignments = vec![Alignment::Left, Alignment::Right];
             let rows: Vec<Vec<String>> = vec![];
 
-            let lines = render_table(
-                &headers,
-                &alignments,
-                &rows,
-                &theme,
-                false,
-                false,
-                None,
-                None,
-            );
+            let lines = render_table(&headers, &alignments, &rows, &theme, TableLayout::default());
 
             // Should have: top border, header, separator, bottom border = 4 lines
             assert_eq!(lines.len(), 4);
@@ -488,16 +682,8 @@ mod tests {
             ]];
 
             // Without width constraint - table should be natural width
-            let lines_unconstrained = render_table(
-                &headers,
-                &alignments,
-                &rows,
-                &theme,
-                false,
-                false,
-                None,
-                None,
-            );
+            let lines_unconstrained =
+                render_table(&headers, &alignments, &rows, &theme, TableLayout::default());
 
             // With width constraint - table should shrink
             let lines_constrained = render_table(
@@ -505,10 +691,10 @@ mod tests {
                 &alignments,
                 &rows,
                 &theme,
-                false,
-                false,
-                None,
-                Some(40),
+                TableLayout {
+                    available_width: Some(40),
+                    ..Default::default()
+                },
             );
 
             // Both should have same number of lines
@@ -519,6 +705,142 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_visible_rows_window_keeps_true_row_numbers() {
+        let theme = test_theme();
+        let headers = vec!["n".to_string()];
+        let rows: Vec<Vec<String>> = (0..50).map(|i| vec![format!("row{}", i)]).collect();
+
+        let lines = render_table(
+            &headers,
+            &[],
+            &rows,
+            &theme,
+            TableLayout {
+                is_selected: true,
+                in_table_mode: true,
+                selected_cell: Some((22, 0)),
+                visible_rows: Some(20..40),
+                ..Default::default()
+            },
+        );
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        // Borders, header, separator, 20 rows and the paging indicator
+        assert_eq!(text.len(), 25);
+        assert!(text[3].contains("row20"));
+        assert!(text[22].contains("row39"));
+        // Selection arrow points at data row 21, the second visible row
+        assert!(text[4].starts_with("→ ") && text[4].contains("row21"));
+        assert_eq!(text[24], "  … (showing 21–40 of 50)");
+    }
+
+    #[test]
+    fn test_ragged_rows_stay_aligned() {
+        let theme = test_theme();
+        let headers = vec!["Name".to_string(), "Role".to_string(), "Team".to_string()];
+        let rows = vec![
+            vec!["Alice".to_string(), "Dev".to_string(), "Core".to_string()],
+            vec!["Bob".to_string(), "Ops".to_string()],
+            vec![
+                "Carol".to_string(),
+                "QA".to_string(),
+                "Web".to_string(),
+                "extra".to_string(),
+            ],
+        ];
+
+        let lines = render_table(&headers, &[], &rows, &theme, TableLayout::default());
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        // Borders, header, separator and one line per row
+        assert_eq!(text.len(), 7);
+        let width = text[0].width();
+        for line in &text {
+            assert_eq!(line.width(), width, "misaligned line: {}", line);
+            assert_eq!(
+                line.matches(['│', '┬', '┼', '┴', '┌', '┐', '├', '┤', '└', '┘'])
+                    .count(),
+                4
+            );
+        }
+        assert!(!text[5].contains("extra"));
+    }
+
+    fn wrapped_table(width: u16) -> Vec<String> {
+        let headers = vec!["Key".to_string(), "Description".to_string()];
+        let rows = vec![
+            vec![
+                "theme".to_string(),
+                "Color theme for every pane, popup and the status bar".to_string(),
+            ],
+            vec!["width".to_string(), "Outline width".to_string()],
+        ];
+        render_table(
+            &headers,
+            &[],
+            &rows,
+            &test_theme(),
+            TableLayout {
+                available_width: Some(width),
+                wrap: true,
+                ..Default::default()
+            },
+        )
+        .iter()
+        .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+        .collect()
+    }
+
+    #[test]
+    fn test_wrapped_long_cell_snapshots() {
+        assert_eq!(
+            wrapped_table(40),
+            [
+                "┌───────┬──────────────────────────────┐",
+                "│ Key   │ Description                  │",
+                "├───────┼──────────────────────────────┤",
+                "│ theme │ Color theme for every pane,  │",
+                "│       │ popup and the status bar     │",
+                "│ width │ Outline width                │",
+                "└───────┴──────────────────────────────┘",
+            ]
+        );
+        // Narrower: the short column keeps its width, the long one wraps more
+        assert_eq!(
+            wrapped_table(24),
+            [
+                "┌───────┬──────────────┐",
+                "│ Key   │ Description  │",
+                "├───────┼──────────────┤",
+                "│ theme │ Color theme  │",
+                "│       │ for every    │",
+                "│       │ pane, popup  │",
+                "│       │ and the      │",
+                "│       │ status bar   │",
+                "│ width │ Outline      │",
+                "│       │ width        │",
+                "└───────┴──────────────┘",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_cell_breaks_words_longer_than_the_column() {
+        assert_eq!(wrap_cell("short", 10), ["short"]);
+        assert_eq!(wrap_cell("", 10), [""]);
+        assert_eq!(
+            wrap_cell("see https://example.com/x now", 8),
+            ["see", "https://", "example.", "com/x", "now"]
+        );
+    }
+
     mod render_table_row_tests {
         use super::*;
 
@@ -3,6 +3,8 @@
 //! Pure functions for layout calculations, text parsing, and formatting.
 
 use crate::parser::output::Alignment;
+use crate::parser::utils::frontmatter_len;
+use crate::parser::{Link, LinkTarget, TaskStats};
 use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
@@ -42,38 +44,6 @@ pub fn popup_area(
     Rect::new(x, y, width, height)
 }
 
-/// Detect checkbox markers in text for task list items.
-///
-/// Parses text to identify task list checkbox patterns (`[x]`, `[X]`, `[ ]`).
-///
-/// # Arguments
-/// * `text` - The text to parse
-///
-/// # Returns
-/// A tuple of `(is_task, is_checked, remaining_text)`:
-/// - `is_task`: true if a checkbox pattern was found
-/// - `is_checked`: true if the checkbox is checked (`[x]` or `[X]`)
-/// - `remaining_text`: the text after the checkbox marker (or original text if no checkbox)
-pub fn detect_checkbox_in_text(text: &str) -> (bool, bool, &str) {
-    let trimmed = text.trim_start();
-
-    // Check for [x] or [X] (checked)
-    if let Some(stripped) = trimmed
-        .strip_prefix("[x]")
-        .or_else(|| trimmed.strip_prefix("[X]"))
-    {
-        return (true, true, stripped.trim_start());
-    }
-
-    // Check for [ ] (unchecked)
-    if let Some(stripped) = trimmed.strip_prefix("[ ]") {
-        return (true, false, stripped.trim_start());
-    }
-
-    // Not a task list item
-    (false, false, text)
-}
-
 /// Align text within a fixed width using Unicode-aware width calculations.
 ///
 /// Handles left, center, right, and none (defaults to left) alignments.
@@ -149,6 +119,64 @@ pub fn align_text(text: &str, width: usize, alignment: &Alignment) -> String {
     }
 }
 
+/// Truncate text to a maximum display width, ending with `…` when cut.
+///
+/// Uses Unicode display width so wide characters never overflow. Text that
+/// already fits is returned unchanged.
+pub fn truncate_with_ellipsis(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    // Reserve one column for the ellipsis
+    let available = max_width - 1;
+    let mut truncated = String::new();
+    let mut current_width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if current_width + char_width > available {
+            break;
+        }
+        truncated.push(c);
+        current_width += char_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Keep the end of text within a maximum display width, starting with `…`
+/// when cut.
+///
+/// Cuts between grapheme clusters, so emoji sequences and combining accents
+/// are never split. Text that already fits is returned unchanged.
+pub fn truncate_start_with_ellipsis(text: &str, max_width: usize) -> String {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    // Reserve one column for the ellipsis
+    let available = max_width - 1;
+    let mut start = text.len();
+    let mut current_width = 0;
+    for (idx, grapheme) in text.grapheme_indices(true).rev() {
+        let grapheme_width = grapheme.width();
+        if current_width + grapheme_width > available {
+            break;
+        }
+        start = idx;
+        current_width += grapheme_width;
+    }
+    format!("…{}", &text[start..])
+}
+
 /// Highlight search matches within text, returning a Line with styled spans.
 ///
 /// Performs case-insensitive matching and splits the text into segments,
@@ -260,10 +288,8 @@ pub fn build_highlighted_line(
 
 /// Strip YAML frontmatter from the beginning of a document.
 ///
-/// Frontmatter must:
-/// - Start at the very beginning of the document (possibly after leading newlines)
-/// - Begin with `---` on its own line
-/// - End with `---` on its own line
+/// Frontmatter is detected by [`frontmatter_len`]: it must open with `---` on
+/// the first line and close with `---` or `...` on its own line.
 ///
 /// # Arguments
 /// * `content` - The document content
@@ -271,27 +297,7 @@ pub fn build_highlighted_line(
 /// # Returns
 /// Content with frontmatter removed, or original content if no frontmatter found
 pub fn strip_frontmatter(content: &str) -> String {
-    // Frontmatter must start at the beginning (after optional whitespace/newlines)
-    let trimmed = content.trim_start();
-
-    if !trimmed.starts_with("---") {
-        return content.to_string();
-    }
-
-    // Find the closing ---
-    // The pattern is: ---\n...\n---\n (or end of content)
-    if let Some(rest) = trimmed.strip_prefix("---") {
-        // Find the closing marker (must be on its own line)
-        if let Some(end_pos) = rest.find("\n---") {
-            // Skip past the closing ---
-            let after_close = &rest[end_pos + 4..];
-            // Also skip the newline after the closing --- if present
-            let result = after_close.strip_prefix('\n').unwrap_or(after_close);
-            return result.to_string();
-        }
-    }
-
-    content.to_string()
+    content[frontmatter_len(content).unwrap_or(0)..].to_string()
 }
 
 /// Strip LaTeX math expressions and commands from content.
@@ -425,10 +431,137 @@ pub fn filter_content(
     result
 }
 
+/// Format a link's navigation target for the status bar and link picker.
+///
+/// Wikilinks show the page they resolve to (`[[target]]`); their alias is shown
+/// as the link text instead. External URLs longer than `max_url_len` characters
+/// are truncated with `...`.
+pub fn format_link_target(target: &LinkTarget, max_url_len: usize) -> String {
+    match target {
+        LinkTarget::Anchor(a) => format!("#{}", a),
+        LinkTarget::RelativeFile { path, anchor } => {
+            if let Some(a) = anchor {
+                format!("{}#{}", path.display(), a)
+            } else {
+                path.display().to_string()
+            }
+        }
+        LinkTarget::WikiLink { target, .. } => format!("[[{}]]", target),
+        LinkTarget::External(url) => {
+            if url.chars().count() > max_url_len {
+                let keep: String = url.chars().take(max_url_len.saturating_sub(3)).collect();
+                format!("{}...", keep)
+            } else {
+                url.clone()
+            }
+        }
+    }
+}
+
+/// Format where a link goes, naming the reference definition it came from
+/// for reference-style links, e.g. `https://example.com (via [docs])`.
+pub fn format_link_destination(link: &Link, max_url_len: usize) -> String {
+    let target = format_link_target(&link.target, max_url_len);
+    match &link.reference {
+        Some(label) => format!("{} (via [{}])", target, label),
+        None => target,
+    }
+}
+
+/// Cells in a task progress bar
+const PROGRESS_CELLS: usize = 5;
+
+/// Format task completion as a small bar and count, e.g. `▰▰▰▱▱ 3/5`.
+///
+/// The bar only fills completely once every task is done.
+pub fn task_progress(stats: TaskStats) -> String {
+    let filled = (stats.done * PROGRESS_CELLS)
+        .checked_div(stats.total)
+        .unwrap_or(0)
+        .min(PROGRESS_CELLS);
+    format!(
+        "{}{} {}/{}",
+        "▰".repeat(filled),
+        "▱".repeat(PROGRESS_CELLS - filled),
+        stats.done,
+        stats.total
+    )
+}
+
+/// Fill in `{token}` placeholders in a status bar format.
+///
+/// `resolve` supplies each token's value; unknown tokens render empty. Runs
+/// of spaces left by empty tokens are squeezed and trailing space trimmed.
+pub fn format_status(format: &str, resolve: impl Fn(&str) -> Option<String>) -> String {
+    let mut filled = String::with_capacity(format.len());
+    let mut rest = format;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        match rest[start..].find('}') {
+            Some(len) => {
+                if let Some(value) = resolve(&rest[start + 1..start + len]) {
+                    filled.push_str(&value);
+                }
+                rest = &rest[start + len + 1..];
+            }
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        }
+    }
+    filled.push_str(rest);
+
+    let mut out = String::with_capacity(filled.len());
+    for c in filled.chars() {
+        if c == ' ' && out.ends_with(' ') {
+            continue;
+        }
+        out.push(c);
+    }
+    out.trim_end().to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    mod truncate_with_ellipsis_tests {
+        use super::*;
+
+        #[test]
+        fn test_short_text_is_unchanged() {
+            assert_eq!(truncate_with_ellipsis("Intro", 5), "Intro");
+            assert_eq!(truncate_with_ellipsis("", 0), "");
+        }
+
+        #[test]
+        fn test_long_text_ends_with_ellipsis() {
+            assert_eq!(truncate_with_ellipsis("Installation", 6), "Insta…");
+            assert_eq!(truncate_with_ellipsis("Installation", 1), "…");
+            assert_eq!(truncate_with_ellipsis("Installation", 0), "");
+        }
+
+        #[test]
+        fn test_wide_characters_respect_display_width() {
+            let result = truncate_with_ellipsis("日本語の見出し", 6);
+            assert_eq!(result, "日本…");
+            assert!(result.width() <= 6);
+        }
+
+        #[test]
+        fn test_start_truncation_keeps_graphemes_whole() {
+            let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+            let text = format!("abc{}e\u{0301}", family);
+            assert_eq!(truncate_start_with_ellipsis(&text, 20), text);
+
+            let result = truncate_start_with_ellipsis(&text, 4);
+            assert_eq!(result, format!("…{}e\u{0301}", family));
+            assert!(result.width() <= 4);
+            assert_eq!(truncate_start_with_ellipsis(&text, 2), "…e\u{0301}");
+        }
+    }
+
     mod strip_frontmatter_tests {
         use super::*;
 
@@ -453,6 +586,12 @@ mod tests {
             assert_eq!(result, "# Start");
         }
 
+        #[test]
+        fn test_leading_rule_is_not_frontmatter() {
+            let content = "---\n\nIntro\n\n---\nMore text";
+            assert_eq!(strip_frontmatter(content), content);
+        }
+
         #[test]
         fn test_frontmatter_not_at_start() {
             let content = "Some text\n---\ntitle: Test\n---\nMore text";
@@ -621,66 +760,6 @@ mod tests {
         }
     }
 
-    mod detect_checkbox_tests {
-        use super::*;
-
-        #[test]
-        fn test_checked_lowercase() {
-            let (is_task, is_checked, text) = detect_checkbox_in_text("[x] Task done");
-            assert!(is_task);
-            assert!(is_checked);
-            assert_eq!(text, "Task done");
-        }
-
-        #[test]
-        fn test_checked_uppercase() {
-            let (is_task, is_checked, text) = detect_checkbox_in_text("[X] Also done");
-            assert!(is_task);
-            assert!(is_checked);
-            assert_eq!(text, "Also done");
-        }
-
-        #[test]
-        fn test_unchecked() {
-            let (is_task, is_checked, text) = detect_checkbox_in_text("[ ] Not done yet");
-            assert!(is_task);
-            assert!(!is_checked);
-            assert_eq!(text, "Not done yet");
-        }
-
-        #[test]
-        fn test_not_a_task() {
-            let (is_task, is_checked, text) = detect_checkbox_in_text("Regular text");
-            assert!(!is_task);
-            assert!(!is_checked);
-            assert_eq!(text, "Regular text");
-        }
-
-        #[test]
-        fn test_with_leading_whitespace() {
-            let (is_task, is_checked, text) = detect_checkbox_in_text("  [x] Indented task");
-            assert!(is_task);
-            assert!(is_checked);
-            assert_eq!(text, "Indented task");
-        }
-
-        #[test]
-        fn test_empty_task() {
-            let (is_task, is_checked, text) = detect_checkbox_in_text("[x]");
-            assert!(is_task);
-            assert!(is_checked);
-            assert_eq!(text, "");
-        }
-
-        #[test]
-        fn test_bracket_but_not_checkbox() {
-            let (is_task, is_checked, text) = detect_checkbox_in_text("[a] Not a checkbox");
-            assert!(!is_task);
-            assert!(!is_checked);
-            assert_eq!(text, "[a] Not a checkbox");
-        }
-    }
-
     mod align_text_tests {
         use super::*;
 
@@ -807,4 +886,91 @@ mod tests {
             assert_eq!(spans[1].content.as_ref(), " World");
         }
     }
+
+    mod format_link_target_tests {
+        use super::*;
+
+        #[test]
+        fn test_wikilink_shows_navigation_target() {
+            let aliased = LinkTarget::WikiLink {
+                target: "notes/page".to_string(),
+                alias: Some("Display Name".to_string()),
+            };
+            let bare = LinkTarget::WikiLink {
+                target: "page".to_string(),
+                alias: None,
+            };
+            assert_eq!(format_link_target(&aliased, 40), "[[notes/page]]");
+            assert_eq!(format_link_target(&bare, 40), "[[page]]");
+        }
+
+        #[test]
+        fn test_long_url_is_truncated_on_char_boundary() {
+            let url = LinkTarget::External(format!("https://例え.jp/{}", "é".repeat(50)));
+            let formatted = format_link_target(&url, 20);
+            assert_eq!(formatted.chars().count(), 20);
+            assert!(formatted.ends_with("..."));
+        }
+
+        #[test]
+        fn test_reference_links_name_their_definition() {
+            let md = "[the docs][docs], [docs] and [inline](https://b.example)\n\n[docs]: https://a.example\n";
+            let links = crate::parser::extract_links(md);
+            let formatted: Vec<String> = links
+                .iter()
+                .map(|link| format_link_destination(link, 40))
+                .collect();
+            assert_eq!(
+                formatted,
+                [
+                    "https://a.example (via [docs])",
+                    "https://a.example (via [docs])",
+                    "https://b.example"
+                ]
+            );
+        }
+    }
+
+    mod task_progress_tests {
+        use super::*;
+
+        #[test]
+        fn test_bar_fills_with_completion() {
+            let progress = |done, total| task_progress(TaskStats { done, total });
+            assert_eq!(progress(0, 5), "▱▱▱▱▱ 0/5");
+            assert_eq!(progress(3, 5), "▰▰▰▱▱ 3/5");
+            assert_eq!(progress(99, 100), "▰▰▰▰▱ 99/100");
+            assert_eq!(progress(2, 2), "▰▰▰▰▰ 2/2");
+        }
+    }
+
+    mod format_status_tests {
+        use super::*;
+
+        fn resolve(token: &str) -> Option<String> {
+            match token {
+                "focus" => Some("Outline".to_string()),
+                "bookmark" => Some(String::new()),
+                "index" => Some("3".to_string()),
+                _ => None,
+            }
+        }
+
+        #[test]
+        fn test_tokens_are_filled_in() {
+            assert_eq!(
+                format_status(" [{focus}] {index} {bookmark} • x", resolve),
+                " [Outline] 3 • x"
+            );
+        }
+
+        #[test]
+        fn test_unknown_tokens_render_empty() {
+            assert_eq!(
+                format_status("{focus} {nope} {index} {", resolve),
+                "Outline 3 {"
+            );
+            assert_eq!(format_status("{index}{bookmark}", resolve), "3");
+        }
+    }
 }
@@ -1,7 +1,9 @@
 //! File system watcher for live reload functionality.
 //!
 //! Watches the currently open file for changes and notifies the TUI
-//! to reload when modifications are detected.
+//! to reload when modifications are detected. The TUI polls
+//! [`FileWatcher::check_for_changes`]; with the `async` feature, other hosts
+//! can await [`FileWatcher::changes`] instead.
 
 use notify::{
     Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
@@ -11,6 +13,13 @@ use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::time::{Duration, Instant};
 
+#[cfg(feature = "async")]
+use futures_util::Stream;
+#[cfg(feature = "async")]
+use std::sync::Arc;
+#[cfg(feature = "async")]
+use tokio::sync::Notify;
+
 /// Manages file watching for live reload.
 pub struct FileWatcher {
     watcher: RecommendedWatcher,
@@ -19,13 +28,27 @@ pub struct FileWatcher {
     /// Debounce: ignore events within this duration of the last reload
     last_reload: Instant,
     debounce_duration: Duration,
+    /// Woken by the notify thread whenever an event is queued
+    #[cfg(feature = "async")]
+    pending: Arc<Notify>,
 }
 
 impl FileWatcher {
     /// Create a new file watcher.
     pub fn new() -> Result<Self, notify::Error> {
         let (tx, rx) = mpsc::channel();
+        #[cfg(not(feature = "async"))]
         let watcher = notify::recommended_watcher(tx)?;
+        #[cfg(feature = "async")]
+        let pending = Arc::new(Notify::new());
+        #[cfg(feature = "async")]
+        let watcher = {
+            let pending = Arc::clone(&pending);
+            notify::recommended_watcher(move |event| {
+                let _ = tx.send(event);
+                pending.notify_one();
+            })?
+        };
 
         Ok(Self {
             watcher,
@@ -33,6 +56,8 @@ impl FileWatcher {
             current_path: None,
             last_reload: Instant::now(),
             debounce_duration: Duration::from_millis(100),
+            #[cfg(feature = "async")]
+            pending,
         })
     }
 
@@ -96,6 +121,42 @@ impl FileWatcher {
         false
     }
 
+    /// Stream the watched path each time it changes, without polling.
+    ///
+    /// Changes are debounced like [`check_for_changes`](Self::check_for_changes),
+    /// which shares the same queue, so use one or the other. The stream ends
+    /// only when dropped; it waits quietly while no file is watched.
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), notify::Error> {
+    /// use futures_util::StreamExt;
+    /// use treemd::tui::FileWatcher;
+    ///
+    /// let mut watcher = FileWatcher::new()?;
+    /// watcher.watch(&"README.md".into())?;
+    /// let mut changes = std::pin::pin!(watcher.changes());
+    /// while let Some(path) = changes.next().await {
+    ///     println!("{} changed", path.display());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn changes(&mut self) -> impl Stream<Item = PathBuf> + '_ {
+        futures_util::stream::unfold(self, |watcher| async move {
+            loop {
+                if watcher.check_for_changes()
+                    && let Some(path) = watcher.current_path.clone()
+                {
+                    return Some((path, watcher));
+                }
+                // A notification sent since the last wait is kept, so an
+                // event queued while draining isn't missed
+                watcher.pending.notified().await;
+            }
+        })
+    }
+
     /// Mark that a reload just happened (for debouncing after internal saves).
     #[allow(dead_code)]
     pub fn mark_reloaded(&mut self) {
@@ -193,4 +254,26 @@ mod tests {
         let watcher = FileWatcher::new();
         assert!(watcher.is_ok());
     }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_changes_yields_touched_file() {
+        use futures_util::StreamExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.md");
+        std::fs::write(&path, "# Notes\n").unwrap();
+
+        let mut watcher = FileWatcher::new().unwrap();
+        watcher.watch(&path).unwrap();
+        // Let the debounce window that `watch` starts run out
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        std::fs::write(&path, "# Notes\n\nUpdated\n").unwrap();
+
+        let mut changes = std::pin::pin!(watcher.changes());
+        let changed = tokio::time::timeout(Duration::from_secs(5), changes.next())
+            .await
+            .expect("no change event within 5s");
+        assert_eq!(changed, Some(path));
+    }
 }